@@ -5,12 +5,11 @@ fn main() -> Result<(), Box<dyn Error>> {
     let descriptor_path = PathBuf::from(out_dir).join("descriptor.bin");
 
     tonic_prost_build::configure()
-        .build_client(false)
         .file_descriptor_set_path(descriptor_path)
         .compile_protos(protos(), &["proto"])?;
     Ok(())
 }
 
 const fn protos() -> &'static [&'static str] {
-    &["proto/wallet.proto"]
+    &["proto/wallet.proto", "proto/admin.proto"]
 }
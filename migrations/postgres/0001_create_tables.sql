@@ -0,0 +1,48 @@
+-- Records are the same bincode blobs the fs store writes, with the columns
+-- that are looked up pulled out beside them.
+
+CREATE TABLE wallets (
+    name TEXT PRIMARY KEY,
+    id TEXT NOT NULL,
+    address BYTEA NOT NULL,
+    record BYTEA NOT NULL
+);
+CREATE INDEX wallets_id ON wallets (id);
+CREATE INDEX wallets_address ON wallets (address);
+
+CREATE TABLE trash (
+    name TEXT PRIMARY KEY,
+    id TEXT NOT NULL,
+    address BYTEA NOT NULL,
+    record BYTEA NOT NULL,
+    trashed_at BIGINT NOT NULL
+);
+
+CREATE TABLE alerts (
+    id TEXT PRIMARY KEY,
+    rule BYTEA NOT NULL
+);
+
+CREATE TABLE pins (
+    principal TEXT PRIMARY KEY,
+    wallet_ids BYTEA NOT NULL
+);
+
+CREATE TABLE views (
+    principal TEXT NOT NULL,
+    name TEXT NOT NULL,
+    view BYTEA NOT NULL,
+    PRIMARY KEY (principal, name)
+);
+
+CREATE TABLE outbox (
+    id TEXT PRIMARY KEY,
+    entry BYTEA NOT NULL
+);
+
+CREATE TABLE history (
+    seq BIGSERIAL PRIMARY KEY,
+    wallet_id TEXT NOT NULL,
+    sample BYTEA NOT NULL
+);
+CREATE INDEX history_wallet_id ON history (wallet_id);
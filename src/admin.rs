@@ -0,0 +1,100 @@
+mod admin_backup;
+mod admin_collect;
+mod admin_compact;
+mod admin_digest;
+mod admin_downsample;
+mod admin_endpoint;
+mod admin_info;
+mod admin_snapshot;
+mod admin_stats;
+
+use std::{error, fmt, result};
+
+use crate::{
+    infra::{ClientError, StoreError},
+    wallet::WalletError,
+};
+
+pub type Result<T> = result::Result<T, AdminError>;
+
+pub use admin_backup::{Backup, BackupExecutor};
+pub use admin_collect::{Collect, CollectExecutor, Collection};
+pub use admin_compact::{Compact, CompactExecutor, Compaction, CompactionTotals, Compactions};
+pub use admin_digest::{Digest, DigestExecutor, StoreDigest};
+pub use admin_downsample::{Downsample, DownsampleExecutor, Downsampling};
+pub use admin_endpoint::{Endpoint, EndpointExecutor};
+pub use admin_info::{Info, InfoExecutor, ServerInfo};
+pub use admin_snapshot::{Snapshot, SnapshotExecutor};
+pub use admin_stats::{Stats, StatsExecutor, StoreReport};
+
+#[derive(Debug)]
+pub struct AdminError {
+    kind: AdminErrorKind,
+    source: Option<Box<dyn error::Error + Send + Sync + 'static>>,
+}
+
+impl AdminError {
+    pub fn kind(&self) -> AdminErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for AdminError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            AdminErrorKind::EndpointInvalid => {
+                write!(f, "endpoint must be an http or https url")
+            }
+            AdminErrorKind::PathInvalid => {
+                write!(f, "path must be absolute")
+            }
+            AdminErrorKind::Store => {
+                write!(f, "store maintenance error")
+            }
+            AdminErrorKind::Client => {
+                write!(f, "client maintenance error")
+            }
+        }
+    }
+}
+
+impl error::Error for AdminError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        self.source.as_deref().map(|e| e as _)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AdminErrorKind {
+    EndpointInvalid,
+    PathInvalid,
+    Store,
+    Client,
+}
+
+impl From<StoreError> for AdminError {
+    fn from(error: StoreError) -> Self {
+        Self {
+            kind: AdminErrorKind::Store,
+            source: Some(error.0),
+        }
+    }
+}
+
+impl From<WalletError> for AdminError {
+    fn from(error: WalletError) -> Self {
+        Self {
+            kind: AdminErrorKind::Store,
+            source: Some(Box::new(error)),
+        }
+    }
+}
+
+impl From<ClientError> for AdminError {
+    fn from(error: ClientError) -> Self {
+        Self {
+            kind: AdminErrorKind::Client,
+            source: Some(error.0),
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use std::{any::type_name, fmt, sync::Arc};
+
+use async_trait::async_trait;
+use tracing::info;
+
+use super::Result;
+use crate::infra::StoreAdmin;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait Backup: Send + Sync + 'static {
+    async fn execute(&self) -> Result<String>;
+}
+
+#[derive(Clone)]
+pub struct BackupExecutor {
+    pub store_admin: Arc<dyn StoreAdmin>,
+}
+
+impl fmt::Debug for BackupExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>()).finish()
+    }
+}
+
+#[async_trait]
+impl Backup for BackupExecutor {
+    async fn execute(&self) -> Result<String> {
+        let location = self.store_admin.backup().await?;
+        info!(location, "backed up wallet store");
+        Ok(location)
+    }
+}
@@ -0,0 +1,110 @@
+use std::{any::type_name, fmt, sync::Arc};
+
+use async_trait::async_trait;
+use tracing::info;
+
+use super::{Compact, Compaction, Result};
+use crate::wallet::Purge;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait Collect: Send + Sync + 'static {
+    async fn execute(&self) -> Result<Collection>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Collection {
+    pub purged: usize,
+    pub compaction: Option<Compaction>,
+}
+
+#[derive(Clone)]
+pub struct CollectExecutor {
+    pub purge: Arc<dyn Purge>,
+    pub compact: Arc<dyn Compact>,
+}
+
+impl fmt::Debug for CollectExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>()).finish()
+    }
+}
+
+#[async_trait]
+impl Collect for CollectExecutor {
+    async fn execute(&self) -> Result<Collection> {
+        let purged = self.purge.execute(None).await?;
+        if purged == 0 {
+            return Ok(Collection {
+                purged,
+                compaction: None,
+            });
+        }
+
+        let compaction = self.compact.execute().await?;
+        info!(
+            purged,
+            reclaimed = compaction.reclaimed_bytes(),
+            "collected wallet store garbage"
+        );
+        Ok(Collection {
+            purged,
+            compaction: Some(compaction),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use async_trait::async_trait;
+
+    use crate::{
+        admin::{Collect, CollectExecutor, CompactExecutor, Compactions},
+        infra::{MockStoreAdmin, StoreStats},
+        wallet::{self, Purge},
+    };
+
+    struct FixedPurge(usize);
+
+    #[async_trait]
+    impl Purge for FixedPurge {
+        async fn execute(&self, _name: Option<String>) -> wallet::Result<usize> {
+            Ok(self.0)
+        }
+    }
+
+    fn collect(purged: usize, compactions: usize) -> CollectExecutor {
+        let mut store_admin = MockStoreAdmin::new();
+        store_admin
+            .expect_stats()
+            .returning(|| Ok(StoreStats::default()));
+        store_admin
+            .expect_compact()
+            .times(compactions)
+            .returning(|| Ok(()));
+
+        CollectExecutor {
+            purge: Arc::new(FixedPurge(purged)),
+            compact: Arc::new(CompactExecutor {
+                store_admin: Arc::new(store_admin),
+                compactions: Arc::new(Compactions::default()),
+            }),
+        }
+    }
+
+    #[tokio::test]
+    async fn admin_collect_compacts_after_purge() {
+        let collection = collect(2, 1).execute().await.unwrap();
+        assert_eq!(collection.purged, 2);
+        assert!(collection.compaction.is_some());
+    }
+
+    #[tokio::test]
+    async fn admin_collect_nothing_expired() {
+        let collection = collect(0, 0).execute().await.unwrap();
+        assert_eq!(collection.purged, 0);
+        assert!(collection.compaction.is_none());
+    }
+}
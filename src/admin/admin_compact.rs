@@ -0,0 +1,145 @@
+use std::{
+    any::type_name,
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use tracing::info;
+
+use super::Result;
+use crate::infra::StoreAdmin;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait Compact: Send + Sync + 'static {
+    async fn execute(&self) -> Result<Compaction>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Compaction {
+    pub size_bytes_before: u64,
+    pub size_bytes_after: u64,
+    pub duration: Duration,
+    pub finished_at: DateTime<Utc>,
+}
+
+impl Compaction {
+    pub fn reclaimed_bytes(&self) -> u64 {
+        self.size_bytes_before.saturating_sub(self.size_bytes_after)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Compactions {
+    totals: Mutex<CompactionTotals>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CompactionTotals {
+    pub runs: u64,
+    pub reclaimed_bytes: u64,
+    pub last: Option<Compaction>,
+}
+
+impl Compactions {
+    pub fn record(&self, compaction: Compaction) {
+        let mut totals = self.totals.lock().unwrap();
+        totals.runs += 1;
+        totals.reclaimed_bytes += compaction.reclaimed_bytes();
+        totals.last = Some(compaction);
+    }
+
+    pub fn totals(&self) -> CompactionTotals {
+        *self.totals.lock().unwrap()
+    }
+}
+
+#[derive(Clone)]
+pub struct CompactExecutor {
+    pub store_admin: Arc<dyn StoreAdmin>,
+    pub compactions: Arc<Compactions>,
+}
+
+impl fmt::Debug for CompactExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>()).finish()
+    }
+}
+
+#[async_trait]
+impl Compact for CompactExecutor {
+    async fn execute(&self) -> Result<Compaction> {
+        let started = Instant::now();
+        let before = self.store_admin.stats().await?.size_bytes;
+        self.store_admin.compact().await?;
+        let after = self.store_admin.stats().await?.size_bytes;
+
+        let compaction = Compaction {
+            size_bytes_before: before,
+            size_bytes_after: after,
+            duration: started.elapsed(),
+            finished_at: Utc::now(),
+        };
+        self.compactions.record(compaction);
+
+        info!(
+            before,
+            after,
+            reclaimed = compaction.reclaimed_bytes(),
+            duration_ms = compaction.duration.as_millis() as u64,
+            "compacted wallet store"
+        );
+        Ok(compaction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    };
+
+    use crate::{
+        admin::{Compact, CompactExecutor, Compactions},
+        infra::{MockStoreAdmin, StoreStats},
+    };
+
+    #[tokio::test]
+    async fn admin_compact_records_reclaimed() {
+        let size = Arc::new(AtomicU64::new(4096));
+
+        let mut store_admin = MockStoreAdmin::new();
+        let stats_size = size.clone();
+        store_admin.expect_stats().returning(move || {
+            Ok(StoreStats {
+                size_bytes: stats_size.load(Ordering::SeqCst),
+                ..Default::default()
+            })
+        });
+        let compact_size = size.clone();
+        store_admin.expect_compact().times(2).returning(move || {
+            compact_size.fetch_sub(1024, Ordering::SeqCst);
+            Ok(())
+        });
+
+        let compactions = Arc::new(Compactions::default());
+        let compact = CompactExecutor {
+            store_admin: Arc::new(store_admin),
+            compactions: compactions.clone(),
+        };
+
+        let compaction = compact.execute().await.unwrap();
+        assert_eq!(compaction.size_bytes_before, 4096);
+        assert_eq!(compaction.reclaimed_bytes(), 1024);
+        compact.execute().await.unwrap();
+
+        let totals = compactions.totals();
+        assert_eq!(totals.runs, 2);
+        assert_eq!(totals.reclaimed_bytes, 2048);
+        assert_eq!(totals.last.unwrap().size_bytes_after, 2048);
+    }
+}
@@ -0,0 +1,148 @@
+use std::{any::type_name, fmt, sync::Arc};
+
+use async_trait::async_trait;
+
+use super::Result;
+use crate::{
+    core::{merkle_leaf, merkle_root},
+    infra::{Provenance, TrashStore, WalletRecord, WalletStore},
+};
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait Digest: Send + Sync + 'static {
+    async fn execute(&self) -> Result<StoreDigest>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoreDigest {
+    pub root: String,
+    pub entries: usize,
+}
+
+#[derive(Clone)]
+pub struct DigestExecutor {
+    pub wallet_store: Arc<dyn WalletStore>,
+    pub trash_store: Arc<dyn TrashStore>,
+}
+
+impl fmt::Debug for DigestExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>()).finish()
+    }
+}
+
+#[async_trait]
+impl Digest for DigestExecutor {
+    async fn execute(&self) -> Result<StoreDigest> {
+        // Both come back ordered by name.
+        let wallets = self.wallet_store.all().await?;
+        let trashed = self.trash_store.all_trashed().await?;
+
+        let mut leaves = Vec::with_capacity(wallets.len() + trashed.len());
+        for (name, record) in &wallets {
+            let mut bytes = Vec::new();
+            push_field(&mut bytes, b"wallet");
+            encode_record(&mut bytes, name, record);
+            leaves.push(merkle_leaf(&bytes));
+        }
+        for (name, trashed) in &trashed {
+            let mut bytes = Vec::new();
+            push_field(&mut bytes, b"trash");
+            encode_record(&mut bytes, name, &trashed.record);
+            push_field(&mut bytes, &trashed.trashed_at.timestamp().to_be_bytes());
+            leaves.push(merkle_leaf(&bytes));
+        }
+
+        let root = merkle_root(&leaves);
+        Ok(StoreDigest {
+            root: hex::encode(root),
+            entries: leaves.len(),
+        })
+    }
+}
+
+fn encode_record(bytes: &mut Vec<u8>, name: &str, record: &WalletRecord) {
+    push_field(bytes, name.as_bytes());
+    push_field(bytes, record.id.as_bytes());
+    push_field(bytes, record.wallet.address().inner());
+    push_field(bytes, &(record.wallet.linked().len() as u32).to_be_bytes());
+    for address in record.wallet.linked() {
+        push_field(bytes, address.inner());
+    }
+    push_field(bytes, &record.wallet.balance().wei().to_big_endian());
+    push_field(bytes, &(record.tags.len() as u32).to_be_bytes());
+    for tag in &record.tags {
+        push_field(bytes, tag.as_bytes());
+    }
+    push_field(bytes, record.note.as_deref().unwrap_or_default().as_bytes());
+    push_field(bytes, &record.created_at.timestamp().to_be_bytes());
+    encode_provenance(bytes, &record.created_by);
+    encode_provenance(bytes, &record.modified_by);
+    push_field(bytes, &record.last_update.timestamp().to_be_bytes());
+    match record.verified_at {
+        Some(verified_at) => push_field(bytes, &verified_at.timestamp().to_be_bytes()),
+        None => push_field(bytes, &[]),
+    }
+}
+
+fn encode_provenance(bytes: &mut Vec<u8>, provenance: &Provenance) {
+    push_field(bytes, provenance.principal.as_bytes());
+    push_field(bytes, &[provenance.interface as u8]);
+}
+
+fn push_field(bytes: &mut Vec<u8>, field: &[u8]) {
+    bytes.extend_from_slice(&(field.len() as u32).to_be_bytes());
+    bytes.extend_from_slice(field);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, str::FromStr, sync::Arc};
+
+    use crate::{
+        admin::{Digest, DigestExecutor},
+        core::{Address, Balance, Wallet},
+        infra::{MockTrashStore, MockWalletStore, WalletRecord},
+    };
+
+    fn digest_of(balances: &'static [u128]) -> DigestExecutor {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_all().returning(|| {
+            let address = "0xB644Babc370f46f202DB5eaf2071A9Ee66fA1D5E";
+            let address = Address::from_str(address).unwrap();
+            let wallets = balances.iter().enumerate().map(|(i, wei)| {
+                let mut wallet = Wallet::new(address);
+                *wallet.balance_mut() = Balance::new(*wei);
+                let record = WalletRecord {
+                    id: format!("id-{i}"),
+                    last_update: Default::default(),
+                    ..WalletRecord::new(wallet)
+                };
+                (format!("Wallet {i}"), record)
+            });
+            Ok(wallets.collect())
+        });
+
+        let mut trash_store = MockTrashStore::new();
+        trash_store
+            .expect_all_trashed()
+            .returning(|| Ok(BTreeMap::new()));
+
+        DigestExecutor {
+            wallet_store: Arc::new(wallet_store),
+            trash_store: Arc::new(trash_store),
+        }
+    }
+
+    #[tokio::test]
+    async fn admin_digest_drift() {
+        let a = digest_of(&[1, 2, 3]).execute().await.unwrap();
+        let b = digest_of(&[1, 2, 3]).execute().await.unwrap();
+        let c = digest_of(&[1, 2, 4]).execute().await.unwrap();
+
+        assert_eq!(a.entries, 3);
+        assert_eq!(a, b);
+        assert_ne!(a.root, c.root);
+    }
+}
@@ -0,0 +1,157 @@
+use std::{any::type_name, fmt, sync::Arc};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use tracing::info;
+
+use super::Result;
+use crate::{
+    core::{self, RetentionTier},
+    infra::{HistoryStore, TrashStore, WalletStore},
+};
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait Downsample: Send + Sync + 'static {
+    async fn execute(&self) -> Result<Downsampling>;
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Downsampling {
+    pub wallets: usize,
+    pub removed: usize,
+}
+
+#[derive(Clone)]
+pub struct DownsampleExecutor {
+    pub wallet_store: Arc<dyn WalletStore>,
+    pub trash_store: Arc<dyn TrashStore>,
+    pub history_store: Arc<dyn HistoryStore>,
+    pub tiers: Vec<RetentionTier>,
+}
+
+impl fmt::Debug for DownsampleExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>())
+            .field("tiers", &self.tiers)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl Downsample for DownsampleExecutor {
+    async fn execute(&self) -> Result<Downsampling> {
+        let mut wallet_ids: Vec<String> = self
+            .wallet_store
+            .all()
+            .await?
+            .into_values()
+            .map(|record| record.id)
+            .collect();
+        wallet_ids.extend(
+            self.trash_store
+                .all_trashed()
+                .await?
+                .into_values()
+                .map(|trashed| trashed.record.id),
+        );
+
+        let now = Utc::now().timestamp();
+        let mut downsampling = Downsampling::default();
+        for wallet_id in wallet_ids {
+            let history = self.history_store.history(&wallet_id).await?;
+            let kept = core::downsample(&history, now, &self.tiers);
+            if kept.len() == history.len() {
+                continue;
+            }
+
+            self.history_store
+                .replace_history(&wallet_id, &kept)
+                .await?;
+            downsampling.wallets += 1;
+            downsampling.removed += history.len() - kept.len();
+        }
+
+        if downsampling.removed > 0 {
+            info!(
+                wallets = downsampling.wallets,
+                removed = downsampling.removed,
+                "downsampled balance history"
+            );
+        }
+        Ok(downsampling)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, sync::Arc};
+
+    use chrono::Utc;
+
+    use crate::{
+        admin::{Downsample, DownsampleExecutor},
+        core::{Address, Balance, RetentionTier, Sample, Wallet},
+        infra::{MockHistoryStore, MockTrashStore, MockWalletStore, WalletRecord},
+    };
+
+    const HOUR: i64 = 60 * 60;
+
+    fn sample(timestamp: i64) -> Sample {
+        Sample {
+            timestamp,
+            block_number: None,
+            balance: Balance::new(1),
+        }
+    }
+
+    #[tokio::test]
+    async fn admin_downsample_thins_old_samples() {
+        let now = Utc::now().timestamp();
+        let hour = (now - 24 * HOUR).div_euclid(HOUR) * HOUR;
+        let mut old: Vec<Sample> = (0..4).map(|i| sample(hour + i * 60)).collect();
+        old.push(sample(now));
+
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_all().returning(|| {
+            let record = WalletRecord {
+                id: "thinned".to_owned(),
+                ..WalletRecord::new(Wallet::new(Address::new([1; 20])))
+            };
+            Ok(BTreeMap::from([("main".to_owned(), record)]))
+        });
+        let mut trash_store = MockTrashStore::new();
+        trash_store
+            .expect_all_trashed()
+            .returning(|| Ok(BTreeMap::new()));
+        let mut history_store = MockHistoryStore::new();
+        history_store
+            .expect_history()
+            .returning(move |_| Ok(old.clone()));
+        history_store
+            .expect_replace_history()
+            .withf(|wallet_id, samples| wallet_id == "thinned" && samples.len() == 2)
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let downsample = DownsampleExecutor {
+            wallet_store: Arc::new(wallet_store),
+            trash_store: Arc::new(trash_store),
+            history_store: Arc::new(history_store),
+            tiers: vec![
+                RetentionTier {
+                    max_age: Some(HOUR),
+                    resolution: 0,
+                },
+                RetentionTier {
+                    max_age: None,
+                    resolution: HOUR,
+                },
+            ],
+        };
+
+        let downsampling = downsample.execute().await.unwrap();
+        assert_eq!(downsampling.wallets, 1);
+        assert_eq!(downsampling.removed, 3);
+    }
+}
@@ -0,0 +1,111 @@
+use std::{any::type_name, fmt, sync::Arc};
+
+use async_trait::async_trait;
+use reqwest::Url;
+use tracing::info;
+
+use super::{AdminError, AdminErrorKind, Result};
+use crate::infra::ClientAdmin;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait Endpoint: Send + Sync + 'static {
+    async fn execute(&self, url: Option<String>) -> Result<String>;
+}
+
+#[derive(Clone)]
+pub struct EndpointExecutor {
+    pub client_admin: Arc<dyn ClientAdmin>,
+}
+
+impl fmt::Debug for EndpointExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>()).finish()
+    }
+}
+
+#[async_trait]
+impl Endpoint for EndpointExecutor {
+    async fn execute(&self, url: Option<String>) -> Result<String> {
+        let previous = self.client_admin.endpoint();
+
+        if let Some(url) = url {
+            validate_endpoint(&url)?;
+            self.client_admin.set_endpoint(&url);
+            info!(previous, current = url, "switched chain endpoint");
+        }
+
+        Ok(previous)
+    }
+}
+
+fn validate_endpoint(url: &str) -> Result<()> {
+    match Url::parse(url) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") && url.has_host() => Ok(()),
+        Ok(_) => Err(AdminError {
+            kind: AdminErrorKind::EndpointInvalid,
+            source: None,
+        }),
+        Err(e) => Err(AdminError {
+            kind: AdminErrorKind::EndpointInvalid,
+            source: Some(e.into()),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{
+        admin::{AdminErrorKind, Endpoint, EndpointExecutor},
+        infra::MockClientAdmin,
+    };
+
+    #[tokio::test]
+    async fn admin_endpoint_switch() {
+        let mut client_admin = MockClientAdmin::new();
+        client_admin
+            .expect_endpoint()
+            .returning(|| "https://eth.llamarpc.com".to_string());
+        client_admin
+            .expect_set_endpoint()
+            .withf(|url| url == "https://ethereum-rpc.publicnode.com")
+            .times(1)
+            .return_const(());
+
+        let endpoint = EndpointExecutor {
+            client_admin: Arc::new(client_admin),
+        };
+
+        let previous = endpoint
+            .execute(Some("https://ethereum-rpc.publicnode.com".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(previous, "https://eth.llamarpc.com");
+    }
+
+    #[tokio::test]
+    async fn admin_endpoint_invalid() {
+        let mut client_admin = MockClientAdmin::new();
+        client_admin
+            .expect_endpoint()
+            .returning(|| "https://eth.llamarpc.com".to_string());
+
+        let endpoint = EndpointExecutor {
+            client_admin: Arc::new(client_admin),
+        };
+
+        let error = endpoint
+            .execute(Some("not a url".to_string()))
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), AdminErrorKind::EndpointInvalid);
+
+        let error = endpoint
+            .execute(Some("ftp://eth.llamarpc.com".to_string()))
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), AdminErrorKind::EndpointInvalid);
+    }
+}
@@ -0,0 +1,109 @@
+use std::{any::type_name, fmt, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use tokio::time::Instant;
+
+use super::Result;
+use crate::infra::{ClientAdmin, StoreAdmin};
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait Info: Send + Sync + 'static {
+    async fn execute(&self) -> Result<ServerInfo>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerInfo {
+    pub version: String,
+    pub chain: String,
+    pub endpoint: String,
+    pub store_backend: String,
+    pub store_location: String,
+    pub store_recovered_from: Option<String>,
+    pub uptime: Duration,
+    pub wallets: usize,
+}
+
+#[derive(Clone)]
+pub struct InfoExecutor {
+    pub store_admin: Arc<dyn StoreAdmin>,
+    pub client_admin: Arc<dyn ClientAdmin>,
+    pub chain: String,
+    pub started_at: Instant,
+}
+
+impl fmt::Debug for InfoExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>()).finish()
+    }
+}
+
+#[async_trait]
+impl Info for InfoExecutor {
+    async fn execute(&self) -> Result<ServerInfo> {
+        let stats = self.store_admin.stats().await?;
+        let store = self.store_admin.describe();
+
+        Ok(ServerInfo {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            chain: self.chain.clone(),
+            endpoint: self.client_admin.endpoint(),
+            store_backend: store.backend,
+            store_location: store.location,
+            store_recovered_from: store.recovered_from,
+            uptime: self.started_at.elapsed(),
+            wallets: stats.entries,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use tokio::time::Instant;
+
+    use crate::{
+        admin::{Info, InfoExecutor},
+        infra::{MockClientAdmin, MockStoreAdmin, StoreInfo, StoreStats},
+    };
+
+    #[tokio::test]
+    async fn admin_info_success() {
+        let mut store_admin = MockStoreAdmin::new();
+        store_admin.expect_stats().returning(|| {
+            Ok(StoreStats {
+                entries: 3,
+                trashed: 1,
+                size_bytes: 512,
+                ..Default::default()
+            })
+        });
+        store_admin.expect_describe().returning(|| StoreInfo {
+            backend: "fs".to_string(),
+            location: "wallet.db".to_string(),
+            recovered_from: Some("wallet.db.bak".to_string()),
+        });
+
+        let mut client_admin = MockClientAdmin::new();
+        client_admin
+            .expect_endpoint()
+            .returning(|| "https://eth.llamarpc.com".to_string());
+
+        let info = InfoExecutor {
+            store_admin: Arc::new(store_admin),
+            client_admin: Arc::new(client_admin),
+            chain: "mainnet".to_string(),
+            started_at: Instant::now(),
+        };
+
+        let info = info.execute().await.unwrap();
+        assert_eq!(info.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(info.chain, "mainnet");
+        assert_eq!(info.endpoint, "https://eth.llamarpc.com");
+        assert_eq!(info.store_backend, "fs");
+        assert_eq!(info.store_location, "wallet.db");
+        assert_eq!(info.store_recovered_from.as_deref(), Some("wallet.db.bak"));
+        assert_eq!(info.wallets, 3);
+    }
+}
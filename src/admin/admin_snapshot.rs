@@ -0,0 +1,77 @@
+use std::{any::type_name, fmt, path::Path, sync::Arc};
+
+use async_trait::async_trait;
+use tracing::info;
+
+use super::{AdminError, AdminErrorKind, Result};
+use crate::infra::StoreAdmin;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait Snapshot: Send + Sync + 'static {
+    async fn execute(&self, dest: &str) -> Result<()>;
+}
+
+#[derive(Clone)]
+pub struct SnapshotExecutor {
+    pub store_admin: Arc<dyn StoreAdmin>,
+}
+
+impl fmt::Debug for SnapshotExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>()).finish()
+    }
+}
+
+#[async_trait]
+impl Snapshot for SnapshotExecutor {
+    async fn execute(&self, dest: &str) -> Result<()> {
+        // Relative to the server's working directory, which the caller
+        // can't see.
+        if !Path::new(dest).is_absolute() {
+            return Err(AdminError {
+                kind: AdminErrorKind::PathInvalid,
+                source: None,
+            });
+        }
+
+        self.store_admin.snapshot(dest).await?;
+        info!(dest, "snapshotted wallet store");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{
+        admin::{AdminErrorKind, Snapshot, SnapshotExecutor},
+        infra::MockStoreAdmin,
+    };
+
+    #[tokio::test]
+    async fn admin_snapshot_success() {
+        let mut store_admin = MockStoreAdmin::new();
+        store_admin
+            .expect_snapshot()
+            .withf(|dest| dest == "/var/backups/wallet.db")
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let snapshot = SnapshotExecutor {
+            store_admin: Arc::new(store_admin),
+        };
+        snapshot.execute("/var/backups/wallet.db").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn admin_snapshot_relative_path() {
+        let snapshot = SnapshotExecutor {
+            store_admin: Arc::new(MockStoreAdmin::new()),
+        };
+
+        let error = snapshot.execute("wallet.db").await.unwrap_err();
+        assert_eq!(error.kind(), AdminErrorKind::PathInvalid);
+    }
+}
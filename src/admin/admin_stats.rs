@@ -0,0 +1,41 @@
+use std::{any::type_name, fmt, sync::Arc};
+
+use async_trait::async_trait;
+
+use super::{CompactionTotals, Compactions, Result};
+use crate::infra::{StoreAdmin, StoreStats};
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait Stats: Send + Sync + 'static {
+    async fn execute(&self) -> Result<StoreReport>;
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct StoreReport {
+    pub stats: StoreStats,
+    pub compactions: CompactionTotals,
+}
+
+#[derive(Clone)]
+pub struct StatsExecutor {
+    pub store_admin: Arc<dyn StoreAdmin>,
+    pub compactions: Arc<Compactions>,
+}
+
+impl fmt::Debug for StatsExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>()).finish()
+    }
+}
+
+#[async_trait]
+impl Stats for StatsExecutor {
+    async fn execute(&self) -> Result<StoreReport> {
+        let stats = self.store_admin.stats().await?;
+        Ok(StoreReport {
+            stats,
+            compactions: self.compactions.totals(),
+        })
+    }
+}
@@ -0,0 +1,87 @@
+mod alert_create;
+mod alert_delete;
+mod alert_evaluate;
+mod alert_list;
+
+use std::{error, fmt, result};
+
+use crate::{
+    infra::StoreError,
+    wallet::{WalletError, WalletErrorKind},
+};
+
+pub type Result<T> = result::Result<T, AlertError>;
+
+pub use alert_create::{Create, CreateExecutor, NewAlertRule};
+pub use alert_delete::{Delete, DeleteExecutor};
+pub use alert_evaluate::{Evaluate, EvaluateExecutor, FiredAlert};
+pub use alert_list::{List, ListExecutor};
+
+#[derive(Debug)]
+pub struct AlertError {
+    kind: AlertErrorKind,
+    source: Option<Box<dyn error::Error + Send + Sync + 'static>>,
+}
+
+impl AlertError {
+    pub fn kind(&self) -> AlertErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for AlertError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            AlertErrorKind::NotFound => {
+                write!(f, "alert rule not found")
+            }
+            AlertErrorKind::WalletNotFound => {
+                write!(f, "wallet not found")
+            }
+            AlertErrorKind::PercentZero => {
+                write!(f, "change percent must be above zero")
+            }
+            AlertErrorKind::Store => {
+                write!(f, "alert store error")
+            }
+        }
+    }
+}
+
+impl error::Error for AlertError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        self.source.as_deref().map(|e| e as _)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AlertErrorKind {
+    NotFound,
+    WalletNotFound,
+    PercentZero,
+    Store,
+}
+
+impl From<StoreError> for AlertError {
+    fn from(error: StoreError) -> Self {
+        Self {
+            kind: AlertErrorKind::Store,
+            source: Some(error.0),
+        }
+    }
+}
+
+impl From<WalletError> for AlertError {
+    fn from(error: WalletError) -> Self {
+        match error.kind() {
+            WalletErrorKind::NotFound => Self {
+                kind: AlertErrorKind::WalletNotFound,
+                source: None,
+            },
+            _ => Self {
+                kind: AlertErrorKind::Store,
+                source: Some(error.into()),
+            },
+        }
+    }
+}
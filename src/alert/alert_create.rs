@@ -0,0 +1,140 @@
+use std::{any::type_name, fmt, sync::Arc};
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use super::{AlertError, AlertErrorKind, Result};
+use crate::{
+    core::AlertCondition,
+    infra::{AlertRule, AlertStore, Provenance, WalletStore},
+    wallet,
+};
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait Create: Send + Sync + 'static {
+    async fn execute(&self, rule: NewAlertRule) -> Result<AlertRule>;
+}
+
+#[derive(Debug, Clone)]
+pub struct NewAlertRule {
+    pub wallet: Option<String>,
+    pub condition: AlertCondition,
+    pub created_by: Provenance,
+}
+
+#[derive(Clone)]
+pub struct CreateExecutor {
+    pub alert_store: Arc<dyn AlertStore>,
+    pub wallet_store: Arc<dyn WalletStore>,
+}
+
+impl fmt::Debug for CreateExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>()).finish()
+    }
+}
+
+#[async_trait]
+impl Create for CreateExecutor {
+    async fn execute(&self, rule: NewAlertRule) -> Result<AlertRule> {
+        let NewAlertRule {
+            wallet,
+            condition,
+            created_by,
+        } = rule;
+
+        if condition == (AlertCondition::Change { percent: 0 }) {
+            return Err(AlertError {
+                kind: AlertErrorKind::PercentZero,
+                source: None,
+            });
+        }
+
+        // Rules follow the wallet's id so they survive a rename.
+        let wallet_id = match wallet {
+            Some(wallet) => {
+                let (_, record) = wallet::resolve(self.wallet_store.as_ref(), &wallet).await?;
+                Some(record.id)
+            }
+            None => None,
+        };
+
+        let rule = AlertRule {
+            id: Uuid::new_v4().to_string(),
+            wallet_id,
+            condition,
+            created_by,
+        };
+
+        self.alert_store.save_alert(&rule).await?;
+        Ok(rule)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr, sync::Arc};
+
+    use crate::{
+        alert::{AlertErrorKind, Create, CreateExecutor, NewAlertRule},
+        core::{Address, AlertCondition, Balance, Wallet},
+        infra::{MockAlertStore, MockWalletStore, Provenance, WalletRecord},
+    };
+
+    const ADDR: &str = "0xB644Babc370f46f202DB5eaf2071A9Ee66fA1D5E";
+
+    #[tokio::test]
+    async fn alert_create_success() {
+        let record = WalletRecord::new(Wallet::new(Address::from_str(ADDR).unwrap()));
+        let wallet_id = record.id.clone();
+
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store
+            .expect_find()
+            .returning(move |_| Ok(Some(record.clone())));
+
+        let mut alert_store = MockAlertStore::new();
+        let expected = wallet_id.clone();
+        alert_store
+            .expect_save_alert()
+            .withf(move |rule| rule.wallet_id.as_deref() == Some(expected.as_str()))
+            .returning(|_| Ok(()));
+
+        let create = CreateExecutor {
+            alert_store: Arc::new(alert_store),
+            wallet_store: Arc::new(wallet_store),
+        };
+
+        let rule = create
+            .execute(NewAlertRule {
+                wallet: Some("Cold".to_string()),
+                condition: AlertCondition::Below(Balance::new(10)),
+                created_by: Provenance::default(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(rule.wallet_id, Some(wallet_id));
+    }
+
+    #[tokio::test]
+    async fn alert_create_wallet_not_found() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_find().returning(|_| Ok(None));
+
+        let create = CreateExecutor {
+            alert_store: Arc::new(MockAlertStore::new()),
+            wallet_store: Arc::new(wallet_store),
+        };
+
+        let error = create
+            .execute(NewAlertRule {
+                wallet: Some("Cold".to_string()),
+                condition: AlertCondition::Change { percent: 10 },
+                created_by: Provenance::default(),
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), AlertErrorKind::WalletNotFound);
+    }
+}
@@ -0,0 +1,37 @@
+use std::{any::type_name, fmt, sync::Arc};
+
+use async_trait::async_trait;
+
+use super::{AlertError, AlertErrorKind, Result};
+use crate::infra::AlertStore;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait Delete: Send + Sync + 'static {
+    async fn execute(&self, id: &str) -> Result<()>;
+}
+
+#[derive(Clone)]
+pub struct DeleteExecutor {
+    pub alert_store: Arc<dyn AlertStore>,
+}
+
+impl fmt::Debug for DeleteExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>()).finish()
+    }
+}
+
+#[async_trait]
+impl Delete for DeleteExecutor {
+    async fn execute(&self, id: &str) -> Result<()> {
+        if self.alert_store.delete_alert(id).await? {
+            Ok(())
+        } else {
+            Err(AlertError {
+                kind: AlertErrorKind::NotFound,
+                source: None,
+            })
+        }
+    }
+}
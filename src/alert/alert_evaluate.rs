@@ -0,0 +1,109 @@
+use std::{any::type_name, fmt, sync::Arc};
+
+use async_trait::async_trait;
+
+use super::Result;
+use crate::{
+    infra::{AlertRule, AlertStore},
+    wallet::BalanceChange,
+};
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait Evaluate: Send + Sync + 'static {
+    async fn execute(&self, changes: &[BalanceChange]) -> Result<Vec<FiredAlert>>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FiredAlert {
+    pub rule: AlertRule,
+    pub change: BalanceChange,
+}
+
+#[derive(Clone)]
+pub struct EvaluateExecutor {
+    pub alert_store: Arc<dyn AlertStore>,
+}
+
+impl fmt::Debug for EvaluateExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>()).finish()
+    }
+}
+
+#[async_trait]
+impl Evaluate for EvaluateExecutor {
+    async fn execute(&self, changes: &[BalanceChange]) -> Result<Vec<FiredAlert>> {
+        if changes.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let rules = self.alert_store.all_alerts().await?;
+        let mut fired = Vec::new();
+        for change in changes {
+            for rule in &rules {
+                let watches = rule
+                    .wallet_id
+                    .as_ref()
+                    .is_none_or(|wallet_id| *wallet_id == change.wallet_id);
+                if watches && rule.condition.fires(change.before, change.after) {
+                    fired.push(FiredAlert {
+                        rule: rule.clone(),
+                        change: change.clone(),
+                    });
+                }
+            }
+        }
+
+        Ok(fired)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{
+        alert::{Evaluate, EvaluateExecutor},
+        core::{AlertCondition, Balance},
+        infra::{AlertRule, MockAlertStore, Provenance},
+        wallet::BalanceChange,
+    };
+
+    #[tokio::test]
+    async fn alert_evaluate_scoped_rules() {
+        let mut alert_store = MockAlertStore::new();
+        alert_store.expect_all_alerts().returning(|| {
+            let rule = |id: &str, wallet_id: Option<&str>, condition| AlertRule {
+                id: id.to_string(),
+                wallet_id: wallet_id.map(ToString::to_string),
+                condition,
+                created_by: Provenance::default(),
+            };
+            Ok(vec![
+                rule("low", Some("cold"), AlertCondition::Below(Balance::new(50))),
+                rule(
+                    "other",
+                    Some("hot"),
+                    AlertCondition::Below(Balance::new(50)),
+                ),
+                rule("swing", None, AlertCondition::Change { percent: 10 }),
+            ])
+        });
+
+        let evaluate = EvaluateExecutor {
+            alert_store: Arc::new(alert_store),
+        };
+
+        let changes = [BalanceChange {
+            name: "Cold".to_string(),
+            wallet_id: "cold".to_string(),
+            before: Balance::new(100),
+            after: Balance::new(40),
+        }];
+        let fired = evaluate.execute(&changes).await.unwrap();
+
+        let ids: Vec<_> = fired.iter().map(|f| f.rule.id.as_str()).collect();
+        assert_eq!(ids, ["low", "swing"]);
+    }
+}
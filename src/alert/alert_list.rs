@@ -0,0 +1,32 @@
+use std::{any::type_name, fmt, sync::Arc};
+
+use async_trait::async_trait;
+
+use super::Result;
+use crate::infra::{AlertRule, AlertStore};
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait List: Send + Sync + 'static {
+    async fn execute(&self) -> Result<Vec<AlertRule>>;
+}
+
+#[derive(Clone)]
+pub struct ListExecutor {
+    pub alert_store: Arc<dyn AlertStore>,
+}
+
+impl fmt::Debug for ListExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>()).finish()
+    }
+}
+
+#[async_trait]
+impl List for ListExecutor {
+    async fn execute(&self) -> Result<Vec<AlertRule>> {
+        let mut rules = self.alert_store.all_alerts().await?;
+        rules.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(rules)
+    }
+}
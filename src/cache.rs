@@ -0,0 +1,506 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    ops::Range,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::{
+    core::{Address, ChainId, Sample},
+    infra::{
+        AlertRule, AlertStore, HistoryStore, Insertion, OutboxEntry, OutboxStore, PreferenceStore,
+        SavedView, Store, StoreAdmin, StoreError, StoreInfo, StoreStats, TrashStore, TrashedRecord,
+        WalletChange, WalletRecord, WalletStore, WalletUpdate,
+    },
+};
+
+#[derive(Debug, Default)]
+pub struct CacheMetrics {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CacheTotals {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+}
+
+impl CacheMetrics {
+    pub fn totals(&self) -> CacheTotals {
+        CacheTotals {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+        }
+    }
+}
+
+pub struct CachedWalletStore<S: ?Sized> {
+    inner: Arc<S>,
+    capacity: usize,
+    state: Mutex<CacheState>,
+    metrics: Arc<CacheMetrics>,
+}
+
+#[derive(Debug, Default)]
+struct CacheState {
+    entries: HashMap<String, (u64, Option<WalletRecord>)>,
+    recency: BTreeMap<u64, String>,
+    all: Option<BTreeMap<String, WalletRecord>>,
+    generation: u64,
+    clock: u64,
+}
+
+impl CacheState {
+    fn get(&mut self, name: &str) -> Option<Option<WalletRecord>> {
+        let clock = self.tick();
+        let (used, record) = self.entries.get_mut(name)?;
+        self.recency.remove(used);
+        self.recency.insert(clock, name.to_owned());
+        *used = clock;
+        Some(record.clone())
+    }
+
+    fn put(&mut self, name: &str, record: Option<WalletRecord>, capacity: usize) -> u64 {
+        let clock = self.tick();
+        if let Some((used, _)) = self.entries.insert(name.to_owned(), (clock, record)) {
+            self.recency.remove(&used);
+        }
+        self.recency.insert(clock, name.to_owned());
+
+        let mut evicted = 0;
+        while self.entries.len() > capacity {
+            let Some((_, oldest)) = self.recency.pop_first() else {
+                break;
+            };
+            self.entries.remove(&oldest);
+            evicted += 1;
+        }
+        evicted
+    }
+
+    fn written(&mut self, name: &str, record: Option<WalletRecord>, capacity: usize) -> u64 {
+        self.generation += 1;
+        if let Some(all) = &mut self.all {
+            match &record {
+                Some(record) => all.insert(name.to_owned(), record.clone()),
+                None => all.remove(name),
+            };
+            if all.len() > capacity {
+                self.all = None;
+            }
+        }
+        self.put(name, record, capacity)
+    }
+
+    fn forget(&mut self, name: &str) {
+        self.generation += 1;
+        self.all = None;
+        if let Some((used, _)) = self.entries.remove(name) {
+            self.recency.remove(&used);
+        }
+    }
+
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+}
+
+impl<S: ?Sized> fmt::Debug for CachedWalletStore<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CachedWalletStore")
+            .field("capacity", &self.capacity)
+            .field("metrics", &self.metrics.totals())
+            .finish()
+    }
+}
+
+impl<S: Store + ?Sized> CachedWalletStore<S> {
+    pub fn new(inner: Arc<S>, capacity: usize) -> Self {
+        Self {
+            inner,
+            capacity,
+            state: Mutex::new(CacheState::default()),
+            metrics: Arc::new(CacheMetrics::default()),
+        }
+    }
+
+    pub fn metrics(&self) -> Arc<CacheMetrics> {
+        self.metrics.clone()
+    }
+
+    fn count(&self, counter: &AtomicU64, n: u64) {
+        counter.fetch_add(n, Ordering::Relaxed);
+    }
+
+    fn settle(&self, writes: &[(&str, Option<&WalletRecord>)], accepted: bool) {
+        let mut state = self.state.lock().unwrap();
+        for (name, record) in writes {
+            if accepted {
+                let evicted = state.written(name, record.cloned(), self.capacity);
+                self.count(&self.metrics.evictions, evicted);
+            } else {
+                state.forget(name);
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<S: Store + ?Sized> WalletStore for CachedWalletStore<S> {
+    async fn find(&self, name: &str) -> Result<Option<WalletRecord>, StoreError> {
+        let generation = {
+            let mut state = self.state.lock().unwrap();
+            if let Some(record) = state.get(name) {
+                self.count(&self.metrics.hits, 1);
+                return Ok(record);
+            }
+            state.generation
+        };
+
+        self.count(&self.metrics.misses, 1);
+        let record = self.inner.find(name).await?;
+        let mut state = self.state.lock().unwrap();
+        if state.generation == generation {
+            let evicted = state.put(name, record.clone(), self.capacity);
+            self.count(&self.metrics.evictions, evicted);
+        }
+        Ok(record)
+    }
+
+    async fn all(&self) -> Result<BTreeMap<String, WalletRecord>, StoreError> {
+        let generation = {
+            let state = self.state.lock().unwrap();
+            if let Some(all) = &state.all {
+                self.count(&self.metrics.hits, 1);
+                return Ok(all.clone());
+            }
+            state.generation
+        };
+
+        self.count(&self.metrics.misses, 1);
+        let all = self.inner.all().await?;
+        let mut state = self.state.lock().unwrap();
+        if state.generation == generation && all.len() <= self.capacity {
+            state.all = Some(all.clone());
+        }
+        Ok(all)
+    }
+
+    async fn exists(&self, name: &str) -> Result<bool, StoreError> {
+        Ok(self.find(name).await?.is_some())
+    }
+
+    async fn save(&self, name: &str, record: &WalletRecord) -> Result<(), StoreError> {
+        let result = self.inner.save(name, record).await;
+        self.settle(&[(name, Some(record))], result.is_ok());
+        result
+    }
+
+    async fn save_many(&self, wallets: &[(String, WalletRecord)]) -> Result<(), StoreError> {
+        let result = self.inner.save_many(wallets).await;
+        let writes: Vec<_> = wallets
+            .iter()
+            .map(|(name, record)| (name.as_str(), Some(record)))
+            .collect();
+        self.settle(&writes, result.is_ok());
+        result
+    }
+
+    async fn delete(&self, name: &str) -> Result<(), StoreError> {
+        let result = self.inner.delete(name).await;
+        self.settle(&[(name, None)], result.is_ok());
+        result
+    }
+
+    async fn delete_many(&self, names: &[String]) -> Result<(), StoreError> {
+        let result = self.inner.delete_many(names).await;
+        let writes: Vec<_> = names.iter().map(|name| (name.as_str(), None)).collect();
+        self.settle(&writes, result.is_ok());
+        result
+    }
+
+    async fn update(
+        &self,
+        name: &str,
+        update: WalletUpdate,
+    ) -> Result<Option<WalletRecord>, StoreError> {
+        let result = self.inner.update(name, update).await;
+        match &result {
+            Ok(Some(record)) => self.settle(&[(name, Some(record))], true),
+            Ok(None) => {}
+            Err(_) => self.settle(&[(name, None)], false),
+        }
+        result
+    }
+
+    async fn find_by_address(
+        &self,
+        chain_id: ChainId,
+        address: &Address,
+    ) -> Result<Option<(String, WalletRecord)>, StoreError> {
+        self.inner.find_by_address(chain_id, address).await
+    }
+
+    async fn count(&self) -> Result<usize, StoreError> {
+        self.inner.count().await
+    }
+
+    async fn insert_many(
+        &self,
+        wallets: &[(String, WalletRecord)],
+    ) -> Result<Vec<Insertion>, StoreError> {
+        let result = self.inner.insert_many(wallets).await;
+        match &result {
+            Ok(insertions) => {
+                let writes: Vec<_> = wallets
+                    .iter()
+                    .zip(insertions)
+                    .filter(|(_, insertion)| **insertion == Insertion::Saved)
+                    .map(|((name, record), _)| (name.as_str(), Some(record)))
+                    .collect();
+                self.settle(&writes, true);
+            }
+            Err(_) => {
+                let writes: Vec<_> = wallets
+                    .iter()
+                    .map(|(name, _)| (name.as_str(), None))
+                    .collect();
+                self.settle(&writes, false);
+            }
+        }
+        result
+    }
+
+    async fn search(
+        &self,
+        terms: &[String],
+        limit: usize,
+    ) -> Result<Vec<(String, WalletRecord)>, StoreError> {
+        self.inner.search(terms, limit).await
+    }
+    async fn transaction(&self, changes: &[WalletChange]) -> Result<(), StoreError> {
+        let result = self.inner.transaction(changes).await;
+        let writes: Vec<_> = changes
+            .iter()
+            .map(|change| match change {
+                WalletChange::Save { name, record } => (name.as_str(), Some(&**record)),
+                WalletChange::Delete { name } => (name.as_str(), None),
+            })
+            .collect();
+        self.settle(&writes, result.is_ok());
+        result
+    }
+}
+
+#[async_trait]
+impl<S: Store + ?Sized> TrashStore for CachedWalletStore<S> {
+    async fn find_trashed(&self, name: &str) -> Result<Option<TrashedRecord>, StoreError> {
+        self.inner.find_trashed(name).await
+    }
+
+    async fn all_trashed(&self) -> Result<BTreeMap<String, TrashedRecord>, StoreError> {
+        self.inner.all_trashed().await
+    }
+
+    async fn trash(&self, name: &str) -> Result<(), StoreError> {
+        let result = self.inner.trash(name).await;
+        self.settle(&[(name, None)], result.is_ok());
+        result
+    }
+
+    async fn restore(&self, name: &str) -> Result<(), StoreError> {
+        let result = self.inner.restore(name).await;
+        self.state.lock().unwrap().forget(name);
+        result
+    }
+
+    async fn purge(&self, name: &str) -> Result<(), StoreError> {
+        self.inner.purge(name).await
+    }
+    async fn save_trashed(&self, name: &str, trashed: &TrashedRecord) -> Result<(), StoreError> {
+        self.inner.save_trashed(name, trashed).await
+    }
+}
+
+#[async_trait]
+impl<S: Store + ?Sized> AlertStore for CachedWalletStore<S> {
+    async fn all_alerts(&self) -> Result<Vec<AlertRule>, StoreError> {
+        self.inner.all_alerts().await
+    }
+
+    async fn save_alert(&self, rule: &AlertRule) -> Result<(), StoreError> {
+        self.inner.save_alert(rule).await
+    }
+
+    async fn delete_alert(&self, id: &str) -> Result<bool, StoreError> {
+        self.inner.delete_alert(id).await
+    }
+}
+
+#[async_trait]
+impl<S: Store + ?Sized> PreferenceStore for CachedWalletStore<S> {
+    async fn pinned(&self, principal: &str) -> Result<Vec<String>, StoreError> {
+        self.inner.pinned(principal).await
+    }
+
+    async fn set_pinned(&self, principal: &str, wallet_ids: &[String]) -> Result<(), StoreError> {
+        self.inner.set_pinned(principal, wallet_ids).await
+    }
+
+    async fn views(&self, principal: &str) -> Result<Vec<SavedView>, StoreError> {
+        self.inner.views(principal).await
+    }
+
+    async fn save_view(&self, principal: &str, view: &SavedView) -> Result<(), StoreError> {
+        self.inner.save_view(principal, view).await
+    }
+
+    async fn delete_view(&self, principal: &str, name: &str) -> Result<bool, StoreError> {
+        self.inner.delete_view(principal, name).await
+    }
+}
+
+#[async_trait]
+impl<S: Store + ?Sized> OutboxStore for CachedWalletStore<S> {
+    async fn save_with_outbox(
+        &self,
+        name: &str,
+        record: &WalletRecord,
+        entry: &OutboxEntry,
+    ) -> Result<(), StoreError> {
+        let result = self.inner.save_with_outbox(name, record, entry).await;
+        self.settle(&[(name, Some(record))], result.is_ok());
+        result
+    }
+
+    async fn outbox(&self) -> Result<Vec<OutboxEntry>, StoreError> {
+        self.inner.outbox().await
+    }
+
+    async fn ack(&self, id: &str) -> Result<(), StoreError> {
+        self.inner.ack(id).await
+    }
+
+    async fn reschedule(
+        &self,
+        id: &str,
+        attempts: u32,
+        next_attempt_at: DateTime<Utc>,
+    ) -> Result<(), StoreError> {
+        self.inner.reschedule(id, attempts, next_attempt_at).await
+    }
+}
+
+#[async_trait]
+impl<S: Store + ?Sized> HistoryStore for CachedWalletStore<S> {
+    async fn append_samples(
+        &self,
+        samples: &[(String, Sample)],
+        keep: usize,
+    ) -> Result<(), StoreError> {
+        self.inner.append_samples(samples, keep).await
+    }
+
+    async fn replace_history(&self, wallet_id: &str, samples: &[Sample]) -> Result<(), StoreError> {
+        self.inner.replace_history(wallet_id, samples).await
+    }
+
+    async fn history(&self, wallet_id: &str) -> Result<Vec<Sample>, StoreError> {
+        self.inner.history(wallet_id).await
+    }
+
+    async fn history_in(
+        &self,
+        wallet_id: &str,
+        range: Range<i64>,
+    ) -> Result<Vec<Sample>, StoreError> {
+        self.inner.history_in(wallet_id, range).await
+    }
+}
+
+#[async_trait]
+impl<S: Store + ?Sized> StoreAdmin for CachedWalletStore<S> {
+    fn describe(&self) -> StoreInfo {
+        self.inner.describe()
+    }
+
+    async fn stats(&self) -> Result<StoreStats, StoreError> {
+        self.inner.stats().await
+    }
+
+    async fn compact(&self) -> Result<(), StoreError> {
+        self.inner.compact().await
+    }
+
+    async fn flush(&self) -> Result<(), StoreError> {
+        self.inner.flush().await
+    }
+
+    async fn backup(&self) -> Result<String, StoreError> {
+        self.inner.backup().await
+    }
+
+    async fn snapshot(&self, dest: &str) -> Result<(), StoreError> {
+        self.inner.snapshot(dest).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::{CacheTotals, CachedWalletStore};
+    use crate::{
+        core::{Address, Wallet},
+        infra::{TrashStore, WalletRecord, WalletStore},
+        memory::InMemoryWalletStore,
+    };
+
+    #[tokio::test]
+    async fn cache_write_through_and_evict() {
+        let inner = Arc::new(InMemoryWalletStore::new());
+        let store = CachedWalletStore::new(inner.clone(), 2);
+        let record = |byte| WalletRecord::new(Wallet::new(Address::new([byte; 20])));
+
+        // A miss caches the answer, even a missing wallet.
+        assert!(store.find("a").await.unwrap().is_none());
+        assert!(!store.exists("a").await.unwrap());
+        store.save("a", &record(1)).await.unwrap();
+        assert!(store.exists("a").await.unwrap());
+        assert_eq!(
+            store.metrics().totals(),
+            CacheTotals {
+                hits: 2,
+                misses: 1,
+                evictions: 0,
+            }
+        );
+
+        // The listing follows writes while it fits, then goes uncached.
+        assert_eq!(store.all().await.unwrap().len(), 1);
+        store.save("b", &record(2)).await.unwrap();
+        assert_eq!(store.all().await.unwrap().len(), 2);
+        store.save("c", &record(3)).await.unwrap();
+        assert_eq!(store.metrics().totals().evictions, 1);
+        assert_eq!(store.all().await.unwrap().len(), 3);
+
+        // A wallet restored from the trash is read back from the store.
+        store.trash("c").await.unwrap();
+        assert!(!store.exists("c").await.unwrap());
+        store.restore("c").await.unwrap();
+        let restored = store.find("c").await.unwrap().unwrap();
+        assert_eq!(*restored.wallet.address(), Address::new([3; 20]));
+        assert!(inner.exists("c").await.unwrap());
+    }
+}
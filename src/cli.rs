@@ -0,0 +1,630 @@
+use std::{
+    collections::BTreeSet,
+    error, fmt,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use tokio::io::AsyncWriteExt;
+use tonic::{Status, transport::Error as TransportError};
+use uuid::Uuid;
+
+use crate::{
+    admin::{Digest, DigestExecutor},
+    config::{Config, STORE_BACKENDS},
+    core::ChainId,
+    infra::{Store, StoreError},
+    rpc::{RpcError, RpcWalletClient},
+    secrets::SecretSource,
+    server::compose_error,
+    server::proto::{
+        ExportFormat, ExportRequest, ImportFormat, ImportRequest,
+        wallet_service_client::WalletServiceClient,
+    },
+    wallet,
+};
+
+#[derive(Debug)]
+pub struct CliError(Box<dyn error::Error + Send + Sync + 'static>);
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "command error")
+    }
+}
+
+impl error::Error for CliError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&*self.0)
+    }
+}
+
+impl From<TransportError> for CliError {
+    fn from(error: TransportError) -> Self {
+        Self(error.into())
+    }
+}
+
+impl From<RpcError> for CliError {
+    fn from(error: RpcError) -> Self {
+        Self(error.into())
+    }
+}
+
+impl From<Status> for CliError {
+    fn from(error: Status) -> Self {
+        Self(error.into())
+    }
+}
+
+impl From<StoreError> for CliError {
+    fn from(error: StoreError) -> Self {
+        Self(error.0)
+    }
+}
+
+pub async fn import(server: String, path: &Path, skip_balance: bool) -> Result<(), CliError> {
+    let content = tokio::fs::read_to_string(path)
+        .await
+        .map_err(|e| CliError(format!("couldn't read {}: {e}", path.display()).into()))?;
+    let is_json = path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("json"));
+    let format = if is_json {
+        ImportFormat::Json
+    } else {
+        ImportFormat::Csv
+    };
+
+    let mut client = WalletServiceClient::connect(server).await?;
+    let response = client
+        .import(ImportRequest {
+            format: Some(format.into()),
+            content: Some(content),
+            fetch_balance: Some(!skip_balance),
+        })
+        .await?
+        .into_inner();
+
+    for rejection in &response.rejected {
+        println!(
+            "row {}: skipped {:?}: {}",
+            rejection.row(),
+            rejection.name(),
+            rejection.reason()
+        );
+    }
+    println!(
+        "imported {} wallets, skipped {}",
+        response.imported.len(),
+        response.rejected.len()
+    );
+    Ok(())
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ExportOptions {
+    pub format: wallet::ExportFormat,
+    pub path: Option<PathBuf>,
+}
+
+impl ExportOptions {
+    pub fn parse(args: &[&str]) -> Result<Self, CliError> {
+        let mut format = None;
+        let mut path = None;
+        let mut args = args.iter();
+        while let Some(&arg) = args.next() {
+            match arg {
+                "--format" => {
+                    let name = args
+                        .next()
+                        .ok_or_else(|| CliError("--format needs a value".into()))?;
+                    let parsed = wallet::ExportFormat::from_name(name)
+                        .ok_or_else(|| CliError(format!("unknown export format {name}").into()))?;
+                    format = Some(parsed);
+                }
+                _ if arg.starts_with("--") || path.is_some() => {
+                    return Err(CliError(format!("unexpected export argument {arg}").into()));
+                }
+                _ => path = Some(PathBuf::from(arg)),
+            }
+        }
+
+        let format = format.unwrap_or_else(|| {
+            path.as_deref()
+                .and_then(Path::extension)
+                .and_then(|extension| extension.to_str())
+                .and_then(wallet::ExportFormat::from_name)
+                .unwrap_or_default()
+        });
+        Ok(Self { format, path })
+    }
+}
+
+pub async fn export(server: String, options: ExportOptions) -> Result<(), CliError> {
+    let format = match options.format {
+        wallet::ExportFormat::Csv => ExportFormat::Csv,
+        wallet::ExportFormat::Json => ExportFormat::Json,
+        wallet::ExportFormat::Ndjson => ExportFormat::Ndjson,
+    };
+
+    let mut client = WalletServiceClient::connect(server).await?;
+    let response = client
+        .export(ExportRequest {
+            format: Some(format.into()),
+        })
+        .await?
+        .into_inner();
+
+    match &options.path {
+        Some(path) => tokio::fs::write(path, response.content())
+            .await
+            .map_err(|e| CliError(format!("couldn't write {}: {e}", path.display()).into())),
+        None => {
+            print!("{}", response.content());
+            Ok(())
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MigrateOptions {
+    pub backend: String,
+    pub path: Option<String>,
+    pub url_env: Option<String>,
+}
+
+impl MigrateOptions {
+    pub fn parse(args: &[&str]) -> Result<Self, CliError> {
+        let mut options = Self::default();
+        let mut args = args.iter();
+        while let Some(&arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .map(|value| value.to_string())
+                    .ok_or_else(|| CliError(format!("{arg} needs a value").into()))
+            };
+            match arg {
+                "--path" => options.path = Some(value()?),
+                "--url-env" => options.url_env = Some(value()?),
+                _ if arg.starts_with("--") || !options.backend.is_empty() => {
+                    return Err(CliError(
+                        format!("unexpected migrate argument {arg}").into(),
+                    ));
+                }
+                _ => options.backend = arg.to_string(),
+            }
+        }
+
+        if !STORE_BACKENDS.contains(&options.backend.as_str()) {
+            return Err(CliError(
+                format!("migrate needs one of {}", STORE_BACKENDS.join(", ")).into(),
+            ));
+        }
+        let takes_url = matches!(options.backend.as_str(), "postgres" | "redis");
+        if !takes_url && options.path.is_none() {
+            return Err(CliError(
+                format!("migrating to {} needs --path", options.backend).into(),
+            ));
+        }
+        Ok(options)
+    }
+
+    pub fn target(&self, config: &Config) -> Result<Config, CliError> {
+        let mut target = config.clone();
+        target.store.backend = self.backend.clone();
+        if let Some(path) = &self.path {
+            if *path == config.store.path {
+                return Err(CliError(
+                    format!("{path} is the store being migrated").into(),
+                ));
+            }
+            target.store.path = path.clone();
+        }
+        if let Some(url_env) = &self.url_env {
+            target.store.url = SecretSource::Env(url_env.clone());
+        }
+        Ok(target)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Migration {
+    pub wallets: usize,
+    pub trashed: usize,
+    pub alerts: usize,
+    pub samples: usize,
+    pub digest: String,
+}
+
+pub async fn migrate(from: Arc<dyn Store>, to: Arc<dyn Store>) -> Result<Migration, CliError> {
+    if to.count().await? > 0 || !to.all_trashed().await?.is_empty() {
+        return Err(CliError("the store to migrate to isn't empty".into()));
+    }
+
+    let wallets = from.all().await?;
+    let trashed = from.all_trashed().await?;
+    let alerts = from.all_alerts().await?;
+    let ids: BTreeSet<_> = wallets
+        .values()
+        .chain(trashed.values().map(|trashed| &trashed.record))
+        .map(|record| record.id.clone())
+        .collect();
+
+    let batch: Vec<_> = wallets.clone().into_iter().collect();
+    to.save_many(&batch).await?;
+    for (name, trashed) in &trashed {
+        to.save_trashed(name, trashed).await?;
+    }
+    for rule in &alerts {
+        to.save_alert(rule).await?;
+    }
+    let mut samples = 0;
+    for id in &ids {
+        let history = from.history(id).await?;
+        if history.is_empty() {
+            continue;
+        }
+        samples += history.len();
+        let batch: Vec<_> = history
+            .into_iter()
+            .map(|sample| (id.clone(), sample))
+            .collect();
+        to.append_samples(&batch, batch.len()).await?;
+    }
+
+    let mut copied = Migration {
+        wallets: to.count().await?,
+        trashed: to.all_trashed().await?.len(),
+        alerts: to.all_alerts().await?.len(),
+        samples: 0,
+        digest: String::new(),
+    };
+    for id in &ids {
+        copied.samples += to.history(id).await?.len();
+    }
+    let counts = (wallets.len(), trashed.len(), alerts.len(), samples);
+    if (
+        copied.wallets,
+        copied.trashed,
+        copied.alerts,
+        copied.samples,
+    ) != counts
+    {
+        return Err(CliError(
+            format!(
+                "copied {} wallets, {} trashed, {} alerts, and {} samples of {}, {}, {}, and {}",
+                copied.wallets,
+                copied.trashed,
+                copied.alerts,
+                copied.samples,
+                counts.0,
+                counts.1,
+                counts.2,
+                counts.3
+            )
+            .into(),
+        ));
+    }
+
+    let digest = |store: Arc<dyn Store>| DigestExecutor {
+        wallet_store: store.clone(),
+        trash_store: store,
+    };
+    let expected = digest(from)
+        .execute()
+        .await
+        .map_err(|e| CliError(e.into()))?;
+    copied.digest = digest(to)
+        .execute()
+        .await
+        .map_err(|e| CliError(e.into()))?
+        .root;
+    if copied.digest != expected.root {
+        return Err(CliError(
+            format!(
+                "the copy's digest {} doesn't match the original's {}",
+                copied.digest, expected.root
+            )
+            .into(),
+        ));
+    }
+    Ok(copied)
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct InitOptions {
+    pub store_path: Option<String>,
+    pub rpc_url: Option<String>,
+    pub webhook_secret: Option<bool>,
+    pub yes: bool,
+    pub force: bool,
+}
+
+impl InitOptions {
+    pub fn parse(args: &[&str]) -> Result<Self, CliError> {
+        let mut options = Self::default();
+        let mut args = args.iter();
+        while let Some(&arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .map(|value| value.to_string())
+                    .ok_or_else(|| CliError(format!("{arg} needs a value").into()))
+            };
+            match arg {
+                "--store" => options.store_path = Some(value()?),
+                "--rpc-url" => options.rpc_url = Some(value()?),
+                "--webhook-secret" => options.webhook_secret = Some(true),
+                "--no-webhook-secret" => options.webhook_secret = Some(false),
+                "--yes" => options.yes = true,
+                "--force" => options.force = true,
+                _ => return Err(CliError(format!("unknown init flag {arg}").into())),
+            }
+        }
+        Ok(options)
+    }
+}
+
+pub async fn init(path: &Path, options: InitOptions) -> Result<(), CliError> {
+    if !options.force && tokio::fs::try_exists(path).await.unwrap_or(false) {
+        return Err(CliError(
+            format!(
+                "{} already exists; pass --force to replace it",
+                path.display()
+            )
+            .into(),
+        ));
+    }
+
+    let mut config = Config::default();
+    let mut prompter = Prompter { yes: options.yes };
+
+    config.store.path = match options.store_path {
+        Some(store_path) => store_path,
+        None => prompter.ask("Wallet store file", &config.store.path)?,
+    };
+    config.store.audit_path = Path::new(&config.store.path)
+        .with_extension("audit")
+        .to_string_lossy()
+        .into_owned();
+
+    config.upstream.rpc_url = match options.rpc_url {
+        Some(rpc_url) => rpc_url,
+        None => prompter.ask("Ethereum JSON-RPC endpoint", &config.upstream.rpc_url)?,
+    };
+    let chain_id = RpcWalletClient::new(&config.upstream.rpc_url)?
+        .chain_id()
+        .await;
+    match chain_id {
+        Ok(chain_id) => {
+            println!("{}: ok, chain id {chain_id}", config.upstream.rpc_url);
+            config.upstream.chain_id = ChainId::new(chain_id);
+        }
+        Err(e) => {
+            println!(
+                "{}: not reachable: {}",
+                config.upstream.rpc_url,
+                compose_error(&e)
+            );
+            if !prompter.confirm("Keep this endpoint anyway?", false)? {
+                return Err(e.into());
+            }
+        }
+    }
+
+    let webhook_secret = match options.webhook_secret {
+        Some(webhook_secret) => webhook_secret,
+        None => prompter.confirm("Generate a webhook signing secret?", false)?,
+    };
+    if webhook_secret {
+        let secret_path = path.with_file_name("webhook.secret");
+        write_secret(&secret_path).await?;
+        println!("wrote {}", secret_path.display());
+        config.webhook.secret = SecretSource::File(secret_path.to_string_lossy().into_owned());
+    }
+
+    tokio::fs::write(path, config.to_toml())
+        .await
+        .map_err(|e| CliError(format!("couldn't write {}: {e}", path.display()).into()))?;
+    println!("wrote {}", path.display());
+
+    println!();
+    println!("Start the server:");
+    println!("  MINI_WALLET_CONFIG={} mini-wallet", path.display());
+    println!("Track a first wallet:");
+    println!(
+        "  grpcurl -plaintext -d '{{\"name\": \"vitalik\", \"address\": \"vitalik.eth\"}}' \
+         127.0.0.1:{} wallet.v1.WalletService/Track",
+        config.server.port
+    );
+    println!("Or import a CSV of name,address rows:");
+    println!(
+        "  MINI_WALLET_CONFIG={} mini-wallet import wallets.csv",
+        path.display()
+    );
+    Ok(())
+}
+
+struct Prompter {
+    yes: bool,
+}
+
+impl Prompter {
+    fn ask(&mut self, question: &str, default: &str) -> Result<String, CliError> {
+        let answer = self.read(&format!("{question} [{default}]: "))?;
+        Ok(answer.unwrap_or_else(|| default.to_string()))
+    }
+
+    fn confirm(&mut self, question: &str, default: bool) -> Result<bool, CliError> {
+        let choices = if default { "Y/n" } else { "y/N" };
+        loop {
+            let Some(answer) = self.read(&format!("{question} [{choices}]: "))? else {
+                return Ok(default);
+            };
+            match answer.to_ascii_lowercase().as_str() {
+                "y" | "yes" => return Ok(true),
+                "n" | "no" => return Ok(false),
+                _ => println!("answer y or n"),
+            }
+        }
+    }
+
+    fn read(&mut self, prompt: &str) -> Result<Option<String>, CliError> {
+        if self.yes {
+            return Ok(None);
+        }
+
+        print!("{prompt}");
+        io::stdout().flush().map_err(|e| CliError(e.into()))?;
+        let mut line = String::new();
+        if io::stdin()
+            .read_line(&mut line)
+            .map_err(|e| CliError(e.into()))?
+            == 0
+        {
+            self.yes = true;
+            return Ok(None);
+        }
+
+        let line = line.trim();
+        Ok((!line.is_empty()).then(|| line.to_string()))
+    }
+}
+
+async fn write_secret(path: &Path) -> Result<(), CliError> {
+    let secret = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+
+    let mut options = tokio::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+    let mut file = options
+        .open(path)
+        .await
+        .map_err(|e| CliError(format!("couldn't write {}: {e}", path.display()).into()))?;
+    file.write_all(format!("{secret}\n").as_bytes())
+        .await
+        .map_err(|e| CliError(e.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::PathBuf, str::FromStr, sync::Arc};
+
+    use chrono::DateTime;
+
+    use super::{ExportOptions, InitOptions, MigrateOptions, migrate};
+    use crate::{
+        config::Config,
+        core::{Address, AlertCondition, Balance, Sample, Wallet},
+        infra::{
+            AlertRule, AlertStore, HistoryStore, Provenance, TrashStore, TrashedRecord,
+            WalletRecord, WalletStore,
+        },
+        memory::InMemoryWalletStore,
+        wallet::ExportFormat,
+    };
+
+    #[test]
+    fn cli_init_options() {
+        let options = InitOptions::parse(&[
+            "--store",
+            "/var/lib/mini-wallet/wallet.db",
+            "--no-webhook-secret",
+            "--yes",
+        ])
+        .unwrap();
+        assert_eq!(
+            options,
+            InitOptions {
+                store_path: Some("/var/lib/mini-wallet/wallet.db".to_string()),
+                webhook_secret: Some(false),
+                yes: true,
+                ..Default::default()
+            }
+        );
+
+        assert!(InitOptions::parse(&["--store"]).is_err());
+        assert!(InitOptions::parse(&["--tls"]).is_err());
+    }
+
+    #[test]
+    fn cli_export_options() {
+        let options = ExportOptions::parse(&["wallets.ndjson"]).unwrap();
+        assert_eq!(options.format, ExportFormat::Ndjson);
+
+        let options = ExportOptions::parse(&["--format", "json", "wallets.txt"]).unwrap();
+        assert_eq!(options.format, ExportFormat::Json);
+        assert_eq!(options.path, Some(PathBuf::from("wallets.txt")));
+
+        assert_eq!(ExportOptions::parse(&[]).unwrap(), ExportOptions::default());
+        assert!(ExportOptions::parse(&["--format", "xml"]).is_err());
+        assert!(ExportOptions::parse(&["a.csv", "b.csv"]).is_err());
+    }
+
+    #[test]
+    fn cli_migrate_options() {
+        let options = MigrateOptions::parse(&["sqlite", "--path", "wallet.sqlite"]).unwrap();
+        let config = Config::default();
+        let target = options.target(&config).unwrap();
+        assert_eq!(target.store.backend, "sqlite");
+        assert_eq!(target.store.path, "wallet.sqlite");
+
+        assert!(MigrateOptions::parse(&["postgres", "--url-env", "TARGET_URL"]).is_ok());
+        assert!(MigrateOptions::parse(&["sqlite"]).is_err());
+        assert!(MigrateOptions::parse(&["mysql", "--path", "wallet.db"]).is_err());
+
+        let options = MigrateOptions::parse(&["sqlite", "--path", &config.store.path]).unwrap();
+        assert!(options.target(&config).is_err());
+    }
+
+    #[tokio::test]
+    async fn cli_migrate_copies_store() {
+        let from = Arc::new(InMemoryWalletStore::new());
+        let address = Address::from_str("0xB644Babc370f46f202DB5eaf2071A9Ee66fA1D5E").unwrap();
+        let record = WalletRecord::new(Wallet::new(address));
+        let id = record.id.clone();
+        from.save("Vault", &record).await.unwrap();
+        let trashed_at = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let trashed = TrashedRecord {
+            record: WalletRecord::new(Wallet::new(address)),
+            trashed_at,
+        };
+        from.save_trashed("Old", &trashed).await.unwrap();
+        let rule = AlertRule {
+            id: "a1".to_string(),
+            wallet_id: Some(id.clone()),
+            condition: AlertCondition::Change { percent: 10 },
+            created_by: Provenance::default(),
+        };
+        from.save_alert(&rule).await.unwrap();
+        let samples: Vec<_> = (1..=3)
+            .map(|timestamp| {
+                let sample = Sample {
+                    timestamp,
+                    block_number: None,
+                    balance: Balance::new(1),
+                };
+                (id.clone(), sample)
+            })
+            .collect();
+        from.append_samples(&samples, 10).await.unwrap();
+
+        let to = Arc::new(InMemoryWalletStore::new());
+        let migration = migrate(from.clone(), to.clone()).await.unwrap();
+        assert_eq!(
+            (
+                migration.wallets,
+                migration.trashed,
+                migration.alerts,
+                migration.samples
+            ),
+            (1, 1, 1, 3)
+        );
+        let copied = to.find_trashed("Old").await.unwrap().unwrap();
+        assert_eq!(copied.trashed_at, trashed_at);
+
+        assert!(migrate(from, to).await.is_err());
+    }
+}
@@ -0,0 +1,11 @@
+mod client_breaker;
+mod client_budget;
+mod client_cache;
+mod client_failover;
+mod client_retry;
+
+pub use client_breaker::{CircuitBreakerClient, CircuitOpen};
+pub use client_budget::{BudgetedClient, UpstreamBudget};
+pub use client_cache::CachedWalletClient;
+pub use client_failover::FailoverWalletClient;
+pub use client_retry::{RetryBudget, RetryPolicy, RetryingWalletClient};
@@ -0,0 +1,198 @@
+use std::{
+    any::type_name,
+    error, fmt,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use tokio::time::Instant;
+use tracing::{info, warn};
+
+use super::client_retry::is_transient;
+use crate::{
+    core::{Address, Balance, U256},
+    infra::{ClientAdmin, ClientError, WalletClient},
+};
+
+#[derive(Debug)]
+pub struct CircuitOpen;
+
+impl fmt::Display for CircuitOpen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "upstream circuit is open after repeated failures")
+    }
+}
+
+impl error::Error for CircuitOpen {}
+
+pub struct CircuitBreakerClient<C> {
+    inner: Arc<C>,
+    failures: u32,
+    cooldown: Duration,
+    state: Mutex<Circuit>,
+}
+
+#[derive(Debug, Default)]
+struct Circuit {
+    failures: u32,
+    opened_at: Option<Instant>,
+}
+
+impl<C> fmt::Debug for CircuitBreakerClient<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>())
+            .field("failures", &self.failures)
+            .field("cooldown", &self.cooldown)
+            .finish()
+    }
+}
+
+impl<C> CircuitBreakerClient<C> {
+    pub fn new(inner: Arc<C>, failures: u32, cooldown: Duration) -> Self {
+        Self {
+            inner,
+            failures: failures.max(1),
+            cooldown,
+            state: Mutex::new(Circuit::default()),
+        }
+    }
+
+    fn admit(&self) -> bool {
+        let mut circuit = self.state.lock().unwrap();
+        match circuit.opened_at {
+            Some(opened_at) if opened_at.elapsed() < self.cooldown => false,
+            Some(_) => {
+                circuit.opened_at = Some(Instant::now());
+                true
+            }
+            None => true,
+        }
+    }
+
+    fn record<T>(&self, result: &Result<T, ClientError>) {
+        let mut circuit = self.state.lock().unwrap();
+        match result {
+            Err(error) if is_transient(error) => {
+                circuit.failures += 1;
+                let probing = circuit.opened_at.is_some();
+                if probing || circuit.failures >= self.failures {
+                    if !probing {
+                        warn!(
+                            failures = circuit.failures,
+                            "opened upstream circuit for {:?}", self.cooldown
+                        );
+                    }
+                    circuit.opened_at = Some(Instant::now());
+                }
+            }
+            _ => {
+                if circuit.opened_at.is_some() {
+                    info!("closed upstream circuit");
+                }
+                *circuit = Circuit::default();
+            }
+        }
+    }
+
+    async fn guard<T>(
+        &self,
+        call: impl Future<Output = Result<T, ClientError>>,
+    ) -> Result<T, ClientError> {
+        if !self.admit() {
+            return Err(ClientError(CircuitOpen.into()));
+        }
+        let result = call.await;
+        self.record(&result);
+        result
+    }
+}
+
+#[async_trait]
+impl<C: WalletClient> WalletClient for CircuitBreakerClient<C> {
+    async fn balance(&self, address: &Address) -> Result<Balance, ClientError> {
+        self.guard(self.inner.balance(address)).await
+    }
+
+    async fn balances(&self, addresses: &[Address]) -> Result<Vec<Balance>, ClientError> {
+        self.guard(self.inner.balances(addresses)).await
+    }
+
+    async fn block_number(&self) -> Result<u64, ClientError> {
+        self.guard(self.inner.block_number()).await
+    }
+
+    async fn transaction_count(&self, address: &Address) -> Result<u64, ClientError> {
+        self.guard(self.inner.transaction_count(address)).await
+    }
+
+    async fn token_balance(&self, address: &Address, token: &Address) -> Result<U256, ClientError> {
+        self.guard(self.inner.token_balance(address, token)).await
+    }
+}
+
+impl<C: ClientAdmin> ClientAdmin for CircuitBreakerClient<C> {
+    fn endpoint(&self) -> String {
+        self.inner.endpoint()
+    }
+
+    fn set_endpoint(&self, url: &str) {
+        self.inner.set_endpoint(url);
+        *self.state.lock().unwrap() = Circuit::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io, sync::Arc, time::Duration};
+
+    use crate::{
+        client::CircuitBreakerClient,
+        core::{Address, Balance},
+        infra::{ClientError, MockWalletClient, WalletClient},
+    };
+
+    fn timeout() -> ClientError {
+        ClientError(io::Error::from(io::ErrorKind::TimedOut).into())
+    }
+
+    #[tokio::test]
+    async fn client_breaker_opens_after_failures() {
+        let mut wallet_client = MockWalletClient::new();
+        wallet_client
+            .expect_balance()
+            .times(2)
+            .returning(|_| Err(timeout()));
+
+        let client = CircuitBreakerClient::new(Arc::new(wallet_client), 2, Duration::from_secs(60));
+
+        // The third call never reaches upstream.
+        let address = Address::new([0; 20]);
+        for _ in 0..3 {
+            assert!(client.balance(&address).await.is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn client_breaker_probe_closes() {
+        let mut wallet_client = MockWalletClient::new();
+        let mut seq = mockall::Sequence::new();
+        wallet_client
+            .expect_balance()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_| Err(timeout()));
+        wallet_client
+            .expect_balance()
+            .times(2)
+            .in_sequence(&mut seq)
+            .returning(|_| Ok(Balance::new(1)));
+
+        let client = CircuitBreakerClient::new(Arc::new(wallet_client), 1, Duration::ZERO);
+
+        let address = Address::new([0; 20]);
+        assert!(client.balance(&address).await.is_err());
+        assert_eq!(client.balance(&address).await.unwrap(), Balance::new(1));
+        assert_eq!(client.balance(&address).await.unwrap(), Balance::new(1));
+    }
+}
@@ -0,0 +1,221 @@
+use std::{any::type_name, fmt, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use tokio::{
+    sync::Mutex,
+    time::{Instant, sleep},
+};
+
+use crate::{
+    core::{Address, Balance, ChainId, Transfer, U256},
+    infra::{
+        ClientAdmin, ClientError, HistoryClient, NameResolver, PriceOracle, ReverseResolver,
+        WalletClient,
+    },
+    rpc::MULTICALL_CHUNK,
+};
+
+pub struct UpstreamBudget {
+    per_second: f64,
+    burst: f64,
+    bucket: Mutex<Bucket>,
+}
+
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl fmt::Debug for UpstreamBudget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>())
+            .field("per_second", &self.per_second)
+            .field("burst", &self.burst)
+            .finish()
+    }
+}
+
+impl UpstreamBudget {
+    pub fn new(per_second: u32, burst: u32) -> Self {
+        let burst = burst.max(1) as f64;
+        Self {
+            per_second: per_second.max(1) as f64,
+            burst,
+            bucket: Mutex::new(Bucket {
+                tokens: burst,
+                refilled_at: Instant::now(),
+            }),
+        }
+    }
+
+    pub async fn acquire(&self) {
+        loop {
+            let mut bucket = self.bucket.lock().await;
+
+            let now = Instant::now();
+            let elapsed = now.duration_since(bucket.refilled_at).as_secs_f64();
+            bucket.tokens = (bucket.tokens + elapsed * self.per_second).min(self.burst);
+            bucket.refilled_at = now;
+
+            if bucket.tokens >= 1.0 {
+                bucket.tokens -= 1.0;
+                return;
+            }
+
+            let wait = (1.0 - bucket.tokens) / self.per_second;
+            drop(bucket);
+            sleep(Duration::from_secs_f64(wait)).await;
+        }
+    }
+}
+
+pub struct BudgetedClient<C> {
+    pub inner: Arc<C>,
+    pub budget: Arc<UpstreamBudget>,
+}
+
+impl<C> fmt::Debug for BudgetedClient<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>()).finish()
+    }
+}
+
+#[async_trait]
+impl<C: WalletClient> WalletClient for BudgetedClient<C> {
+    async fn balance(&self, address: &Address) -> Result<Balance, ClientError> {
+        self.budget.acquire().await;
+        self.inner.balance(address).await
+    }
+
+    // The RPC client makes one Multicall3 request per chunk, so each chunk
+    // is its own call and pays on its own.
+    async fn balances(&self, addresses: &[Address]) -> Result<Vec<Balance>, ClientError> {
+        let mut balances = Vec::with_capacity(addresses.len());
+        for chunk in addresses.chunks(MULTICALL_CHUNK) {
+            self.budget.acquire().await;
+            balances.extend(self.inner.balances(chunk).await?);
+        }
+        Ok(balances)
+    }
+
+    async fn block_number(&self) -> Result<u64, ClientError> {
+        self.budget.acquire().await;
+        self.inner.block_number().await
+    }
+
+    async fn transaction_count(&self, address: &Address) -> Result<u64, ClientError> {
+        self.budget.acquire().await;
+        self.inner.transaction_count(address).await
+    }
+
+    async fn token_balance(&self, address: &Address, token: &Address) -> Result<U256, ClientError> {
+        self.budget.acquire().await;
+        self.inner.token_balance(address, token).await
+    }
+}
+
+#[async_trait]
+impl<C: HistoryClient> HistoryClient for BudgetedClient<C> {
+    async fn transfers(
+        &self,
+        address: &Address,
+        chain_id: ChainId,
+        limit: usize,
+    ) -> Result<Vec<Transfer>, ClientError> {
+        self.budget.acquire().await;
+        self.inner.transfers(address, chain_id, limit).await
+    }
+}
+
+#[async_trait]
+impl<C: NameResolver> NameResolver for BudgetedClient<C> {
+    async fn resolve(&self, name: &str) -> Result<Option<Address>, ClientError> {
+        self.budget.acquire().await;
+        self.inner.resolve(name).await
+    }
+}
+
+#[async_trait]
+impl<C: ReverseResolver> ReverseResolver for BudgetedClient<C> {
+    async fn reverse(&self, address: &Address) -> Result<Option<String>, ClientError> {
+        self.budget.acquire().await;
+        self.inner.reverse(address).await
+    }
+}
+
+#[async_trait]
+impl<C: PriceOracle> PriceOracle for BudgetedClient<C> {
+    async fn eth_usd_price(&self) -> Result<f64, ClientError> {
+        self.budget.acquire().await;
+        self.inner.eth_usd_price().await
+    }
+}
+
+impl<C: ClientAdmin> ClientAdmin for BudgetedClient<C> {
+    fn endpoint(&self) -> String {
+        self.inner.endpoint()
+    }
+
+    fn set_endpoint(&self, url: &str) {
+        self.inner.set_endpoint(url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use tokio::time::Instant;
+
+    use crate::{
+        client::{BudgetedClient, UpstreamBudget},
+        core::{Address, Balance},
+        infra::{MockWalletClient, WalletClient},
+        rpc::MULTICALL_CHUNK,
+    };
+
+    #[tokio::test]
+    async fn client_budget_paces_after_burst() {
+        let mut wallet_client = MockWalletClient::new();
+        wallet_client
+            .expect_balance()
+            .times(4)
+            .returning(|_| Ok(Balance::default()));
+
+        let client = BudgetedClient {
+            inner: Arc::new(wallet_client),
+            budget: Arc::new(UpstreamBudget::new(20, 2)),
+        };
+
+        let address = Address::new([0; 20]);
+        let started = Instant::now();
+        for _ in 0..4 {
+            client.balance(&address).await.unwrap();
+        }
+
+        // Two calls fit in the burst, the other two wait ~50ms each.
+        assert!(started.elapsed() >= Duration::from_millis(90));
+    }
+
+    #[tokio::test]
+    async fn client_budget_pays_per_balances_chunk() {
+        let mut wallet_client = MockWalletClient::new();
+        wallet_client
+            .expect_balances()
+            .times(3)
+            .returning(|addresses| Ok(vec![Balance::default(); addresses.len()]));
+
+        let client = BudgetedClient {
+            inner: Arc::new(wallet_client),
+            budget: Arc::new(UpstreamBudget::new(20, 1)),
+        };
+
+        let addresses = vec![Address::new([0; 20]); 2 * MULTICALL_CHUNK + 1];
+        let started = Instant::now();
+        let balances = client.balances(&addresses).await.unwrap();
+        assert_eq!(balances.len(), addresses.len());
+
+        // The first chunk fits in the burst, the other two wait ~50ms each.
+        assert!(started.elapsed() >= Duration::from_millis(90));
+    }
+}
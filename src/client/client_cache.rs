@@ -0,0 +1,153 @@
+use std::{
+    any::type_name,
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use tokio::time::Instant;
+
+use crate::{
+    core::{Address, Balance, U256},
+    infra::{ClientAdmin, ClientError, WalletClient},
+};
+
+pub struct CachedWalletClient<C> {
+    inner: Arc<C>,
+    ttl: Duration,
+    balances: Mutex<HashMap<Address, (Balance, Instant)>>,
+}
+
+impl<C> fmt::Debug for CachedWalletClient<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>())
+            .field("ttl", &self.ttl)
+            .finish()
+    }
+}
+
+impl<C> CachedWalletClient<C> {
+    pub fn new(inner: Arc<C>, ttl: Duration) -> Self {
+        Self {
+            inner,
+            ttl,
+            balances: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn cached(&self, address: &Address) -> Option<Balance> {
+        let balances = self.balances.lock().unwrap();
+        balances
+            .get(address)
+            .filter(|(_, fetched_at)| fetched_at.elapsed() < self.ttl)
+            .map(|(balance, _)| *balance)
+    }
+
+    fn insert(&self, address: Address, balance: Balance) {
+        let mut balances = self.balances.lock().unwrap();
+        balances.retain(|_, (_, fetched_at)| fetched_at.elapsed() < self.ttl);
+        balances.insert(address, (balance, Instant::now()));
+    }
+}
+
+#[async_trait]
+impl<C: WalletClient> WalletClient for CachedWalletClient<C> {
+    async fn balance(&self, address: &Address) -> Result<Balance, ClientError> {
+        if self.ttl.is_zero() {
+            return self.inner.balance(address).await;
+        }
+        if let Some(balance) = self.cached(address) {
+            return Ok(balance);
+        }
+
+        let balance = self.inner.balance(address).await?;
+        self.insert(*address, balance);
+        Ok(balance)
+    }
+
+    async fn balances(&self, addresses: &[Address]) -> Result<Vec<Balance>, ClientError> {
+        if self.ttl.is_zero() {
+            return self.inner.balances(addresses).await;
+        }
+
+        let mut balances: Vec<_> = addresses
+            .iter()
+            .map(|address| self.cached(address))
+            .collect();
+        let missing: Vec<_> = addresses
+            .iter()
+            .zip(&balances)
+            .filter(|(_, balance)| balance.is_none())
+            .map(|(address, _)| *address)
+            .collect();
+        if !missing.is_empty() {
+            let mut fetched = self.inner.balances(&missing).await?.into_iter();
+            for (address, balance) in addresses.iter().zip(&mut balances) {
+                if balance.is_none() {
+                    let value = fetched
+                        .next()
+                        .ok_or(ClientError("fewer balances than asked for".into()))?;
+                    self.insert(*address, value);
+                    *balance = Some(value);
+                }
+            }
+        }
+        Ok(balances.into_iter().flatten().collect())
+    }
+
+    async fn block_number(&self) -> Result<u64, ClientError> {
+        self.inner.block_number().await
+    }
+
+    async fn transaction_count(&self, address: &Address) -> Result<u64, ClientError> {
+        self.inner.transaction_count(address).await
+    }
+
+    async fn token_balance(&self, address: &Address, token: &Address) -> Result<U256, ClientError> {
+        self.inner.token_balance(address, token).await
+    }
+}
+
+impl<C: ClientAdmin> ClientAdmin for CachedWalletClient<C> {
+    fn endpoint(&self) -> String {
+        self.inner.endpoint()
+    }
+
+    fn set_endpoint(&self, url: &str) {
+        self.inner.set_endpoint(url);
+        self.balances.lock().unwrap().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use crate::{
+        client::CachedWalletClient,
+        core::{Address, Balance},
+        infra::{MockWalletClient, WalletClient},
+    };
+
+    #[tokio::test]
+    async fn client_cache_reuses_until_expired() {
+        let mut wallet_client = MockWalletClient::new();
+        wallet_client
+            .expect_balance()
+            .times(3)
+            .returning(|_| Ok(Balance::new(1)));
+
+        let client = CachedWalletClient::new(Arc::new(wallet_client), Duration::from_millis(50));
+
+        // One fetch per address, then another once the first has expired.
+        let first = Address::new([1; 20]);
+        let second = Address::new([2; 20]);
+        for address in [&first, &first, &second] {
+            assert_eq!(client.balance(address).await.unwrap(), Balance::new(1));
+        }
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        client.balance(&first).await.unwrap();
+    }
+}
@@ -0,0 +1,190 @@
+use std::{
+    any::type_name,
+    fmt,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use tokio::time::Instant;
+use tracing::warn;
+
+use super::client_retry::is_transient;
+use crate::{
+    core::{Address, Balance, U256},
+    infra::{ClientAdmin, ClientError, WalletClient},
+};
+
+pub struct FailoverWalletClient<C> {
+    endpoints: Vec<Endpoint<C>>,
+    cooldown: Duration,
+}
+
+struct Endpoint<C> {
+    client: Arc<C>,
+    failed_at: Mutex<Option<Instant>>,
+}
+
+impl<C> fmt::Debug for FailoverWalletClient<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>())
+            .field("endpoints", &self.endpoints.len())
+            .field("cooldown", &self.cooldown)
+            .finish()
+    }
+}
+
+impl<C> FailoverWalletClient<C> {
+    pub fn new(clients: Vec<Arc<C>>, cooldown: Duration) -> Self {
+        assert!(!clients.is_empty(), "failover needs at least one endpoint");
+        let endpoints = clients
+            .into_iter()
+            .map(|client| Endpoint {
+                client,
+                failed_at: Mutex::new(None),
+            })
+            .collect();
+        Self {
+            endpoints,
+            cooldown,
+        }
+    }
+
+    fn healthy(&self, endpoint: &Endpoint<C>) -> bool {
+        endpoint
+            .failed_at
+            .lock()
+            .unwrap()
+            .is_none_or(|failed_at| failed_at.elapsed() >= self.cooldown)
+    }
+
+    fn ordered(&self) -> Vec<&Endpoint<C>> {
+        let (mut ordered, cooling): (Vec<_>, Vec<_>) = self
+            .endpoints
+            .iter()
+            .partition(|endpoint| self.healthy(endpoint));
+        ordered.extend(cooling);
+        ordered
+    }
+
+    async fn failover<'a, T, F, Fut>(&'a self, call: F) -> Result<T, ClientError>
+    where
+        F: Fn(&'a C) -> Fut,
+        Fut: Future<Output = Result<T, ClientError>>,
+    {
+        let mut last_error = None;
+        for (i, endpoint) in self.ordered().into_iter().enumerate() {
+            match call(&endpoint.client).await {
+                Ok(value) => {
+                    *endpoint.failed_at.lock().unwrap() = None;
+                    return Ok(value);
+                }
+                Err(error) if is_transient(&error) => {
+                    if i + 1 < self.endpoints.len() {
+                        warn!("upstream endpoint failed, trying the next");
+                    }
+                    *endpoint.failed_at.lock().unwrap() = Some(Instant::now());
+                    last_error = Some(error);
+                }
+                // An answer, just not a good one. Another endpoint would
+                // say the same.
+                Err(error) => return Err(error),
+            }
+        }
+        Err(last_error.expect("at least one endpoint"))
+    }
+}
+
+#[async_trait]
+impl<C: WalletClient> WalletClient for FailoverWalletClient<C> {
+    async fn balance(&self, address: &Address) -> Result<Balance, ClientError> {
+        self.failover(|client| client.balance(address)).await
+    }
+
+    async fn balances(&self, addresses: &[Address]) -> Result<Vec<Balance>, ClientError> {
+        self.failover(|client| client.balances(addresses)).await
+    }
+
+    async fn block_number(&self) -> Result<u64, ClientError> {
+        self.failover(|client| client.block_number()).await
+    }
+
+    async fn transaction_count(&self, address: &Address) -> Result<u64, ClientError> {
+        self.failover(|client| client.transaction_count(address))
+            .await
+    }
+
+    async fn token_balance(&self, address: &Address, token: &Address) -> Result<U256, ClientError> {
+        self.failover(|client| client.token_balance(address, token))
+            .await
+    }
+}
+
+impl<C: ClientAdmin> ClientAdmin for FailoverWalletClient<C> {
+    fn endpoint(&self) -> String {
+        self.ordered()[0].client.endpoint()
+    }
+
+    fn set_endpoint(&self, url: &str) {
+        let primary = &self.endpoints[0];
+        primary.client.set_endpoint(url);
+        *primary.failed_at.lock().unwrap() = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io, sync::Arc, time::Duration};
+
+    use crate::{
+        client::FailoverWalletClient,
+        core::{Address, Balance},
+        infra::{ClientError, MockWalletClient, WalletClient},
+    };
+
+    fn timeout() -> ClientError {
+        ClientError(io::Error::from(io::ErrorKind::TimedOut).into())
+    }
+
+    #[tokio::test]
+    async fn client_failover_prefers_working_endpoint() {
+        let mut primary = MockWalletClient::new();
+        primary
+            .expect_balance()
+            .times(1)
+            .returning(|_| Err(timeout()));
+        let mut fallback = MockWalletClient::new();
+        fallback
+            .expect_balance()
+            .times(2)
+            .returning(|_| Ok(Balance::new(3)));
+
+        let client = FailoverWalletClient::new(
+            vec![Arc::new(primary), Arc::new(fallback)],
+            Duration::from_secs(60),
+        );
+
+        // The second call skips the primary while it cools down.
+        let address = Address::new([0; 20]);
+        assert_eq!(client.balance(&address).await.unwrap(), Balance::new(3));
+        assert_eq!(client.balance(&address).await.unwrap(), Balance::new(3));
+    }
+
+    #[tokio::test]
+    async fn client_failover_keeps_permanent_errors() {
+        let mut primary = MockWalletClient::new();
+        primary
+            .expect_block_number()
+            .times(1)
+            .returning(|| Err(ClientError("missing result field".into())));
+        let mut fallback = MockWalletClient::new();
+        fallback.expect_block_number().never();
+
+        let client = FailoverWalletClient::new(
+            vec![Arc::new(primary), Arc::new(fallback)],
+            Duration::from_secs(60),
+        );
+
+        assert!(client.block_number().await.is_err());
+    }
+}
@@ -0,0 +1,239 @@
+use std::{
+    any::type_name,
+    error::Error,
+    fmt, io,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use tokio::time::sleep;
+use tracing::debug;
+
+use crate::{
+    core::{Address, Balance, U256},
+    infra::{ClientAdmin, ClientError, WalletClient},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub retries: u32,
+    pub backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    fn delay(&self, retry: u32) -> Duration {
+        let ceiling = self
+            .backoff
+            .saturating_mul(2u32.saturating_pow(retry.saturating_sub(1)))
+            .min(self.max_backoff);
+        ceiling.mul_f64(fastrand::f64())
+    }
+}
+
+#[derive(Debug)]
+pub struct RetryBudget {
+    ratio: f64,
+    max_tokens: f64,
+    tokens: Mutex<f64>,
+}
+
+impl RetryBudget {
+    pub fn new(ratio: f64, max_tokens: u32) -> Self {
+        let max_tokens = max_tokens as f64;
+        Self {
+            ratio: ratio.max(0.0),
+            max_tokens,
+            tokens: Mutex::new(max_tokens),
+        }
+    }
+
+    fn deposit(&self) {
+        let mut tokens = self.tokens.lock().unwrap();
+        *tokens = (*tokens + self.ratio).min(self.max_tokens);
+    }
+
+    fn withdraw(&self) -> bool {
+        let mut tokens = self.tokens.lock().unwrap();
+        if *tokens < 1.0 {
+            return false;
+        }
+        *tokens -= 1.0;
+        true
+    }
+}
+
+pub struct RetryingWalletClient<C> {
+    pub inner: Arc<C>,
+    pub policy: RetryPolicy,
+    pub budget: Arc<RetryBudget>,
+}
+
+impl<C> fmt::Debug for RetryingWalletClient<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>())
+            .field("policy", &self.policy)
+            .finish()
+    }
+}
+
+impl<C> RetryingWalletClient<C> {
+    async fn retry<T, F, Fut>(&self, call: F) -> Result<T, ClientError>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, ClientError>>,
+    {
+        self.budget.deposit();
+        let mut retry = 0;
+        loop {
+            let error = match call().await {
+                Ok(value) => return Ok(value),
+                Err(error) => error,
+            };
+            if retry >= self.policy.retries || !is_transient(&error) || !self.budget.withdraw() {
+                return Err(error);
+            }
+
+            retry += 1;
+            let delay = self.policy.delay(retry);
+            debug!("upstream call failed, retry {retry} in {delay:?}");
+            sleep(delay).await;
+        }
+    }
+}
+
+#[async_trait]
+impl<C: WalletClient> WalletClient for RetryingWalletClient<C> {
+    async fn balance(&self, address: &Address) -> Result<Balance, ClientError> {
+        self.retry(|| self.inner.balance(address)).await
+    }
+
+    async fn balances(&self, addresses: &[Address]) -> Result<Vec<Balance>, ClientError> {
+        self.retry(|| self.inner.balances(addresses)).await
+    }
+
+    async fn block_number(&self) -> Result<u64, ClientError> {
+        self.retry(|| self.inner.block_number()).await
+    }
+
+    async fn transaction_count(&self, address: &Address) -> Result<u64, ClientError> {
+        self.retry(|| self.inner.transaction_count(address)).await
+    }
+
+    async fn token_balance(&self, address: &Address, token: &Address) -> Result<U256, ClientError> {
+        self.retry(|| self.inner.token_balance(address, token))
+            .await
+    }
+}
+
+impl<C: ClientAdmin> ClientAdmin for RetryingWalletClient<C> {
+    fn endpoint(&self) -> String {
+        self.inner.endpoint()
+    }
+
+    fn set_endpoint(&self, url: &str) {
+        self.inner.set_endpoint(url)
+    }
+}
+
+pub(super) fn is_transient(error: &ClientError) -> bool {
+    let mut next: Option<&(dyn Error + 'static)> = Some(error.0.as_ref());
+    while let Some(error) = next {
+        if let Some(error) = error.downcast_ref::<reqwest::Error>() {
+            return error.is_timeout()
+                || error.is_connect()
+                || error
+                    .status()
+                    .is_some_and(|status| status.is_server_error());
+        }
+        if let Some(error) = error.downcast_ref::<io::Error>() {
+            return matches!(
+                error.kind(),
+                io::ErrorKind::TimedOut
+                    | io::ErrorKind::ConnectionReset
+                    | io::ErrorKind::ConnectionAborted
+                    | io::ErrorKind::BrokenPipe
+                    | io::ErrorKind::UnexpectedEof
+            );
+        }
+        next = error.source();
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io, sync::Arc, time::Duration};
+
+    use crate::{
+        client::{RetryBudget, RetryPolicy, RetryingWalletClient},
+        core::{Address, Balance},
+        infra::{ClientError, MockWalletClient, WalletClient},
+    };
+
+    fn reset() -> ClientError {
+        ClientError(io::Error::from(io::ErrorKind::ConnectionReset).into())
+    }
+
+    fn client(
+        wallet_client: MockWalletClient,
+        budget: RetryBudget,
+    ) -> RetryingWalletClient<MockWalletClient> {
+        RetryingWalletClient {
+            inner: Arc::new(wallet_client),
+            policy: RetryPolicy {
+                retries: 3,
+                backoff: Duration::from_millis(1),
+                max_backoff: Duration::from_millis(5),
+            },
+            budget: Arc::new(budget),
+        }
+    }
+
+    #[tokio::test]
+    async fn client_retry_transient_then_succeeds() {
+        let mut wallet_client = MockWalletClient::new();
+        let mut seq = mockall::Sequence::new();
+        wallet_client
+            .expect_balance()
+            .times(2)
+            .in_sequence(&mut seq)
+            .returning(|_| Err(reset()));
+        wallet_client
+            .expect_balance()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_| Ok(Balance::new(7)));
+
+        let client = client(wallet_client, RetryBudget::new(0.1, 10));
+        let balance = client.balance(&Address::new([0; 20])).await.unwrap();
+        assert_eq!(balance, Balance::new(7));
+    }
+
+    #[tokio::test]
+    async fn client_retry_skips_permanent_errors() {
+        let mut wallet_client = MockWalletClient::new();
+        wallet_client
+            .expect_block_number()
+            .times(1)
+            .returning(|| Err(ClientError("missing result field".into())));
+
+        let client = client(wallet_client, RetryBudget::new(0.1, 10));
+        assert!(client.block_number().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn client_retry_stops_when_budget_spent() {
+        let mut wallet_client = MockWalletClient::new();
+        wallet_client
+            .expect_block_number()
+            .times(3)
+            .returning(|| Err(reset()));
+
+        // One token to start: the first call retries once, the second can't.
+        let client = client(wallet_client, RetryBudget::new(0.0, 1));
+        assert!(client.block_number().await.is_err());
+        assert!(client.block_number().await.is_err());
+    }
+}
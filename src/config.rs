@@ -0,0 +1,1539 @@
+use std::{
+    error, fmt, io,
+    net::{IpAddr, Ipv4Addr},
+    path::Path,
+    time::Duration,
+};
+
+use std::str::FromStr;
+
+use chrono::NaiveTime;
+use directories::ProjectDirs;
+use toml::{Table, Value};
+
+use crate::{
+    core::{Address, ChainId, Collection, MAX_TOKEN_DECIMALS, RetentionTier, Token},
+    payload,
+    secrets::SecretSource,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub server: ServerConfig,
+    pub store: StoreConfig,
+    pub upstream: UpstreamConfig,
+    pub refresh: RefreshConfig,
+    pub display: DisplayConfig,
+    pub trash: TrashConfig,
+    pub compaction: CompactionConfig,
+    pub metrics: MetricsConfig,
+    pub history: HistoryConfig,
+    pub price: PriceConfig,
+    pub tokens: TokensConfig,
+    pub nfts: NftsConfig,
+    pub transactions: TransactionsConfig,
+    pub donations: DonationsConfig,
+    pub report: ReportConfig,
+    pub webhook: WebhookConfig,
+    pub events: EventsConfig,
+    pub secrets: SecretsConfig,
+    pub log: LogConfig,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerConfig {
+    pub addr: IpAddr,
+    pub port: u16,
+    pub http_port: u16,
+    pub admin_port: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoreConfig {
+    pub backend: String,
+    pub path: String,
+    pub url: SecretSource,
+    pub pool_size: u32,
+    pub cache_size: usize,
+    pub ttl: Duration,
+    pub audit_path: String,
+    pub backups: u32,
+    pub compression_level: i32,
+    pub flush_interval: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpstreamConfig {
+    pub rpc_url: String,
+    pub chain_id: ChainId,
+    pub fallback_rpc_urls: Vec<String>,
+    pub rate: u32,
+    pub burst: u32,
+    pub retries: u32,
+    pub breaker_failures: u32,
+    pub breaker_cooldown: Duration,
+    pub balance_ttl: Duration,
+    pub multicall: bool,
+    pub unstoppable_api_key: SecretSource,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RefreshConfig {
+    pub interval: Duration,
+    pub jitter: Duration,
+    pub freshness: Duration,
+    pub get_after: Option<Duration>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisplayConfig {
+    pub unit: String,
+    pub decimals: u32,
+    pub thousands_separator: Option<char>,
+    pub stale_after: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrashConfig {
+    pub retention: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompactionConfig {
+    pub window_start: NaiveTime,
+    pub window_end: NaiveTime,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetricsConfig {
+    pub max_series: usize,
+    pub wallets: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryConfig {
+    pub max_samples: usize,
+    pub raw_for: Duration,
+    pub hourly_for: Duration,
+}
+
+impl HistoryConfig {
+    pub fn retention(&self) -> Vec<RetentionTier> {
+        let age = |duration: Duration| i64::try_from(duration.as_secs()).unwrap_or(i64::MAX);
+        vec![
+            RetentionTier {
+                max_age: Some(age(self.raw_for)),
+                resolution: 0,
+            },
+            RetentionTier {
+                max_age: Some(age(self.hourly_for.max(self.raw_for))),
+                resolution: 60 * 60,
+            },
+            RetentionTier {
+                max_age: None,
+                resolution: DAY_SECS as i64,
+            },
+        ]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PriceConfig {
+    pub provider: String,
+    pub coingecko_url: String,
+    pub cache: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokensConfig {
+    pub erc20: Vec<Token>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NftsConfig {
+    pub erc721: Vec<Collection>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionsConfig {
+    pub provider: String,
+    pub etherscan_url: String,
+    pub etherscan_api_key: SecretSource,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DonationsConfig {
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportConfig {
+    pub interval: Duration,
+    pub format: String,
+    pub dir: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookConfig {
+    pub urls: Vec<String>,
+    pub schema_version: u32,
+    pub secret: SecretSource,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventsConfig {
+    pub capacity: usize,
+    pub overflow: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecretsConfig {
+    pub provider: String,
+    pub vault_addr: String,
+    pub vault_token_env: String,
+    pub sops_file: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogConfig {
+    pub level: String,
+}
+
+const LOG_LEVELS: &[&str] = &["error", "warn", "info", "debug", "trace"];
+const OVERFLOWS: &[&str] = &["drop_oldest", "disconnect"];
+const SECRETS_PROVIDERS: &[&str] = &["none", "vault", "sops"];
+const PRICE_PROVIDERS: &[&str] = &["none", "coingecko"];
+pub(crate) const STORE_BACKENDS: &[&str] = &[
+    "fs", "sharded", "sqlite", "postgres", "redis", "sled", "rocksdb",
+];
+const TRANSACTIONS_PROVIDERS: &[&str] = &["none", "etherscan"];
+const REPORT_FORMATS: &[&str] = &["csv", "json", "ndjson"];
+const BALANCE_UNITS: &[&str] = &["eth", "gwei", "wei"];
+const DAY_SECS: u64 = 24 * 60 * 60;
+
+fn data_file(name: &str) -> String {
+    let dir = ProjectDirs::from("", "", "mini-wallet")
+        .map(|dirs| dirs.data_dir().to_path_buf())
+        .unwrap_or_default();
+    dir.join(name).to_string_lossy().into_owned()
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            server: ServerConfig {
+                addr: Ipv4Addr::UNSPECIFIED.into(),
+                port: 50051,
+                http_port: 9100,
+                admin_port: 50052,
+            },
+            store: StoreConfig {
+                backend: "fs".to_string(),
+                path: data_file("wallet.db"),
+                url: SecretSource::Env("DATABASE_URL".to_string()),
+                pool_size: 10,
+                cache_size: 0,
+                ttl: Duration::ZERO,
+                audit_path: data_file("wallet.audit"),
+                backups: 3,
+                compression_level: 0,
+                flush_interval: Duration::ZERO,
+            },
+            upstream: UpstreamConfig {
+                rpc_url: "https://eth.llamarpc.com".to_string(),
+                chain_id: ChainId::MAINNET,
+                fallback_rpc_urls: vec![],
+                rate: 10,
+                burst: 20,
+                retries: 2,
+                breaker_failures: 5,
+                breaker_cooldown: Duration::from_secs(30),
+                balance_ttl: Duration::ZERO,
+                multicall: false,
+                unstoppable_api_key: SecretSource::Env("UNSTOPPABLE_API_KEY".to_string()),
+            },
+            refresh: RefreshConfig {
+                interval: Duration::from_secs(60),
+                jitter: Duration::from_secs(10),
+                freshness: Duration::from_secs(30),
+                get_after: Some(Duration::from_secs(5 * 60)),
+            },
+            display: DisplayConfig {
+                unit: "eth".to_string(),
+                decimals: 18,
+                thousands_separator: None,
+                stale_after: Duration::from_secs(5 * 60),
+            },
+            trash: TrashConfig {
+                retention: Duration::from_secs(30 * DAY_SECS),
+            },
+            compaction: CompactionConfig {
+                window_start: NaiveTime::from_hms_opt(3, 0, 0).unwrap(),
+                window_end: NaiveTime::from_hms_opt(5, 0, 0).unwrap(),
+            },
+            metrics: MetricsConfig {
+                max_series: 1000,
+                wallets: vec![],
+            },
+            history: HistoryConfig {
+                max_samples: 10_000,
+                raw_for: Duration::from_secs(7 * DAY_SECS),
+                hourly_for: Duration::from_secs(90 * DAY_SECS),
+            },
+            price: PriceConfig {
+                provider: "none".to_string(),
+                coingecko_url: "https://api.coingecko.com/api/v3".to_string(),
+                cache: Duration::from_secs(60),
+            },
+            tokens: TokensConfig { erc20: vec![] },
+            nfts: NftsConfig { erc721: vec![] },
+            transactions: TransactionsConfig {
+                provider: "none".to_string(),
+                etherscan_url: "https://api.etherscan.io/v2/api".to_string(),
+                etherscan_api_key: SecretSource::Env("ETHERSCAN_API_KEY".to_string()),
+            },
+            donations: DonationsConfig {
+                tags: vec!["donation".to_string()],
+            },
+            report: ReportConfig {
+                interval: Duration::ZERO,
+                format: "csv".to_string(),
+                dir: "reports".to_string(),
+            },
+            webhook: WebhookConfig {
+                urls: vec![],
+                schema_version: payload::SCHEMA_VERSION,
+                secret: SecretSource::Env("WEBHOOK_SECRET".to_string()),
+            },
+            events: EventsConfig {
+                capacity: 1024,
+                overflow: "drop_oldest".to_string(),
+            },
+            secrets: SecretsConfig {
+                provider: "none".to_string(),
+                vault_addr: "http://127.0.0.1:8200".to_string(),
+                vault_token_env: "VAULT_TOKEN".to_string(),
+                sops_file: "secrets.enc.json".to_string(),
+            },
+            log: LogConfig {
+                level: "info".to_string(),
+            },
+        }
+    }
+}
+
+impl Config {
+    pub async fn load(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        match tokio::fs::read_to_string(path).await {
+            Ok(text) => Self::parse(&text),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(ConfigError {
+                kind: ConfigErrorKind::Read,
+                diagnostics: vec![],
+                source: Some(e.into()),
+            }),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Self, ConfigError> {
+        let root: Table = text.parse().map_err(|e: toml::de::Error| ConfigError {
+            kind: ConfigErrorKind::Syntax,
+            diagnostics: vec![],
+            source: Some(e.into()),
+        })?;
+
+        let defaults = Self::default();
+        let mut reader = Reader::new(&root);
+        let config = Self {
+            server: ServerConfig {
+                addr: reader.addr("server.addr", defaults.server.addr),
+                port: reader.integer("server.port", 1, 65535, defaults.server.port),
+                http_port: reader.integer("server.http_port", 1, 65535, defaults.server.http_port),
+                admin_port: reader.integer(
+                    "server.admin_port",
+                    1,
+                    65535,
+                    defaults.server.admin_port,
+                ),
+            },
+            store: StoreConfig {
+                backend: reader.choice("store.backend", STORE_BACKENDS, defaults.store.backend),
+                path: reader.string("store.path", defaults.store.path),
+                url: reader.secret(
+                    ["store.url_file", "store.url_env", "store.url_ref"],
+                    defaults.store.url,
+                ),
+                pool_size: reader.integer("store.pool_size", 1, 1000, defaults.store.pool_size),
+                cache_size: reader.integer(
+                    "store.cache_size",
+                    0,
+                    1_000_000,
+                    defaults.store.cache_size,
+                ),
+                ttl: reader.seconds("store.ttl_secs", 0, 365 * DAY_SECS, defaults.store.ttl),
+                audit_path: reader.string("store.audit_path", defaults.store.audit_path),
+                backups: reader.integer("store.backups", 0, 100, defaults.store.backups),
+                compression_level: reader.integer(
+                    "store.compression_level",
+                    0,
+                    22,
+                    defaults.store.compression_level,
+                ),
+                flush_interval: Duration::from_millis(reader.integer(
+                    "store.flush_interval_ms",
+                    0,
+                    60_000,
+                    defaults.store.flush_interval.as_millis() as u64,
+                )),
+            },
+            upstream: UpstreamConfig {
+                rpc_url: reader.url("upstream.rpc_url", defaults.upstream.rpc_url),
+                chain_id: ChainId::new(reader.integer(
+                    "upstream.chain_id",
+                    1,
+                    i64::MAX,
+                    defaults.upstream.chain_id.get(),
+                )),
+                fallback_rpc_urls: reader.urls(
+                    "upstream.fallback_rpc_urls",
+                    defaults.upstream.fallback_rpc_urls,
+                ),
+                rate: reader.integer("upstream.rate", 1, 10_000, defaults.upstream.rate),
+                burst: reader.integer("upstream.burst", 1, 10_000, defaults.upstream.burst),
+                retries: reader.integer("upstream.retries", 0, 10, defaults.upstream.retries),
+                breaker_failures: reader.integer(
+                    "upstream.breaker_failures",
+                    1,
+                    1000,
+                    defaults.upstream.breaker_failures,
+                ),
+                breaker_cooldown: reader.seconds(
+                    "upstream.breaker_cooldown_secs",
+                    1,
+                    3600,
+                    defaults.upstream.breaker_cooldown,
+                ),
+                balance_ttl: reader.seconds(
+                    "upstream.balance_ttl_secs",
+                    0,
+                    300,
+                    defaults.upstream.balance_ttl,
+                ),
+                multicall: reader.boolean("upstream.multicall", defaults.upstream.multicall),
+                unstoppable_api_key: reader.secret(
+                    [
+                        "upstream.unstoppable_api_key_file",
+                        "upstream.unstoppable_api_key_env",
+                        "upstream.unstoppable_api_key_ref",
+                    ],
+                    defaults.upstream.unstoppable_api_key,
+                ),
+            },
+            refresh: RefreshConfig {
+                interval: reader.seconds(
+                    "refresh.interval_secs",
+                    1,
+                    DAY_SECS,
+                    defaults.refresh.interval,
+                ),
+                jitter: reader.seconds("refresh.jitter_secs", 0, DAY_SECS, defaults.refresh.jitter),
+                freshness: reader.seconds(
+                    "refresh.freshness_secs",
+                    0,
+                    DAY_SECS,
+                    defaults.refresh.freshness,
+                ),
+                get_after: reader.optional_seconds(
+                    "refresh.get_after_secs",
+                    DAY_SECS,
+                    defaults.refresh.get_after,
+                ),
+            },
+            display: DisplayConfig {
+                unit: reader.choice("display.unit", BALANCE_UNITS, defaults.display.unit),
+                decimals: reader.integer("display.decimals", 0, 18, defaults.display.decimals),
+                thousands_separator: reader.character(
+                    "display.thousands_separator",
+                    defaults.display.thousands_separator,
+                ),
+                stale_after: reader.seconds(
+                    "display.stale_after_secs",
+                    1,
+                    DAY_SECS,
+                    defaults.display.stale_after,
+                ),
+            },
+            trash: TrashConfig {
+                retention: reader.days("trash.retention_days", 0, 3650, defaults.trash.retention),
+            },
+            compaction: CompactionConfig {
+                window_start: reader
+                    .time("compaction.window_start", defaults.compaction.window_start),
+                window_end: reader.time("compaction.window_end", defaults.compaction.window_end),
+            },
+            metrics: MetricsConfig {
+                max_series: reader.integer(
+                    "metrics.max_series",
+                    0,
+                    100_000,
+                    defaults.metrics.max_series,
+                ),
+                wallets: reader.strings("metrics.wallets", defaults.metrics.wallets),
+            },
+            history: HistoryConfig {
+                max_samples: reader.integer(
+                    "history.max_samples",
+                    0,
+                    1_000_000,
+                    defaults.history.max_samples,
+                ),
+                raw_for: reader.days("history.raw_days", 0, 3650, defaults.history.raw_for),
+                hourly_for: reader.days(
+                    "history.hourly_days",
+                    0,
+                    36_500,
+                    defaults.history.hourly_for,
+                ),
+            },
+            price: PriceConfig {
+                provider: reader.choice("price.provider", PRICE_PROVIDERS, defaults.price.provider),
+                coingecko_url: reader.url("price.coingecko_url", defaults.price.coingecko_url),
+                cache: reader.seconds("price.cache_secs", 1, DAY_SECS, defaults.price.cache),
+            },
+            tokens: TokensConfig {
+                erc20: reader.contracts("tokens.erc20", defaults.tokens.erc20, read_token),
+            },
+            nfts: NftsConfig {
+                erc721: reader.contracts("nfts.erc721", defaults.nfts.erc721, read_collection),
+            },
+            transactions: TransactionsConfig {
+                provider: reader.choice(
+                    "transactions.provider",
+                    TRANSACTIONS_PROVIDERS,
+                    defaults.transactions.provider,
+                ),
+                etherscan_url: reader.url(
+                    "transactions.etherscan_url",
+                    defaults.transactions.etherscan_url,
+                ),
+                etherscan_api_key: reader.secret(
+                    [
+                        "transactions.etherscan_api_key_file",
+                        "transactions.etherscan_api_key_env",
+                        "transactions.etherscan_api_key_ref",
+                    ],
+                    defaults.transactions.etherscan_api_key,
+                ),
+            },
+            donations: DonationsConfig {
+                tags: reader.strings("donations.tags", defaults.donations.tags),
+            },
+            report: ReportConfig {
+                interval: reader.seconds(
+                    "report.interval_secs",
+                    0,
+                    30 * DAY_SECS,
+                    defaults.report.interval,
+                ),
+                format: reader.choice("report.format", REPORT_FORMATS, defaults.report.format),
+                dir: reader.string("report.dir", defaults.report.dir),
+            },
+            webhook: WebhookConfig {
+                urls: reader.urls("webhook.urls", defaults.webhook.urls),
+                schema_version: reader
+                    .schema_version("webhook.schema_version", defaults.webhook.schema_version),
+                secret: reader.secret(
+                    [
+                        "webhook.secret_file",
+                        "webhook.secret_env",
+                        "webhook.secret_ref",
+                    ],
+                    defaults.webhook.secret,
+                ),
+            },
+            events: EventsConfig {
+                capacity: reader.integer("events.capacity", 1, 1_000_000, defaults.events.capacity),
+                overflow: reader.choice("events.overflow", OVERFLOWS, defaults.events.overflow),
+            },
+            secrets: SecretsConfig {
+                provider: reader.choice(
+                    "secrets.provider",
+                    SECRETS_PROVIDERS,
+                    defaults.secrets.provider,
+                ),
+                vault_addr: reader.url("secrets.vault_addr", defaults.secrets.vault_addr),
+                vault_token_env: reader
+                    .string("secrets.vault_token_env", defaults.secrets.vault_token_env),
+                sops_file: reader.string("secrets.sops_file", defaults.secrets.sops_file),
+            },
+            log: LogConfig {
+                level: reader.choice("log.level", LOG_LEVELS, defaults.log.level),
+            },
+        };
+
+        if config.secrets.provider == "none" {
+            let references = [
+                (
+                    "upstream.unstoppable_api_key_ref",
+                    &config.upstream.unstoppable_api_key,
+                ),
+                ("webhook.secret_ref", &config.webhook.secret),
+                ("store.url_ref", &config.store.url),
+                (
+                    "transactions.etherscan_api_key_ref",
+                    &config.transactions.etherscan_api_key,
+                ),
+            ];
+            for (path, source) in references {
+                if matches!(source, SecretSource::Provider(_)) {
+                    let message = format!(
+                        "needs `secrets.provider` set to one of {}",
+                        SECRETS_PROVIDERS[1..].join(", ")
+                    );
+                    reader.report(path, message);
+                }
+            }
+        }
+
+        let diagnostics = reader.finish();
+        if !diagnostics.is_empty() {
+            return Err(ConfigError {
+                kind: ConfigErrorKind::Invalid,
+                diagnostics,
+                source: None,
+            });
+        }
+
+        Ok(config)
+    }
+
+    pub fn to_toml(&self) -> String {
+        let secs = |duration: Duration| Value::from(duration.as_secs() as i64);
+        let time = |time: NaiveTime| Value::from(time.format("%H:%M").to_string());
+        let strings = |values: &[String]| Value::from(values.to_vec());
+        let table = |entries: Vec<(&str, Value)>| {
+            Value::Table(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key.to_string(), value))
+                    .collect(),
+            )
+        };
+        // Only where the secret comes from, never the secret itself.
+        let secret = |[file, env, reference]: [&'static str; 3], source: &SecretSource| match source
+        {
+            SecretSource::Unset => None,
+            SecretSource::File(path) => Some((file, Value::from(path.clone()))),
+            SecretSource::Env(name) => Some((env, Value::from(name.clone()))),
+            SecretSource::Provider(key) => Some((reference, Value::from(key.clone()))),
+        };
+
+        let sections: [(&str, Vec<(&str, Value)>); 19] = [
+            (
+                "server",
+                vec![
+                    ("addr", self.server.addr.to_string().into()),
+                    ("port", i64::from(self.server.port).into()),
+                    ("http_port", i64::from(self.server.http_port).into()),
+                    ("admin_port", i64::from(self.server.admin_port).into()),
+                ],
+            ),
+            (
+                "store",
+                vec![
+                    ("backend", self.store.backend.clone().into()),
+                    ("path", self.store.path.clone().into()),
+                    ("audit_path", self.store.audit_path.clone().into()),
+                    ("backups", i64::from(self.store.backups).into()),
+                    (
+                        "compression_level",
+                        i64::from(self.store.compression_level).into(),
+                    ),
+                    (
+                        "flush_interval_ms",
+                        (self.store.flush_interval.as_millis() as i64).into(),
+                    ),
+                    ("pool_size", i64::from(self.store.pool_size).into()),
+                    ("cache_size", (self.store.cache_size as i64).into()),
+                    ("ttl_secs", secs(self.store.ttl)),
+                ]
+                .into_iter()
+                .chain(secret(["url_file", "url_env", "url_ref"], &self.store.url))
+                .collect(),
+            ),
+            (
+                "upstream",
+                vec![
+                    ("rpc_url", self.upstream.rpc_url.clone().into()),
+                    ("chain_id", (self.upstream.chain_id.get() as i64).into()),
+                    (
+                        "fallback_rpc_urls",
+                        strings(&self.upstream.fallback_rpc_urls),
+                    ),
+                    ("rate", i64::from(self.upstream.rate).into()),
+                    ("burst", i64::from(self.upstream.burst).into()),
+                    ("retries", i64::from(self.upstream.retries).into()),
+                    (
+                        "breaker_failures",
+                        i64::from(self.upstream.breaker_failures).into(),
+                    ),
+                    (
+                        "breaker_cooldown_secs",
+                        secs(self.upstream.breaker_cooldown),
+                    ),
+                    ("balance_ttl_secs", secs(self.upstream.balance_ttl)),
+                    ("multicall", self.upstream.multicall.into()),
+                ]
+                .into_iter()
+                .chain(secret(
+                    [
+                        "unstoppable_api_key_file",
+                        "unstoppable_api_key_env",
+                        "unstoppable_api_key_ref",
+                    ],
+                    &self.upstream.unstoppable_api_key,
+                ))
+                .collect(),
+            ),
+            (
+                "refresh",
+                vec![
+                    ("interval_secs", secs(self.refresh.interval)),
+                    ("jitter_secs", secs(self.refresh.jitter)),
+                    ("freshness_secs", secs(self.refresh.freshness)),
+                    (
+                        "get_after_secs",
+                        secs(self.refresh.get_after.unwrap_or_default()),
+                    ),
+                ],
+            ),
+            (
+                "display",
+                vec![
+                    ("unit", self.display.unit.clone().into()),
+                    ("decimals", i64::from(self.display.decimals).into()),
+                    (
+                        "thousands_separator",
+                        self.display
+                            .thousands_separator
+                            .map(String::from)
+                            .unwrap_or_default()
+                            .into(),
+                    ),
+                    ("stale_after_secs", secs(self.display.stale_after)),
+                ],
+            ),
+            (
+                "trash",
+                vec![(
+                    "retention_days",
+                    Value::from((self.trash.retention.as_secs() / DAY_SECS) as i64),
+                )],
+            ),
+            (
+                "compaction",
+                vec![
+                    ("window_start", time(self.compaction.window_start)),
+                    ("window_end", time(self.compaction.window_end)),
+                ],
+            ),
+            (
+                "metrics",
+                vec![
+                    ("max_series", (self.metrics.max_series as i64).into()),
+                    ("wallets", strings(&self.metrics.wallets)),
+                ],
+            ),
+            (
+                "history",
+                vec![
+                    ("max_samples", (self.history.max_samples as i64).into()),
+                    (
+                        "raw_days",
+                        Value::from((self.history.raw_for.as_secs() / DAY_SECS) as i64),
+                    ),
+                    (
+                        "hourly_days",
+                        Value::from((self.history.hourly_for.as_secs() / DAY_SECS) as i64),
+                    ),
+                ],
+            ),
+            (
+                "price",
+                vec![
+                    ("provider", self.price.provider.clone().into()),
+                    ("coingecko_url", self.price.coingecko_url.clone().into()),
+                    ("cache_secs", secs(self.price.cache)),
+                ],
+            ),
+            (
+                "tokens",
+                vec![(
+                    "erc20",
+                    self.tokens
+                        .erc20
+                        .iter()
+                        .map(|token| {
+                            table(vec![
+                                ("symbol", token.symbol.clone().into()),
+                                ("address", token.address.to_string().into()),
+                                ("decimals", i64::from(token.decimals).into()),
+                            ])
+                        })
+                        .collect::<Vec<_>>()
+                        .into(),
+                )],
+            ),
+            (
+                "nfts",
+                vec![(
+                    "erc721",
+                    self.nfts
+                        .erc721
+                        .iter()
+                        .map(|collection| {
+                            table(vec![
+                                ("name", collection.name.clone().into()),
+                                ("address", collection.address.to_string().into()),
+                            ])
+                        })
+                        .collect::<Vec<_>>()
+                        .into(),
+                )],
+            ),
+            (
+                "transactions",
+                vec![
+                    ("provider", self.transactions.provider.clone().into()),
+                    (
+                        "etherscan_url",
+                        self.transactions.etherscan_url.clone().into(),
+                    ),
+                ]
+                .into_iter()
+                .chain(secret(
+                    [
+                        "etherscan_api_key_file",
+                        "etherscan_api_key_env",
+                        "etherscan_api_key_ref",
+                    ],
+                    &self.transactions.etherscan_api_key,
+                ))
+                .collect(),
+            ),
+            ("donations", vec![("tags", strings(&self.donations.tags))]),
+            (
+                "report",
+                vec![
+                    ("interval_secs", secs(self.report.interval)),
+                    ("format", self.report.format.clone().into()),
+                    ("dir", self.report.dir.clone().into()),
+                ],
+            ),
+            (
+                "webhook",
+                [
+                    ("urls", strings(&self.webhook.urls)),
+                    (
+                        "schema_version",
+                        i64::from(self.webhook.schema_version).into(),
+                    ),
+                ]
+                .into_iter()
+                .chain(secret(
+                    ["secret_file", "secret_env", "secret_ref"],
+                    &self.webhook.secret,
+                ))
+                .collect(),
+            ),
+            (
+                "events",
+                vec![
+                    ("capacity", (self.events.capacity as i64).into()),
+                    ("overflow", self.events.overflow.clone().into()),
+                ],
+            ),
+            (
+                "secrets",
+                vec![
+                    ("provider", self.secrets.provider.clone().into()),
+                    ("vault_addr", self.secrets.vault_addr.clone().into()),
+                    (
+                        "vault_token_env",
+                        self.secrets.vault_token_env.clone().into(),
+                    ),
+                    ("sops_file", self.secrets.sops_file.clone().into()),
+                ],
+            ),
+            ("log", vec![("level", self.log.level.clone().into())]),
+        ];
+
+        let mut root = Table::new();
+        for (section, keys) in sections {
+            let table = keys
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect();
+            root.insert(section.to_string(), Value::Table(table));
+        }
+        root.to_string()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}`: {}", self.path, self.message)
+    }
+}
+
+#[derive(Debug)]
+pub struct ConfigError {
+    kind: ConfigErrorKind,
+    diagnostics: Vec<Diagnostic>,
+    source: Option<Box<dyn error::Error + Send + Sync + 'static>>,
+}
+
+impl ConfigError {
+    pub fn kind(&self) -> ConfigErrorKind {
+        self.kind
+    }
+
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ConfigErrorKind::Read => {
+                write!(f, "couldn't read config file")
+            }
+            ConfigErrorKind::Syntax => {
+                write!(f, "config file isn't valid toml")
+            }
+            ConfigErrorKind::Invalid => {
+                write!(f, "config file has {} problems", self.diagnostics.len())?;
+                for diagnostic in &self.diagnostics {
+                    write!(f, "\n  {diagnostic}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        self.source.as_deref().map(|e| e as _)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConfigErrorKind {
+    Read,
+    Syntax,
+    Invalid,
+}
+
+struct Reader<'a> {
+    root: &'a Table,
+    known: Vec<&'static str>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Reader<'a> {
+    fn new(root: &'a Table) -> Self {
+        Self {
+            root,
+            known: vec![],
+            diagnostics: vec![],
+        }
+    }
+
+    fn value(&mut self, path: &'static str) -> Option<&'a Value> {
+        self.known.push(path);
+        let (section, key) = path.split_once('.')?;
+        self.root.get(section)?.as_table()?.get(key)
+    }
+
+    fn report(&mut self, path: &str, message: String) {
+        self.diagnostics.push(Diagnostic {
+            path: path.to_string(),
+            message,
+        });
+    }
+
+    fn mismatch(&mut self, path: &str, expected: &str, found: &Value) {
+        let message = format!("expected {expected}, found {}", found.type_str());
+        self.report(path, message);
+    }
+
+    fn integer<T: TryFrom<i64>>(
+        &mut self,
+        path: &'static str,
+        min: i64,
+        max: i64,
+        default: T,
+    ) -> T {
+        let expected = format!("an integer from {min} to {max}");
+        match self.value(path) {
+            None => default,
+            Some(Value::Integer(n)) if (min..=max).contains(n) => {
+                T::try_from(*n).unwrap_or(default)
+            }
+            Some(Value::Integer(n)) => {
+                self.report(path, format!("expected {expected}, found {n}"));
+                default
+            }
+            Some(value) => {
+                self.mismatch(path, &expected, value);
+                default
+            }
+        }
+    }
+
+    fn seconds(&mut self, path: &'static str, min: u64, max: u64, default: Duration) -> Duration {
+        let secs = self.integer(path, min as i64, max as i64, default.as_secs());
+        Duration::from_secs(secs)
+    }
+
+    fn optional_seconds(
+        &mut self,
+        path: &'static str,
+        max: u64,
+        default: Option<Duration>,
+    ) -> Option<Duration> {
+        let default = default.unwrap_or_default();
+        Some(self.seconds(path, 0, max, default)).filter(|duration| !duration.is_zero())
+    }
+
+    fn days(&mut self, path: &'static str, min: u64, max: u64, default: Duration) -> Duration {
+        let days = self.integer(path, min as i64, max as i64, default.as_secs() / DAY_SECS);
+        Duration::from_secs(days * DAY_SECS)
+    }
+
+    fn schema_version(&mut self, path: &'static str, default: u32) -> u32 {
+        let value = self.integer(path, 0, i64::from(u32::MAX), default);
+        if payload::SCHEMA_VERSIONS.contains(&value) {
+            return value;
+        }
+
+        let accepted: Vec<String> = payload::SCHEMA_VERSIONS
+            .iter()
+            .map(u32::to_string)
+            .collect();
+        let message = format!("expected one of {}, found {value}", accepted.join(", "));
+        self.report(path, message);
+        default
+    }
+
+    fn boolean(&mut self, path: &'static str, default: bool) -> bool {
+        match self.value(path) {
+            None => default,
+            Some(Value::Boolean(b)) => *b,
+            Some(value) => {
+                self.mismatch(path, "a boolean", value);
+                default
+            }
+        }
+    }
+
+    fn string(&mut self, path: &'static str, default: String) -> String {
+        match self.value(path) {
+            None => default,
+            Some(Value::String(s)) => s.clone(),
+            Some(value) => {
+                self.mismatch(path, "a string", value);
+                default
+            }
+        }
+    }
+
+    fn strings(&mut self, path: &'static str, default: Vec<String>) -> Vec<String> {
+        let expected = "an array of strings";
+        match self.value(path) {
+            None => default,
+            Some(Value::Array(values)) => {
+                let strings: Option<Vec<String>> = values
+                    .iter()
+                    .map(|value| value.as_str().map(str::to_owned))
+                    .collect();
+                strings.unwrap_or_else(|| {
+                    self.report(path, format!("expected {expected}, found a mixed array"));
+                    default
+                })
+            }
+            Some(value) => {
+                self.mismatch(path, expected, value);
+                default
+            }
+        }
+    }
+
+    fn secret(
+        &mut self,
+        [file, env, reference]: [&'static str; 3],
+        default: SecretSource,
+    ) -> SecretSource {
+        let mut sources = vec![];
+        if let Some(path) = self.optional_string(file) {
+            sources.push((file, SecretSource::File(path)));
+        }
+        if let Some(name) = self.optional_string(env) {
+            sources.push((env, SecretSource::Env(name)));
+        }
+        if let Some(key) = self.optional_string(reference) {
+            sources.push((reference, SecretSource::Provider(key)));
+        }
+
+        match sources.len() {
+            0 => default,
+            1 => sources.remove(0).1,
+            _ => {
+                let keys: Vec<&str> = sources.iter().map(|(path, _)| *path).collect();
+                let message = format!("set only one of {}", keys.join(", "));
+                self.report(keys[1], message);
+                default
+            }
+        }
+    }
+
+    fn optional_string(&mut self, path: &'static str) -> Option<String> {
+        match self.value(path)? {
+            Value::String(s) => Some(s.clone()),
+            value => {
+                self.mismatch(path, "a string", value);
+                None
+            }
+        }
+    }
+
+    fn choice(&mut self, path: &'static str, accepted: &[&str], default: String) -> String {
+        let value = self.string(path, default.clone());
+        if accepted.contains(&value.as_str()) {
+            return value;
+        }
+
+        let mut message = format!("expected one of {}, found \"{value}\"", accepted.join(", "));
+        if let Some(suggestion) = suggest(&value, accepted.iter().copied()) {
+            message.push_str(&format!("; did you mean \"{suggestion}\"?"));
+        }
+        self.report(path, message);
+        default
+    }
+
+    fn character(&mut self, path: &'static str, default: Option<char>) -> Option<char> {
+        let value = self.string(path, default.map(String::from).unwrap_or_default());
+        let mut chars = value.chars();
+        match (chars.next(), chars.next()) {
+            (first, None) => first,
+            _ => {
+                let message = format!("expected a single character or \"\", found \"{value}\"");
+                self.report(path, message);
+                default
+            }
+        }
+    }
+
+    fn addr(&mut self, path: &'static str, default: IpAddr) -> IpAddr {
+        let value = self.string(path, default.to_string());
+        value.parse().unwrap_or_else(|_| {
+            let message = format!("expected an ip address such as \"0.0.0.0\", found \"{value}\"");
+            self.report(path, message);
+            default
+        })
+    }
+
+    fn url(&mut self, path: &'static str, default: String) -> String {
+        let value = self.string(path, default.clone());
+        if is_http_url(&value) {
+            return value;
+        }
+
+        let message = format!("expected an http or https url, found \"{value}\"");
+        self.report(path, message);
+        default
+    }
+
+    fn urls(&mut self, path: &'static str, default: Vec<String>) -> Vec<String> {
+        let urls = self.strings(path, default.clone());
+        match urls.iter().find(|url| !is_http_url(url)) {
+            Some(url) => {
+                let message = format!("expected http or https urls, found \"{url}\"");
+                self.report(path, message);
+                default
+            }
+            None => urls,
+        }
+    }
+
+    fn contracts<T>(
+        &mut self,
+        path: &'static str,
+        default: Vec<T>,
+        read: fn(&Value) -> Result<(Address, T), String>,
+    ) -> Vec<T> {
+        let values = match self.value(path) {
+            None => return default,
+            Some(Value::Array(values)) => values,
+            Some(value) => {
+                self.mismatch(path, "an array of tables", value);
+                return default;
+            }
+        };
+
+        let mut addresses = vec![];
+        let mut contracts = vec![];
+        for (i, value) in values.iter().enumerate() {
+            let entry = format!("{path}[{i}]");
+            match read(value) {
+                Ok((address, _)) if addresses.contains(&address) => {
+                    self.report(&entry, format!("{address} is listed twice"));
+                }
+                Ok((address, contract)) => {
+                    addresses.push(address);
+                    contracts.push(contract);
+                }
+                Err(message) => self.report(&entry, message),
+            }
+        }
+        contracts
+    }
+
+    fn time(&mut self, path: &'static str, default: NaiveTime) -> NaiveTime {
+        let value = self.string(path, default.format("%H:%M").to_string());
+        NaiveTime::parse_from_str(&value, "%H:%M").unwrap_or_else(|_| {
+            let message = format!("expected a UTC time such as \"03:30\", found \"{value}\"");
+            self.report(path, message);
+            default
+        })
+    }
+
+    fn finish(mut self) -> Vec<Diagnostic> {
+        let sections: Vec<&str> = self
+            .known
+            .iter()
+            .filter_map(|path| path.split_once('.').map(|(section, _)| section))
+            .collect();
+
+        for (section, value) in self.root {
+            if !sections.contains(&section.as_str()) {
+                let mut message = "unknown section".to_string();
+                if let Some(suggestion) = suggest(section, sections.iter().copied()) {
+                    message.push_str(&format!("; did you mean `{suggestion}`?"));
+                }
+                self.report(section, message);
+                continue;
+            }
+
+            let Some(table) = value.as_table() else {
+                self.mismatch(section, "a table", value);
+                continue;
+            };
+
+            for key in table.keys() {
+                let path = format!("{section}.{key}");
+                if self.known.contains(&path.as_str()) {
+                    continue;
+                }
+
+                let keys = self
+                    .known
+                    .iter()
+                    .filter_map(|known| known.strip_prefix(section.as_str())?.strip_prefix('.'));
+                let accepted: Vec<&str> = keys.clone().collect();
+                let mut message = format!("unknown key; {section} accepts {}", accepted.join(", "));
+                if let Some(suggestion) = suggest(key, keys) {
+                    message = format!("unknown key; did you mean `{section}.{suggestion}`?");
+                }
+                self.report(&path, message);
+            }
+        }
+
+        self.diagnostics
+    }
+}
+
+fn contract_table<'a>(value: &'a Value, keys: &[&str]) -> Result<(&'a Table, Address), String> {
+    let Some(table) = value.as_table() else {
+        return Err(format!("expected a table, found {}", value.type_str()));
+    };
+    if let Some(key) = table.keys().find(|key| !keys.contains(&key.as_str())) {
+        return Err(format!("unknown key `{key}`; expected {}", keys.join(", ")));
+    }
+
+    let address = table
+        .get("address")
+        .and_then(Value::as_str)
+        .ok_or("expected an `address` string")?;
+    let address = Address::from_str(address).map_err(|e| format!("`address`: {e}"))?;
+    Ok((table, address))
+}
+
+fn non_empty_string<'a>(table: &'a Table, key: &str) -> Result<&'a str, String> {
+    table
+        .get(key)
+        .and_then(Value::as_str)
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| format!("expected a `{key}` string"))
+}
+
+fn read_token(value: &Value) -> Result<(Address, Token), String> {
+    let (table, address) = contract_table(value, &["symbol", "address", "decimals"])?;
+    let symbol = non_empty_string(table, "symbol")?;
+    let decimals = table
+        .get("decimals")
+        .and_then(Value::as_integer)
+        .and_then(|decimals| u32::try_from(decimals).ok())
+        .filter(|decimals| *decimals <= MAX_TOKEN_DECIMALS)
+        .ok_or_else(|| format!("expected `decimals` from 0 to {MAX_TOKEN_DECIMALS}"))?;
+
+    let token = Token {
+        symbol: symbol.to_string(),
+        address,
+        decimals,
+    };
+    Ok((address, token))
+}
+
+fn read_collection(value: &Value) -> Result<(Address, Collection), String> {
+    let (table, address) = contract_table(value, &["name", "address"])?;
+    let collection = Collection {
+        name: non_empty_string(table, "name")?.to_string(),
+        address,
+    };
+    Ok((address, collection))
+}
+
+fn is_http_url(value: &str) -> bool {
+    value.starts_with("http://") || value.starts_with("https://")
+}
+
+fn suggest<'c>(typo: &str, candidates: impl Iterator<Item = &'c str>) -> Option<&'c str> {
+    let threshold = (typo.chars().count() / 3).max(2);
+    candidates
+        .map(|candidate| (edit_distance(typo, candidate), candidate))
+        .filter(|(distance, _)| *distance <= threshold)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitute = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitute.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::Path, time::Duration};
+
+    use std::str::FromStr;
+
+    use super::{Config, ConfigErrorKind};
+    use crate::{
+        core::{Address, ChainId, Collection, Token},
+        secrets::SecretSource,
+    };
+
+    #[test]
+    fn config_parse_overrides_defaults() {
+        let text = r#"
+            [server]
+            port = 6000
+
+            [store]
+            backend = "sqlite"
+            backups = 5
+
+            [upstream]
+            chain_id = 8453
+
+            [refresh]
+            interval_secs = 120
+
+            [display]
+            unit = "gwei"
+            thousands_separator = ","
+
+            [log]
+            level = "debug"
+        "#;
+
+        let config = Config::parse(text).unwrap();
+        assert_eq!(config.display.unit, "gwei");
+        assert_eq!(config.display.thousands_separator, Some(','));
+        assert_eq!(config.server.port, 6000);
+        assert_eq!(config.upstream.chain_id, ChainId::new(8453));
+        assert_eq!(config.refresh.interval, Duration::from_secs(120));
+        assert_eq!(config.log.level, "debug");
+        assert_eq!(config.store.backend, "sqlite");
+        assert_eq!(config.store.backups, 5);
+        assert_eq!(config.store.path, Config::default().store.path);
+    }
+
+    #[test]
+    fn config_default_store_in_data_dir() {
+        let config = Config::default();
+        let path = Path::new(&config.store.path);
+        assert_eq!(path.file_name().unwrap(), "wallet.db");
+        assert_eq!(Path::new(&config.store.audit_path).parent(), path.parent());
+    }
+
+    #[test]
+    fn config_parse_diagnostics() {
+        let text = r#"
+            [server]
+            prot = 6000
+            http_port = "9100"
+
+            [display]
+            thousands_separator = ", "
+
+            [log]
+            level = "debgu"
+
+            [refrsh]
+            interval_secs = 120
+        "#;
+
+        let err = Config::parse(text).unwrap_err();
+        assert_eq!(err.kind(), ConfigErrorKind::Invalid);
+
+        let messages: Vec<String> = err.diagnostics().iter().map(|d| d.to_string()).collect();
+        assert_eq!(
+            messages,
+            [
+                "`server.http_port`: expected an integer from 1 to 65535, found string",
+                "`display.thousands_separator`: expected a single character or \"\", found \", \"",
+                "`log.level`: expected one of error, warn, info, debug, trace, found \"debgu\"; did you mean \"debug\"?",
+                "`refrsh`: unknown section; did you mean `refresh`?",
+                "`server.prot`: unknown key; did you mean `server.port`?",
+            ]
+        );
+    }
+
+    #[test]
+    fn config_parse_secrets() {
+        let text = r#"
+            [webhook]
+            secret_file = "/run/secrets/webhook"
+
+            [upstream]
+            unstoppable_api_key_ref = "secret/data/mini-wallet#unstoppable"
+
+            [secrets]
+            provider = "vault"
+        "#;
+
+        let config = Config::parse(text).unwrap();
+        assert_eq!(
+            config.webhook.secret,
+            SecretSource::File("/run/secrets/webhook".to_string())
+        );
+        assert_eq!(
+            config.upstream.unstoppable_api_key,
+            SecretSource::Provider("secret/data/mini-wallet#unstoppable".to_string())
+        );
+        assert!(
+            config
+                .to_toml()
+                .contains("secret_file = \"/run/secrets/webhook\"")
+        );
+
+        let text = r#"
+            [webhook]
+            secret_file = "/run/secrets/webhook"
+            secret_env = "HOOK_SECRET"
+
+            [upstream]
+            unstoppable_api_key_ref = "secret/data/mini-wallet#unstoppable"
+        "#;
+
+        let err = Config::parse(text).unwrap_err();
+        let messages: Vec<String> = err.diagnostics().iter().map(|d| d.to_string()).collect();
+        assert_eq!(
+            messages,
+            [
+                "`webhook.secret_env`: set only one of webhook.secret_file, webhook.secret_env",
+                "`upstream.unstoppable_api_key_ref`: needs `secrets.provider` set to one of vault, sops",
+            ]
+        );
+    }
+
+    #[test]
+    fn config_parse_tokens() {
+        let text = r#"
+            [tokens]
+            erc20 = [
+                { symbol = "USDC", address = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48", decimals = 6 },
+                { symbol = "USDC.e", address = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48", decimals = 6 },
+                { symbol = "DAI", address = "0x6B17", decimals = 18 },
+                { symbol = "WETH", address = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2", decimals = 80 },
+            ]
+        "#;
+
+        let err = Config::parse(text).unwrap_err();
+        let messages: Vec<String> = err.diagnostics().iter().map(|d| d.to_string()).collect();
+        assert_eq!(
+            messages,
+            [
+                "`tokens.erc20[1]`: 0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48 is listed twice",
+                "`tokens.erc20[2]`: `address`: address is wrong length",
+                "`tokens.erc20[3]`: expected `decimals` from 0 to 77",
+            ]
+        );
+
+        let text = r#"
+            [nfts]
+            erc721 = [
+                { name = "Punks", address = "0xb47e3cd837dDF8e4c57F05d70Ab865de6e193BBB" },
+                { address = "0xBC4CA0EdA7647A8aB7C2061c2E118A18a936f13D", symbol = "BAYC" },
+            ]
+        "#;
+
+        let err = Config::parse(text).unwrap_err();
+        let messages: Vec<String> = err.diagnostics().iter().map(|d| d.to_string()).collect();
+        assert_eq!(
+            messages,
+            ["`nfts.erc721[1]`: unknown key `symbol`; expected name, address"]
+        );
+    }
+
+    #[test]
+    fn config_print_round_trips() {
+        let mut config = Config::default();
+        config.webhook.urls = vec!["https://example.com/hook".to_string()];
+        config.server.port = 6000;
+        config.refresh.get_after = None;
+        config.webhook.schema_version = 1;
+        config.upstream.chain_id = ChainId::new(11155111);
+        config.tokens.erc20 = vec![Token {
+            symbol: "USDC".to_string(),
+            address: Address::from_str("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48").unwrap(),
+            decimals: 6,
+        }];
+        config.nfts.erc721 = vec![Collection {
+            name: "Punks".to_string(),
+            address: Address::from_str("0xb47e3cd837dDF8e4c57F05d70Ab865de6e193BBB").unwrap(),
+        }];
+
+        assert_eq!(Config::parse(&config.to_toml()).unwrap(), config);
+    }
+}
@@ -1,26 +1,45 @@
+pub mod uri;
+
 use std::{
-    error,
-    fmt::{self, Write},
+    collections::BTreeMap,
+    error, fmt,
+    iter::Sum,
+    ops::{Add, AddAssign, Sub, SubAssign},
     str::FromStr,
 };
 
 use hex::FromHexError;
+use idna::uts46::{AsciiDenyList, Hyphens, Uts46};
+use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+pub use primitive_types::U256;
 use tiny_keccak::{Hasher, Keccak};
 
 #[derive(Debug, Clone)]
 pub struct Wallet {
+    chain_id: ChainId,
     address: Address,
+    linked: Vec<Address>,
     balance: Balance,
 }
 
 impl Wallet {
     pub fn new(address: Address) -> Self {
+        Self::on_chain(ChainId::MAINNET, address)
+    }
+
+    pub fn on_chain(chain_id: ChainId, address: Address) -> Self {
         Self {
+            chain_id,
             address,
+            linked: Vec::new(),
             balance: Balance::default(),
         }
     }
 
+    pub fn chain_id(&self) -> ChainId {
+        self.chain_id
+    }
+
     pub fn address(&self) -> &Address {
         &self.address
     }
@@ -29,6 +48,23 @@ impl Wallet {
         &mut self.address
     }
 
+    pub fn linked(&self) -> &[Address] {
+        &self.linked
+    }
+
+    pub fn set_linked(&mut self, linked: impl IntoIterator<Item = Address>) {
+        self.linked.clear();
+        for address in linked {
+            if address != self.address && !self.linked.contains(&address) {
+                self.linked.push(address);
+            }
+        }
+    }
+
+    pub fn addresses(&self) -> impl Iterator<Item = &Address> {
+        std::iter::once(&self.address).chain(&self.linked)
+    }
+
     pub fn balance(&self) -> Balance {
         self.balance
     }
@@ -38,8 +74,96 @@ impl Wallet {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ChainId(u64);
+
+impl ChainId {
+    pub const MAINNET: Self = Self(1);
+
+    pub fn new(id: u64) -> Self {
+        Self(id)
+    }
+
+    pub fn get(&self) -> u64 {
+        self.0
+    }
+
+    pub fn name(&self) -> String {
+        let name = match self.0 {
+            1 => "mainnet",
+            10 => "optimism",
+            137 => "polygon",
+            8453 => "base",
+            17000 => "holesky",
+            42161 => "arbitrum",
+            11155111 => "sepolia",
+            id => return id.to_string(),
+        };
+        name.to_string()
+    }
+}
+
+impl Default for ChainId {
+    fn default() -> Self {
+        Self::MAINNET
+    }
+}
+
+impl fmt::Display for ChainId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+pub const ETH_DECIMALS: u32 = 18;
+pub const GWEI_DECIMALS: u32 = 9;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BalanceUnit {
+    Wei,
+    Gwei,
+    #[default]
+    Eth,
+}
+
+impl BalanceUnit {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "wei" => Some(Self::Wei),
+            "gwei" => Some(Self::Gwei),
+            "eth" => Some(Self::Eth),
+            _ => None,
+        }
+    }
+
+    pub fn decimals(self) -> u32 {
+        match self {
+            Self::Wei => 0,
+            Self::Gwei => GWEI_DECIMALS,
+            Self::Eth => ETH_DECIMALS,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BalanceFormat {
+    pub unit: BalanceUnit,
+    pub decimals: u32,
+    pub thousands_separator: Option<char>,
+}
+
+impl Default for BalanceFormat {
+    fn default() -> Self {
+        Self {
+            unit: BalanceUnit::Eth,
+            decimals: ETH_DECIMALS,
+            thousands_separator: None,
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct Balance(u128);
+pub struct Balance(U256);
 
 impl fmt::Display for Balance {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -49,20 +173,487 @@ impl fmt::Display for Balance {
 
 impl Balance {
     pub fn new(wei: u128) -> Self {
+        Self(U256::from(wei))
+    }
+
+    pub fn from_wei(wei: U256) -> Self {
         Self(wei)
     }
 
-    pub fn wei(&self) -> u128 {
+    pub fn wei(&self) -> U256 {
         self.0
     }
 
+    pub fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
     pub fn eth(&self) -> String {
-        const ONE_ETH: u128 = 1_000_000_000_000_000_000;
+        self.eth_rounded(ETH_DECIMALS)
+    }
+
+    pub fn usd(&self, usd_per_eth: f64) -> String {
+        let eth = self.0.to_f64_lossy() / 10f64.powi(ETH_DECIMALS as i32);
+        format!("{:.2}", eth * usd_per_eth)
+    }
+
+    pub fn eth_rounded(&self, decimals: u32) -> String {
+        self.format(&BalanceFormat {
+            decimals,
+            ..BalanceFormat::default()
+        })
+    }
+
+    pub fn gwei(&self) -> String {
+        self.format(&BalanceFormat {
+            unit: BalanceUnit::Gwei,
+            decimals: GWEI_DECIMALS,
+            thousands_separator: None,
+        })
+    }
+
+    pub fn format(&self, format: &BalanceFormat) -> String {
+        let places = format.unit.decimals();
+        let decimals = format.decimals.min(places);
+        let unit = U256::exp10((places - decimals) as usize);
         let wei = self.wei();
-        let whole = wei / ONE_ETH;
-        let fraction = wei % ONE_ETH;
-        format!("{whole}.{fraction}")
+
+        let mut rounded = wei / unit;
+        if (wei % unit) * 2 >= unit && unit > U256::one() {
+            rounded += U256::one();
+        }
+
+        let scale = U256::exp10(decimals as usize);
+        let mut whole = (rounded / scale).to_string();
+        if let Some(separator) = format.thousands_separator {
+            whole = group_thousands(&whole, separator);
+        }
+        // Under 10^18, so it fits; `U256` ignores the zero padding.
+        let fraction = (rounded % scale).as_u128();
+        if decimals == 0 {
+            whole
+        } else {
+            format!("{whole}.{fraction:0width$}", width = decimals as usize)
+        }
+    }
+}
+
+fn group_thousands(digits: &str, separator: char) -> String {
+    let mut grouped = String::with_capacity(digits.len() * 4 / 3);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(separator);
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+impl Balance {
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.0.checked_add(other.0).map(Self)
+    }
+
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.0.checked_sub(other.0).map(Self)
+    }
+
+    pub fn saturating_add(self, other: Self) -> Self {
+        Self(self.0.saturating_add(other.0))
+    }
+
+    pub fn saturating_sub(self, other: Self) -> Self {
+        Self(self.0.saturating_sub(other.0))
+    }
+
+    pub fn checked_sum(balances: impl IntoIterator<Item = Self>) -> Option<Self> {
+        balances
+            .into_iter()
+            .try_fold(Self::default(), Self::checked_add)
+    }
+}
+
+impl Add for Balance {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self(self.0 + other.0)
+    }
+}
+
+impl Sub for Balance {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self(self.0 - other.0)
+    }
+}
+
+impl AddAssign for Balance {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Balance {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl Sum for Balance {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Balance> for Balance {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl FromStr for Balance {
+    type Err = BalanceParseError;
+
+    fn from_str(wei: &str) -> Result<Self, Self::Err> {
+        if wei.is_empty() {
+            return Err(BalanceParseError);
+        }
+        U256::from_dec_str(wei)
+            .map(Self)
+            .map_err(|_| BalanceParseError)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BalanceParseError;
+
+impl fmt::Display for BalanceParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "not a whole number of wei under 2^256")
+    }
+}
+
+impl error::Error for BalanceParseError {}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Balance {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Balance {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BalanceDelta {
+    pub before: Balance,
+    pub after: Balance,
+}
+
+impl BalanceDelta {
+    pub fn wei(&self) -> String {
+        if self.after < self.before {
+            format!("-{}", self.before - self.after)
+        } else {
+            (self.after - self.before).to_string()
+        }
+    }
+
+    pub fn percent(&self) -> Option<f64> {
+        if self.before.is_zero() {
+            return None;
+        }
+        let before = self.before.wei().to_f64_lossy();
+        let percent = (self.after.wei().to_f64_lossy() - before) * 100.0 / before;
+        Some(percent)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub symbol: String,
+    pub address: Address,
+    pub decimals: u32,
+}
+
+pub const MAX_TOKEN_DECIMALS: u32 = 77;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenBalance {
+    pub token: Token,
+    pub amount: U256,
+}
+
+impl TokenBalance {
+    pub fn units(&self) -> String {
+        let decimals = self.token.decimals.min(MAX_TOKEN_DECIMALS);
+        if decimals == 0 {
+            return self.amount.to_string();
+        }
+
+        let scale = U256::exp10(decimals as usize);
+        let whole = self.amount / scale;
+        let fraction = self.amount % scale;
+        format!("{whole}.{fraction:0width$}", width = decimals as usize)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transfer {
+    pub hash: String,
+    pub block_number: u64,
+    pub timestamp: i64,
+    pub from: Address,
+    pub to: Option<Address>,
+    pub value: Balance,
+    pub failed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Collection {
+    pub name: String,
+    pub address: Address,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertCondition {
+    Below(Balance),
+    Above(Balance),
+    Change { percent: u32 },
+}
+
+impl AlertCondition {
+    pub fn fires(&self, before: Balance, after: Balance) -> bool {
+        match *self {
+            Self::Below(threshold) => before >= threshold && after < threshold,
+            Self::Above(threshold) => before <= threshold && after > threshold,
+            Self::Change { percent } => {
+                let delta = before.wei().abs_diff(after.wei());
+                !before.is_zero()
+                    && delta.saturating_mul(100.into())
+                        > before.wei().saturating_mul(percent.into())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sample {
+    pub timestamp: i64,
+    pub block_number: Option<u64>,
+    pub balance: Balance,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetentionTier {
+    pub max_age: Option<i64>,
+    pub resolution: i64,
+}
+
+pub fn downsample(samples: &[Sample], now: i64, tiers: &[RetentionTier]) -> Vec<Sample> {
+    let mut sorted = samples.to_vec();
+    sorted.sort_by_key(|s| s.timestamp);
+
+    let mut kept: Vec<(usize, i64, Sample)> = Vec::with_capacity(sorted.len());
+    for sample in sorted {
+        let age = now.saturating_sub(sample.timestamp);
+        let Some(tier) = tiers
+            .iter()
+            .position(|t| t.max_age.is_none_or(|max_age| age <= max_age))
+        else {
+            continue;
+        };
+
+        let resolution = tiers[tier].resolution;
+        if resolution <= 0 {
+            kept.push((tier, sample.timestamp, sample));
+            continue;
+        }
+
+        let bucket = sample.timestamp.div_euclid(resolution);
+        match kept.last_mut() {
+            Some((last_tier, last_bucket, last))
+                if *last_tier == tier && *last_bucket == bucket =>
+            {
+                *last = sample;
+            }
+            _ => kept.push((tier, bucket, sample)),
+        }
     }
+
+    kept.into_iter().map(|(_, _, sample)| sample).collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Candle {
+    pub start: i64,
+    pub open: Balance,
+    pub high: Balance,
+    pub low: Balance,
+    pub close: Balance,
+}
+
+pub fn candles(samples: &[Sample], interval: i64) -> Vec<Candle> {
+    let interval = interval.max(1);
+    let mut sorted = samples.to_vec();
+    sorted.sort_by_key(|s| s.timestamp);
+
+    let mut candles: Vec<Candle> = Vec::new();
+    for sample in sorted {
+        let start = sample.timestamp.div_euclid(interval) * interval;
+        match candles.last_mut() {
+            Some(candle) if candle.start == start => {
+                candle.high = candle.high.max(sample.balance);
+                candle.low = candle.low.min(sample.balance);
+                candle.close = sample.balance;
+            }
+            _ => candles.push(Candle {
+                start,
+                open: sample.balance,
+                high: sample.balance,
+                low: sample.balance,
+                close: sample.balance,
+            }),
+        }
+    }
+
+    candles
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Inflow {
+    pub start: i64,
+    pub amount: Balance,
+}
+
+pub fn inflows(received: impl IntoIterator<Item = (i64, Balance)>, interval: i64) -> Vec<Inflow> {
+    let interval = interval.max(1);
+    let mut buckets: BTreeMap<i64, Balance> = BTreeMap::new();
+    for (timestamp, amount) in received {
+        let start = timestamp.div_euclid(interval) * interval;
+        let total = buckets.entry(start).or_default();
+        *total = total.saturating_add(amount);
+    }
+
+    buckets
+        .into_iter()
+        .filter(|(_, amount)| !amount.is_zero())
+        .map(|(start, amount)| Inflow { start, amount })
+        .collect()
+}
+
+pub type MerkleNode = [u8; 32];
+
+pub fn merkle_leaf(bytes: &[u8]) -> MerkleNode {
+    keccak256(&[&[0x00], bytes])
+}
+
+pub fn merkle_root(leaves: &[MerkleNode]) -> MerkleNode {
+    if leaves.is_empty() {
+        return merkle_leaf(&[]);
+    }
+
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => keccak256(&[&[0x01], left, right]),
+                [only] => *only,
+                _ => unreachable!(),
+            })
+            .collect();
+    }
+    level[0]
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EnsName(String);
+
+impl EnsName {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn is_eth(&self) -> bool {
+        self.0.ends_with(".eth")
+    }
+
+    pub fn namehash(&self) -> [u8; 32] {
+        namehash(&self.0)
+    }
+}
+
+impl FromStr for EnsName {
+    type Err = EnsNameError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let (normalized, result) =
+            Uts46::new().to_unicode(name.as_bytes(), AsciiDenyList::URL, Hyphens::Allow);
+        if result.is_err() {
+            return Err(EnsNameError::Disallowed);
+        }
+        // UTS-46 lets empty labels through; ENS has no use for them.
+        if normalized.split('.').any(str::is_empty) {
+            return Err(EnsNameError::EmptyLabel);
+        }
+        Ok(Self(normalized.into_owned()))
+    }
+}
+
+impl fmt::Display for EnsName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnsNameError {
+    Disallowed,
+    EmptyLabel,
+}
+
+impl fmt::Display for EnsNameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Disallowed => write!(f, "name contains characters UTS-46 disallows"),
+            Self::EmptyLabel => write!(f, "name has an empty label"),
+        }
+    }
+}
+
+impl error::Error for EnsNameError {}
+
+pub fn namehash(name: &str) -> [u8; 32] {
+    let mut node = [0u8; 32];
+    if name.is_empty() {
+        return node;
+    }
+
+    for label in name.rsplit('.') {
+        let label = keccak256(&[label.to_lowercase().as_bytes()]);
+        node = keccak256(&[&node, &label]);
+    }
+    node
+}
+
+fn keccak256(parts: &[&[u8]]) -> MerkleNode {
+    let mut digest = [0u8; 32];
+    let mut keccak = Keccak::v256();
+    for part in parts {
+        keccak.update(part);
+    }
+    keccak.finalize(&mut digest);
+    digest
 }
 
 #[derive(Debug)]
@@ -107,32 +698,59 @@ impl From<InnerAddrParseError> for AddrParseError {
 const ADDR_DECODE_SIZE: usize = 20;
 const ADDR_ENCODE_SIZE: usize = ADDR_DECODE_SIZE * 2;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Address([u8; ADDR_DECODE_SIZE]);
 
 impl Address {
+    pub const ZERO: Self = Self([0; ADDR_DECODE_SIZE]);
+
+    pub const DEAD: Self = {
+        let mut bytes = [0; ADDR_DECODE_SIZE];
+        bytes[ADDR_DECODE_SIZE - 2] = 0xde;
+        bytes[ADDR_DECODE_SIZE - 1] = 0xad;
+        Self(bytes)
+    };
+
     pub fn new(bytes: [u8; ADDR_DECODE_SIZE]) -> Self {
         Self(bytes)
     }
 
+    pub fn is_zero(&self) -> bool {
+        *self == Self::ZERO
+    }
+
+    pub fn is_burn(&self) -> bool {
+        self.is_zero() || *self == Self::DEAD
+    }
+
     pub fn inner(&self) -> &[u8; ADDR_DECODE_SIZE] {
         &self.0
     }
-}
 
-impl fmt::Display for Address {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    pub fn from_str_with_chain(addr: &str, chain_id: u64) -> Result<Self, AddrParseError> {
+        parse_addr(addr, Some(chain_id))
+    }
+
+    pub fn to_string_with_chain(&self, chain_id: u64) -> String {
+        self.checksummed(Some(chain_id))
+    }
+
+    fn checksummed(&self, chain_id: Option<u64>) -> String {
         let mut addr_encoded = [0u8; ADDR_ENCODE_SIZE];
         hex::encode_to_slice(self.inner(), &mut addr_encoded)
             .expect("20 bytes encodes to 40 bytes");
-        make_addr_checksum(&mut addr_encoded);
+        make_addr_checksum(&mut addr_encoded, chain_id);
 
-        write!(f, "0x")?;
-        for ch in addr_encoded {
-            f.write_char(ch as char)?;
-        }
+        let mut checksummed = String::with_capacity(2 + ADDR_ENCODE_SIZE);
+        checksummed.push_str("0x");
+        checksummed.extend(addr_encoded.map(char::from));
+        checksummed
+    }
+}
 
-        Ok(())
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.checksummed(None))
     }
 }
 
@@ -140,36 +758,58 @@ impl FromStr for Address {
     type Err = AddrParseError;
 
     fn from_str(addr: &str) -> Result<Self, Self::Err> {
-        let addr_encoded: &[u8; ADDR_ENCODE_SIZE] = addr
-            .as_bytes()
-            .strip_prefix(b"0x")
-            .ok_or(InnerAddrParseError::MissingPrefix)?
-            .try_into()
-            .map_err(|_| InnerAddrParseError::WrongLen)?;
-
-        let mut addr_decoded = [0; ADDR_DECODE_SIZE];
-        hex::decode_to_slice(addr_encoded, &mut addr_decoded)
-            .map_err(InnerAddrParseError::Decode)?;
-
-        if !checksum_eq(addr_encoded) {
-            Err(InnerAddrParseError::BadChecksum)?;
-        }
+        parse_addr(addr, None)
+    }
+}
+
+fn parse_addr(addr: &str, chain_id: Option<u64>) -> Result<Address, AddrParseError> {
+    let addr_encoded: &[u8; ADDR_ENCODE_SIZE] = addr
+        .as_bytes()
+        .strip_prefix(b"0x")
+        .ok_or(InnerAddrParseError::MissingPrefix)?
+        .try_into()
+        .map_err(|_| InnerAddrParseError::WrongLen)?;
+
+    let mut addr_decoded = [0; ADDR_DECODE_SIZE];
+    hex::decode_to_slice(addr_encoded, &mut addr_decoded).map_err(InnerAddrParseError::Decode)?;
+
+    if !checksum_eq(addr_encoded, chain_id) {
+        Err(InnerAddrParseError::BadChecksum)?;
+    }
 
-        Ok(Self(addr_decoded))
+    Ok(Address(addr_decoded))
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Address {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Address {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
     }
 }
 
-fn checksum_eq(addr: &[u8; ADDR_ENCODE_SIZE]) -> bool {
+fn checksum_eq(addr: &[u8; ADDR_ENCODE_SIZE], chain_id: Option<u64>) -> bool {
     let mut addr_checksum = *addr;
-    make_addr_checksum(&mut addr_checksum);
+    make_addr_checksum(&mut addr_checksum, chain_id);
     addr.eq(&addr_checksum)
 }
 
-fn make_addr_checksum(addr: &mut [u8; ADDR_ENCODE_SIZE]) {
+fn make_addr_checksum(addr: &mut [u8; ADDR_ENCODE_SIZE], chain_id: Option<u64>) {
     addr.make_ascii_lowercase();
 
     let mut addr_hash = [0u8; ADDR_DECODE_SIZE];
     let mut keccak = Keccak::v256();
+    if let Some(chain_id) = chain_id {
+        keccak.update(format!("{chain_id}0x").as_bytes());
+    }
     keccak.update(addr);
     keccak.finalize(&mut addr_hash);
 
@@ -188,10 +828,412 @@ fn make_addr_checksum(addr: &mut [u8; ADDR_ENCODE_SIZE]) {
     }
 }
 
+#[derive(Debug)]
+pub struct SignatureError {
+    inner: InnerSignatureError,
+}
+
+impl fmt::Display for SignatureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.inner {
+            InnerSignatureError::WrongLen => write!(f, "signature is wrong length"),
+            InnerSignatureError::BadRecoveryId => write!(f, "signature recovery id is invalid"),
+            InnerSignatureError::Decode(_) => write!(f, "couldn't decode signature"),
+            InnerSignatureError::Recover(_) => write!(f, "couldn't recover signer"),
+        }
+    }
+}
+
+impl error::Error for SignatureError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match &self.inner {
+            InnerSignatureError::Decode(e) => Some(e),
+            InnerSignatureError::Recover(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+enum InnerSignatureError {
+    WrongLen,
+    BadRecoveryId,
+    Decode(FromHexError),
+    Recover(k256::ecdsa::Error),
+}
+
+impl From<InnerSignatureError> for SignatureError {
+    fn from(error: InnerSignatureError) -> Self {
+        Self { inner: error }
+    }
+}
+
+const SIGNATURE_SIZE: usize = 65;
+
+pub fn recover_personal_sign(message: &str, signature: &str) -> Result<Address, SignatureError> {
+    let signature = signature.strip_prefix("0x").unwrap_or(signature);
+    let signature = hex::decode(signature).map_err(InnerSignatureError::Decode)?;
+    if signature.len() != SIGNATURE_SIZE {
+        Err(InnerSignatureError::WrongLen)?;
+    }
+
+    let recovery_id = match signature[64] {
+        0 | 27 => RecoveryId::new(false, false),
+        1 | 28 => RecoveryId::new(true, false),
+        _ => Err(InnerSignatureError::BadRecoveryId)?,
+    };
+    let rs = Signature::from_slice(&signature[..64]).map_err(InnerSignatureError::Recover)?;
+
+    let prefix = format!("\x19Ethereum Signed Message:\n{}", message.len());
+    let hash = keccak256(&[prefix.as_bytes(), message.as_bytes()]);
+    let key = VerifyingKey::recover_from_prehash(&hash, &rs, recovery_id)
+        .map_err(InnerSignatureError::Recover)?;
+
+    let point = key.to_encoded_point(false);
+    let key_hash = keccak256(&[&point.as_bytes()[1..]]);
+    let mut address = [0; ADDR_DECODE_SIZE];
+    address.copy_from_slice(&key_hash[32 - ADDR_DECODE_SIZE..]);
+    Ok(Address(address))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn wallet_set_linked() {
+        let address = |byte| Address::new([byte; 20]);
+        let mut wallet = Wallet::new(address(1));
+        wallet.set_linked([address(2), address(1), address(3), address(2)]);
+
+        assert_eq!(wallet.linked(), [address(2), address(3)]);
+        assert_eq!(wallet.addresses().count(), 3);
+    }
+
+    #[test]
+    fn chain_id_name() {
+        assert_eq!(ChainId::MAINNET.name(), "mainnet");
+        assert_eq!(ChainId::new(11155111).name(), "sepolia");
+        assert_eq!(ChainId::new(31337).name(), "31337");
+    }
+
+    #[test]
+    fn alert_condition_fires() {
+        let wei = Balance::new;
+        let below = AlertCondition::Below(wei(100));
+        assert!(below.fires(wei(150), wei(99)));
+        assert!(below.fires(wei(100), wei(99)));
+        assert!(!below.fires(wei(99), wei(50)));
+        assert!(!below.fires(wei(150), wei(100)));
+
+        let above = AlertCondition::Above(wei(100));
+        assert!(above.fires(wei(50), wei(101)));
+        assert!(!above.fires(wei(101), wei(200)));
+
+        let change = AlertCondition::Change { percent: 10 };
+        assert!(change.fires(wei(100), wei(111)));
+        assert!(change.fires(wei(100), wei(89)));
+        assert!(!change.fires(wei(100), wei(110)));
+        assert!(!change.fires(wei(0), wei(100)));
+    }
+
+    #[test]
+    fn balance_usd() {
+        assert_eq!(
+            Balance::new(1_500_000_000_000_000_000).usd(2000.0),
+            "3000.00"
+        );
+        assert_eq!(Balance::new(1_000_000_000_000_000).usd(3012.45), "3.01");
+        assert_eq!(Balance::new(0).usd(3012.45), "0.00");
+    }
+
+    #[test]
+    fn token_balance_units() {
+        let balance = |decimals, amount| TokenBalance {
+            token: Token {
+                symbol: "USDC".to_string(),
+                address: Address::new([1; 20]),
+                decimals,
+            },
+            amount,
+        };
+        assert_eq!(balance(6, U256::from(12_500_000)).units(), "12.500000");
+        assert_eq!(balance(6, U256::from(42)).units(), "0.000042");
+        assert_eq!(balance(0, U256::from(42)).units(), "42");
+        assert!(
+            balance(38, U256::from(u128::MAX))
+                .units()
+                .starts_with("3.40282366")
+        );
+        assert!(balance(77, U256::MAX).units().starts_with("1.15792089"));
+    }
+
+    #[test]
+    fn balance_delta() {
+        let delta = |before, after| BalanceDelta {
+            before: Balance::new(before),
+            after: Balance::new(after),
+        };
+
+        assert_eq!(delta(200, 250).wei(), "50");
+        assert_eq!(delta(200, 250).percent(), Some(25.0));
+        assert_eq!(delta(200, 150).wei(), "-50");
+        assert_eq!(delta(200, 150).percent(), Some(-25.0));
+        assert_eq!(delta(200, 200).wei(), "0");
+        assert_eq!(delta(0, 100).percent(), None);
+        assert_eq!(delta(u128::MAX, 0).wei(), format!("-{}", u128::MAX));
+    }
+
+    #[test]
+    fn balance_format() {
+        let balance = Balance::new(1_234_567_891_500_000_000_000);
+        let format = |unit, decimals, thousands_separator| {
+            balance.format(&BalanceFormat {
+                unit,
+                decimals,
+                thousands_separator,
+            })
+        };
+        assert_eq!(format(BalanceUnit::Eth, 2, Some(',')), "1,234.57");
+        assert_eq!(format(BalanceUnit::Eth, 0, Some(' ')), "1 235");
+        assert_eq!(format(BalanceUnit::Gwei, 0, Some(',')), "1,234,567,891,500");
+        assert_eq!(format(BalanceUnit::Wei, 4, None), "1234567891500000000000");
+        assert_eq!(Balance::new(21_500_000_001).gwei(), "21.500000001");
+        assert_eq!(
+            Balance::new(999).format(&BalanceFormat::default()),
+            Balance::new(999).eth()
+        );
+        assert_eq!(Balance::new(0).gwei(), "0.000000000");
+    }
+
+    #[test]
+    fn balance_arithmetic() {
+        let wei = Balance::new;
+        assert_eq!(wei(2) + wei(3), wei(5));
+        assert_eq!(wei(5) - wei(3), wei(2));
+        assert_eq!([wei(1), wei(2), wei(3)].iter().sum::<Balance>(), wei(6));
+
+        let max = Balance::from_wei(U256::MAX);
+        assert_eq!(max.checked_add(wei(1)), None);
+        assert_eq!(max.saturating_add(wei(1)), max);
+        assert_eq!(wei(1).checked_sub(wei(2)), None);
+        assert_eq!(wei(1).saturating_sub(wei(2)), wei(0));
+        assert_eq!(Balance::checked_sum([max, wei(0)]), Some(max));
+        assert_eq!(Balance::checked_sum([max, wei(1)]), None);
+    }
+
+    #[test]
+    fn balance_eth_rounded() {
+        let balance = Balance::new(3_756_447_340_569_860_785);
+        assert_eq!(balance.eth(), "3.756447340569860785");
+        assert_eq!(balance.eth_rounded(6), "3.756447");
+        assert_eq!(balance.eth_rounded(4), "3.7564");
+        assert_eq!(balance.eth_rounded(2), "3.76");
+        assert_eq!(balance.eth_rounded(0), "4");
+        assert_eq!(balance.eth_rounded(30), "3.756447340569860785");
+
+        let balance = Balance::new(999_950_000_000_000_000);
+        assert_eq!(balance.eth_rounded(4), "1.0000");
+
+        let balance = Balance::from_wei(U256::MAX);
+        assert_eq!(balance.to_string().parse(), Ok(balance));
+        assert!("".parse::<Balance>().is_err());
+        assert!(format!("{balance}0").parse::<Balance>().is_err());
+        assert_eq!(
+            balance.eth(),
+            "115792089237316195423570985008687907853269984665640564039457.584007913129639935"
+        );
+        assert_eq!(
+            balance.eth_rounded(0),
+            "115792089237316195423570985008687907853269984665640564039458"
+        );
+
+        let balance = Balance::default();
+        assert_eq!(balance.eth(), "0.000000000000000000");
+        assert_eq!(balance.eth_rounded(4), "0.0000");
+    }
+
+    #[test]
+    fn history_downsample_tiers() {
+        const HOUR: i64 = 60 * 60;
+        const DAY: i64 = 24 * HOUR;
+
+        let tiers = [
+            RetentionTier {
+                max_age: Some(7 * DAY),
+                resolution: 0,
+            },
+            RetentionTier {
+                max_age: Some(90 * DAY),
+                resolution: HOUR,
+            },
+            RetentionTier {
+                max_age: None,
+                resolution: DAY,
+            },
+        ];
+
+        let now = 1000 * DAY;
+        let sample = |age: i64, wei: u128| Sample {
+            timestamp: now - age,
+            block_number: None,
+            balance: Balance::new(wei),
+        };
+
+        let samples = [
+            sample(400 * DAY + 2 * HOUR, 1),
+            sample(400 * DAY + HOUR, 2),
+            sample(30 * DAY + 50 * 60, 3),
+            sample(30 * DAY + 40 * 60, 4),
+            sample(30 * DAY, 5),
+            sample(HOUR, 6),
+            sample(HOUR - 60, 7),
+        ];
+
+        let kept: Vec<u128> = downsample(&samples, now, &tiers)
+            .iter()
+            .map(|s| s.balance.wei().as_u128())
+            .collect();
+        assert_eq!(kept, [2, 4, 5, 6, 7]);
+
+        let bounded = [RetentionTier {
+            max_age: Some(7 * DAY),
+            resolution: 0,
+        }];
+        let kept = downsample(&samples, now, &bounded);
+        assert_eq!(kept.len(), 2);
+    }
+
+    #[test]
+    fn history_candles() {
+        let sample = |timestamp: i64, wei: u128| Sample {
+            timestamp,
+            block_number: None,
+            balance: Balance::new(wei),
+        };
+
+        let samples = [
+            sample(130, 8),
+            sample(100, 5),
+            sample(110, 9),
+            sample(120, 2),
+            sample(250, 4),
+        ];
+
+        let candles = candles(&samples, 100);
+        assert_eq!(candles.len(), 2);
+
+        assert_eq!(candles[0].start, 100);
+        assert_eq!(candles[0].open, Balance::new(5));
+        assert_eq!(candles[0].high, Balance::new(9));
+        assert_eq!(candles[0].low, Balance::new(2));
+        assert_eq!(candles[0].close, Balance::new(8));
+
+        assert_eq!(candles[1].start, 200);
+        assert_eq!(candles[1].open, Balance::new(4));
+        assert_eq!(candles[1].close, Balance::new(4));
+    }
+
+    #[test]
+    fn history_inflows() {
+        let received = [
+            (150, Balance::new(3)),
+            (120, Balance::new(5)),
+            (210, Balance::new(4)),
+            (260, Balance::new(0)),
+            (-20, Balance::new(2)),
+            (420, Balance::new(1)),
+        ];
+
+        let inflows = inflows(received, 100);
+        assert_eq!(
+            inflows,
+            [
+                Inflow {
+                    start: -100,
+                    amount: Balance::new(2),
+                },
+                Inflow {
+                    start: 100,
+                    amount: Balance::new(8),
+                },
+                Inflow {
+                    start: 200,
+                    amount: Balance::new(4),
+                },
+                Inflow {
+                    start: 400,
+                    amount: Balance::new(1),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn merkle_root_shape() {
+        let [a, b, c] = [b"a", b"b", b"c"].map(|leaf| merkle_leaf(leaf));
+
+        assert_eq!(merkle_root(&[]), merkle_leaf(&[]));
+        assert_eq!(merkle_root(&[a]), a);
+
+        let ab = keccak256(&[&[0x01], &a, &b]);
+        assert_eq!(merkle_root(&[a, b]), ab);
+        assert_eq!(merkle_root(&[a, b, c]), keccak256(&[&[0x01], &ab, &c]));
+        assert_ne!(merkle_root(&[a, b]), merkle_root(&[b, a]));
+    }
+
+    #[test]
+    fn namehash_eip137() {
+        assert_eq!(namehash(""), [0; 32]);
+        assert_eq!(
+            hex::encode(namehash("eth")),
+            "93cdeb708b7545dc668eb9280176169d1c33cfd8ed6f04690a0bcc88a93fc4ae"
+        );
+        assert_eq!(
+            hex::encode(namehash("foo.eth")),
+            "de9b09fd7c5f901e23a3f19fecc54828e9c848539801e86591bd9801b019f84f"
+        );
+    }
+
+    #[test]
+    fn ens_name_normalize() {
+        let name = EnsName::from_str("Vitalik.ETH").unwrap();
+        assert_eq!(name.as_str(), "vitalik.eth");
+        assert!(name.is_eth());
+        assert_eq!(name, EnsName::from_str("vitalik.eth").unwrap());
+        assert_eq!(name.namehash(), namehash("vitalik.eth"));
+
+        // Fullwidth letters map to their ASCII forms, punycode is decoded.
+        assert_eq!(EnsName::from_str("ｆｏｏ.eth").unwrap().as_str(), "foo.eth");
+        assert_eq!(
+            EnsName::from_str("xn--mnchen-3ya.eth").unwrap().as_str(),
+            "münchen.eth"
+        );
+
+        assert_eq!(EnsName::from_str("foo..eth"), Err(EnsNameError::EmptyLabel));
+        assert_eq!(
+            EnsName::from_str("foo bar.eth"),
+            Err(EnsNameError::Disallowed)
+        );
+    }
+
+    #[test]
+    fn signature_recover_personal_sign() {
+        // Example from the web3.js `accounts.sign` docs.
+        let signature = "0xb91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd\
+                         6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a0291c";
+        let signer = recover_personal_sign("Some data", signature).unwrap();
+        assert_eq!(
+            signer.to_string(),
+            "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23"
+        );
+
+        let other = recover_personal_sign("Other data", signature).unwrap();
+        assert_ne!(other, signer);
+
+        assert!(recover_personal_sign("Some data", "0x1234").is_err());
+    }
+
     #[test]
     fn addr_display_checksum() {
         let encoded = "0xAb5801a7D398351b8bE11C439e05C5B3259aeC9B";
@@ -249,9 +1291,86 @@ mod tests {
         assert!(matches!(error.inner, InnerAddrParseError::BadChecksum));
     }
 
+    #[test]
+    fn addr_burn() {
+        assert!(Address::ZERO.is_zero());
+        assert_eq!(
+            Address::ZERO.to_string(),
+            "0x0000000000000000000000000000000000000000"
+        );
+        assert_eq!(
+            Address::DEAD.to_string(),
+            "0x000000000000000000000000000000000000dEaD"
+        );
+        assert!(Address::DEAD.is_burn() && !Address::DEAD.is_zero());
+
+        let address = Address::from_str("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045").unwrap();
+        assert!(!address.is_burn());
+    }
+
+    #[test]
+    fn addr_ordering() {
+        let mut addresses = [
+            Address::from_str("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045").unwrap(),
+            Address::DEAD,
+            Address::from_str("0xB644Babc370f46f202DB5eaf2071A9Ee66fA1D5E").unwrap(),
+            Address::ZERO,
+        ];
+        addresses.sort();
+        assert_eq!(addresses[0], Address::ZERO);
+        assert_eq!(addresses[1], Address::DEAD);
+        assert_eq!(
+            addresses[2].to_string(),
+            "0xB644Babc370f46f202DB5eaf2071A9Ee66fA1D5E"
+        );
+    }
+
+    #[test]
+    fn addr_parse_with_chain() {
+        // EIP-1191 test vectors for RSK mainnet (30) and testnet (31).
+        for (chain_id, encoded) in [
+            (30, "0x5aaEB6053f3e94c9b9a09f33669435E7ef1bEAeD"),
+            (30, "0xFb6916095cA1Df60bb79ce92cE3EA74c37c5d359"),
+            (31, "0x5aAeb6053F3e94c9b9A09F33669435E7EF1BEaEd"),
+            (31, "0xFb6916095CA1dF60bb79CE92ce3Ea74C37c5D359"),
+        ] {
+            let decoded = Address::from_str_with_chain(encoded, chain_id).unwrap();
+            assert_eq!(decoded.to_string_with_chain(chain_id), encoded);
+            assert!(Address::from_str(encoded).is_err());
+        }
+
+        let mainnet = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
+        let error = Address::from_str_with_chain(mainnet, 30).unwrap_err();
+        assert!(matches!(error.inner, InnerAddrParseError::BadChecksum));
+    }
+
     #[test]
     fn addr_parse_decode_err() {
         let error = Address::from_str("0xABCDEFGHIJKLMNOPQRSTabcdefghijklmnopqrst").unwrap_err();
         assert!(matches!(error.inner, InnerAddrParseError::Decode(_)));
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_address_and_balance() {
+        let address = Address::from_str("0xB644Babc370f46f202DB5eaf2071A9Ee66fA1D5E").unwrap();
+        let balance = Balance::from_wei(U256::MAX);
+
+        let json = serde_json::to_string(&(address, balance)).unwrap();
+        assert_eq!(
+            json,
+            format!(
+                r#"["0xB644Babc370f46f202DB5eaf2071A9Ee66fA1D5E","{}"]"#,
+                U256::MAX
+            )
+        );
+        assert_eq!(
+            serde_json::from_str::<(Address, Balance)>(&json).unwrap(),
+            (address, balance)
+        );
+
+        let lowercase = r#""0xb644babc370f46f202db5eaf2071a9ee66fa1d5e""#;
+        assert!(serde_json::from_str::<Address>(lowercase).is_err());
+        assert!(serde_json::from_str::<Balance>("1").is_err());
+    }
 }
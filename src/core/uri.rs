@@ -0,0 +1,158 @@
+use std::{error, fmt, num::ParseIntError, str::FromStr};
+
+use super::{AddrParseError, Address};
+
+const SCHEME: &str = "ethereum:";
+const PAY_PREFIX: &str = "pay-";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaymentUri {
+    pub target: Address,
+    pub chain_id: Option<u64>,
+    pub function: Option<String>,
+    pub params: Vec<(String, String)>,
+}
+
+impl PaymentUri {
+    pub fn recipient(&self) -> Result<Address, UriParseError> {
+        if self.function.as_deref() != Some("transfer") {
+            return Ok(self.target);
+        }
+
+        let (_, address) = self
+            .params
+            .iter()
+            .find(|(key, _)| key == "address")
+            .ok_or(InnerUriParseError::MissingRecipient)?;
+        let address = Address::from_str(address).map_err(InnerUriParseError::Address)?;
+        Ok(address)
+    }
+}
+
+impl FromStr for PaymentUri {
+    type Err = UriParseError;
+
+    fn from_str(uri: &str) -> Result<Self, Self::Err> {
+        let rest = uri
+            .get(..SCHEME.len())
+            .filter(|scheme| scheme.eq_ignore_ascii_case(SCHEME))
+            .map(|_| &uri[SCHEME.len()..])
+            .ok_or(InnerUriParseError::MissingScheme)?;
+        let rest = rest.strip_prefix(PAY_PREFIX).unwrap_or(rest);
+
+        let (rest, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let (rest, function) = match rest.split_once('/') {
+            Some((rest, function)) => (rest, Some(function.to_owned())),
+            None => (rest, None),
+        };
+        let (target, chain_id) = match rest.split_once('@') {
+            Some((target, chain_id)) => {
+                let chain_id = chain_id.parse().map_err(InnerUriParseError::ChainId)?;
+                (target, Some(chain_id))
+            }
+            None => (rest, None),
+        };
+
+        let target = Address::from_str(target).map_err(InnerUriParseError::Address)?;
+        let params = query
+            .split('&')
+            .filter(|param| !param.is_empty())
+            .map(|param| {
+                let (key, value) = param.split_once('=').unwrap_or((param, ""));
+                (key.to_owned(), value.to_owned())
+            })
+            .collect();
+
+        Ok(Self {
+            target,
+            chain_id,
+            function,
+            params,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct UriParseError {
+    inner: InnerUriParseError,
+}
+
+impl fmt::Display for UriParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.inner {
+            InnerUriParseError::MissingScheme => write!(f, "uri missing ethereum: scheme"),
+            InnerUriParseError::MissingRecipient => write!(f, "transfer uri missing address"),
+            InnerUriParseError::ChainId(_) => write!(f, "couldn't parse uri chain id"),
+            InnerUriParseError::Address(_) => write!(f, "couldn't parse uri address"),
+        }
+    }
+}
+
+impl error::Error for UriParseError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match &self.inner {
+            InnerUriParseError::ChainId(e) => Some(e),
+            InnerUriParseError::Address(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+enum InnerUriParseError {
+    MissingScheme,
+    MissingRecipient,
+    ChainId(ParseIntError),
+    Address(AddrParseError),
+}
+
+impl From<InnerUriParseError> for UriParseError {
+    fn from(error: InnerUriParseError) -> Self {
+        Self { inner: error }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDR: &str = "0xAb5801a7D398351b8bE11C439e05C5B3259aeC9B";
+    const TOKEN: &str = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2";
+
+    #[test]
+    fn uri_parse_plain() {
+        let uri = PaymentUri::from_str(&format!("ethereum:{ADDR}")).unwrap();
+        assert_eq!(uri.target.to_string(), ADDR);
+        assert_eq!(uri.chain_id, None);
+        assert_eq!(uri.recipient().unwrap(), uri.target);
+    }
+
+    #[test]
+    fn uri_parse_chain_and_params() {
+        let uri = PaymentUri::from_str(&format!("ethereum:pay-{ADDR}@137?value=2.014e18")).unwrap();
+        assert_eq!(uri.target.to_string(), ADDR);
+        assert_eq!(uri.chain_id, Some(137));
+        assert_eq!(uri.params, [("value".to_string(), "2.014e18".to_string())]);
+    }
+
+    #[test]
+    fn uri_parse_transfer() {
+        let uri = format!("ethereum:{TOKEN}@1/transfer?address={ADDR}&uint256=1");
+        let uri = PaymentUri::from_str(&uri).unwrap();
+        assert_eq!(uri.target.to_string(), TOKEN);
+        assert_eq!(uri.function.as_deref(), Some("transfer"));
+        assert_eq!(uri.recipient().unwrap().to_string(), ADDR);
+    }
+
+    #[test]
+    fn uri_parse_errors() {
+        let error = PaymentUri::from_str(ADDR).unwrap_err();
+        assert!(matches!(error.inner, InnerUriParseError::MissingScheme));
+
+        let error = PaymentUri::from_str(&format!("ethereum:{ADDR}@main")).unwrap_err();
+        assert!(matches!(error.inner, InnerUriParseError::ChainId(_)));
+
+        let error = PaymentUri::from_str("ethereum:vitalik.eth").unwrap_err();
+        assert!(matches!(error.inner, InnerUriParseError::Address(_)));
+    }
+}
@@ -0,0 +1,173 @@
+use std::{error, fmt, time::Duration};
+
+use async_trait::async_trait;
+use reqwest::{Client, Error as ReqwestError};
+use serde_json::Value;
+use tracing::{debug, instrument};
+
+use crate::{
+    core::{Address, ChainId, Transfer},
+    infra::{ClientError, HistoryClient},
+};
+
+#[derive(Debug)]
+pub struct EtherscanError(Box<dyn error::Error + Send + Sync + 'static>);
+
+impl fmt::Display for EtherscanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "etherscan error")
+    }
+}
+
+impl error::Error for EtherscanError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&*self.0)
+    }
+}
+
+impl From<ReqwestError> for EtherscanError {
+    fn from(error: ReqwestError) -> Self {
+        Self(error.into())
+    }
+}
+
+impl From<EtherscanError> for ClientError {
+    fn from(error: EtherscanError) -> Self {
+        ClientError(error.into())
+    }
+}
+
+#[derive(Clone)]
+pub struct EtherscanClient {
+    client: Client,
+    url: String,
+    api_key: String,
+}
+
+impl fmt::Debug for EtherscanClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EtherscanClient")
+            .field("url", &self.url)
+            .finish()
+    }
+}
+
+impl EtherscanClient {
+    pub fn new(url: impl Into<String>, api_key: impl Into<String>) -> Result<Self, EtherscanError> {
+        Ok(Self {
+            client: Client::builder().timeout(Duration::from_secs(30)).build()?,
+            url: url.into(),
+            api_key: api_key.into(),
+        })
+    }
+}
+
+#[async_trait]
+impl HistoryClient for EtherscanClient {
+    #[instrument(skip(self), fields(address = %address.to_string(), chain_id = %chain_id))]
+    async fn transfers(
+        &self,
+        address: &Address,
+        chain_id: ChainId,
+        limit: usize,
+    ) -> Result<Vec<Transfer>, ClientError> {
+        debug!("calling etherscan txlist");
+        let body: Value = self
+            .client
+            .get(&self.url)
+            .query(&[
+                ("chainid", chain_id.to_string().as_str()),
+                ("module", "account"),
+                ("action", "txlist"),
+                ("address", &address.to_string()),
+                ("page", "1"),
+                ("offset", &limit.to_string()),
+                ("sort", "desc"),
+                ("apikey", &self.api_key),
+            ])
+            .send()
+            .await
+            .map_err(EtherscanError::from)?
+            .error_for_status()
+            .map_err(EtherscanError::from)?
+            .json()
+            .await
+            .map_err(EtherscanError::from)?;
+
+        Ok(parse_transfers(&body)?)
+    }
+}
+
+fn parse_transfers(body: &Value) -> Result<Vec<Transfer>, EtherscanError> {
+    let Some(entries) = body["result"].as_array() else {
+        let reason = body["result"].as_str().unwrap_or("malformed response");
+        return Err(EtherscanError(reason.into()));
+    };
+
+    entries
+        .iter()
+        .map(|entry| parse_transfer(entry).ok_or(EtherscanError("malformed transaction".into())))
+        .collect()
+}
+
+fn parse_transfer(entry: &Value) -> Option<Transfer> {
+    let field = |key: &str| entry[key].as_str();
+    Some(Transfer {
+        hash: field("hash")?.to_string(),
+        block_number: field("blockNumber")?.parse().ok()?,
+        timestamp: field("timeStamp")?.parse().ok()?,
+        from: parse_address(field("from")?)?,
+        to: match field("to")? {
+            "" => None,
+            to => Some(parse_address(to)?),
+        },
+        value: field("value")?.parse().ok()?,
+        failed: field("isError") == Some("1"),
+    })
+}
+
+fn parse_address(address: &str) -> Option<Address> {
+    let mut bytes = [0u8; 20];
+    hex::decode_to_slice(address.strip_prefix("0x")?, &mut bytes).ok()?;
+    Some(Address::new(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::parse_transfers;
+    use crate::core::Balance;
+
+    #[test]
+    fn etherscan_parse_txlist() {
+        let body = json!({
+            "status": "1",
+            "message": "OK",
+            "result": [{
+                "blockNumber": "19000000",
+                "timeStamp": "1705000000",
+                "hash": "0xabc",
+                "from": "0xb644babc370f46f202db5eaf2071a9ee66fa1d5e",
+                "to": "",
+                "value": "1500000000000000000",
+                "isError": "1",
+            }],
+        });
+        let transfers = parse_transfers(&body).unwrap();
+        assert_eq!(transfers[0].block_number, 19_000_000);
+        assert_eq!(
+            transfers[0].from.to_string(),
+            "0xB644Babc370f46f202DB5eaf2071A9Ee66fA1D5E"
+        );
+        assert_eq!(transfers[0].to, None);
+        assert_eq!(transfers[0].value, Balance::new(1_500_000_000_000_000_000));
+        assert!(transfers[0].failed);
+
+        let empty = json!({ "status": "0", "message": "No transactions found", "result": [] });
+        assert!(parse_transfers(&empty).unwrap().is_empty());
+
+        let denied = json!({ "status": "0", "message": "NOTOK", "result": "Invalid API Key" });
+        assert!(parse_transfers(&denied).is_err());
+    }
+}
@@ -1,17 +1,43 @@
-use std::{collections::HashMap, error, fmt, io, path::PathBuf, sync::Arc};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    error, fmt,
+    fs::{File, TryLockError},
+    io, mem,
+    ops::{Deref, Range},
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
 
 use async_trait::async_trait;
 use bincode::{
     Decode, Encode,
+    de::Decoder,
     error::{DecodeError, EncodeError},
 };
-use chrono::DateTime;
-use tokio::{fs, sync::RwLock};
-use tracing::{debug, info, instrument};
+use chrono::{DateTime, Utc};
+use tokio::{
+    fs::{self, OpenOptions},
+    io::AsyncWriteExt,
+    sync::{Mutex, RwLock},
+    time::sleep,
+};
+use tracing::{debug, info, instrument, warn};
+use uuid::Uuid;
+use xxhash_rust::xxh3::xxh3_64;
 
 use crate::{
-    core::{Address, Balance, Wallet},
-    infra::{StoreError, WalletRecord, WalletStore},
+    core::{Address, AlertCondition, Balance, ChainId, Sample, Token, TokenBalance, U256, Wallet},
+    infra::{
+        AlertRule, AlertStore, AuditEntry, AuditOutcome, AuditStore, HistoryStore, Insertion,
+        Interface, ListSort, Notification, OutboxEntry, OutboxStore, PreferenceStore, Provenance,
+        SavedView, StoreAdmin, StoreError, StoreInfo, StoreStats, TrashStore, TrashedRecord,
+        WalletChange, WalletRecord, WalletStore, WalletUpdate, WriteCounters, plan_insertions,
+        search_records,
+    },
 };
 
 #[derive(Debug)]
@@ -47,71 +73,651 @@ impl From<EncodeError> for FsError {
     }
 }
 
+const STORE_MAGIC: [u8; 4] = *b"MWDB";
+const STORE_VERSION: u16 = 24;
+
+const CODEC_NONE: u8 = 0;
+const CODEC_ZSTD: u8 = 1;
+
+const COMPACT_MIN_BYTES: u64 = 64 * 1024;
+
+#[derive(Debug, Default, Encode, Decode)]
+#[bincode(decode_context = "u16")]
+struct FsSnapshot {
+    seq: u64,
+    data: FsData,
+}
+
+fn encode_store(seq: u64, data: &FsData, compression: i32) -> Result<Vec<u8>, FsError> {
+    let config = bincode::config::standard();
+    let mut payload = bincode::encode_to_vec((seq, data), config)?;
+    let mut codec = CODEC_NONE;
+    if compression != 0 {
+        payload = zstd::encode_all(payload.as_slice(), compression)?;
+        codec = CODEC_ZSTD;
+    }
+
+    let mut bytes = STORE_MAGIC.to_vec();
+    bytes.extend_from_slice(&STORE_VERSION.to_le_bytes());
+    bytes.push(codec);
+    bytes.extend_from_slice(&xxh3_64(&payload).to_le_bytes());
+    bytes.extend(payload);
+    Ok(bytes)
+}
+
+fn decode_store(bytes: &[u8]) -> Result<(u16, FsSnapshot), FsError> {
+    let truncated = || FsError("wallet store header is truncated".into());
+    let (version, mut payload) = match bytes.strip_prefix(&STORE_MAGIC) {
+        Some(rest) => {
+            let (version, payload) = rest.split_first_chunk::<2>().ok_or_else(truncated)?;
+            (u16::from_le_bytes(*version), payload)
+        }
+        None => (0, bytes),
+    };
+    if version > STORE_VERSION {
+        return Err(FsError(
+            format!("wallet store format {version} is newer than this build").into(),
+        ));
+    }
+
+    let config = bincode::config::standard();
+    let mut codec = CODEC_NONE;
+    if version >= 24 {
+        let (stored, rest) = payload.split_first().ok_or_else(truncated)?;
+        codec = *stored;
+        payload = rest;
+    }
+    if version >= 22 {
+        let (checksum, checked) = payload.split_first_chunk::<8>().ok_or_else(truncated)?;
+        if u64::from_le_bytes(*checksum) != xxh3_64(checked) {
+            return Err(FsError("wallet store checksum mismatch".into()));
+        }
+        payload = checked;
+    }
+    let decompressed;
+    let payload = match codec {
+        CODEC_NONE => payload,
+        CODEC_ZSTD => {
+            decompressed = zstd::decode_all(payload)?;
+            decompressed.as_slice()
+        }
+        _ => {
+            return Err(FsError(
+                format!("wallet store codec {codec} is unknown").into(),
+            ));
+        }
+    };
+    let snapshot = if version < 23 {
+        FsSnapshot {
+            seq: 0,
+            data: bincode::decode_from_slice_with_context(payload, config, version)?.0,
+        }
+    } else {
+        bincode::decode_from_slice_with_context(payload, config, version)?.0
+    };
+    Ok((version, snapshot))
+}
+
+fn since<T, D>(decoder: &mut D, version: u16) -> Result<Option<T>, DecodeError>
+where
+    T: Decode<u16>,
+    D: Decoder<Context = u16>,
+{
+    if *decoder.context() < version {
+        return Ok(None);
+    }
+    T::decode(decoder).map(Some)
+}
+
+impl Decode<u16> for FsData {
+    fn decode<D: Decoder<Context = u16>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        if *decoder.context() < 2 {
+            return Ok(Self {
+                wallets: Decode::decode(decoder)?,
+                ..Self::default()
+            });
+        }
+        Ok(Self {
+            wallets: Decode::decode(decoder)?,
+            trash: Decode::decode(decoder)?,
+            alerts: since(decoder, 10)?.unwrap_or_default(),
+            pins: since(decoder, 11)?.unwrap_or_default(),
+            views: since(decoder, 12)?.unwrap_or_default(),
+            outbox: since(decoder, 13)?.unwrap_or_default(),
+            history: since(decoder, 15)?.unwrap_or_default(),
+        })
+    }
+}
+
+bincode::impl_borrow_decode_with_context!(FsData, u16);
+
+impl Decode<u16> for FsWallet {
+    fn decode<D: Decoder<Context = u16>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let mut wallet = Self {
+            id: since(decoder, 4)?.unwrap_or_else(|| Uuid::new_v4().to_string()),
+            chain_id: since(decoder, 20)?.unwrap_or(ChainId::MAINNET.get()),
+            address: Decode::decode(decoder)?,
+            linked: since(decoder, 7)?.unwrap_or_default(),
+            balance: Decode::decode(decoder)?,
+            tags: since(decoder, 1)?.unwrap_or_default(),
+            note: since(decoder, 8)?.flatten(),
+            created_at: since(decoder, 21)?.unwrap_or_default(),
+            created_by: since(decoder, 3)?.unwrap_or_default(),
+            modified_by: since(decoder, 3)?.unwrap_or_default(),
+            last_update: Decode::decode(decoder)?,
+            verified_at: since(decoder, 5)?.flatten(),
+            ens_name: since(decoder, 6)?.flatten(),
+            ens_checked_at: since(decoder, 6)?.flatten(),
+            funded_at: since(decoder, 9)?.flatten(),
+            swept_at: since(decoder, 9)?.flatten(),
+            previous_balance: since(decoder, 14)?.flatten(),
+            tokens: since(decoder, 16)?.unwrap_or_default(),
+            transaction_count: since(decoder, 17)?.flatten(),
+            resolved_from: since(decoder, 18)?.flatten(),
+            resolved_at: since(decoder, 18)?.flatten(),
+        };
+        if *decoder.context() < 21 {
+            // The closest to its creation that's known.
+            wallet.created_at = wallet.last_update;
+        }
+        Ok(wallet)
+    }
+}
+
+bincode::impl_borrow_decode_with_context!(FsWallet, u16);
+
+fn encode_op(bytes: &mut Vec<u8>, seq: u64, op: &FsOp) -> Result<(), FsError> {
+    let config = bincode::config::standard();
+    let record = bincode::encode_to_vec((seq, op), config)?;
+    bytes.extend_from_slice(&(record.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(&xxh3_64(&record).to_le_bytes());
+    bytes.extend(record);
+    Ok(())
+}
+
+fn decode_log(bytes: &[u8], version: u16) -> (Vec<(u64, FsOp)>, usize) {
+    let config = bincode::config::standard();
+    let mut ops = Vec::new();
+    let mut rest = bytes;
+    while let Some((len, tail)) = rest.split_first_chunk::<4>() {
+        let len = u32::from_le_bytes(*len) as usize;
+        let Some((checksum, tail)) = tail.split_first_chunk::<8>() else {
+            break;
+        };
+        let Some((record, tail)) = tail.split_at_checked(len) else {
+            break;
+        };
+        if u64::from_le_bytes(*checksum) != xxh3_64(record) {
+            break;
+        }
+        let Ok((op, _)) = bincode::decode_from_slice_with_context(record, config, version) else {
+            break;
+        };
+        ops.push(op);
+        rest = tail;
+    }
+    (ops, bytes.len() - rest.len())
+}
+
 #[derive(Debug, Clone)]
 pub struct FsWalletStore {
     path: PathBuf,
-    wallets: Arc<RwLock<HashMap<String, FsWallet>>>,
+    data: Arc<RwLock<FsState>>,
+    log: Arc<Mutex<FsLog>>,
+    compacting: Arc<AtomicBool>,
+    recovered_from: Option<PathBuf>,
+    backups: u32,
+    compression: i32,
+    flush_interval: Duration,
+    flush_scheduled: Arc<AtomicBool>,
+    writes: Arc<WriteCounters>,
+    _lock: Arc<File>,
+}
+
+#[derive(Debug)]
+struct FsLog {
+    file: fs::File,
+    seq: u64,
+    len: u64,
+    snapshot_len: u64,
+    pending: Vec<u8>,
 }
 
 impl FsWalletStore {
     #[instrument(fields(path = %path.as_ref()))]
-    pub async fn open(path: impl AsRef<str>) -> Result<Self, FsError> {
+    pub async fn open(
+        path: impl AsRef<str>,
+        backups: u32,
+        compression: i32,
+    ) -> Result<Self, FsError> {
         let path_str = path.as_ref();
         let path = PathBuf::from(path_str);
+        let lock = Arc::new(lock_store(&path).await?);
 
-        let store = if !path.exists() {
-            let wallets = Arc::new(RwLock::new(HashMap::new()));
-            let store = Self { path, wallets };
-            store.write().await?;
-            info!("created wallet store");
-            store
+        let existed = path.exists();
+        let (version, snapshot, snapshot_len, recovered_from) = if !existed {
+            (STORE_VERSION, FsSnapshot::default(), 0, None)
         } else {
             let bytes = fs::read(&path).await?;
-            let config = bincode::config::standard();
-            let (wallets, _) = bincode::decode_from_slice(&bytes, config)?;
-            let wallets = Arc::new(RwLock::new(wallets));
-            info!("opened wallet store");
-            Self { path, wallets }
+            match decode_store(&bytes) {
+                Ok((version, snapshot)) => (version, snapshot, bytes.len() as u64, None),
+                Err(error) => {
+                    let Some((backup_path, (version, snapshot))) =
+                        read_newest_backup(&path, backups).await
+                    else {
+                        return Err(error);
+                    };
+                    warn!(
+                        %error,
+                        backup = %backup_path.to_string_lossy(),
+                        "wallet store is corrupt, recovered from backup"
+                    );
+                    (version, snapshot, 0, Some(backup_path))
+                }
+            }
+        };
+
+        let log_path = log_path(&path);
+        let log_bytes = match fs::read(&log_path).await {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e.into()),
+        };
+        let (ops, valid_len) = decode_log(&log_bytes, version);
+        if valid_len < log_bytes.len() {
+            warn!(
+                dropped = log_bytes.len() - valid_len,
+                "wallet store log ends in a torn record, dropped it"
+            );
+        }
+
+        let FsSnapshot { mut seq, mut data } = snapshot;
+        let mut replayed = 0;
+        for (op_seq, op) in ops {
+            if op_seq <= seq {
+                continue;
+            }
+            // A backup can be older than the last compaction, which emptied
+            // the log of the ops in between.
+            if op_seq != seq + 1 {
+                warn!(
+                    from = seq,
+                    to = op_seq,
+                    "wallet store log has a gap, stopped replay"
+                );
+                break;
+            }
+            data.apply(op);
+            seq = op_seq;
+            replayed += 1;
+        }
+
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&log_path)
+            .await?;
+        file.set_len(valid_len as u64).await?;
+        let log = FsLog {
+            file,
+            seq,
+            len: valid_len as u64,
+            snapshot_len,
+            pending: Vec::new(),
+        };
+
+        let store = Self {
+            path,
+            data: Arc::new(RwLock::new(FsState::new(data))),
+            log: Arc::new(Mutex::new(log)),
+            compacting: Arc::new(AtomicBool::new(false)),
+            recovered_from,
+            backups,
+            compression,
+            flush_interval: Duration::ZERO,
+            flush_scheduled: Arc::new(AtomicBool::new(false)),
+            writes: Arc::default(),
+            _lock: lock,
         };
+        if replayed > 0 {
+            debug!(replayed, "replayed wallet store log");
+        }
+        // Not rotated, so the corrupt file doesn't push a good backup out.
+        if store.recovered_from.is_some() {
+            store.compact_into(false).await?;
+        } else if !existed {
+            store.write().await?;
+            info!("created wallet store");
+        } else if version < STORE_VERSION {
+            store.write().await?;
+            info!(from = version, to = STORE_VERSION, "migrated wallet store");
+        }
+        info!("opened wallet store");
 
         Ok(store)
     }
 
+    pub fn with_flush_interval(mut self, interval: Duration) -> Self {
+        self.flush_interval = interval;
+        self
+    }
+
     #[instrument(skip(self), fields(path = %self.path.to_string_lossy()))]
     async fn write(&self) -> Result<(), FsError> {
-        let wallet = self.wallets.read().await;
+        self.compact_into(true).await
+    }
 
-        let config = bincode::config::standard();
-        let bytes = bincode::encode_to_vec(&*wallet, config)?;
+    async fn compact_into(&self, rotate: bool) -> Result<(), FsError> {
+        let data = self.data.read().await;
+        let mut log = self.log.lock().await;
+
+        if rotate {
+            self.rotate_backups().await?;
+        }
+        let bytes = encode_store(log.seq, &data, self.compression)?;
+        write_atomic(&self.path, &bytes).await?;
+        log.file.set_len(0).await?;
+        log.file.sync_all().await?;
+        log.len = 0;
+        log.snapshot_len = bytes.len() as u64;
+        // The snapshot already holds them.
+        log.pending.clear();
+
+        debug!("wrote {} bytes to wallet store", bytes.len());
+        Ok(())
+    }
+
+    async fn commit(&self, data: &mut FsState, ops: Vec<FsOp>) -> Result<(), FsError> {
+        let mut log = self.log.lock().await;
+        let mut bytes = Vec::new();
+        for (seq, op) in (log.seq + 1..).zip(&ops) {
+            encode_op(&mut bytes, seq, op)?;
+        }
+
+        if self.flush_interval.is_zero() {
+            append(&mut log, &self.writes, &bytes).await?;
+        } else {
+            log.pending.extend_from_slice(&bytes);
+            self.schedule_flush();
+        }
+        log.seq += ops.len() as u64;
+        for op in ops {
+            data.apply(op);
+        }
+
+        self.compact_if_outgrown(&log);
+        Ok(())
+    }
+
+    async fn flush_pending(&self) -> Result<(), FsError> {
+        let mut log = self.log.lock().await;
+        flush_log(&mut log, &self.writes).await?;
+        self.compact_if_outgrown(&log);
+        Ok(())
+    }
+
+    fn schedule_flush(&self) {
+        if self.flush_scheduled.swap(true, Ordering::AcqRel) {
+            return;
+        }
+
+        let log = Arc::downgrade(&self.log);
+        let writes = self.writes.clone();
+        let scheduled = self.flush_scheduled.clone();
+        let interval = self.flush_interval;
+        tokio::spawn(async move {
+            sleep(interval).await;
+            scheduled.store(false, Ordering::Release);
+            let Some(log) = log.upgrade() else {
+                return;
+            };
+            if let Err(error) = flush_log(&mut *log.lock().await, &writes).await {
+                warn!(%error, "wallet store flush failed, retrying with the next change");
+            }
+        });
+    }
+
+    fn compact_if_outgrown(&self, log: &FsLog) {
+        if log.len > log.snapshot_len.max(COMPACT_MIN_BYTES)
+            && !self.compacting.swap(true, Ordering::AcqRel)
+        {
+            let store = self.clone();
+            tokio::spawn(async move {
+                if let Err(error) = store.write().await {
+                    warn!(%error, "wallet store compaction failed");
+                }
+                store.compacting.store(false, Ordering::Release);
+            });
+        }
+    }
+
+    async fn copy_to(&self, dest: &Path) -> Result<(), FsError> {
+        let data = self.data.read().await;
+        let seq = self.log.lock().await.seq;
+        let bytes = encode_store(seq, &data, self.compression)?;
+        drop(data);
+
+        write_atomic(dest, &bytes).await
+    }
 
-        if let Some(parent) = self.path.parent() {
-            fs::create_dir_all(parent).await?;
+    async fn rotate_backups(&self) -> Result<(), FsError> {
+        if self.backups == 0 || !fs::try_exists(&self.path).await? {
+            return Ok(());
         }
 
-        let len = bytes.len();
-        fs::write(&self.path, bytes).await?;
-        debug!("wrote {} bytes to wallet store", len);
+        for n in (1..self.backups).rev() {
+            let from = rotated_path(&self.path, n);
+            if fs::try_exists(&from).await? {
+                fs::rename(&from, rotated_path(&self.path, n + 1)).await?;
+            }
+        }
+        fs::copy(&self.path, rotated_path(&self.path, 1)).await?;
         Ok(())
     }
 }
 
+async fn lock_store(path: &Path) -> Result<File, FsError> {
+    let mut lock_path = path.as_os_str().to_owned();
+    lock_path.push(".lock");
+    let lock_path = PathBuf::from(lock_path);
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent).await?;
+    }
+
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_path)
+        .await?
+        .into_std()
+        .await;
+    match file.try_lock() {
+        Ok(()) => Ok(file),
+        Err(TryLockError::WouldBlock) => Err(FsError(
+            format!(
+                "wallet store is in use by another process ({} is locked)",
+                lock_path.to_string_lossy()
+            )
+            .into(),
+        )),
+        Err(TryLockError::Error(error)) => Err(error.into()),
+    }
+}
+
+async fn append(log: &mut FsLog, writes: &WriteCounters, bytes: &[u8]) -> Result<(), FsError> {
+    let appended = async {
+        log.file.write_all(bytes).await?;
+        log.file.sync_data().await
+    }
+    .await;
+    writes.record(&appended);
+    if let Err(error) = appended {
+        // Don't leave half a record for the next append to follow.
+        let _ = log.file.set_len(log.len).await;
+        return Err(error.into());
+    }
+    log.len += bytes.len() as u64;
+    Ok(())
+}
+
+async fn flush_log(log: &mut FsLog, writes: &WriteCounters) -> Result<(), FsError> {
+    if log.pending.is_empty() {
+        return Ok(());
+    }
+
+    let pending = mem::take(&mut log.pending);
+    if let Err(error) = append(log, writes, &pending).await {
+        log.pending = pending;
+        return Err(error);
+    }
+    Ok(())
+}
+
+fn log_path(path: &Path) -> PathBuf {
+    let mut log = path.to_owned().into_os_string();
+    log.push(".wal");
+    PathBuf::from(log)
+}
+
+pub(crate) async fn write_atomic(path: &Path, bytes: &[u8]) -> Result<(), FsError> {
+    let parent = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    fs::create_dir_all(parent).await?;
+
+    let mut temp_path = path.as_os_str().to_owned();
+    temp_path.push(".tmp");
+    let mut file = fs::File::create(&temp_path).await?;
+    file.write_all(bytes).await?;
+    file.sync_all().await?;
+    drop(file);
+    fs::rename(&temp_path, path).await?;
+
+    // The rename itself is only durable once the directory is synced.
+    fs::File::open(parent).await?.sync_all().await?;
+    Ok(())
+}
+
+pub(crate) async fn ensure_absent(path: &Path) -> Result<(), FsError> {
+    if fs::try_exists(path).await? {
+        let message = format!("{} already exists", path.display());
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, message).into());
+    }
+    Ok(())
+}
+
+fn backup_path(path: &Path) -> PathBuf {
+    let mut backup = path.to_owned().into_os_string();
+    backup.push(".bak");
+    PathBuf::from(backup)
+}
+
+fn rotated_path(path: &Path, n: u32) -> PathBuf {
+    let mut backup = backup_path(path).into_os_string();
+    backup.push(format!(".{n}"));
+    PathBuf::from(backup)
+}
+
+async fn read_newest_backup(path: &Path, backups: u32) -> Option<(PathBuf, (u16, FsSnapshot))> {
+    let candidates = (1..=backups)
+        .map(|n| rotated_path(path, n))
+        .chain([backup_path(path)]);
+    for candidate in candidates {
+        let Ok(bytes) = fs::read(&candidate).await else {
+            continue;
+        };
+        if let Ok(decoded) = decode_store(&bytes) {
+            return Some((candidate, decoded));
+        }
+    }
+    None
+}
+
 impl From<FsError> for StoreError {
     fn from(error: FsError) -> Self {
         Self(error.into())
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct FsAuditStore {
+    path: PathBuf,
+    append: Arc<Mutex<()>>,
+}
+
+impl FsAuditStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            append: Arc::new(Mutex::new(())),
+        }
+    }
+}
+
+#[async_trait]
+impl AuditStore for FsAuditStore {
+    async fn append(&self, entry: &AuditEntry) -> Result<(), StoreError> {
+        let config = bincode::config::standard();
+        let record = bincode::encode_to_vec(audit_to_fs(entry), config).map_err(FsError::from)?;
+        let mut bytes = Vec::with_capacity(4 + record.len());
+        bytes.extend_from_slice(&(record.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&record);
+
+        let _guard = self.append.lock().await;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await
+            .map_err(FsError::from)?;
+        file.write_all(&bytes).await.map_err(FsError::from)?;
+        file.flush().await.map_err(FsError::from)?;
+        Ok(())
+    }
+
+    async fn audit_log(&self) -> Result<Vec<AuditEntry>, StoreError> {
+        let bytes = match fs::read(&self.path).await {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(FsError::from(e).into()),
+        };
+
+        let config = bincode::config::standard();
+        let mut entries = Vec::new();
+        let mut rest = bytes.as_slice();
+        // A torn final record from a crash mid-append is ignored.
+        while let Some((len, tail)) = rest.split_first_chunk::<4>() {
+            let len = u32::from_le_bytes(*len) as usize;
+            let Some((record, tail)) = tail.split_at_checked(len) else {
+                break;
+            };
+            let (fs, _): (FsAuditEntry, _) =
+                bincode::decode_from_slice(record, config).map_err(FsError::from)?;
+            entries.push(fs_to_audit(&fs));
+            rest = tail;
+        }
+
+        Ok(entries)
+    }
+}
+
 #[async_trait]
 impl WalletStore for FsWalletStore {
     async fn find(&self, name: &str) -> Result<Option<WalletRecord>, StoreError> {
-        let fs_wallets = self.wallets.read().await;
-        let maybe_record = fs_wallets.get(name).map(fs_to_record);
+        let data = self.data.read().await;
+        let maybe_record = data.wallets.get(name).map(fs_to_record);
         Ok(maybe_record)
     }
 
-    async fn all(&self) -> Result<HashMap<String, WalletRecord>, StoreError> {
-        let fs_wallets = self.wallets.read().await;
-        let wallets = fs_wallets
+    async fn all(&self) -> Result<BTreeMap<String, WalletRecord>, StoreError> {
+        let data = self.data.read().await;
+        let wallets = data
+            .wallets
             .iter()
             .map(|(name, record)| (name.to_owned(), fs_to_record(record)))
             .collect();
@@ -119,49 +725,1601 @@ impl WalletStore for FsWalletStore {
     }
 
     async fn exists(&self, name: &str) -> Result<bool, StoreError> {
-        let fs_wallets = self.wallets.read().await;
-        let found = fs_wallets.contains_key(name);
+        let data = self.data.read().await;
+        let found = data.wallets.contains_key(name);
         Ok(found)
     }
 
     async fn save(&self, name: &str, record: &WalletRecord) -> Result<(), StoreError> {
-        let mut fs_wallets = self.wallets.write().await;
-        fs_wallets.insert(name.to_owned(), record_to_fs(record));
-        drop(fs_wallets);
-        self.write().await?;
+        let op = FsOp::SaveWallet {
+            name: name.to_owned(),
+            wallet: Box::new(record_to_fs(record)),
+        };
+        let mut data = self.data.write().await;
+        self.commit(&mut data, vec![op]).await?;
+        Ok(())
+    }
+
+    async fn save_many(&self, wallets: &[(String, WalletRecord)]) -> Result<(), StoreError> {
+        let ops = wallets
+            .iter()
+            .map(|(name, record)| FsOp::SaveWallet {
+                name: name.clone(),
+                wallet: Box::new(record_to_fs(record)),
+            })
+            .collect();
+        let mut data = self.data.write().await;
+        self.commit(&mut data, ops).await?;
         Ok(())
     }
 
     async fn delete(&self, name: &str) -> Result<(), StoreError> {
-        let mut fs_wallets = self.wallets.write().await;
-        fs_wallets.remove(name);
-        drop(fs_wallets);
-        self.write().await?;
+        let mut data = self.data.write().await;
+        if data.wallets.contains_key(name) {
+            let op = FsOp::DeleteWallet {
+                name: name.to_owned(),
+            };
+            self.commit(&mut data, vec![op]).await?;
+        }
+        Ok(())
+    }
+
+    async fn delete_many(&self, names: &[String]) -> Result<(), StoreError> {
+        let mut data = self.data.write().await;
+        let ops: Vec<_> = names
+            .iter()
+            .filter(|name| data.wallets.contains_key(*name))
+            .map(|name| FsOp::DeleteWallet { name: name.clone() })
+            .collect();
+        if !ops.is_empty() {
+            self.commit(&mut data, ops).await?;
+        }
+        Ok(())
+    }
+
+    async fn update(
+        &self,
+        name: &str,
+        mut update: WalletUpdate,
+    ) -> Result<Option<WalletRecord>, StoreError> {
+        let mut data = self.data.write().await;
+        let current = data.wallets.get(name).map(fs_to_record);
+        let Some(record) = update(current) else {
+            return Ok(None);
+        };
+        let op = FsOp::SaveWallet {
+            name: name.to_owned(),
+            wallet: Box::new(record_to_fs(&record)),
+        };
+        self.commit(&mut data, vec![op]).await?;
+        Ok(Some(record))
+    }
+
+    async fn find_by_address(
+        &self,
+        chain_id: ChainId,
+        address: &Address,
+    ) -> Result<Option<(String, WalletRecord)>, StoreError> {
+        let data = self.data.read().await;
+        let found = data
+            .by_address
+            .get(&(chain_id.get(), *address.inner()))
+            .and_then(|names| names.first())
+            .map(|name| (name.clone(), fs_to_record(&data.wallets[name])));
+        Ok(found)
+    }
+
+    async fn count(&self) -> Result<usize, StoreError> {
+        Ok(self.data.read().await.wallets.len())
+    }
+
+    async fn insert_many(
+        &self,
+        wallets: &[(String, WalletRecord)],
+    ) -> Result<Vec<Insertion>, StoreError> {
+        let mut data = self.data.write().await;
+        let stored: Vec<_> = wallets
+            .iter()
+            .map(|(name, record)| {
+                let key = (
+                    record.wallet.chain_id().get(),
+                    *record.wallet.address().inner(),
+                );
+                let holder = data.by_address.get(&key).and_then(|names| names.first());
+                (data.wallets.contains_key(name), holder.cloned())
+            })
+            .collect();
+        let insertions = plan_insertions(wallets, stored);
+        let ops: Vec<_> = wallets
+            .iter()
+            .zip(&insertions)
+            .filter(|(_, insertion)| **insertion == Insertion::Saved)
+            .map(|((name, record), _)| FsOp::SaveWallet {
+                name: name.clone(),
+                wallet: Box::new(record_to_fs(record)),
+            })
+            .collect();
+        if !ops.is_empty() {
+            self.commit(&mut data, ops).await?;
+        }
+        Ok(insertions)
+    }
+
+    async fn search(
+        &self,
+        terms: &[String],
+        limit: usize,
+    ) -> Result<Vec<(String, WalletRecord)>, StoreError> {
+        Ok(search_records(self.all().await?, terms, limit))
+    }
+
+    async fn transaction(&self, changes: &[WalletChange]) -> Result<(), StoreError> {
+        let ops = changes
+            .iter()
+            .map(|change| match change {
+                WalletChange::Save { name, record } => FsOp::SaveWallet {
+                    name: name.clone(),
+                    wallet: Box::new(record_to_fs(record)),
+                },
+                WalletChange::Delete { name } => FsOp::DeleteWallet { name: name.clone() },
+            })
+            .collect();
+        let mut data = self.data.write().await;
+        self.commit(&mut data, ops).await?;
         Ok(())
     }
 }
 
-#[derive(Debug, Clone, Encode, Decode)]
-struct FsWallet {
-    address: [u8; 20],
-    balance: u128,
-    last_update: i64,
+#[async_trait]
+impl TrashStore for FsWalletStore {
+    async fn find_trashed(&self, name: &str) -> Result<Option<TrashedRecord>, StoreError> {
+        let data = self.data.read().await;
+        let maybe_trashed = data.trash.get(name).map(fs_to_trashed);
+        Ok(maybe_trashed)
+    }
+
+    async fn all_trashed(&self) -> Result<BTreeMap<String, TrashedRecord>, StoreError> {
+        let data = self.data.read().await;
+        let trashed = data
+            .trash
+            .iter()
+            .map(|(name, trashed)| (name.to_owned(), fs_to_trashed(trashed)))
+            .collect();
+        Ok(trashed)
+    }
+
+    async fn trash(&self, name: &str) -> Result<(), StoreError> {
+        let mut data = self.data.write().await;
+        if data.wallets.contains_key(name) {
+            let op = FsOp::Trash {
+                name: name.to_owned(),
+                trashed_at: Utc::now().timestamp(),
+            };
+            self.commit(&mut data, vec![op]).await?;
+        }
+        Ok(())
+    }
+
+    async fn restore(&self, name: &str) -> Result<(), StoreError> {
+        let mut data = self.data.write().await;
+        if data.trash.contains_key(name) {
+            let op = FsOp::Restore {
+                name: name.to_owned(),
+            };
+            self.commit(&mut data, vec![op]).await?;
+        }
+        Ok(())
+    }
+
+    async fn purge(&self, name: &str) -> Result<(), StoreError> {
+        let mut data = self.data.write().await;
+        if data.trash.contains_key(name) {
+            let op = FsOp::Purge {
+                name: name.to_owned(),
+            };
+            self.commit(&mut data, vec![op]).await?;
+        }
+        Ok(())
+    }
+    async fn save_trashed(&self, name: &str, trashed: &TrashedRecord) -> Result<(), StoreError> {
+        let op = FsOp::SaveTrashed {
+            name: name.to_owned(),
+            trashed: Box::new(FsTrashed {
+                wallet: record_to_fs(&trashed.record),
+                trashed_at: trashed.trashed_at.timestamp(),
+            }),
+        };
+        let mut data = self.data.write().await;
+        self.commit(&mut data, vec![op]).await?;
+        Ok(())
+    }
 }
 
-fn fs_to_record(fs: &FsWallet) -> WalletRecord {
-    let address = Address::new(fs.address);
-    let mut wallet = Wallet::new(address);
-    *wallet.balance_mut() = Balance::new(fs.balance);
-    WalletRecord {
-        wallet,
-        last_update: DateTime::from_timestamp(fs.last_update, 0).unwrap_or_default(),
+#[async_trait]
+impl AlertStore for FsWalletStore {
+    async fn all_alerts(&self) -> Result<Vec<AlertRule>, StoreError> {
+        let data = self.data.read().await;
+        let rules = data.alerts.values().map(fs_to_alert).collect();
+        Ok(rules)
+    }
+
+    async fn save_alert(&self, rule: &AlertRule) -> Result<(), StoreError> {
+        let op = FsOp::SaveAlert(alert_to_fs(rule));
+        let mut data = self.data.write().await;
+        self.commit(&mut data, vec![op]).await?;
+        Ok(())
+    }
+
+    async fn delete_alert(&self, id: &str) -> Result<bool, StoreError> {
+        let mut data = self.data.write().await;
+        let existed = data.alerts.contains_key(id);
+        if existed {
+            let op = FsOp::DeleteAlert { id: id.to_owned() };
+            self.commit(&mut data, vec![op]).await?;
+        }
+        Ok(existed)
     }
 }
 
-fn record_to_fs(record: &WalletRecord) -> FsWallet {
-    FsWallet {
-        address: *record.wallet.address().inner(),
-        balance: record.wallet.balance().wei(),
-        last_update: record.last_update.timestamp(),
+#[async_trait]
+impl PreferenceStore for FsWalletStore {
+    async fn pinned(&self, principal: &str) -> Result<Vec<String>, StoreError> {
+        let data = self.data.read().await;
+        let pinned = data.pins.get(principal).cloned().unwrap_or_default();
+        Ok(pinned)
+    }
+
+    async fn set_pinned(&self, principal: &str, wallet_ids: &[String]) -> Result<(), StoreError> {
+        let op = FsOp::SetPinned {
+            principal: principal.to_owned(),
+            wallet_ids: wallet_ids.to_vec(),
+        };
+        let mut data = self.data.write().await;
+        self.commit(&mut data, vec![op]).await?;
+        Ok(())
+    }
+
+    async fn views(&self, principal: &str) -> Result<Vec<SavedView>, StoreError> {
+        let data = self.data.read().await;
+        let views = data
+            .views
+            .get(principal)
+            .map(|views| {
+                views
+                    .iter()
+                    .map(|(name, fs)| fs_to_view(name, fs))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(views)
+    }
+
+    async fn save_view(&self, principal: &str, view: &SavedView) -> Result<(), StoreError> {
+        let op = FsOp::SaveView {
+            principal: principal.to_owned(),
+            name: view.name.clone(),
+            view: view_to_fs(view),
+        };
+        let mut data = self.data.write().await;
+        self.commit(&mut data, vec![op]).await?;
+        Ok(())
+    }
+
+    async fn delete_view(&self, principal: &str, name: &str) -> Result<bool, StoreError> {
+        let mut data = self.data.write().await;
+        let existed = data
+            .views
+            .get(principal)
+            .is_some_and(|views| views.contains_key(name));
+        if existed {
+            let op = FsOp::DeleteView {
+                principal: principal.to_owned(),
+                name: name.to_owned(),
+            };
+            self.commit(&mut data, vec![op]).await?;
+        }
+        Ok(existed)
+    }
+}
+
+#[async_trait]
+impl OutboxStore for FsWalletStore {
+    async fn save_with_outbox(
+        &self,
+        name: &str,
+        record: &WalletRecord,
+        entry: &OutboxEntry,
+    ) -> Result<(), StoreError> {
+        let ops = vec![
+            FsOp::SaveWallet {
+                name: name.to_owned(),
+                wallet: Box::new(record_to_fs(record)),
+            },
+            FsOp::SaveOutbox {
+                id: entry.id.clone(),
+                entry: outbox_to_fs(entry),
+            },
+        ];
+        let mut data = self.data.write().await;
+        self.commit(&mut data, ops).await?;
+        Ok(())
+    }
+
+    async fn outbox(&self) -> Result<Vec<OutboxEntry>, StoreError> {
+        let data = self.data.read().await;
+        let entries = data
+            .outbox
+            .iter()
+            .map(|(id, fs)| fs_to_outbox(id, fs))
+            .collect();
+        Ok(entries)
+    }
+
+    async fn ack(&self, id: &str) -> Result<(), StoreError> {
+        let mut data = self.data.write().await;
+        if data.outbox.contains_key(id) {
+            let op = FsOp::Ack { id: id.to_owned() };
+            self.commit(&mut data, vec![op]).await?;
+        }
+        Ok(())
+    }
+
+    async fn reschedule(
+        &self,
+        id: &str,
+        attempts: u32,
+        next_attempt_at: DateTime<Utc>,
+    ) -> Result<(), StoreError> {
+        let mut data = self.data.write().await;
+        if data.outbox.contains_key(id) {
+            let op = FsOp::Reschedule {
+                id: id.to_owned(),
+                attempts,
+                next_attempt_at: next_attempt_at.timestamp(),
+            };
+            self.commit(&mut data, vec![op]).await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl HistoryStore for FsWalletStore {
+    async fn append_samples(
+        &self,
+        samples: &[(String, Sample)],
+        keep: usize,
+    ) -> Result<(), StoreError> {
+        let op = FsOp::AppendSamples {
+            samples: samples
+                .iter()
+                .map(|(wallet_id, sample)| (wallet_id.clone(), sample_to_fs(sample)))
+                .collect(),
+            keep: keep as u64,
+        };
+        let mut data = self.data.write().await;
+        self.commit(&mut data, vec![op]).await?;
+        Ok(())
+    }
+
+    async fn replace_history(&self, wallet_id: &str, samples: &[Sample]) -> Result<(), StoreError> {
+        let op = FsOp::ReplaceHistory {
+            wallet_id: wallet_id.to_owned(),
+            samples: samples.iter().map(sample_to_fs).collect(),
+        };
+        let mut data = self.data.write().await;
+        self.commit(&mut data, vec![op]).await?;
+        Ok(())
+    }
+
+    async fn history(&self, wallet_id: &str) -> Result<Vec<Sample>, StoreError> {
+        let data = self.data.read().await;
+        let samples = data
+            .history
+            .get(wallet_id)
+            .map(|history| history.iter().map(fs_to_sample).collect())
+            .unwrap_or_default();
+        Ok(samples)
+    }
+
+    async fn history_in(
+        &self,
+        wallet_id: &str,
+        range: Range<i64>,
+    ) -> Result<Vec<Sample>, StoreError> {
+        let data = self.data.read().await;
+        let samples = data
+            .history
+            .get(wallet_id)
+            .map(|history| {
+                history
+                    .iter()
+                    .filter(|sample| range.contains(&sample.timestamp))
+                    .map(fs_to_sample)
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(samples)
+    }
+}
+
+#[async_trait]
+impl StoreAdmin for FsWalletStore {
+    fn describe(&self) -> StoreInfo {
+        StoreInfo {
+            backend: "fs".to_string(),
+            location: self.path.to_string_lossy().into_owned(),
+            recovered_from: self
+                .recovered_from
+                .as_ref()
+                .map(|path| path.to_string_lossy().into_owned()),
+        }
+    }
+
+    async fn stats(&self) -> Result<StoreStats, StoreError> {
+        let data = self.data.read().await;
+        let entries = data.wallets.len();
+        let trashed = data.trash.len();
+        drop(data);
+
+        let snapshot_len = fs::metadata(&self.path).await.map_err(FsError::from)?.len();
+        let log_len = self.log.lock().await.len;
+        Ok(self.writes.fill(StoreStats {
+            entries,
+            trashed,
+            size_bytes: snapshot_len + log_len,
+            ..Default::default()
+        }))
+    }
+
+    async fn compact(&self) -> Result<(), StoreError> {
+        self.write().await?;
+        Ok(())
+    }
+
+    async fn flush(&self) -> Result<(), StoreError> {
+        self.flush_pending().await?;
+        Ok(())
+    }
+
+    async fn backup(&self) -> Result<String, StoreError> {
+        let backup_path = backup_path(&self.path);
+        self.copy_to(&backup_path).await?;
+        Ok(backup_path.to_string_lossy().into_owned())
+    }
+
+    async fn snapshot(&self, dest: &str) -> Result<(), StoreError> {
+        let dest = Path::new(dest);
+        ensure_absent(dest).await?;
+        self.copy_to(dest).await?;
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct FsState {
+    data: FsData,
+    by_address: BTreeMap<(u64, [u8; 20]), BTreeSet<String>>,
+}
+
+impl FsState {
+    fn new(data: FsData) -> Self {
+        let mut state = Self {
+            data,
+            by_address: BTreeMap::new(),
+        };
+        let names: Vec<_> = state.data.wallets.keys().cloned().collect();
+        for name in &names {
+            state.index(name);
+        }
+        state
+    }
+
+    fn apply(&mut self, op: FsOp) {
+        let name = match &op {
+            FsOp::SaveWallet { name, .. }
+            | FsOp::DeleteWallet { name }
+            | FsOp::Trash { name, .. }
+            | FsOp::Restore { name } => Some(name.clone()),
+            _ => None,
+        };
+        if let Some(name) = &name {
+            self.unindex(name);
+        }
+        self.data.apply(op);
+        if let Some(name) = &name {
+            self.index(name);
+        }
+    }
+
+    fn index(&mut self, name: &str) {
+        if let Some(wallet) = self.data.wallets.get(name) {
+            self.by_address
+                .entry((wallet.chain_id, wallet.address))
+                .or_default()
+                .insert(name.to_owned());
+        }
+    }
+
+    fn unindex(&mut self, name: &str) {
+        let Some(wallet) = self.data.wallets.get(name) else {
+            return;
+        };
+        let key = (wallet.chain_id, wallet.address);
+        if let Some(names) = self.by_address.get_mut(&key) {
+            names.remove(name);
+            if names.is_empty() {
+                self.by_address.remove(&key);
+            }
+        }
+    }
+}
+
+impl Deref for FsState {
+    type Target = FsData;
+
+    fn deref(&self) -> &FsData {
+        &self.data
+    }
+}
+
+#[derive(Debug, Clone, Default, Encode)]
+struct FsData {
+    wallets: BTreeMap<String, FsWallet>,
+    trash: BTreeMap<String, FsTrashed>,
+    alerts: BTreeMap<String, FsAlertRule>,
+    pins: BTreeMap<String, Vec<String>>,
+    views: BTreeMap<String, BTreeMap<String, FsView>>,
+    outbox: BTreeMap<String, FsOutboxEntry>,
+    history: BTreeMap<String, Vec<FsSample>>,
+}
+
+#[derive(Debug, Clone, Encode, Decode)]
+#[bincode(decode_context = "u16")]
+enum FsOp {
+    SaveWallet {
+        name: String,
+        wallet: Box<FsWallet>,
+    },
+    DeleteWallet {
+        name: String,
+    },
+    Trash {
+        name: String,
+        trashed_at: i64,
+    },
+    Restore {
+        name: String,
+    },
+    Purge {
+        name: String,
+    },
+    SaveAlert(FsAlertRule),
+    DeleteAlert {
+        id: String,
+    },
+    SetPinned {
+        principal: String,
+        wallet_ids: Vec<String>,
+    },
+    SaveView {
+        principal: String,
+        name: String,
+        view: FsView,
+    },
+    DeleteView {
+        principal: String,
+        name: String,
+    },
+    SaveOutbox {
+        id: String,
+        entry: FsOutboxEntry,
+    },
+    Ack {
+        id: String,
+    },
+    Reschedule {
+        id: String,
+        attempts: u32,
+        next_attempt_at: i64,
+    },
+    AppendSamples {
+        samples: Vec<(String, FsSample)>,
+        keep: u64,
+    },
+    ReplaceHistory {
+        wallet_id: String,
+        samples: Vec<FsSample>,
+    },
+    SaveTrashed {
+        name: String,
+        trashed: Box<FsTrashed>,
+    },
+}
+
+impl FsData {
+    fn apply(&mut self, op: FsOp) {
+        match op {
+            FsOp::SaveWallet { name, wallet } => {
+                self.wallets.insert(name, *wallet);
+            }
+            FsOp::DeleteWallet { name } => {
+                self.wallets.remove(&name);
+            }
+            FsOp::Trash { name, trashed_at } => {
+                if let Some(wallet) = self.wallets.remove(&name) {
+                    self.trash.insert(name, FsTrashed { wallet, trashed_at });
+                }
+            }
+            FsOp::Restore { name } => {
+                if let Some(trashed) = self.trash.remove(&name) {
+                    self.wallets.insert(name, trashed.wallet);
+                }
+            }
+            FsOp::Purge { name } => {
+                if let Some(trashed) = self.trash.remove(&name) {
+                    self.history.remove(&trashed.wallet.id);
+                }
+            }
+            FsOp::SaveAlert(rule) => {
+                self.alerts.insert(rule.id.clone(), rule);
+            }
+            FsOp::DeleteAlert { id } => {
+                self.alerts.remove(&id);
+            }
+            FsOp::SetPinned {
+                principal,
+                wallet_ids,
+            } => {
+                if wallet_ids.is_empty() {
+                    self.pins.remove(&principal);
+                } else {
+                    self.pins.insert(principal, wallet_ids);
+                }
+            }
+            FsOp::SaveView {
+                principal,
+                name,
+                view,
+            } => {
+                self.views.entry(principal).or_default().insert(name, view);
+            }
+            FsOp::DeleteView { principal, name } => {
+                if let Some(views) = self.views.get_mut(&principal) {
+                    views.remove(&name);
+                    if views.is_empty() {
+                        self.views.remove(&principal);
+                    }
+                }
+            }
+            FsOp::SaveOutbox { id, entry } => {
+                self.outbox.insert(id, entry);
+            }
+            FsOp::Ack { id } => {
+                self.outbox.remove(&id);
+            }
+            FsOp::Reschedule {
+                id,
+                attempts,
+                next_attempt_at,
+            } => {
+                if let Some(entry) = self.outbox.get_mut(&id) {
+                    entry.attempts = attempts;
+                    entry.next_attempt_at = next_attempt_at;
+                }
+            }
+            FsOp::AppendSamples { samples, keep } => {
+                for (wallet_id, sample) in samples {
+                    let history = self.history.entry(wallet_id).or_default();
+                    history.push(sample);
+                    let excess = history.len().saturating_sub(keep as usize);
+                    history.drain(..excess);
+                }
+            }
+            FsOp::ReplaceHistory { wallet_id, samples } => {
+                if samples.is_empty() {
+                    self.history.remove(&wallet_id);
+                } else {
+                    self.history.insert(wallet_id, samples);
+                }
+            }
+            FsOp::SaveTrashed { name, trashed } => {
+                self.trash.insert(name, *trashed);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Encode)]
+struct FsWallet {
+    id: String,
+    chain_id: u64,
+    address: [u8; 20],
+    linked: Vec<[u8; 20]>,
+    balance: FsBalance,
+    tags: Vec<String>,
+    note: Option<String>,
+    created_at: i64,
+    created_by: FsProvenance,
+    modified_by: FsProvenance,
+    last_update: i64,
+    verified_at: Option<i64>,
+    ens_name: Option<String>,
+    ens_checked_at: Option<i64>,
+    funded_at: Option<i64>,
+    swept_at: Option<i64>,
+    previous_balance: Option<FsBalance>,
+    tokens: Vec<FsTokenBalance>,
+    transaction_count: Option<u64>,
+    resolved_from: Option<String>,
+    resolved_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, Encode, Decode)]
+#[bincode(decode_context = "u16")]
+struct FsTokenBalance {
+    symbol: String,
+    address: [u8; 20],
+    decimals: u32,
+    amount: FsTokenAmount,
+}
+
+#[derive(Debug, Clone, Copy, Encode)]
+struct FsTokenAmount([u8; 32]);
+
+impl Decode<u16> for FsTokenAmount {
+    fn decode<D: Decoder<Context = u16>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        if *decoder.context() < 19 {
+            let amount = u128::decode(decoder)?;
+            return Ok(Self(U256::from(amount).to_big_endian()));
+        }
+        Decode::decode(decoder).map(Self)
+    }
+}
+
+bincode::impl_borrow_decode_with_context!(FsTokenAmount, u16);
+
+#[derive(Debug, Clone, Copy, Encode)]
+struct FsBalance([u8; 32]);
+
+impl Decode<u16> for FsBalance {
+    fn decode<D: Decoder<Context = u16>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        if *decoder.context() < 19 {
+            let wei = u128::decode(decoder)?;
+            return Ok(balance_to_fs(Balance::new(wei)));
+        }
+        Decode::decode(decoder).map(Self)
+    }
+}
+
+bincode::impl_borrow_decode_with_context!(FsBalance, u16);
+
+fn balance_to_fs(balance: Balance) -> FsBalance {
+    FsBalance(balance.wei().to_big_endian())
+}
+
+fn fs_to_balance(fs: &FsBalance) -> Balance {
+    Balance::from_wei(U256::from_big_endian(&fs.0))
+}
+
+#[derive(Debug, Clone, Default, Encode, Decode)]
+struct FsProvenance {
+    principal: String,
+    interface: u8,
+}
+
+fn fs_to_record(fs: &FsWallet) -> WalletRecord {
+    let address = Address::new(fs.address);
+    let mut wallet = Wallet::on_chain(ChainId::new(fs.chain_id), address);
+    wallet.set_linked(fs.linked.iter().copied().map(Address::new));
+    *wallet.balance_mut() = fs_to_balance(&fs.balance);
+    WalletRecord {
+        id: fs.id.clone(),
+        wallet,
+        tags: fs.tags.clone(),
+        note: fs.note.clone(),
+        created_at: DateTime::from_timestamp(fs.created_at, 0).unwrap_or_default(),
+        created_by: fs_to_provenance(&fs.created_by),
+        modified_by: fs_to_provenance(&fs.modified_by),
+        last_update: DateTime::from_timestamp(fs.last_update, 0).unwrap_or_default(),
+        previous_balance: fs.previous_balance.as_ref().map(fs_to_balance),
+        tokens: fs
+            .tokens
+            .iter()
+            .map(|token| TokenBalance {
+                token: Token {
+                    symbol: token.symbol.clone(),
+                    address: Address::new(token.address),
+                    decimals: token.decimals,
+                },
+                amount: U256::from_big_endian(&token.amount.0),
+            })
+            .collect(),
+        transaction_count: fs.transaction_count,
+        resolved_from: fs.resolved_from.clone(),
+        resolved_at: fs
+            .resolved_at
+            .and_then(|resolved_at| DateTime::from_timestamp(resolved_at, 0)),
+        verified_at: fs
+            .verified_at
+            .and_then(|verified_at| DateTime::from_timestamp(verified_at, 0)),
+        ens_name: fs.ens_name.clone(),
+        ens_checked_at: fs
+            .ens_checked_at
+            .and_then(|checked_at| DateTime::from_timestamp(checked_at, 0)),
+        funded_at: fs
+            .funded_at
+            .and_then(|funded_at| DateTime::from_timestamp(funded_at, 0)),
+        swept_at: fs
+            .swept_at
+            .and_then(|swept_at| DateTime::from_timestamp(swept_at, 0)),
+    }
+}
+
+#[derive(Debug, Clone, Encode, Decode)]
+#[bincode(decode_context = "u16")]
+struct FsSample {
+    timestamp: i64,
+    block_number: Option<u64>,
+    balance: FsBalance,
+}
+
+fn fs_to_sample(fs: &FsSample) -> Sample {
+    Sample {
+        timestamp: fs.timestamp,
+        block_number: fs.block_number,
+        balance: fs_to_balance(&fs.balance),
+    }
+}
+
+fn sample_to_fs(sample: &Sample) -> FsSample {
+    FsSample {
+        timestamp: sample.timestamp,
+        block_number: sample.block_number,
+        balance: balance_to_fs(sample.balance),
+    }
+}
+
+#[derive(Debug, Clone, Encode, Decode)]
+#[bincode(decode_context = "u16")]
+struct FsTrashed {
+    wallet: FsWallet,
+    trashed_at: i64,
+}
+
+fn fs_to_trashed(fs: &FsTrashed) -> TrashedRecord {
+    TrashedRecord {
+        record: fs_to_record(&fs.wallet),
+        trashed_at: DateTime::from_timestamp(fs.trashed_at, 0).unwrap_or_default(),
+    }
+}
+
+fn record_to_fs(record: &WalletRecord) -> FsWallet {
+    FsWallet {
+        id: record.id.clone(),
+        chain_id: record.wallet.chain_id().get(),
+        address: *record.wallet.address().inner(),
+        linked: record
+            .wallet
+            .linked()
+            .iter()
+            .map(|address| *address.inner())
+            .collect(),
+        balance: balance_to_fs(record.wallet.balance()),
+        tags: record.tags.clone(),
+        note: record.note.clone(),
+        created_at: record.created_at.timestamp(),
+        created_by: provenance_to_fs(&record.created_by),
+        modified_by: provenance_to_fs(&record.modified_by),
+        last_update: record.last_update.timestamp(),
+        verified_at: record
+            .verified_at
+            .map(|verified_at| verified_at.timestamp()),
+        ens_name: record.ens_name.clone(),
+        ens_checked_at: record
+            .ens_checked_at
+            .map(|checked_at| checked_at.timestamp()),
+        funded_at: record.funded_at.map(|funded_at| funded_at.timestamp()),
+        swept_at: record.swept_at.map(|swept_at| swept_at.timestamp()),
+        previous_balance: record.previous_balance.map(balance_to_fs),
+        tokens: record
+            .tokens
+            .iter()
+            .map(|balance| FsTokenBalance {
+                symbol: balance.token.symbol.clone(),
+                address: *balance.token.address.inner(),
+                decimals: balance.token.decimals,
+                amount: FsTokenAmount(balance.amount.to_big_endian()),
+            })
+            .collect(),
+        transaction_count: record.transaction_count,
+        resolved_from: record.resolved_from.clone(),
+        resolved_at: record
+            .resolved_at
+            .map(|resolved_at| resolved_at.timestamp()),
+    }
+}
+
+#[derive(Debug, Clone, Encode, Decode)]
+#[bincode(decode_context = "u16")]
+struct FsAlertRule {
+    id: String,
+    wallet_id: Option<String>,
+    condition: u8,
+    value: FsBalance,
+    created_by: FsProvenance,
+}
+
+fn fs_to_alert(fs: &FsAlertRule) -> AlertRule {
+    let condition = match fs.condition {
+        0 => AlertCondition::Below(fs_to_balance(&fs.value)),
+        1 => AlertCondition::Above(fs_to_balance(&fs.value)),
+        _ => AlertCondition::Change {
+            percent: u32::try_from(fs_to_balance(&fs.value).wei()).unwrap_or(u32::MAX),
+        },
+    };
+
+    AlertRule {
+        id: fs.id.clone(),
+        wallet_id: fs.wallet_id.clone(),
+        condition,
+        created_by: fs_to_provenance(&fs.created_by),
+    }
+}
+
+fn alert_to_fs(rule: &AlertRule) -> FsAlertRule {
+    let (condition, value) = match rule.condition {
+        AlertCondition::Below(threshold) => (0, balance_to_fs(threshold)),
+        AlertCondition::Above(threshold) => (1, balance_to_fs(threshold)),
+        AlertCondition::Change { percent } => (2, balance_to_fs(Balance::new(percent.into()))),
+    };
+
+    FsAlertRule {
+        id: rule.id.clone(),
+        wallet_id: rule.wallet_id.clone(),
+        condition,
+        value,
+        created_by: provenance_to_fs(&rule.created_by),
+    }
+}
+
+#[derive(Debug, Clone, Encode, Decode)]
+#[bincode(decode_context = "u16")]
+struct FsOutboxEntry {
+    kind: u8,
+    rule_id: Option<String>,
+    wallet_id: String,
+    name: String,
+    before: FsBalance,
+    after: FsBalance,
+    created_at: i64,
+    attempts: u32,
+    next_attempt_at: i64,
+    schema_version: u32,
+}
+
+fn fs_to_outbox(id: &str, fs: &FsOutboxEntry) -> OutboxEntry {
+    let wallet_id = fs.wallet_id.clone();
+    let name = fs.name.clone();
+    let before = fs_to_balance(&fs.before);
+    let after = fs_to_balance(&fs.after);
+    let notification = match (fs.kind, &fs.rule_id) {
+        (1, Some(rule_id)) => Notification::AlertFired {
+            rule_id: rule_id.clone(),
+            wallet_id,
+            name,
+            before,
+            after,
+        },
+        _ => Notification::BalanceChanged {
+            wallet_id,
+            name,
+            before,
+            after,
+        },
+    };
+
+    OutboxEntry {
+        id: id.to_owned(),
+        notification,
+        schema_version: fs.schema_version,
+        created_at: DateTime::from_timestamp(fs.created_at, 0).unwrap_or_default(),
+        attempts: fs.attempts,
+        next_attempt_at: DateTime::from_timestamp(fs.next_attempt_at, 0).unwrap_or_default(),
+    }
+}
+
+fn outbox_to_fs(entry: &OutboxEntry) -> FsOutboxEntry {
+    let (kind, rule_id, wallet_id, name, before, after) = match &entry.notification {
+        Notification::BalanceChanged {
+            wallet_id,
+            name,
+            before,
+            after,
+        } => (0, None, wallet_id, name, before, after),
+        Notification::AlertFired {
+            rule_id,
+            wallet_id,
+            name,
+            before,
+            after,
+        } => (1, Some(rule_id.clone()), wallet_id, name, before, after),
+    };
+
+    FsOutboxEntry {
+        kind,
+        rule_id,
+        wallet_id: wallet_id.clone(),
+        name: name.clone(),
+        before: balance_to_fs(*before),
+        after: balance_to_fs(*after),
+        created_at: entry.created_at.timestamp(),
+        attempts: entry.attempts,
+        next_attempt_at: entry.next_attempt_at.timestamp(),
+        schema_version: entry.schema_version,
+    }
+}
+
+#[derive(Debug, Clone, Encode, Decode)]
+struct FsAuditEntry {
+    operation: String,
+    name: String,
+    address: Option<String>,
+    at: i64,
+    error: Option<String>,
+}
+
+fn fs_to_audit(fs: &FsAuditEntry) -> AuditEntry {
+    AuditEntry {
+        operation: fs.operation.clone(),
+        name: fs.name.clone(),
+        address: fs.address.clone(),
+        at: DateTime::from_timestamp(fs.at, 0).unwrap_or_default(),
+        outcome: match &fs.error {
+            None => AuditOutcome::Succeeded,
+            Some(error) => AuditOutcome::Failed(error.clone()),
+        },
+    }
+}
+
+fn audit_to_fs(entry: &AuditEntry) -> FsAuditEntry {
+    FsAuditEntry {
+        operation: entry.operation.clone(),
+        name: entry.name.clone(),
+        address: entry.address.clone(),
+        at: entry.at.timestamp(),
+        error: match &entry.outcome {
+            AuditOutcome::Succeeded => None,
+            AuditOutcome::Failed(error) => Some(error.clone()),
+        },
+    }
+}
+
+#[derive(Debug, Clone, Encode, Decode)]
+struct FsView {
+    tags: Vec<String>,
+    created_by: Option<String>,
+    created_via: Option<u8>,
+    sort: u8,
+    fields: Vec<String>,
+}
+
+fn fs_to_view(name: &str, fs: &FsView) -> SavedView {
+    let sort = match fs.sort {
+        1 => ListSort::Balance,
+        2 => ListSort::LastUpdate,
+        _ => ListSort::Name,
+    };
+
+    SavedView {
+        name: name.to_owned(),
+        tags: fs.tags.clone(),
+        created_by: fs.created_by.clone(),
+        created_via: fs.created_via.map(fs_to_interface),
+        sort,
+        fields: fs.fields.clone(),
+    }
+}
+
+fn view_to_fs(view: &SavedView) -> FsView {
+    let sort = match view.sort {
+        ListSort::Name => 0,
+        ListSort::Balance => 1,
+        ListSort::LastUpdate => 2,
+    };
+
+    FsView {
+        tags: view.tags.clone(),
+        created_by: view.created_by.clone(),
+        created_via: view.created_via.map(interface_to_fs),
+        sort,
+        fields: view.fields.clone(),
+    }
+}
+
+fn fs_to_provenance(fs: &FsProvenance) -> Provenance {
+    Provenance {
+        principal: fs.principal.clone(),
+        interface: fs_to_interface(fs.interface),
+    }
+}
+
+fn provenance_to_fs(provenance: &Provenance) -> FsProvenance {
+    FsProvenance {
+        principal: provenance.principal.clone(),
+        interface: interface_to_fs(provenance.interface),
+    }
+}
+
+fn fs_to_interface(fs: u8) -> Interface {
+    match fs {
+        1 => Interface::Grpc,
+        2 => Interface::Rest,
+        3 => Interface::Cli,
+        4 => Interface::Import,
+        _ => Interface::Unknown,
+    }
+}
+
+fn interface_to_fs(interface: Interface) -> u8 {
+    match interface {
+        Interface::Unknown => 0,
+        Interface::Grpc => 1,
+        Interface::Rest => 2,
+        Interface::Cli => 3,
+        Interface::Import => 4,
+    }
+}
+
+pub(crate) mod blob {
+    use bincode::{Decode, Encode};
+    use chrono::DateTime;
+
+    use super::{
+        FsAlertRule, FsError, FsOutboxEntry, FsSample, FsView, FsWallet, STORE_VERSION,
+        alert_to_fs, fs_to_alert, fs_to_outbox, fs_to_record, fs_to_sample, fs_to_view,
+        outbox_to_fs, record_to_fs, sample_to_fs, view_to_fs,
+    };
+    use crate::{
+        core::Sample,
+        infra::{AlertRule, OutboxEntry, SavedView, TrashedRecord, WalletRecord},
+    };
+
+    pub(crate) fn encode<T: Encode>(value: T) -> Result<Vec<u8>, FsError> {
+        let mut bytes = STORE_VERSION.to_le_bytes().to_vec();
+        bytes.extend(bincode::encode_to_vec(value, bincode::config::standard())?);
+        Ok(bytes)
+    }
+
+    pub(crate) fn decode<T: Decode<u16>>(bytes: &[u8]) -> Result<T, FsError> {
+        let (version, payload) = bytes
+            .split_first_chunk::<2>()
+            .ok_or_else(|| FsError("stored record is truncated".into()))?;
+        let version = u16::from_le_bytes(*version);
+        if version > STORE_VERSION {
+            return Err(FsError(
+                format!("stored record format {version} is newer than this build").into(),
+            ));
+        }
+
+        let config = bincode::config::standard();
+        Ok(bincode::decode_from_slice_with_context(payload, config, version)?.0)
+    }
+
+    pub(crate) fn from_record(record: &WalletRecord) -> Result<Vec<u8>, FsError> {
+        encode(record_to_fs(record))
+    }
+
+    pub(crate) fn to_record(bytes: &[u8]) -> Result<WalletRecord, FsError> {
+        Ok(fs_to_record(&decode::<FsWallet>(bytes)?))
+    }
+
+    pub(crate) fn to_trashed(bytes: &[u8], trashed_at: i64) -> Result<TrashedRecord, FsError> {
+        Ok(TrashedRecord {
+            record: to_record(bytes)?,
+            trashed_at: DateTime::from_timestamp(trashed_at, 0).unwrap_or_default(),
+        })
+    }
+
+    pub(crate) fn from_alert(rule: &AlertRule) -> Result<Vec<u8>, FsError> {
+        encode(alert_to_fs(rule))
+    }
+
+    pub(crate) fn to_alert(bytes: &[u8]) -> Result<AlertRule, FsError> {
+        Ok(fs_to_alert(&decode::<FsAlertRule>(bytes)?))
+    }
+
+    pub(crate) fn from_outbox(entry: &OutboxEntry) -> Result<Vec<u8>, FsError> {
+        encode(outbox_to_fs(entry))
+    }
+
+    pub(crate) fn to_outbox(id: &str, bytes: &[u8]) -> Result<OutboxEntry, FsError> {
+        Ok(fs_to_outbox(id, &decode::<FsOutboxEntry>(bytes)?))
+    }
+
+    pub(crate) fn from_view(view: &SavedView) -> Result<Vec<u8>, FsError> {
+        encode(view_to_fs(view))
+    }
+
+    pub(crate) fn to_view(name: &str, bytes: &[u8]) -> Result<SavedView, FsError> {
+        Ok(fs_to_view(name, &decode::<FsView>(bytes)?))
+    }
+
+    pub(crate) fn from_sample(sample: &Sample) -> Result<Vec<u8>, FsError> {
+        encode(sample_to_fs(sample))
+    }
+
+    pub(crate) fn to_sample(bytes: &[u8]) -> Result<Sample, FsError> {
+        Ok(fs_to_sample(&decode::<FsSample>(bytes)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, env, str::FromStr, time::Duration};
+
+    use bincode::Encode;
+    use tokio::{fs::OpenOptions, io::AsyncWriteExt};
+    use uuid::Uuid;
+
+    use super::{
+        FsData, FsTokenAmount, FsWalletStore, STORE_MAGIC, STORE_VERSION, decode_store,
+        encode_store, log_path,
+    };
+    use crate::{
+        core::{Address, Balance, ChainId, Sample, U256, Wallet},
+        infra::{HistoryStore, Insertion, StoreAdmin, TrashStore, WalletRecord, WalletStore},
+    };
+
+    #[derive(Encode)]
+    struct BaselineWallet {
+        address: [u8; 20],
+        balance: u128,
+        last_update: i64,
+    }
+
+    #[test]
+    fn fs_decode_store_versions() {
+        let config = bincode::config::standard();
+        let baseline = HashMap::<String, BaselineWallet>::new();
+        let (version, _) =
+            decode_store(&bincode::encode_to_vec(baseline, config).unwrap()).unwrap();
+        assert_eq!(version, 0);
+
+        let payload = bincode::encode_to_vec(FsData::default(), config).unwrap();
+
+        let mut unchecked = STORE_MAGIC.to_vec();
+        unchecked.extend_from_slice(&21u16.to_le_bytes());
+        unchecked.extend_from_slice(&payload);
+        let (version, _) = decode_store(&unchecked).unwrap();
+        assert_eq!(version, 21);
+
+        let mut newer = STORE_MAGIC.to_vec();
+        newer.extend_from_slice(&u16::MAX.to_le_bytes());
+        newer.extend_from_slice(&payload);
+        assert!(decode_store(&newer).is_err());
+
+        assert!(decode_store(&STORE_MAGIC).is_err());
+    }
+
+    #[test]
+    fn fs_decode_token_amount_versions() {
+        let config = bincode::config::standard();
+        let decode = |bytes: &[u8], version: u16| {
+            let (amount, _): (FsTokenAmount, _) =
+                bincode::decode_from_slice_with_context(bytes, config, version).unwrap();
+            U256::from_big_endian(&amount.0)
+        };
+
+        let old = bincode::encode_to_vec(u128::MAX, config).unwrap();
+        assert_eq!(decode(&old, 18), U256::from(u128::MAX));
+
+        let amount = FsTokenAmount(U256::MAX.to_big_endian());
+        let new = bincode::encode_to_vec(amount, config).unwrap();
+        assert_eq!(decode(&new, STORE_VERSION), U256::MAX);
+    }
+
+    #[test]
+    fn fs_decode_store_checksum() {
+        let mut bytes = encode_store(7, &FsData::default(), 0).unwrap();
+        let (version, snapshot) = decode_store(&bytes).unwrap();
+        assert_eq!(version, STORE_VERSION);
+        assert_eq!(snapshot.seq, 7);
+
+        *bytes.last_mut().unwrap() ^= 1;
+        assert!(decode_store(&bytes).is_err());
+    }
+
+    #[tokio::test]
+    async fn fs_open_baseline_store() {
+        let path = env::temp_dir().join(format!("mini-wallet-{}.db", Uuid::new_v4()));
+        let wallets = HashMap::from([(
+            "Alice".to_owned(),
+            BaselineWallet {
+                address: [7; 20],
+                balance: 5,
+                last_update: 1_700_000_000,
+            },
+        )]);
+        let config = bincode::config::standard();
+        std::fs::write(&path, bincode::encode_to_vec(wallets, config).unwrap()).unwrap();
+
+        let store = FsWalletStore::open(path.to_string_lossy(), 0, 0)
+            .await
+            .unwrap();
+        let record = store.find("Alice").await.unwrap().unwrap();
+        assert_eq!(*record.wallet.address(), Address::new([7; 20]));
+        assert_eq!(record.wallet.balance(), Balance::new(5));
+        assert_eq!(record.wallet.chain_id(), ChainId::MAINNET);
+        assert!(record.tags.is_empty());
+        assert_eq!(record.last_update.timestamp(), 1_700_000_000);
+        assert_eq!(record.created_at, record.last_update);
+
+        // Rewritten as the current version on open, keeping the id it got.
+        drop(store);
+        let (version, _) = decode_store(&std::fs::read(&path).unwrap()).unwrap();
+        assert_eq!(version, STORE_VERSION);
+        let store = FsWalletStore::open(path.to_string_lossy(), 0, 0)
+            .await
+            .unwrap();
+        let reopened = store.find("Alice").await.unwrap().unwrap();
+        assert_eq!(reopened.id, record.id);
+    }
+
+    #[test]
+    fn fs_decode_store_compressed() {
+        let mut data = FsData::default();
+        let pins = vec!["0123456789abcdef".to_string(); 200];
+        data.pins.insert("admin".to_string(), pins.clone());
+
+        let plain = encode_store(1, &data, 0).unwrap();
+        let compressed = encode_store(1, &data, 3).unwrap();
+        assert!(compressed.len() < plain.len() / 10);
+
+        let (_, snapshot) = decode_store(&compressed).unwrap();
+        assert_eq!(snapshot.data.pins["admin"], pins);
+    }
+
+    #[tokio::test]
+    async fn fs_open_locked() {
+        let path = env::temp_dir().join(format!("mini-wallet-{}.db", Uuid::new_v4()));
+        let path = path.to_string_lossy();
+        let store = FsWalletStore::open(&path, 0, 0).await.unwrap();
+        assert!(FsWalletStore::open(&path, 0, 0).await.is_err());
+
+        // Clones share the lock; it's released once the last one is dropped.
+        let clone = store.clone();
+        drop(store);
+        assert!(FsWalletStore::open(&path, 0, 0).await.is_err());
+        drop(clone);
+        assert!(FsWalletStore::open(&path, 0, 0).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn fs_log_replay() {
+        let path = env::temp_dir().join(format!("mini-wallet-{}.db", Uuid::new_v4()));
+        let path = path.to_string_lossy().into_owned();
+        let address = Address::from_str("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045").unwrap();
+        let record = WalletRecord::new(Wallet::new(address));
+
+        let store = FsWalletStore::open(&path, 0, 0).await.unwrap();
+        store.save("Vault", &record).await.unwrap();
+        store.save("Spare", &record).await.unwrap();
+        store.trash("Spare").await.unwrap();
+        drop(store);
+
+        // A crash mid-append leaves a torn record, which is dropped.
+        let mut log = OpenOptions::new()
+            .append(true)
+            .open(log_path(path.as_ref()))
+            .await
+            .unwrap();
+        log.write_all(&[9, 0, 0, 0, 1, 2]).await.unwrap();
+        drop(log);
+
+        let store = FsWalletStore::open(&path, 0, 0).await.unwrap();
+        assert!(store.exists("Vault").await.unwrap());
+        assert!(store.find_trashed("Spare").await.unwrap().is_some());
+        store.save("Petty", &record).await.unwrap();
+
+        store.compact().await.unwrap();
+        let size = store.stats().await.unwrap().size_bytes;
+        drop(store);
+
+        let store = FsWalletStore::open(&path, 0, 0).await.unwrap();
+        assert_eq!(store.all().await.unwrap().len(), 2);
+        assert_eq!(store.stats().await.unwrap().size_bytes, size);
+    }
+
+    #[tokio::test]
+    async fn fs_snapshot_opens_as_store() {
+        let path = env::temp_dir().join(format!("mini-wallet-{}.db", Uuid::new_v4()));
+        let path = path.to_string_lossy().into_owned();
+        let dest = format!("{path}.snapshot");
+        let address = Address::from_str("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045").unwrap();
+
+        let store = FsWalletStore::open(&path, 0, 0).await.unwrap();
+        store
+            .save("Vault", &WalletRecord::new(Wallet::new(address)))
+            .await
+            .unwrap();
+        store.snapshot(&dest).await.unwrap();
+        assert!(store.snapshot(&dest).await.is_err());
+
+        let copy = FsWalletStore::open(&dest, 0, 0).await.unwrap();
+        assert!(copy.exists("Vault").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn fs_flush_interval_holds_commits() {
+        let path = env::temp_dir().join(format!("mini-wallet-{}.db", Uuid::new_v4()));
+        let address = Address::from_str("0xB644Babc370f46f202DB5eaf2071A9Ee66fA1D5E").unwrap();
+        let record = WalletRecord::new(Wallet::new(address));
+
+        let store = FsWalletStore::open(path.to_string_lossy(), 0, 0)
+            .await
+            .unwrap()
+            .with_flush_interval(Duration::from_secs(60));
+        store.save("One", &record).await.unwrap();
+        store.save("Two", &record).await.unwrap();
+        assert!(store.exists("Two").await.unwrap());
+        assert_eq!(std::fs::metadata(log_path(&path)).unwrap().len(), 0);
+
+        StoreAdmin::flush(&store).await.unwrap();
+        assert!(std::fs::metadata(log_path(&path)).unwrap().len() > 0);
+        drop(store);
+
+        let store = FsWalletStore::open(path.to_string_lossy(), 0, 0)
+            .await
+            .unwrap();
+        assert_eq!(store.all().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn fs_history_in_range_after_reopen() {
+        let path = env::temp_dir().join(format!("mini-wallet-{}.db", Uuid::new_v4()));
+        let path = path.to_string_lossy().into_owned();
+        let sample = |timestamp| Sample {
+            timestamp,
+            block_number: None,
+            balance: Balance::new(1),
+        };
+
+        let store = FsWalletStore::open(&path, 0, 0).await.unwrap();
+        let samples: Vec<_> = (1..=5).map(|t| ("w1".to_string(), sample(t))).collect();
+        store.append_samples(&samples, 4).await.unwrap();
+        drop(store);
+
+        let store = FsWalletStore::open(&path, 0, 0).await.unwrap();
+        let timestamps = |samples: Vec<Sample>| -> Vec<i64> {
+            samples.iter().map(|sample| sample.timestamp).collect()
+        };
+        assert_eq!(timestamps(store.history("w1").await.unwrap()), [2, 3, 4, 5]);
+        assert_eq!(
+            timestamps(store.history_in("w1", 1..4).await.unwrap()),
+            [2, 3]
+        );
+        assert!(store.history_in("w2", 0..10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn fs_find_by_address_follows_changes() {
+        let path = env::temp_dir().join(format!("mini-wallet-{}.db", Uuid::new_v4()));
+        let path = path.to_string_lossy().into_owned();
+        let address = Address::from_str("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045").unwrap();
+        let other = Address::new([7; 20]);
+
+        let store = FsWalletStore::open(&path, 0, 0).await.unwrap();
+        store
+            .save("Vault", &WalletRecord::new(Wallet::new(address)))
+            .await
+            .unwrap();
+        store
+            .save("Spare", &WalletRecord::new(Wallet::new(address)))
+            .await
+            .unwrap();
+        let optimism = ChainId::new(10);
+        store
+            .save(
+                "Bridge",
+                &WalletRecord::new(Wallet::on_chain(optimism, address)),
+            )
+            .await
+            .unwrap();
+        let mainnet = ChainId::MAINNET;
+        let (name, _) = store
+            .find_by_address(mainnet, &address)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(name, "Spare");
+        let (name, _) = store
+            .find_by_address(optimism, &address)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(name, "Bridge");
+        assert_eq!(store.count().await.unwrap(), 3);
+
+        store.trash("Spare").await.unwrap();
+        store
+            .save("Vault", &WalletRecord::new(Wallet::new(other)))
+            .await
+            .unwrap();
+        assert!(
+            store
+                .find_by_address(mainnet, &address)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        drop(store);
+
+        let store = FsWalletStore::open(&path, 0, 0).await.unwrap();
+        let (name, _) = store
+            .find_by_address(mainnet, &other)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(name, "Vault");
+        store.restore("Spare").await.unwrap();
+        assert!(
+            store
+                .find_by_address(mainnet, &address)
+                .await
+                .unwrap()
+                .is_some()
+        );
+        assert_eq!(store.count().await.unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn fs_insert_many_refuses_duplicates() {
+        let path = env::temp_dir().join(format!("mini-wallet-{}.db", Uuid::new_v4()));
+        let path = path.to_string_lossy().into_owned();
+        let address = Address::from_str("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045").unwrap();
+        let on = |chain_id, byte| {
+            WalletRecord::new(Wallet::on_chain(
+                ChainId::new(chain_id),
+                Address::new([byte; 20]),
+            ))
+        };
+
+        let store = FsWalletStore::open(&path, 0, 0).await.unwrap();
+        store
+            .save("Vault", &WalletRecord::new(Wallet::new(address)))
+            .await
+            .unwrap();
+
+        let wallets = [
+            ("Vault".to_string(), on(1, 1)),
+            ("Copy".to_string(), WalletRecord::new(Wallet::new(address))),
+            (
+                "Bridge".to_string(),
+                WalletRecord::new(Wallet::on_chain(ChainId::new(10), address)),
+            ),
+            ("Hot".to_string(), on(1, 2)),
+            ("Hot".to_string(), on(1, 3)),
+            ("Twin".to_string(), on(1, 2)),
+        ];
+        let insertions = store.insert_many(&wallets).await.unwrap();
+        assert_eq!(
+            insertions,
+            [
+                Insertion::NameTaken,
+                Insertion::AddressTaken("Vault".to_string()),
+                Insertion::Saved,
+                Insertion::Saved,
+                Insertion::NameTaken,
+                Insertion::AddressTaken("Hot".to_string()),
+            ]
+        );
+        drop(store);
+
+        let store = FsWalletStore::open(&path, 0, 0).await.unwrap();
+        let all = store.all().await.unwrap();
+        assert_eq!(all.keys().collect::<Vec<_>>(), ["Bridge", "Hot", "Vault"]);
+        assert_eq!(all["Hot"].wallet.address(), &Address::new([2; 20]));
+        assert_eq!(all["Vault"].wallet.address(), &address);
     }
 }
@@ -0,0 +1,458 @@
+use std::{fmt::Write, sync::Arc};
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use serde_json::json;
+use tracing::{debug, error};
+
+use crate::{
+    admin::{self, CompactionTotals, Compactions},
+    cache::{CacheMetrics, CacheTotals},
+    core::Balance,
+    infra::StoreStats,
+    payload,
+    wallet::{self, ListFilter, Wallet, WalletErrorKind},
+};
+
+const BADGE_MAX_AGE_SECS: u64 = 300;
+const BADGE_DECIMALS: u32 = 4;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BalanceGauges {
+    pub max_series: usize,
+    pub names: Vec<String>,
+}
+
+#[derive(Clone)]
+struct HttpState {
+    wallet_list: Arc<dyn wallet::List>,
+    wallet_get: Arc<dyn wallet::Get>,
+    wallet_shared: Arc<dyn wallet::ViewShared>,
+    balance_gauges: Option<BalanceGauges>,
+    compactions: Option<Arc<Compactions>>,
+    cache: Option<Arc<CacheMetrics>>,
+    store_stats: Option<Arc<dyn admin::Stats>>,
+}
+
+pub(crate) fn router(
+    wallet_list: Arc<dyn wallet::List>,
+    wallet_get: Arc<dyn wallet::Get>,
+    wallet_shared: Arc<dyn wallet::ViewShared>,
+    balance_gauges: Option<BalanceGauges>,
+    compactions: Option<Arc<Compactions>>,
+    cache: Option<Arc<CacheMetrics>>,
+    store_stats: Option<Arc<dyn admin::Stats>>,
+) -> Router {
+    let state = HttpState {
+        wallet_list,
+        wallet_get,
+        wallet_shared,
+        balance_gauges,
+        compactions,
+        cache,
+        store_stats,
+    };
+
+    Router::new()
+        .route("/metrics", get(metrics))
+        .route("/schemas/events/{version}", get(event_schema))
+        .route("/shared/{token}", get(shared))
+        .route("/badge/{name}", get(badge))
+        .with_state(state)
+}
+
+async fn shared(State(state): State<HttpState>, Path(token): Path<String>) -> Response {
+    debug!("received shared wallet request");
+
+    let wallet = match state.wallet_shared.execute(&token).await {
+        Ok(wallet) => wallet,
+        Err(e) if e.kind() == WalletErrorKind::NotFound => {
+            return StatusCode::NOT_FOUND.into_response();
+        }
+        Err(e) => {
+            error!("{}", crate::server::compose_error(&e));
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let body = json!({
+        "name": wallet.name,
+        "address": wallet.address,
+        "linked_addresses": wallet.linked_addresses,
+        "ens_name": wallet.ens_name,
+        "balance": wallet.balance,
+        "balance_wei": wallet.balance_wei,
+        "last_update": wallet.last_update.to_rfc3339(),
+        "is_stale": wallet.is_stale,
+    });
+
+    debug!("completed shared wallet request");
+    ([(header::CACHE_CONTROL, "private, no-store")], Json(body)).into_response()
+}
+
+async fn metrics(State(state): State<HttpState>) -> Response {
+    debug!("received metrics request");
+
+    let wallets = match state.wallet_list.execute(&ListFilter::default()).await {
+        Ok(wallets) => wallets,
+        Err(e) => {
+            error!("{}", crate::server::compose_error(&e));
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let mut body = String::new();
+    write_tracked(&mut body, &wallets);
+    if let Some(gauges) = &state.balance_gauges {
+        write_balance_gauges(&mut body, &wallets, gauges);
+    }
+    if let Some(compactions) = &state.compactions {
+        write_compactions(&mut body, &compactions.totals());
+    }
+    if let Some(cache) = &state.cache {
+        write_cache(&mut body, &cache.totals());
+    }
+    // Left out rather than failing the scrape, which still has the rest.
+    if let Some(store_stats) = &state.store_stats {
+        match store_stats.execute().await {
+            Ok(report) => write_store(&mut body, &report.stats),
+            Err(e) => error!("{}", crate::server::compose_error(&e)),
+        }
+    }
+
+    debug!("completed metrics request");
+    ([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], body).into_response()
+}
+
+async fn event_schema(Path(version): Path<u32>) -> Response {
+    match payload::schema(version) {
+        Some(schema) => (
+            [(header::CONTENT_TYPE, "application/schema+json")],
+            Json(schema),
+        )
+            .into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+async fn badge(State(state): State<HttpState>, Path(name): Path<String>) -> Response {
+    debug!("received badge request");
+
+    let wallet = match state.wallet_get.execute(&name).await {
+        Ok(wallet) => wallet,
+        Err(e) if e.kind() == WalletErrorKind::NotFound => {
+            return StatusCode::NOT_FOUND.into_response();
+        }
+        Err(e) => {
+            error!("{}", crate::server::compose_error(&e));
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let wei = wallet.balance_wei.parse().unwrap_or_default();
+    let balance = Balance::new(wei).eth_rounded(BADGE_DECIMALS);
+    let body = render_badge("balance", &format!("{balance} ETH"));
+
+    debug!("completed badge request");
+    (
+        [
+            (header::CONTENT_TYPE, "image/svg+xml".to_string()),
+            (
+                header::CACHE_CONTROL,
+                format!("public, max-age={BADGE_MAX_AGE_SECS}"),
+            ),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+fn render_badge(label: &str, message: &str) -> String {
+    let width = |text: &str| text.chars().count() as u32 * 7 + 10;
+    let (label_width, message_width) = (width(label), width(message));
+    let total = label_width + message_width;
+    let (label_x, message_x) = (label_width / 2, label_width + message_width / 2);
+    let (label, message) = (escape_xml(label), escape_xml(message));
+
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{total}" height="20" role="img" aria-label="{label}: {message}"><title>{label}: {message}</title><rect width="{label_width}" height="20" fill="#555"/><rect x="{label_width}" width="{message_width}" height="20" fill="#4c1"/><g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11"><text x="{label_x}" y="14">{label}</text><text x="{message_x}" y="14">{message}</text></g></svg>"##
+    )
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn write_tracked(body: &mut String, wallets: &[Wallet]) {
+    let _ = writeln!(body, "# HELP wallet_tracked Number of tracked wallets.");
+    let _ = writeln!(body, "# TYPE wallet_tracked gauge");
+    let _ = writeln!(body, "wallet_tracked {}", wallets.len());
+}
+
+fn write_compactions(body: &mut String, totals: &CompactionTotals) {
+    let _ = writeln!(
+        body,
+        "# HELP wallet_store_compactions_total Store compactions since startup."
+    );
+    let _ = writeln!(body, "# TYPE wallet_store_compactions_total counter");
+    let _ = writeln!(body, "wallet_store_compactions_total {}", totals.runs);
+    let _ = writeln!(
+        body,
+        "# HELP wallet_store_compaction_reclaimed_bytes_total Bytes reclaimed by store compactions."
+    );
+    let _ = writeln!(
+        body,
+        "# TYPE wallet_store_compaction_reclaimed_bytes_total counter"
+    );
+    let _ = writeln!(
+        body,
+        "wallet_store_compaction_reclaimed_bytes_total {}",
+        totals.reclaimed_bytes
+    );
+
+    let Some(last) = totals.last else {
+        return;
+    };
+    let _ = writeln!(
+        body,
+        "# HELP wallet_store_last_compaction_duration_seconds How long the last store compaction took."
+    );
+    let _ = writeln!(
+        body,
+        "# TYPE wallet_store_last_compaction_duration_seconds gauge"
+    );
+    let _ = writeln!(
+        body,
+        "wallet_store_last_compaction_duration_seconds {}",
+        last.duration.as_secs_f64()
+    );
+    let _ = writeln!(
+        body,
+        "# HELP wallet_store_last_compaction_timestamp_seconds When the last store compaction finished."
+    );
+    let _ = writeln!(
+        body,
+        "# TYPE wallet_store_last_compaction_timestamp_seconds gauge"
+    );
+    let _ = writeln!(
+        body,
+        "wallet_store_last_compaction_timestamp_seconds {}",
+        last.finished_at.timestamp()
+    );
+}
+
+fn write_cache(body: &mut String, totals: &CacheTotals) {
+    for (name, help, value) in [
+        ("hits", "Store reads answered from the cache.", totals.hits),
+        (
+            "misses",
+            "Store reads that went to the store.",
+            totals.misses,
+        ),
+        (
+            "evictions",
+            "Wallets evicted from the cache to make room.",
+            totals.evictions,
+        ),
+    ] {
+        let _ = writeln!(body, "# HELP wallet_store_cache_{name}_total {help}");
+        let _ = writeln!(body, "# TYPE wallet_store_cache_{name}_total counter");
+        let _ = writeln!(body, "wallet_store_cache_{name}_total {value}");
+    }
+}
+
+fn write_store(body: &mut String, stats: &StoreStats) {
+    for (name, kind, help, value) in [
+        (
+            "wallet_store_entries",
+            "gauge",
+            "Wallets in the store.",
+            stats.entries as u64,
+        ),
+        (
+            "wallet_store_trashed",
+            "gauge",
+            "Wallets in the store's trash.",
+            stats.trashed as u64,
+        ),
+        (
+            "wallet_store_size_bytes",
+            "gauge",
+            "Bytes the store takes up.",
+            stats.size_bytes,
+        ),
+        (
+            "wallet_store_writes_total",
+            "counter",
+            "Writes the store took since startup.",
+            stats.writes,
+        ),
+        (
+            "wallet_store_write_errors_total",
+            "counter",
+            "Writes the store failed since startup.",
+            stats.write_errors,
+        ),
+    ] {
+        let _ = writeln!(body, "# HELP {name} {help}");
+        let _ = writeln!(body, "# TYPE {name} {kind}");
+        let _ = writeln!(body, "{name} {value}");
+    }
+
+    let Some(last_write) = stats.last_write else {
+        return;
+    };
+    let _ = writeln!(
+        body,
+        "# HELP wallet_store_last_write_timestamp_seconds When the store last took a write."
+    );
+    let _ = writeln!(
+        body,
+        "# TYPE wallet_store_last_write_timestamp_seconds gauge"
+    );
+    let _ = writeln!(
+        body,
+        "wallet_store_last_write_timestamp_seconds {}",
+        last_write.timestamp()
+    );
+}
+
+fn write_balance_gauges(body: &mut String, wallets: &[Wallet], gauges: &BalanceGauges) {
+    let mut selected = wallets
+        .iter()
+        .filter(|w| gauges.names.is_empty() || gauges.names.contains(&w.name));
+
+    let _ = writeln!(body, "# HELP wallet_balance_wei Wallet balance in wei.");
+    let _ = writeln!(body, "# TYPE wallet_balance_wei gauge");
+    for wallet in selected.by_ref().take(gauges.max_series) {
+        let wei: f64 = wallet.balance_wei.parse().unwrap_or(f64::NAN);
+        let _ = writeln!(
+            body,
+            "wallet_balance_wei{{name=\"{}\",address=\"{}\"}} {wei}",
+            escape_label(&wallet.name),
+            escape_label(&wallet.address),
+        );
+    }
+
+    let dropped = selected.count();
+    let _ = writeln!(
+        body,
+        "# HELP wallet_balance_series_dropped Wallets left out of wallet_balance_wei by the series cap."
+    );
+    let _ = writeln!(body, "# TYPE wallet_balance_series_dropped gauge");
+    let _ = writeln!(body, "wallet_balance_series_dropped {dropped}");
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+    use crate::infra::Provenance;
+
+    fn wallet(name: &str, balance_wei: &str) -> Wallet {
+        Wallet {
+            id: String::new(),
+            name: name.to_string(),
+            chain_id: 1,
+            address: "0xB644Babc370f46f202DB5eaf2071A9Ee66fA1D5E".to_string(),
+            linked_addresses: vec![],
+            balance: String::new(),
+            balance_wei: balance_wei.to_string(),
+            value_usd: None,
+            delta_wei: None,
+            delta_pct: None,
+            tokens: vec![],
+            transaction_count: None,
+            resolved_from: None,
+            tags: vec![],
+            note: None,
+            created_at: Utc::now(),
+            created_by: Provenance::default(),
+            modified_by: Provenance::default(),
+            last_update: Utc::now(),
+            field_errors: vec![],
+            verified_at: None,
+            ens_name: None,
+            age_seconds: 0,
+            is_stale: false,
+            pinned: false,
+        }
+    }
+
+    #[test]
+    fn balance_gauges_cap_and_filter() {
+        let wallets = [
+            wallet("David's \"Hot\" Wallet", "1000"),
+            wallet("Cold", "2000"),
+            wallet("Savings", "3000"),
+        ];
+
+        let mut body = String::new();
+        let gauges = BalanceGauges {
+            max_series: 1,
+            names: vec![],
+        };
+        write_balance_gauges(&mut body, &wallets, &gauges);
+        assert!(body.contains("wallet_balance_wei{name=\"David's \\\"Hot\\\" Wallet\""));
+        assert!(!body.contains("name=\"Cold\""));
+        assert!(body.contains("wallet_balance_series_dropped 2"));
+
+        let mut body = String::new();
+        let gauges = BalanceGauges {
+            max_series: 10,
+            names: vec!["Savings".to_string()],
+        };
+        write_balance_gauges(&mut body, &wallets, &gauges);
+        assert!(body.contains("name=\"Savings\""));
+        assert!(body.contains("} 3000\n"));
+        assert!(!body.contains("name=\"Cold\""));
+        assert!(body.contains("wallet_balance_series_dropped 0"));
+    }
+
+    #[test]
+    fn store_metrics_without_writes() {
+        let mut stats = StoreStats {
+            entries: 3,
+            size_bytes: 512,
+            ..Default::default()
+        };
+        let mut body = String::new();
+        write_store(&mut body, &stats);
+        assert!(body.contains("wallet_store_entries 3\n"));
+        assert!(body.contains("wallet_store_writes_total 0\n"));
+        assert!(!body.contains("last_write"));
+
+        stats.writes = 2;
+        stats.last_write = Some(Utc::now());
+        let mut body = String::new();
+        write_store(&mut body, &stats);
+        assert!(body.contains("wallet_store_writes_total 2\n"));
+        assert!(body.contains("wallet_store_last_write_timestamp_seconds "));
+    }
+
+    #[test]
+    fn badge_render_escapes() {
+        let svg = render_badge("<balance>", "1.0000 ETH");
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains(">&lt;balance&gt;</text>"));
+        assert!(svg.contains(">1.0000 ETH</text>"));
+        assert!(!svg.contains("<balance>"));
+    }
+}
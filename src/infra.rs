@@ -1,9 +1,17 @@
-use std::{collections::HashMap, error, fmt};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    error, fmt,
+    ops::Range,
+    sync::Mutex,
+};
 
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
+use uuid::Uuid;
 
-use crate::core::{Address, Balance, Wallet};
+use crate::core::{
+    Address, AlertCondition, Balance, ChainId, Sample, TokenBalance, Transfer, U256, Wallet,
+};
 
 #[derive(Debug)]
 pub struct StoreError(pub Box<dyn error::Error + Send + Sync + 'static>);
@@ -22,18 +30,397 @@ impl error::Error for StoreError {
 
 #[derive(Debug, Clone)]
 pub struct WalletRecord {
+    pub id: String,
     pub wallet: Wallet,
+    pub tags: Vec<String>,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub created_by: Provenance,
+    pub modified_by: Provenance,
     pub last_update: DateTime<Utc>,
+    pub previous_balance: Option<Balance>,
+    pub tokens: Vec<TokenBalance>,
+    pub transaction_count: Option<u64>,
+    pub verified_at: Option<DateTime<Utc>>,
+    pub resolved_from: Option<String>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub ens_name: Option<String>,
+    pub ens_checked_at: Option<DateTime<Utc>>,
+    pub funded_at: Option<DateTime<Utc>>,
+    pub swept_at: Option<DateTime<Utc>>,
+}
+
+impl WalletRecord {
+    pub fn new(wallet: Wallet) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            wallet,
+            tags: Vec::new(),
+            note: None,
+            created_at: Utc::now(),
+            created_by: Provenance::default(),
+            modified_by: Provenance::default(),
+            last_update: Utc::now(),
+            previous_balance: None,
+            tokens: Vec::new(),
+            transaction_count: None,
+            resolved_from: None,
+            resolved_at: None,
+            verified_at: None,
+            ens_name: None,
+            ens_checked_at: None,
+            funded_at: None,
+            swept_at: None,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct Provenance {
+    pub principal: String,
+    pub interface: Interface,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Interface {
+    #[default]
+    Unknown,
+    Grpc,
+    Rest,
+    Cli,
+    Import,
 }
 
 #[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait WalletStore: Send + Sync + 'static {
     async fn find(&self, name: &str) -> Result<Option<WalletRecord>, StoreError>;
-    async fn all(&self) -> Result<HashMap<String, WalletRecord>, StoreError>;
+    async fn all(&self) -> Result<BTreeMap<String, WalletRecord>, StoreError>;
     async fn exists(&self, name: &str) -> Result<bool, StoreError>;
     async fn save(&self, name: &str, wallet: &WalletRecord) -> Result<(), StoreError>;
+    async fn save_many(&self, wallets: &[(String, WalletRecord)]) -> Result<(), StoreError>;
     async fn delete(&self, name: &str) -> Result<(), StoreError>;
+    async fn delete_many(&self, names: &[String]) -> Result<(), StoreError>;
+    async fn update(
+        &self,
+        name: &str,
+        update: WalletUpdate,
+    ) -> Result<Option<WalletRecord>, StoreError>;
+    async fn find_by_address(
+        &self,
+        chain_id: ChainId,
+        address: &Address,
+    ) -> Result<Option<(String, WalletRecord)>, StoreError>;
+    async fn count(&self) -> Result<usize, StoreError>;
+    async fn insert_many(
+        &self,
+        wallets: &[(String, WalletRecord)],
+    ) -> Result<Vec<Insertion>, StoreError>;
+    async fn search(
+        &self,
+        terms: &[String],
+        limit: usize,
+    ) -> Result<Vec<(String, WalletRecord)>, StoreError>;
+    async fn transaction(&self, changes: &[WalletChange]) -> Result<(), StoreError>;
+}
+
+pub type WalletUpdate = Box<dyn FnMut(Option<WalletRecord>) -> Option<WalletRecord> + Send>;
+
+#[derive(Debug, Clone)]
+pub enum WalletChange {
+    Save {
+        name: String,
+        record: Box<WalletRecord>,
+    },
+    Delete {
+        name: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Insertion {
+    Saved,
+    NameTaken,
+    AddressTaken(String),
+}
+
+pub fn plan_insertions(
+    wallets: &[(String, WalletRecord)],
+    stored: impl IntoIterator<Item = (bool, Option<String>)>,
+) -> Vec<Insertion> {
+    let mut names = HashSet::new();
+    let mut addresses = HashMap::new();
+    wallets
+        .iter()
+        .zip(stored)
+        .map(|((name, record), (name_taken, holder))| {
+            let key = (record.wallet.chain_id(), *record.wallet.address());
+            if name_taken || names.contains(name) {
+                return Insertion::NameTaken;
+            }
+            if let Some(holder) = holder.or_else(|| addresses.get(&key).cloned()) {
+                return Insertion::AddressTaken(holder);
+            }
+            names.insert(name.clone());
+            addresses.insert(key, name.clone());
+            Insertion::Saved
+        })
+        .collect()
+}
+
+pub fn plan_insertions_among(
+    wallets: &[(String, WalletRecord)],
+    stored: &BTreeMap<String, WalletRecord>,
+) -> Vec<Insertion> {
+    let lookups = wallets.iter().map(|(name, record)| {
+        let holder = stored.iter().find(|(_, held)| {
+            held.wallet.chain_id() == record.wallet.chain_id()
+                && held.wallet.address() == record.wallet.address()
+        });
+        (
+            stored.contains_key(name),
+            holder.map(|(holder, _)| holder.clone()),
+        )
+    });
+    plan_insertions(wallets, lookups)
+}
+
+pub fn search_records(
+    records: impl IntoIterator<Item = (String, WalletRecord)>,
+    terms: &[String],
+    limit: usize,
+) -> Vec<(String, WalletRecord)> {
+    let mut ranked: Vec<_> = records
+        .into_iter()
+        .filter_map(|(name, record)| {
+            let score = search_score(terms, &name, &record)?;
+            Some((score, name, record))
+        })
+        .collect();
+
+    ranked.sort_by(|(a_score, a_name, _), (b_score, b_name, _)| {
+        b_score
+            .cmp(a_score)
+            .then_with(|| a_name.to_lowercase().cmp(&b_name.to_lowercase()))
+    });
+
+    ranked
+        .into_iter()
+        .take(limit)
+        .map(|(_, name, record)| (name, record))
+        .collect()
+}
+
+fn search_score(terms: &[String], name: &str, record: &WalletRecord) -> Option<u32> {
+    let name = name.to_lowercase();
+    let addresses: Vec<_> = record
+        .wallet
+        .addresses()
+        .map(|address| address.to_string().to_lowercase())
+        .collect();
+    let ens_name = record
+        .ens_name
+        .as_deref()
+        .unwrap_or_default()
+        .to_lowercase();
+    let note = record.note.as_deref().unwrap_or_default().to_lowercase();
+
+    terms.iter().try_fold(0, |total, term| {
+        let term = term.as_str();
+        let name_score = if name == term {
+            100
+        } else if name.starts_with(term) {
+            50
+        } else if name.split_whitespace().any(|w| w.starts_with(term)) {
+            30
+        } else if name.contains(term) {
+            20
+        } else {
+            0
+        };
+
+        let ens_score = if ens_name.is_empty() {
+            0
+        } else if ens_name == term {
+            40
+        } else if ens_name.starts_with(term) {
+            20
+        } else if ens_name.contains(term) {
+            10
+        } else {
+            0
+        };
+
+        let note_score = if note.split_whitespace().any(|w| w.starts_with(term)) {
+            8
+        } else if note.contains(term) {
+            3
+        } else {
+            0
+        };
+
+        let tag_score = if record.tags.iter().any(|t| t == term) {
+            15
+        } else if record.tags.iter().any(|t| t.contains(term)) {
+            5
+        } else {
+            0
+        };
+
+        let address_score = if addresses.iter().any(|a| a.starts_with(term)) {
+            25
+        } else if term.len() >= 4 && addresses.iter().any(|a| a.contains(term)) {
+            10
+        } else {
+            0
+        };
+
+        let term_score = name_score + ens_score + note_score + tag_score + address_score;
+        (term_score > 0).then_some(total + term_score)
+    })
+}
+
+#[derive(Debug, Clone)]
+pub struct TrashedRecord {
+    pub record: WalletRecord,
+    pub trashed_at: DateTime<Utc>,
+}
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait TrashStore: Send + Sync + 'static {
+    async fn find_trashed(&self, name: &str) -> Result<Option<TrashedRecord>, StoreError>;
+    async fn all_trashed(&self) -> Result<BTreeMap<String, TrashedRecord>, StoreError>;
+    async fn trash(&self, name: &str) -> Result<(), StoreError>;
+    async fn restore(&self, name: &str) -> Result<(), StoreError>;
+    async fn purge(&self, name: &str) -> Result<(), StoreError>;
+    async fn save_trashed(&self, name: &str, trashed: &TrashedRecord) -> Result<(), StoreError>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlertRule {
+    pub id: String,
+    pub wallet_id: Option<String>,
+    pub condition: AlertCondition,
+    pub created_by: Provenance,
+}
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait AlertStore: Send + Sync + 'static {
+    async fn all_alerts(&self) -> Result<Vec<AlertRule>, StoreError>;
+    async fn save_alert(&self, rule: &AlertRule) -> Result<(), StoreError>;
+    async fn delete_alert(&self, id: &str) -> Result<bool, StoreError>;
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ListSort {
+    #[default]
+    Name,
+    Balance,
+    LastUpdate,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SavedView {
+    pub name: String,
+    pub tags: Vec<String>,
+    pub created_by: Option<String>,
+    pub created_via: Option<Interface>,
+    pub sort: ListSort,
+    pub fields: Vec<String>,
+}
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait PreferenceStore: Send + Sync + 'static {
+    async fn pinned(&self, principal: &str) -> Result<Vec<String>, StoreError>;
+    async fn set_pinned(&self, principal: &str, wallet_ids: &[String]) -> Result<(), StoreError>;
+    async fn views(&self, principal: &str) -> Result<Vec<SavedView>, StoreError>;
+    async fn save_view(&self, principal: &str, view: &SavedView) -> Result<(), StoreError>;
+    async fn delete_view(&self, principal: &str, name: &str) -> Result<bool, StoreError>;
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct StoreStats {
+    pub entries: usize,
+    pub trashed: usize,
+    pub size_bytes: u64,
+    pub last_write: Option<DateTime<Utc>>,
+    pub writes: u64,
+    pub write_errors: u64,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct WriteCounters {
+    counts: Mutex<(u64, u64, Option<DateTime<Utc>>)>,
+}
+
+impl WriteCounters {
+    pub(crate) fn record<T, E>(&self, result: &Result<T, E>) {
+        let mut counts = self.counts.lock().unwrap();
+        match result {
+            Ok(_) => {
+                counts.0 += 1;
+                counts.2 = Some(Utc::now());
+            }
+            Err(_) => counts.1 += 1,
+        }
+    }
+
+    pub(crate) fn fill(&self, stats: StoreStats) -> StoreStats {
+        let (writes, write_errors, last_write) = *self.counts.lock().unwrap();
+        StoreStats {
+            last_write,
+            writes,
+            write_errors,
+            ..stats
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoreInfo {
+    pub backend: String,
+    pub location: String,
+    pub recovered_from: Option<String>,
+}
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait StoreAdmin: Send + Sync + 'static {
+    fn describe(&self) -> StoreInfo;
+    async fn stats(&self) -> Result<StoreStats, StoreError>;
+    async fn compact(&self) -> Result<(), StoreError>;
+    async fn flush(&self) -> Result<(), StoreError> {
+        Ok(())
+    }
+    async fn backup(&self) -> Result<String, StoreError>;
+    async fn snapshot(&self, dest: &str) -> Result<(), StoreError>;
+}
+
+pub trait Store:
+    WalletStore
+    + TrashStore
+    + AlertStore
+    + PreferenceStore
+    + OutboxStore
+    + HistoryStore
+    + StoreAdmin
+    + fmt::Debug
+{
+}
+
+impl<T> Store for T where
+    T: WalletStore
+        + TrashStore
+        + AlertStore
+        + PreferenceStore
+        + OutboxStore
+        + HistoryStore
+        + StoreAdmin
+        + fmt::Debug
+{
 }
 
 #[derive(Debug)]
@@ -55,4 +442,158 @@ impl error::Error for ClientError {
 #[async_trait]
 pub trait WalletClient: Send + Sync + 'static {
     async fn balance(&self, address: &Address) -> Result<Balance, ClientError>;
+    async fn balances(&self, addresses: &[Address]) -> Result<Vec<Balance>, ClientError> {
+        futures::future::try_join_all(addresses.iter().map(|address| self.balance(address))).await
+    }
+    async fn block_number(&self) -> Result<u64, ClientError>;
+    async fn transaction_count(&self, address: &Address) -> Result<u64, ClientError>;
+    async fn token_balance(&self, address: &Address, token: &Address) -> Result<U256, ClientError>;
+}
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait HistoryClient: Send + Sync + 'static {
+    async fn transfers(
+        &self,
+        address: &Address,
+        chain_id: ChainId,
+        limit: usize,
+    ) -> Result<Vec<Transfer>, ClientError>;
+}
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait NameResolver: Send + Sync + 'static {
+    async fn resolve(&self, name: &str) -> Result<Option<Address>, ClientError>;
+}
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait ReverseResolver: Send + Sync + 'static {
+    async fn reverse(&self, address: &Address) -> Result<Option<String>, ClientError>;
+}
+
+#[cfg_attr(test, mockall::automock)]
+pub trait ClientAdmin: Send + Sync + 'static {
+    fn endpoint(&self) -> String;
+    fn set_endpoint(&self, url: &str);
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Notification {
+    BalanceChanged {
+        wallet_id: String,
+        name: String,
+        before: Balance,
+        after: Balance,
+    },
+    AlertFired {
+        rule_id: String,
+        wallet_id: String,
+        name: String,
+        before: Balance,
+        after: Balance,
+    },
+}
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait Notifier: Send + Sync + 'static {
+    async fn notify(
+        &self,
+        notification: &Notification,
+        schema_version: u32,
+    ) -> Result<(), ClientError>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutboxEntry {
+    pub id: String,
+    pub notification: Notification,
+    pub schema_version: u32,
+    pub created_at: DateTime<Utc>,
+    pub attempts: u32,
+    pub next_attempt_at: DateTime<Utc>,
+}
+
+impl OutboxEntry {
+    pub fn new(notification: Notification, schema_version: u32) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4().to_string(),
+            notification,
+            schema_version,
+            created_at: now,
+            attempts: 0,
+            next_attempt_at: now,
+        }
+    }
+}
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait OutboxStore: Send + Sync + 'static {
+    async fn save_with_outbox(
+        &self,
+        name: &str,
+        record: &WalletRecord,
+        entry: &OutboxEntry,
+    ) -> Result<(), StoreError>;
+    async fn outbox(&self) -> Result<Vec<OutboxEntry>, StoreError>;
+    async fn ack(&self, id: &str) -> Result<(), StoreError>;
+    async fn reschedule(
+        &self,
+        id: &str,
+        attempts: u32,
+        next_attempt_at: DateTime<Utc>,
+    ) -> Result<(), StoreError>;
+}
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait PriceOracle: Send + Sync + 'static {
+    async fn eth_usd_price(&self) -> Result<f64, ClientError>;
+}
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait HistoryStore: Send + Sync + 'static {
+    async fn append_samples(
+        &self,
+        samples: &[(String, Sample)],
+        keep: usize,
+    ) -> Result<(), StoreError>;
+    async fn replace_history(&self, wallet_id: &str, samples: &[Sample]) -> Result<(), StoreError>;
+    async fn history(&self, wallet_id: &str) -> Result<Vec<Sample>, StoreError>;
+    async fn history_in(
+        &self,
+        wallet_id: &str,
+        range: Range<i64>,
+    ) -> Result<Vec<Sample>, StoreError> {
+        let mut samples = self.history(wallet_id).await?;
+        samples.retain(|sample| range.contains(&sample.timestamp));
+        Ok(samples)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuditOutcome {
+    Succeeded,
+    Failed(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditEntry {
+    pub operation: String,
+    pub name: String,
+    pub address: Option<String>,
+    pub at: DateTime<Utc>,
+    pub outcome: AuditOutcome,
+}
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait AuditStore: Send + Sync + 'static {
+    async fn append(&self, entry: &AuditEntry) -> Result<(), StoreError>;
+    async fn audit_log(&self) -> Result<Vec<AuditEntry>, StoreError>;
 }
@@ -1,9 +1,34 @@
 #![forbid(unsafe_code)]
 #![warn(missing_debug_implementations)]
 
+pub mod admin;
+pub mod alert;
+pub mod cache;
+pub mod cli;
+pub mod client;
+pub mod config;
 pub mod core;
+pub mod etherscan;
 pub mod fs;
+pub mod http;
 pub mod infra;
+pub mod memory;
+pub mod payload;
+#[cfg(feature = "postgres")]
+pub mod postgres;
+pub mod price;
+#[cfg(feature = "redis")]
+pub mod redis;
+pub mod resolve;
+#[cfg(feature = "rocksdb")]
+pub mod rocksdb;
 pub mod rpc;
+pub mod scheduler;
+pub mod secrets;
 pub mod server;
+pub mod shard;
+#[cfg(feature = "sled")]
+pub mod sled;
+pub mod sqlite;
 pub mod wallet;
+pub mod webhook;
@@ -1,38 +1,228 @@
 #![forbid(unsafe_code)]
 #![warn(missing_debug_implementations)]
 
-use std::{env, error::Error, process, sync::Arc};
+use std::{
+    env,
+    error::Error,
+    net::{IpAddr, Ipv4Addr},
+    path::Path,
+    process,
+    sync::Arc,
+    time::Duration,
+};
 
 use mini_wallet::{
-    fs::FsWalletStore,
+    admin, alert,
+    cache::{CacheMetrics, CachedWalletStore},
+    cli,
+    client::{
+        self, BudgetedClient, CachedWalletClient, CircuitBreakerClient, FailoverWalletClient,
+        RetryBudget, RetryingWalletClient, UpstreamBudget,
+    },
+    config::Config,
+    core::{BalanceFormat, BalanceUnit},
+    etherscan::EtherscanClient,
+    fs::{FsAuditStore, FsWalletStore},
+    http::BalanceGauges,
+    infra::{NameResolver, PriceOracle, Store},
+    price::{CachedPriceOracle, CoinGeckoOracle},
+    resolve::{ChainResolver, EnsResolver, LensResolver, UnstoppableResolver},
     rpc::RpcWalletClient,
-    server::{Controller, Server},
-    wallet,
+    scheduler::{CompactionWindow, ReportSchedule, Scheduler},
+    secrets::{self, SecretSource, SecretsProvider, SopsSecrets, VaultSecrets},
+    server::{AdminController, Controller, Server},
+    shard::ShardedWalletStore,
+    sqlite::SqliteWalletStore,
+    wallet::{
+        self, Challenges, DisplayPolicy, EnrichmentErrors, EventBus, ExportFormat, Overflow,
+        Shares, SweepPolicy,
+    },
+    webhook::{RetryPolicy, WebhookNotifier},
 };
 
+#[cfg(feature = "postgres")]
+use mini_wallet::postgres::PostgresWalletStore;
+#[cfg(feature = "redis")]
+use mini_wallet::redis::RedisWalletStore;
+#[cfg(feature = "rocksdb")]
+use mini_wallet::rocksdb::RocksWalletStore;
+#[cfg(feature = "sled")]
+use mini_wallet::sled::SledWalletStore;
+use tokio::time::Instant;
+use tokio_util::sync::CancellationToken;
 use tracing::error;
 use tracing_subscriber::{EnvFilter, filter::LevelFilter, fmt, prelude::*};
 
+type UpstreamClient = CachedWalletClient<
+    RetryingWalletClient<
+        CircuitBreakerClient<FailoverWalletClient<BudgetedClient<RpcWalletClient>>>,
+    >,
+>;
+
 #[derive(Debug, Clone)]
 struct Dependencies {
-    wallet_store: Arc<FsWalletStore>,
-    wallet_client: Arc<RpcWalletClient>,
+    wallet_store: Arc<dyn Store>,
+    audit_store: Arc<FsAuditStore>,
+    wallet_client: Arc<UpstreamClient>,
+    events: Arc<EventBus>,
+    enrichment_errors: Arc<EnrichmentErrors>,
+    name_resolver: Arc<ChainResolver>,
+    reverse_resolver: Arc<EnsResolver>,
+    notifier: Option<Arc<WebhookNotifier>>,
+    compactions: Arc<admin::Compactions>,
+    cache_metrics: Option<Arc<CacheMetrics>>,
+    price_oracle: Option<Arc<CachedPriceOracle<BudgetedClient<CoinGeckoOracle>>>>,
+    history_client: Option<Arc<BudgetedClient<EtherscanClient>>>,
 }
 
+const USAGE: &str = "usage: mini-wallet [--store <path>] [init [--store <path>] \
+                     [--rpc-url <url>] [--[no-]webhook-secret] [--yes] [--force] | \
+                     config validate | \
+                     config print --effective | import <file> [--skip-balance] | \
+                     export [--format csv|json|ndjson] [file] | \
+                     migrate <backend> [--path <path>] [--url-env <var>]]";
+
 #[tokio::main]
 async fn main() {
-    subscribe_tracing();
-    let dependencies = build_dependencies().await;
-    let controller = build_controller(&dependencies);
+    let path = env::var("MINI_WALLET_CONFIG").unwrap_or_else(|_| "mini-wallet.toml".to_string());
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    // Ahead of any command, overrides the store path from the config.
+    let (store_path, args) = match &args[..] {
+        ["--store", store_path, args @ ..] => (Some(store_path.to_string()), args),
+        args => (None, args),
+    };
+
+    // Runs before the config is loaded, since it's what writes it.
+    if let ["init", flags @ ..] = args {
+        let result = match cli::InitOptions::parse(flags) {
+            Ok(mut options) => {
+                options.store_path = options.store_path.or(store_path);
+                cli::init(Path::new(&path), options).await
+            }
+            Err(e) => Err(e),
+        };
+        result.unwrap_or_else(|e| {
+            eprintln!("{}", compose_error(&e));
+            process::exit(1);
+        });
+        return;
+    }
+
+    let mut config = Config::load(&path).await.unwrap_or_else(|e| {
+        eprintln!("{path}: {}", compose_error(&e));
+        process::exit(1);
+    });
+    if let Some(store_path) = store_path {
+        config.store.path = store_path;
+    }
+
+    match *args {
+        [] => serve(config).await,
+        ["config", "validate"] => println!("{path}: ok"),
+        ["config", "print", "--effective"] => print!("{}", config.to_toml()),
+        ["import", file] | ["import", file, "--skip-balance"] => {
+            let skip_balance = args.len() == 3;
+            cli::import(server_url(&config), Path::new(file), skip_balance)
+                .await
+                .unwrap_or_else(|e| {
+                    eprintln!("{}", compose_error(&e));
+                    process::exit(1);
+                });
+        }
+        ["export", ref flags @ ..] => {
+            let result = match cli::ExportOptions::parse(flags) {
+                Ok(options) => cli::export(server_url(&config), options).await,
+                Err(e) => Err(e),
+            };
+            result.unwrap_or_else(|e| {
+                eprintln!("{}", compose_error(&e));
+                process::exit(1);
+            })
+        }
+        ["migrate", ref flags @ ..] => {
+            let target = cli::MigrateOptions::parse(flags)
+                .and_then(|options| options.target(&config))
+                .unwrap_or_else(|e| {
+                    eprintln!("{}", compose_error(&e));
+                    process::exit(1);
+                });
+            let secrets = build_secrets_provider(&config);
+            let from = build_store(&config, secrets.as_deref()).await;
+            let to = build_store(&target, secrets.as_deref()).await;
+            let migration = cli::migrate(from, to).await.unwrap_or_else(|e| {
+                eprintln!("{}", compose_error(&e));
+                process::exit(1);
+            });
+            println!(
+                "migrated {} wallets, {} trashed, {} alerts, and {} samples to {}; digest {}",
+                migration.wallets,
+                migration.trashed,
+                migration.alerts,
+                migration.samples,
+                target.store.backend,
+                migration.digest
+            );
+        }
+        _ => {
+            eprintln!("{USAGE}");
+            process::exit(2);
+        }
+    }
+}
+
+async fn serve(config: Config) {
+    subscribe_tracing(&config);
+    let dependencies = build_dependencies(&config).await;
+    let controller = build_controller(&config, &dependencies);
+    let admin_controller = build_admin_controller(&dependencies, &controller);
+    let scheduler = build_scheduler(&config, &dependencies, &controller);
+    let shutdown = CancellationToken::new();
+
+    let mut server = Server::new(controller)
+        .with_refresh_trigger(scheduler.trigger())
+        .with_shutdown(shutdown.clone())
+        .with_admin(admin_controller)
+        .with_addr(config.server.addr)
+        .with_port(config.server.port)
+        .with_admin_port(config.server.admin_port)
+        .with_http_port(config.server.http_port)
+        .with_compaction_metrics(dependencies.compactions.clone())
+        .with_balance_gauges(BalanceGauges {
+            max_series: config.metrics.max_series,
+            names: config.metrics.wallets.clone(),
+        });
+    if let Some(cache_metrics) = &dependencies.cache_metrics {
+        server = server.with_cache_metrics(cache_metrics.clone());
+    }
 
-    let server = Server::new(controller);
-    server.run().await.unwrap_or_else(|e| {
+    let scheduler = tokio::spawn(scheduler.run(shutdown.clone()));
+    let result = server.run().await;
+    shutdown.cancel();
+    let _ = scheduler.await;
+    if let Err(e) = dependencies.wallet_store.flush().await {
+        trace_error(&e);
+    }
+
+    result.unwrap_or_else(|e| {
         trace_error(&e);
         process::exit(1);
     });
 }
 
-fn subscribe_tracing() {
+fn server_url(config: &Config) -> String {
+    let addr = match config.server.addr {
+        addr if addr.is_unspecified() => IpAddr::V4(Ipv4Addr::LOCALHOST),
+        addr => addr,
+    };
+    let host = match addr {
+        IpAddr::V4(addr) => addr.to_string(),
+        IpAddr::V6(addr) => format!("[{addr}]"),
+    };
+    format!("http://{host}:{}", config.server.port)
+}
+
+fn subscribe_tracing(config: &Config) {
     if env::var("RUST_LOG").is_ok() {
         tracing_subscriber::registry()
             .with(EnvFilter::from_default_env())
@@ -40,54 +230,678 @@ fn subscribe_tracing() {
             .init();
     } else {
         tracing_subscriber::registry()
-            .with(LevelFilter::INFO)
+            .with(config.log.level.parse().unwrap_or(LevelFilter::INFO))
             .with(fmt::layer())
             .init();
     }
 }
 
-async fn build_dependencies() -> Dependencies {
-    let wallet_store = FsWalletStore::open("wallet.db").await.unwrap_or_else(|e| {
+async fn build_dependencies(config: &Config) -> Dependencies {
+    let wallet_client = RpcWalletClient::new(&config.upstream.rpc_url).unwrap_or_else(|e| {
         trace_error(&e);
         process::exit(1);
     });
 
-    let wallet_client = RpcWalletClient::new("https://eth.llamarpc.com").unwrap_or_else(|e| {
+    let wallet_client = Arc::new(wallet_client);
+    let secrets = build_secrets_provider(config);
+    let secrets = secrets.as_deref();
+    let mut wallet_store = build_store(config, secrets).await;
+    let mut cache_metrics = None;
+    if config.store.cache_size > 0 {
+        let cached = CachedWalletStore::new(wallet_store, config.store.cache_size);
+        cache_metrics = Some(cached.metrics());
+        wallet_store = Arc::new(cached);
+    }
+
+    let upstream_budget = Arc::new(UpstreamBudget::new(
+        config.upstream.rate,
+        config.upstream.burst,
+    ));
+    // ENS pays per eth_call, the others per HTTP request.
+    let ens_resolver = Arc::new(EnsResolver {
+        rpc: wallet_client.clone(),
+        budget: upstream_budget.clone(),
+    });
+
+    let mut resolvers: Vec<Arc<dyn NameResolver>> = vec![ens_resolver.clone()];
+    if let Some(api_key) = load_secret(&config.upstream.unstoppable_api_key, secrets).await {
+        let resolver = UnstoppableResolver::new("https://api.unstoppabledomains.com", api_key)
+            .unwrap_or_else(|e| {
+                trace_error(&e);
+                process::exit(1);
+            });
+        resolvers.push(Arc::new(BudgetedClient {
+            inner: Arc::new(resolver),
+            budget: upstream_budget.clone(),
+        }));
+    }
+    let resolver = LensResolver::new("https://api-v2.lens.dev").unwrap_or_else(|e| {
         trace_error(&e);
         process::exit(1);
     });
+    resolvers.push(Arc::new(BudgetedClient {
+        inner: Arc::new(resolver),
+        budget: upstream_budget.clone(),
+    }));
+
+    // Failovers and retries spend from the upstream budget like any other
+    // call.
+    let mut endpoints = vec![wallet_client];
+    for url in &config.upstream.fallback_rpc_urls {
+        let client = RpcWalletClient::new(url).unwrap_or_else(|e| {
+            trace_error(&e);
+            process::exit(1);
+        });
+        endpoints.push(Arc::new(client));
+    }
+    let endpoints = endpoints
+        .into_iter()
+        .map(|inner| {
+            Arc::new(BudgetedClient {
+                inner,
+                budget: upstream_budget.clone(),
+            })
+        })
+        .collect();
+    let wallet_client = RetryingWalletClient {
+        // Opens only once every endpoint has failed.
+        inner: Arc::new(CircuitBreakerClient::new(
+            Arc::new(FailoverWalletClient::new(
+                endpoints,
+                Duration::from_secs(30),
+            )),
+            config.upstream.breaker_failures,
+            config.upstream.breaker_cooldown,
+        )),
+        policy: client::RetryPolicy {
+            retries: config.upstream.retries,
+            backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(5),
+        },
+        // A tenth of calls may be retried, with a burst of ten saved up.
+        budget: Arc::new(RetryBudget::new(0.1, 10)),
+    };
+    let wallet_client =
+        CachedWalletClient::new(Arc::new(wallet_client), config.upstream.balance_ttl);
+    let name_resolver = ChainResolver::new(resolvers);
+
+    let history_client = match config.transactions.provider.as_str() {
+        "etherscan" => {
+            let Some(api_key) = load_secret(&config.transactions.etherscan_api_key, secrets).await
+            else {
+                error!("transactions.etherscan_api_key must be set to read from etherscan");
+                process::exit(1);
+            };
+            let client = EtherscanClient::new(&config.transactions.etherscan_url, api_key)
+                .unwrap_or_else(|e| {
+                    trace_error(&e);
+                    process::exit(1);
+                });
+            Some(Arc::new(BudgetedClient {
+                inner: Arc::new(client),
+                budget: upstream_budget.clone(),
+            }))
+        }
+        _ => None,
+    };
+    let price_oracle = build_price_oracle(config, upstream_budget);
+
+    // WEBHOOK_URLS still overrides the config file.
+    let urls = match env::var("WEBHOOK_URLS") {
+        Ok(urls) => urls.split(',').map(|url| url.trim().to_string()).collect(),
+        Err(_) => config.webhook.urls.clone(),
+    };
+    let notifier = if urls.is_empty() {
+        None
+    } else {
+        let Some(secret) = load_secret(&config.webhook.secret, secrets).await else {
+            error!("webhook.secret must be set to sign webhooks");
+            process::exit(1);
+        };
+        let retry = RetryPolicy {
+            attempts: 5,
+            backoff: Duration::from_secs(1),
+        };
+        let notifier = WebhookNotifier::new(urls, secret, retry).unwrap_or_else(|e| {
+            trace_error(&e);
+            process::exit(1);
+        });
+        Some(Arc::new(notifier))
+    };
+
+    let overflow = match config.events.overflow.as_str() {
+        "disconnect" => Overflow::Disconnect,
+        _ => Overflow::DropOldest,
+    };
 
     Dependencies {
-        wallet_store: Arc::new(wallet_store),
+        wallet_store,
+        audit_store: Arc::new(FsAuditStore::new(&config.store.audit_path)),
         wallet_client: Arc::new(wallet_client),
+        events: Arc::new(EventBus::new(config.events.capacity).with_overflow(overflow)),
+        enrichment_errors: Arc::new(EnrichmentErrors::default()),
+        name_resolver: Arc::new(name_resolver),
+        reverse_resolver: ens_resolver,
+        notifier,
+        compactions: Arc::new(admin::Compactions::default()),
+        cache_metrics,
+        price_oracle,
+        history_client,
     }
 }
 
-fn build_controller(dependencies: &Dependencies) -> Controller {
+async fn build_store(config: &Config, secrets: Option<&dyn SecretsProvider>) -> Arc<dyn Store> {
+    create_parent_dir(&config.store.audit_path).await;
+    if !matches!(config.store.backend.as_str(), "postgres" | "redis") {
+        create_parent_dir(&config.store.path).await;
+    }
+    let store: Result<Arc<dyn Store>, Box<dyn Error>> = match config.store.backend.as_str() {
+        "postgres" => build_postgres_store(config, secrets).await,
+        "redis" => build_redis_store(config, secrets).await,
+        "sled" => build_sled_store(config),
+        "rocksdb" => build_rocksdb_store(config),
+        "sharded" => ShardedWalletStore::open(&config.store.path)
+            .await
+            .map(|store| Arc::new(store) as _)
+            .map_err(Into::into),
+        "sqlite" => SqliteWalletStore::open(&config.store.path)
+            .await
+            .map(|store| Arc::new(store) as _)
+            .map_err(Into::into),
+        _ => FsWalletStore::open(
+            &config.store.path,
+            config.store.backups,
+            config.store.compression_level,
+        )
+        .await
+        .map(|store| Arc::new(store.with_flush_interval(config.store.flush_interval)) as _)
+        .map_err(Into::into),
+    };
+    store.unwrap_or_else(|e| {
+        trace_error(&*e);
+        process::exit(1);
+    })
+}
+
+async fn create_parent_dir(path: &str) {
+    let Some(parent) = Path::new(path).parent() else {
+        return;
+    };
+    if let Err(e) = tokio::fs::create_dir_all(parent).await {
+        error!("couldn't create {}: {e}", parent.display());
+        process::exit(1);
+    }
+}
+
+#[cfg(feature = "postgres")]
+async fn build_postgres_store(
+    config: &Config,
+    secrets: Option<&dyn SecretsProvider>,
+) -> Result<Arc<dyn Store>, Box<dyn Error>> {
+    let Some(url) = load_secret(&config.store.url, secrets).await else {
+        error!("store.url must be set for the postgres backend");
+        process::exit(1);
+    };
+    let store = PostgresWalletStore::open(&url, config.store.pool_size).await?;
+    Ok(Arc::new(store))
+}
+
+#[cfg(not(feature = "postgres"))]
+async fn build_postgres_store(
+    _config: &Config,
+    _secrets: Option<&dyn SecretsProvider>,
+) -> Result<Arc<dyn Store>, Box<dyn Error>> {
+    Err("the postgres backend needs a build with the `postgres` feature".into())
+}
+
+#[cfg(feature = "redis")]
+async fn build_redis_store(
+    config: &Config,
+    secrets: Option<&dyn SecretsProvider>,
+) -> Result<Arc<dyn Store>, Box<dyn Error>> {
+    let Some(url) = load_secret(&config.store.url, secrets).await else {
+        error!("store.url must be set for the redis backend");
+        process::exit(1);
+    };
+    let ttl = (!config.store.ttl.is_zero()).then_some(config.store.ttl);
+    let store = RedisWalletStore::open(&url, ttl).await?;
+    Ok(Arc::new(store))
+}
+
+#[cfg(not(feature = "redis"))]
+async fn build_redis_store(
+    _config: &Config,
+    _secrets: Option<&dyn SecretsProvider>,
+) -> Result<Arc<dyn Store>, Box<dyn Error>> {
+    Err("the redis backend needs a build with the `redis` feature".into())
+}
+
+#[cfg(feature = "sled")]
+fn build_sled_store(config: &Config) -> Result<Arc<dyn Store>, Box<dyn Error>> {
+    Ok(Arc::new(SledWalletStore::open(&config.store.path)?))
+}
+
+#[cfg(not(feature = "sled"))]
+fn build_sled_store(_config: &Config) -> Result<Arc<dyn Store>, Box<dyn Error>> {
+    Err("the sled backend needs a build with the `sled` feature".into())
+}
+
+#[cfg(feature = "rocksdb")]
+fn build_rocksdb_store(config: &Config) -> Result<Arc<dyn Store>, Box<dyn Error>> {
+    Ok(Arc::new(RocksWalletStore::open(&config.store.path)?))
+}
+
+#[cfg(not(feature = "rocksdb"))]
+fn build_rocksdb_store(_config: &Config) -> Result<Arc<dyn Store>, Box<dyn Error>> {
+    Err("the rocksdb backend needs a build with the `rocksdb` feature".into())
+}
+
+fn build_price_oracle(
+    config: &Config,
+    upstream_budget: Arc<UpstreamBudget>,
+) -> Option<Arc<CachedPriceOracle<BudgetedClient<CoinGeckoOracle>>>> {
+    match config.price.provider.as_str() {
+        "coingecko" => {
+            let oracle = CoinGeckoOracle::new(&config.price.coingecko_url).unwrap_or_else(|e| {
+                trace_error(&e);
+                process::exit(1);
+            });
+            // Only cache misses reach CoinGecko, so only they spend budget.
+            let oracle = BudgetedClient {
+                inner: Arc::new(oracle),
+                budget: upstream_budget,
+            };
+            Some(Arc::new(CachedPriceOracle::new(
+                Arc::new(oracle),
+                config.price.cache,
+            )))
+        }
+        _ => None,
+    }
+}
+
+fn build_secrets_provider(config: &Config) -> Option<Box<dyn SecretsProvider>> {
+    match config.secrets.provider.as_str() {
+        "vault" => {
+            let token = env::var(&config.secrets.vault_token_env).unwrap_or_else(|_| {
+                error!(
+                    "{} must be set to read secrets from vault",
+                    config.secrets.vault_token_env
+                );
+                process::exit(1);
+            });
+            let vault = VaultSecrets::new(&config.secrets.vault_addr, token).unwrap_or_else(|e| {
+                trace_error(&e);
+                process::exit(1);
+            });
+            Some(Box::new(vault))
+        }
+        "sops" => Some(Box::new(SopsSecrets::new(&config.secrets.sops_file))),
+        _ => None,
+    }
+}
+
+async fn load_secret(
+    source: &SecretSource,
+    secrets: Option<&dyn SecretsProvider>,
+) -> Option<String> {
+    secrets::resolve(source, secrets).await.unwrap_or_else(|e| {
+        trace_error(&e);
+        process::exit(1);
+    })
+}
+
+fn build_controller(config: &Config, dependencies: &Dependencies) -> Controller {
     let Dependencies {
         wallet_store,
+        audit_store,
         wallet_client,
+        events,
+        price_oracle,
+        enrichment_errors,
+        name_resolver,
+        history_client,
+        ..
     } = dependencies;
+    let price_oracle: Option<Arc<dyn PriceOracle>> = price_oracle.clone().map(|oracle| oracle as _);
+
+    let display = DisplayPolicy {
+        format: BalanceFormat {
+            unit: BalanceUnit::from_name(&config.display.unit).unwrap_or_default(),
+            decimals: config.display.decimals,
+            thousands_separator: config.display.thousands_separator,
+        },
+        stale_after: config.display.stale_after,
+    };
+    let challenges = Arc::new(Challenges::new(Duration::from_secs(10 * 60)));
+    let shares = Arc::new(Shares::new(
+        Duration::from_secs(24 * 60 * 60),
+        Duration::from_secs(30 * 24 * 60 * 60),
+    ));
 
     Controller {
         wallet_list: Arc::new(wallet::ListExecutor {
             wallet_store: wallet_store.clone(),
+            preference_store: wallet_store.clone(),
+            display,
+            enrichment_errors: enrichment_errors.clone(),
+            price_oracle: price_oracle.clone(),
+        }),
+        wallet_get: Arc::new(wallet::GetExecutor {
+            wallet_store: wallet_store.clone(),
+            wallet_client: wallet_client.clone(),
+            display,
+            refresh_after: config.refresh.get_after,
+            price_oracle: price_oracle.clone(),
+        }),
+        wallet_search: Arc::new(wallet::SearchExecutor {
+            wallet_store: wallet_store.clone(),
+            display,
+        }),
+        wallet_portfolio: Arc::new(wallet::PortfolioExecutor {
+            wallet_store: wallet_store.clone(),
+            display,
+            price_oracle: price_oracle.clone(),
+        }),
+        wallet_pending_sweeps: Arc::new(wallet::PendingSweepsExecutor {
+            wallet_store: wallet_store.clone(),
+            policy: sweep_policy(),
+            display,
         }),
         wallet_track: Arc::new(wallet::TrackExecutor {
             wallet_store: wallet_store.clone(),
             wallet_client: wallet_client.clone(),
+            name_resolver: name_resolver.clone(),
+            audit_store: audit_store.clone(),
+            events: events.clone(),
+            display,
+            chain_id: config.upstream.chain_id,
         }),
-        wallet_refresh: Arc::new(wallet::RefreshExecutor {
+        wallet_import: Arc::new(wallet::ImportExecutor {
             wallet_store: wallet_store.clone(),
             wallet_client: wallet_client.clone(),
+            audit_store: audit_store.clone(),
+            events: events.clone(),
+            chain_id: config.upstream.chain_id,
+            batch_balances: config.upstream.multicall,
+        }),
+        wallet_export: Arc::new(wallet::ExportExecutor {
+            wallet_store: wallet_store.clone(),
+            display,
+        }),
+        wallet_nft_holdings: (!config.nfts.erc721.is_empty()).then(|| {
+            Arc::new(wallet::NftHoldingsExecutor {
+                wallet_store: wallet_store.clone(),
+                wallet_client: wallet_client.clone(),
+                collections: config.nfts.erc721.clone(),
+                enrichment_errors: enrichment_errors.clone(),
+            }) as _
+        }),
+        wallet_transactions: history_client.as_ref().map(|history_client| {
+            Arc::new(wallet::TransactionsExecutor {
+                wallet_store: wallet_store.clone(),
+                history_client: history_client.clone(),
+                display,
+            }) as _
+        }),
+        wallet_donations: history_client.as_ref().map(|history_client| {
+            Arc::new(wallet::DonationsExecutor {
+                wallet_store: wallet_store.clone(),
+                history_client: history_client.clone(),
+                tags: config.donations.tags.clone(),
+                display,
+            }) as _
+        }),
+        wallet_history: (config.history.max_samples > 0).then(|| {
+            Arc::new(wallet::HistoryExecutor {
+                wallet_store: wallet_store.clone(),
+                history_store: wallet_store.clone(),
+                display,
+            }) as _
+        }),
+
+        wallet_update: Arc::new(wallet::UpdateExecutor {
+            wallet_store: wallet_store.clone(),
+            wallet_client: wallet_client.clone(),
+            audit_store: audit_store.clone(),
+            display,
+        }),
+        wallet_rename: Arc::new(wallet::RenameExecutor {
+            wallet_store: wallet_store.clone(),
+            audit_store: audit_store.clone(),
+            events: events.clone(),
+            display,
+        }),
+        wallet_pin: Arc::new(wallet::PinExecutor {
+            wallet_store: wallet_store.clone(),
+            preference_store: wallet_store.clone(),
+        }),
+        wallet_save_view: Arc::new(wallet::SaveViewExecutor {
+            preference_store: wallet_store.clone(),
+        }),
+        wallet_views: Arc::new(wallet::ViewsExecutor {
+            preference_store: wallet_store.clone(),
+        }),
+        wallet_delete_view: Arc::new(wallet::DeleteViewExecutor {
+            preference_store: wallet_store.clone(),
+        }),
+        wallet_share: Arc::new(wallet::ShareExecutor {
+            wallet_store: wallet_store.clone(),
+            shares: shares.clone(),
+        }),
+        wallet_shared: Arc::new(wallet::ViewSharedExecutor {
+            wallet_store: wallet_store.clone(),
+            shares,
+            display,
+        }),
+        wallet_challenge: Arc::new(wallet::ChallengeExecutor {
+            wallet_store: wallet_store.clone(),
+            challenges: challenges.clone(),
+        }),
+        wallet_verify: Arc::new(wallet::VerifyExecutor {
+            wallet_store: wallet_store.clone(),
+            challenges,
+            audit_store: audit_store.clone(),
+            display,
         }),
         wallet_untrack: Arc::new(wallet::UntrackExecutor {
             wallet_store: wallet_store.clone(),
+            trash_store: wallet_store.clone(),
+            audit_store: audit_store.clone(),
+            events: events.clone(),
+        }),
+        wallet_restore: Arc::new(wallet::RestoreExecutor {
+            wallet_store: wallet_store.clone(),
+            trash_store: wallet_store.clone(),
+            audit_store: audit_store.clone(),
+        }),
+        wallet_purge: Arc::new(wallet::PurgeExecutor {
+            trash_store: wallet_store.clone(),
+            audit_store: audit_store.clone(),
+            retention: config.trash.retention,
+        }),
+        wallet_watch: Arc::new(wallet::WatchExecutor {
+            events: events.clone(),
+        }),
+        alert_create: Arc::new(alert::CreateExecutor {
+            alert_store: wallet_store.clone(),
+            wallet_store: wallet_store.clone(),
+        }),
+        alert_list: Arc::new(alert::ListExecutor {
+            alert_store: wallet_store.clone(),
+        }),
+        alert_delete: Arc::new(alert::DeleteExecutor {
+            alert_store: wallet_store.clone(),
+        }),
+        server_info: Arc::new(admin::InfoExecutor {
+            store_admin: wallet_store.clone(),
+            client_admin: wallet_client.clone(),
+            chain: config.upstream.chain_id.name(),
+            started_at: Instant::now(),
+        }),
+    }
+}
+
+fn build_scheduler(
+    config: &Config,
+    dependencies: &Dependencies,
+    controller: &Controller,
+) -> Scheduler {
+    let Dependencies {
+        wallet_store,
+        wallet_client,
+        events,
+        enrichment_errors,
+        reverse_resolver,
+        name_resolver,
+        notifier,
+        compactions,
+        ..
+    } = dependencies;
+
+    let refresh = Arc::new(wallet::RefreshExecutor {
+        wallet_store: wallet_store.clone(),
+        wallet_client: wallet_client.clone(),
+        events: events.clone(),
+        freshness: config.refresh.freshness,
+        reverse_resolver: Some(reverse_resolver.clone()),
+        reverse_ttl: Duration::from_secs(24 * 60 * 60),
+        enrichment_errors: enrichment_errors.clone(),
+        name_resolver: Some(name_resolver.clone()),
+        resolve_ttl: Duration::from_secs(24 * 60 * 60),
+        // Without a notifier nothing would drain the outbox.
+        outbox_store: notifier.as_ref().map(|_| wallet_store.clone() as _),
+        outbox_schema_version: config.webhook.schema_version,
+        history_store: (config.history.max_samples > 0).then(|| wallet_store.clone() as _),
+        history_keep: config.history.max_samples,
+        tokens: config.tokens.erc20.clone(),
+        batch_balances: config.upstream.multicall,
+    });
+
+    let sweep = Arc::new(wallet::SweepExecutor {
+        wallet_store: wallet_store.clone(),
+        trash_store: wallet_store.clone(),
+        policy: sweep_policy(),
+    });
+
+    let collect = Arc::new(admin::CollectExecutor {
+        purge: controller.wallet_purge.clone(),
+        compact: Arc::new(admin::CompactExecutor {
+            store_admin: wallet_store.clone(),
+            compactions: compactions.clone(),
+        }),
+    });
+
+    let scheduler = Scheduler::new(refresh, collect)
+        .with_interval(config.refresh.interval)
+        .with_jitter(config.refresh.jitter)
+        .with_sweep(sweep)
+        .with_alerts(Arc::new(alert::EvaluateExecutor {
+            alert_store: wallet_store.clone(),
+        }))
+        .with_portfolio(controller.wallet_portfolio.clone())
+        .with_events(events.clone())
+        .with_compaction(
+            Arc::new(admin::CompactExecutor {
+                store_admin: wallet_store.clone(),
+                compactions: compactions.clone(),
+            }),
+            CompactionWindow {
+                start: config.compaction.window_start,
+                end: config.compaction.window_end,
+                // Once a night, without drifting later each day.
+                every: Duration::from_secs(20 * 60 * 60),
+            },
+        );
+
+    let scheduler = if config.history.max_samples > 0 {
+        scheduler.with_downsampling(Arc::new(admin::DownsampleExecutor {
+            wallet_store: wallet_store.clone(),
+            trash_store: wallet_store.clone(),
+            history_store: wallet_store.clone(),
+            tiers: config.history.retention(),
+        }))
+    } else {
+        scheduler
+    };
+
+    let scheduler = match ExportFormat::from_name(&config.report.format) {
+        Some(format) if !config.report.interval.is_zero() => scheduler.with_reports(
+            controller.wallet_export.clone(),
+            ReportSchedule {
+                every: config.report.interval,
+                format,
+                dir: config.report.dir.clone().into(),
+            },
+        ),
+        _ => scheduler,
+    };
+
+    match notifier {
+        Some(notifier) => scheduler
+            .with_notifier(notifier.clone(), config.webhook.schema_version)
+            .with_delivery(Arc::new(wallet::DeliverExecutor {
+                outbox_store: wallet_store.clone(),
+                notifier: notifier.clone(),
+                backoff: Duration::from_secs(30),
+                max_backoff: Duration::from_secs(60 * 60),
+            })),
+        None => scheduler,
+    }
+}
+
+fn sweep_policy() -> SweepPolicy {
+    SweepPolicy {
+        tag: "deposit".to_string(),
+        grace: Duration::from_secs(7 * 24 * 60 * 60),
+    }
+}
+
+fn build_admin_controller(dependencies: &Dependencies, controller: &Controller) -> AdminController {
+    let Dependencies {
+        wallet_store,
+        wallet_client,
+        compactions,
+        ..
+    } = dependencies;
+
+    AdminController {
+        admin_stats: Arc::new(admin::StatsExecutor {
+            store_admin: wallet_store.clone(),
+            compactions: compactions.clone(),
+        }),
+        admin_compact: Arc::new(admin::CompactExecutor {
+            store_admin: wallet_store.clone(),
+            compactions: compactions.clone(),
+        }),
+        admin_collect: Arc::new(admin::CollectExecutor {
+            purge: controller.wallet_purge.clone(),
+            compact: Arc::new(admin::CompactExecutor {
+                store_admin: wallet_store.clone(),
+                compactions: compactions.clone(),
+            }),
+        }),
+        admin_backup: Arc::new(admin::BackupExecutor {
+            store_admin: wallet_store.clone(),
+        }),
+        admin_snapshot: Arc::new(admin::SnapshotExecutor {
+            store_admin: wallet_store.clone(),
+        }),
+        admin_digest: Arc::new(admin::DigestExecutor {
+            wallet_store: wallet_store.clone(),
+            trash_store: wallet_store.clone(),
+        }),
+        admin_endpoint: Arc::new(admin::EndpointExecutor {
+            client_admin: wallet_client.clone(),
         }),
     }
 }
 
 fn trace_error(error: &dyn Error) {
+    error!("{}", compose_error(error));
+}
+
+fn compose_error(error: &dyn Error) -> String {
     let mut composed = error.to_string();
 
     let mut next: &dyn Error = &error;
@@ -97,5 +911,5 @@ fn trace_error(error: &dyn Error) {
         next = source;
     }
 
-    error!("{composed}");
+    composed
 }
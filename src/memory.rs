@@ -0,0 +1,416 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use tokio::sync::RwLock;
+
+use crate::{
+    core::{Address, ChainId, Sample},
+    infra::{
+        AlertRule, AlertStore, AuditEntry, AuditStore, HistoryStore, Insertion, OutboxEntry,
+        OutboxStore, PreferenceStore, SavedView, StoreAdmin, StoreError, StoreInfo, StoreStats,
+        TrashStore, TrashedRecord, WalletChange, WalletRecord, WalletStore, WalletUpdate,
+        plan_insertions_among, search_records,
+    },
+};
+
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryWalletStore {
+    data: Arc<RwLock<MemoryData>>,
+}
+
+#[derive(Debug, Default)]
+struct MemoryData {
+    wallets: BTreeMap<String, WalletRecord>,
+    trash: BTreeMap<String, TrashedRecord>,
+    alerts: BTreeMap<String, AlertRule>,
+    pins: BTreeMap<String, Vec<String>>,
+    views: BTreeMap<String, BTreeMap<String, SavedView>>,
+    outbox: BTreeMap<String, OutboxEntry>,
+    history: BTreeMap<String, Vec<Sample>>,
+}
+
+impl InMemoryWalletStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl WalletStore for InMemoryWalletStore {
+    async fn find(&self, name: &str) -> Result<Option<WalletRecord>, StoreError> {
+        Ok(self.data.read().await.wallets.get(name).cloned())
+    }
+
+    async fn all(&self) -> Result<BTreeMap<String, WalletRecord>, StoreError> {
+        Ok(self.data.read().await.wallets.clone())
+    }
+
+    async fn exists(&self, name: &str) -> Result<bool, StoreError> {
+        Ok(self.data.read().await.wallets.contains_key(name))
+    }
+
+    async fn save(&self, name: &str, record: &WalletRecord) -> Result<(), StoreError> {
+        let mut data = self.data.write().await;
+        data.wallets.insert(name.to_owned(), record.clone());
+        Ok(())
+    }
+
+    async fn save_many(&self, wallets: &[(String, WalletRecord)]) -> Result<(), StoreError> {
+        let mut data = self.data.write().await;
+        data.wallets.extend(wallets.iter().cloned());
+        Ok(())
+    }
+
+    async fn delete(&self, name: &str) -> Result<(), StoreError> {
+        self.data.write().await.wallets.remove(name);
+        Ok(())
+    }
+
+    async fn delete_many(&self, names: &[String]) -> Result<(), StoreError> {
+        let mut data = self.data.write().await;
+        for name in names {
+            data.wallets.remove(name);
+        }
+        Ok(())
+    }
+
+    async fn update(
+        &self,
+        name: &str,
+        mut update: WalletUpdate,
+    ) -> Result<Option<WalletRecord>, StoreError> {
+        let mut data = self.data.write().await;
+        let Some(record) = update(data.wallets.get(name).cloned()) else {
+            return Ok(None);
+        };
+        data.wallets.insert(name.to_owned(), record.clone());
+        Ok(Some(record))
+    }
+
+    async fn find_by_address(
+        &self,
+        chain_id: ChainId,
+        address: &Address,
+    ) -> Result<Option<(String, WalletRecord)>, StoreError> {
+        let data = self.data.read().await;
+        let found = data
+            .wallets
+            .iter()
+            .find(|(_, record)| {
+                record.wallet.chain_id() == chain_id && record.wallet.address() == address
+            })
+            .map(|(name, record)| (name.clone(), record.clone()));
+        Ok(found)
+    }
+
+    async fn count(&self) -> Result<usize, StoreError> {
+        Ok(self.data.read().await.wallets.len())
+    }
+
+    async fn insert_many(
+        &self,
+        wallets: &[(String, WalletRecord)],
+    ) -> Result<Vec<Insertion>, StoreError> {
+        let mut data = self.data.write().await;
+        let insertions = plan_insertions_among(wallets, &data.wallets);
+        for ((name, record), insertion) in wallets.iter().zip(&insertions) {
+            if *insertion == Insertion::Saved {
+                data.wallets.insert(name.clone(), record.clone());
+            }
+        }
+        Ok(insertions)
+    }
+
+    async fn search(
+        &self,
+        terms: &[String],
+        limit: usize,
+    ) -> Result<Vec<(String, WalletRecord)>, StoreError> {
+        Ok(search_records(self.all().await?, terms, limit))
+    }
+
+    async fn transaction(&self, changes: &[WalletChange]) -> Result<(), StoreError> {
+        let mut data = self.data.write().await;
+        for change in changes {
+            match change {
+                WalletChange::Save { name, record } => {
+                    data.wallets.insert(name.clone(), (**record).clone());
+                }
+                WalletChange::Delete { name } => {
+                    data.wallets.remove(name);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TrashStore for InMemoryWalletStore {
+    async fn find_trashed(&self, name: &str) -> Result<Option<TrashedRecord>, StoreError> {
+        Ok(self.data.read().await.trash.get(name).cloned())
+    }
+
+    async fn all_trashed(&self) -> Result<BTreeMap<String, TrashedRecord>, StoreError> {
+        Ok(self.data.read().await.trash.clone())
+    }
+
+    async fn trash(&self, name: &str) -> Result<(), StoreError> {
+        let mut data = self.data.write().await;
+        if let Some(record) = data.wallets.remove(name) {
+            let trashed = TrashedRecord {
+                record,
+                trashed_at: Utc::now(),
+            };
+            data.trash.insert(name.to_owned(), trashed);
+        }
+        Ok(())
+    }
+
+    async fn restore(&self, name: &str) -> Result<(), StoreError> {
+        let mut data = self.data.write().await;
+        if let Some(trashed) = data.trash.remove(name) {
+            data.wallets.insert(name.to_owned(), trashed.record);
+        }
+        Ok(())
+    }
+
+    async fn purge(&self, name: &str) -> Result<(), StoreError> {
+        let mut data = self.data.write().await;
+        if let Some(trashed) = data.trash.remove(name) {
+            data.history.remove(&trashed.record.id);
+        }
+        Ok(())
+    }
+    async fn save_trashed(&self, name: &str, trashed: &TrashedRecord) -> Result<(), StoreError> {
+        let mut data = self.data.write().await;
+        data.trash.insert(name.to_owned(), trashed.clone());
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AlertStore for InMemoryWalletStore {
+    async fn all_alerts(&self) -> Result<Vec<AlertRule>, StoreError> {
+        Ok(self.data.read().await.alerts.values().cloned().collect())
+    }
+
+    async fn save_alert(&self, rule: &AlertRule) -> Result<(), StoreError> {
+        let mut data = self.data.write().await;
+        data.alerts.insert(rule.id.clone(), rule.clone());
+        Ok(())
+    }
+
+    async fn delete_alert(&self, id: &str) -> Result<bool, StoreError> {
+        Ok(self.data.write().await.alerts.remove(id).is_some())
+    }
+}
+
+#[async_trait]
+impl PreferenceStore for InMemoryWalletStore {
+    async fn pinned(&self, principal: &str) -> Result<Vec<String>, StoreError> {
+        let data = self.data.read().await;
+        Ok(data.pins.get(principal).cloned().unwrap_or_default())
+    }
+
+    async fn set_pinned(&self, principal: &str, wallet_ids: &[String]) -> Result<(), StoreError> {
+        let mut data = self.data.write().await;
+        if wallet_ids.is_empty() {
+            data.pins.remove(principal);
+        } else {
+            data.pins.insert(principal.to_owned(), wallet_ids.to_vec());
+        }
+        Ok(())
+    }
+
+    async fn views(&self, principal: &str) -> Result<Vec<SavedView>, StoreError> {
+        let data = self.data.read().await;
+        let views = data
+            .views
+            .get(principal)
+            .map(|views| views.values().cloned().collect())
+            .unwrap_or_default();
+        Ok(views)
+    }
+
+    async fn save_view(&self, principal: &str, view: &SavedView) -> Result<(), StoreError> {
+        let mut data = self.data.write().await;
+        data.views
+            .entry(principal.to_owned())
+            .or_default()
+            .insert(view.name.clone(), view.clone());
+        Ok(())
+    }
+
+    async fn delete_view(&self, principal: &str, name: &str) -> Result<bool, StoreError> {
+        let mut data = self.data.write().await;
+        let Some(views) = data.views.get_mut(principal) else {
+            return Ok(false);
+        };
+        let existed = views.remove(name).is_some();
+        if views.is_empty() {
+            data.views.remove(principal);
+        }
+        Ok(existed)
+    }
+}
+
+#[async_trait]
+impl OutboxStore for InMemoryWalletStore {
+    async fn save_with_outbox(
+        &self,
+        name: &str,
+        record: &WalletRecord,
+        entry: &OutboxEntry,
+    ) -> Result<(), StoreError> {
+        let mut data = self.data.write().await;
+        data.wallets.insert(name.to_owned(), record.clone());
+        data.outbox.insert(entry.id.clone(), entry.clone());
+        Ok(())
+    }
+
+    async fn outbox(&self) -> Result<Vec<OutboxEntry>, StoreError> {
+        Ok(self.data.read().await.outbox.values().cloned().collect())
+    }
+
+    async fn ack(&self, id: &str) -> Result<(), StoreError> {
+        self.data.write().await.outbox.remove(id);
+        Ok(())
+    }
+
+    async fn reschedule(
+        &self,
+        id: &str,
+        attempts: u32,
+        next_attempt_at: DateTime<Utc>,
+    ) -> Result<(), StoreError> {
+        let mut data = self.data.write().await;
+        if let Some(entry) = data.outbox.get_mut(id) {
+            entry.attempts = attempts;
+            entry.next_attempt_at = next_attempt_at;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl HistoryStore for InMemoryWalletStore {
+    async fn append_samples(
+        &self,
+        samples: &[(String, Sample)],
+        keep: usize,
+    ) -> Result<(), StoreError> {
+        let mut data = self.data.write().await;
+        for (wallet_id, sample) in samples {
+            let history = data.history.entry(wallet_id.clone()).or_default();
+            history.push(*sample);
+            let excess = history.len().saturating_sub(keep);
+            history.drain(..excess);
+        }
+        Ok(())
+    }
+
+    async fn replace_history(&self, wallet_id: &str, samples: &[Sample]) -> Result<(), StoreError> {
+        let mut data = self.data.write().await;
+        if samples.is_empty() {
+            data.history.remove(wallet_id);
+        } else {
+            data.history.insert(wallet_id.to_owned(), samples.to_vec());
+        }
+        Ok(())
+    }
+
+    async fn history(&self, wallet_id: &str) -> Result<Vec<Sample>, StoreError> {
+        let data = self.data.read().await;
+        Ok(data.history.get(wallet_id).cloned().unwrap_or_default())
+    }
+}
+
+#[async_trait]
+impl StoreAdmin for InMemoryWalletStore {
+    fn describe(&self) -> StoreInfo {
+        StoreInfo {
+            backend: "memory".to_string(),
+            location: String::new(),
+            recovered_from: None,
+        }
+    }
+
+    async fn stats(&self) -> Result<StoreStats, StoreError> {
+        let data = self.data.read().await;
+        Ok(StoreStats {
+            entries: data.wallets.len(),
+            trashed: data.trash.len(),
+            size_bytes: 0,
+            ..Default::default()
+        })
+    }
+
+    async fn compact(&self) -> Result<(), StoreError> {
+        Ok(())
+    }
+
+    async fn backup(&self) -> Result<String, StoreError> {
+        Err(StoreError(
+            "an in-memory store has nowhere to back up to".into(),
+        ))
+    }
+
+    async fn snapshot(&self, _dest: &str) -> Result<(), StoreError> {
+        self.backup().await.map(|_| ())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryAuditStore {
+    entries: Arc<RwLock<Vec<AuditEntry>>>,
+}
+
+impl InMemoryAuditStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl AuditStore for InMemoryAuditStore {
+    async fn append(&self, entry: &AuditEntry) -> Result<(), StoreError> {
+        self.entries.write().await.push(entry.clone());
+        Ok(())
+    }
+
+    async fn audit_log(&self) -> Result<Vec<AuditEntry>, StoreError> {
+        Ok(self.entries.read().await.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr, sync::Arc};
+
+    use super::InMemoryWalletStore;
+    use crate::{
+        core::{Address, Wallet},
+        infra::{Store, TrashStore, WalletRecord, WalletStore},
+    };
+
+    #[tokio::test]
+    async fn memory_trash_and_restore() {
+        let store = InMemoryWalletStore::new();
+        let address = Address::from_str("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045").unwrap();
+        store
+            .save("Vault", &WalletRecord::new(Wallet::new(address)))
+            .await
+            .unwrap();
+
+        // Clones share data, so one can go to the executors as a `dyn Store`.
+        let shared: Arc<dyn Store> = Arc::new(store.clone());
+        shared.trash("Vault").await.unwrap();
+        assert!(!store.exists("Vault").await.unwrap());
+        assert!(store.find_trashed("Vault").await.unwrap().is_some());
+
+        store.restore("Vault").await.unwrap();
+        assert!(shared.exists("Vault").await.unwrap());
+    }
+}
@@ -0,0 +1,294 @@
+use serde_json::{Value, json};
+
+use crate::{core::Balance, infra::Notification, wallet::WalletEvent};
+
+pub const SCHEMA_VERSION: u32 = 2;
+pub const SCHEMA_VERSIONS: &[u32] = &[1, 2];
+
+pub fn render(event: &WalletEvent, schema_version: u32) -> Option<Value> {
+    if !SCHEMA_VERSIONS.contains(&schema_version) {
+        return None;
+    }
+
+    let payload = match event {
+        WalletEvent::Tracked { wallet_id, name } => json!({
+            "schema_version": schema_version,
+            "event": "tracked",
+            "wallet": wallet(schema_version, wallet_id, name),
+        }),
+        WalletEvent::Untracked { wallet_id, name } => json!({
+            "schema_version": schema_version,
+            "event": "untracked",
+            "wallet": wallet(schema_version, wallet_id, name),
+        }),
+        WalletEvent::Renamed {
+            wallet_id,
+            from,
+            to,
+        } => json!({
+            "schema_version": schema_version,
+            "event": "renamed",
+            "wallet": wallet(schema_version, wallet_id, to),
+            "from": from,
+        }),
+        WalletEvent::BalanceChanged(change) => balance_changed(
+            schema_version,
+            &change.wallet_id,
+            &change.name,
+            change.before,
+            change.after,
+        ),
+        WalletEvent::AddressChanged {
+            wallet_id,
+            name,
+            from,
+            to,
+        } => json!({
+            "schema_version": schema_version,
+            "event": "address_changed",
+            "wallet": wallet(schema_version, wallet_id, name),
+            "from": from.to_string(),
+            "to": to.to_string(),
+        }),
+    };
+
+    Some(payload)
+}
+
+pub fn render_notification(notification: &Notification, schema_version: u32) -> Option<Value> {
+    if !SCHEMA_VERSIONS.contains(&schema_version) {
+        return None;
+    }
+
+    let payload = match notification {
+        Notification::BalanceChanged {
+            wallet_id,
+            name,
+            before,
+            after,
+        } => balance_changed(schema_version, wallet_id, name, *before, *after),
+        Notification::AlertFired {
+            rule_id,
+            wallet_id,
+            name,
+            before,
+            after,
+        } => json!({
+            "schema_version": schema_version,
+            "event": "alert_fired",
+            "rule_id": rule_id,
+            "wallet": wallet(schema_version, wallet_id, name),
+            "before_wei": before.to_string(),
+            "after_wei": after.to_string(),
+        }),
+    };
+
+    Some(payload)
+}
+
+fn balance_changed(
+    schema_version: u32,
+    wallet_id: &str,
+    name: &str,
+    before: Balance,
+    after: Balance,
+) -> Value {
+    json!({
+        "schema_version": schema_version,
+        "event": "balance_changed",
+        "wallet": wallet(schema_version, wallet_id, name),
+        "before_wei": before.to_string(),
+        "after_wei": after.to_string(),
+    })
+}
+
+fn wallet(schema_version: u32, wallet_id: &str, name: &str) -> Value {
+    match schema_version {
+        1 => json!(name),
+        _ => json!({ "id": wallet_id, "name": name }),
+    }
+}
+
+pub fn schema(schema_version: u32) -> Option<Value> {
+    if !SCHEMA_VERSIONS.contains(&schema_version) {
+        return None;
+    }
+
+    let wei = json!({ "type": "string", "pattern": "^[0-9]+$" });
+    let wallet = match schema_version {
+        1 => json!({ "type": "string" }),
+        _ => json!({
+            "type": "object",
+            "properties": {
+                "id": { "type": "string" },
+                "name": { "type": "string" },
+            },
+            "required": ["id", "name"],
+        }),
+    };
+    let event = |event: &str, properties: Value| {
+        let mut schema = json!({
+            "type": "object",
+            "properties": {
+                "schema_version": { "const": schema_version },
+                "event": { "const": event },
+                "wallet": wallet,
+            },
+            "required": ["schema_version", "event", "wallet"],
+        });
+        for (name, property) in properties.as_object().into_iter().flatten() {
+            schema["properties"][name] = property.clone();
+            schema["required"].as_array_mut().unwrap().push(json!(name));
+        }
+        schema
+    };
+    let events = [
+        event("tracked", json!({})),
+        event("untracked", json!({})),
+        event("renamed", json!({ "from": { "type": "string" } })),
+        event(
+            "address_changed",
+            json!({ "from": { "type": "string" }, "to": { "type": "string" } }),
+        ),
+        event(
+            "balance_changed",
+            json!({ "before_wei": wei, "after_wei": wei }),
+        ),
+        event(
+            "alert_fired",
+            json!({ "rule_id": { "type": "string" }, "before_wei": wei, "after_wei": wei }),
+        ),
+    ];
+
+    Some(json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "$id": format!("/schemas/events/{schema_version}"),
+        "title": "mini-wallet event",
+        "oneOf": events,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{SCHEMA_VERSIONS, render, render_notification, schema};
+    use crate::{
+        core::Balance,
+        infra::Notification,
+        wallet::{BalanceChange, WalletEvent},
+    };
+
+    fn balance_changed() -> WalletEvent {
+        WalletEvent::BalanceChanged(BalanceChange {
+            wallet_id: "w1".to_string(),
+            name: "Hot".to_string(),
+            before: Balance::new(1),
+            after: Balance::new(2),
+        })
+    }
+
+    #[test]
+    fn payload_render_v1() {
+        assert_eq!(
+            render(&balance_changed(), 1),
+            Some(json!({
+                "schema_version": 1,
+                "event": "balance_changed",
+                "wallet": "Hot",
+                "before_wei": "1",
+                "after_wei": "2",
+            }))
+        );
+        assert_eq!(render(&balance_changed(), 0), None);
+    }
+
+    #[test]
+    fn payload_render_v2() {
+        assert_eq!(
+            render(&balance_changed(), 2),
+            Some(json!({
+                "schema_version": 2,
+                "event": "balance_changed",
+                "wallet": { "id": "w1", "name": "Hot" },
+                "before_wei": "1",
+                "after_wei": "2",
+            }))
+        );
+    }
+
+    fn alert_fired() -> Notification {
+        Notification::AlertFired {
+            rule_id: "r1".to_string(),
+            wallet_id: "w1".to_string(),
+            name: "Cold".to_string(),
+            before: Balance::new(2_000),
+            after: Balance::new(500),
+        }
+    }
+
+    #[test]
+    fn payload_render_notification() {
+        assert_eq!(
+            render_notification(&alert_fired(), 1),
+            Some(json!({
+                "schema_version": 1,
+                "event": "alert_fired",
+                "rule_id": "r1",
+                "wallet": "Cold",
+                "before_wei": "2000",
+                "after_wei": "500",
+            }))
+        );
+        assert_eq!(
+            render_notification(&alert_fired(), 2).unwrap()["wallet"],
+            json!({ "id": "w1", "name": "Cold" })
+        );
+    }
+
+    #[test]
+    fn payload_schema_matches_render() {
+        let renamed = WalletEvent::Renamed {
+            wallet_id: "w1".to_string(),
+            from: "Hot".to_string(),
+            to: "Warm".to_string(),
+        };
+        let bodies = |version| {
+            [
+                render(&balance_changed(), version).unwrap(),
+                render(&renamed, version).unwrap(),
+                render_notification(&alert_fired(), version).unwrap(),
+            ]
+        };
+        for (version, body) in SCHEMA_VERSIONS
+            .iter()
+            .flat_map(|&version| bodies(version).map(|body| (version, body)))
+        {
+            let schema = schema(version).unwrap();
+            let event = schema["oneOf"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|event| event["properties"]["event"]["const"] == body["event"])
+                .unwrap();
+
+            let mut fields: Vec<&str> = body
+                .as_object()
+                .unwrap()
+                .keys()
+                .map(String::as_str)
+                .collect();
+            let mut required: Vec<&str> = event["required"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|field| field.as_str().unwrap())
+                .collect();
+            fields.sort_unstable();
+            required.sort_unstable();
+            assert_eq!(fields, required);
+            assert_eq!(event["properties"]["schema_version"]["const"], version);
+        }
+        assert!(schema(0).is_none());
+    }
+}
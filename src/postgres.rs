@@ -0,0 +1,733 @@
+use std::{collections::BTreeMap, error, fmt};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{
+    Error as SqlxError, PgPool,
+    migrate::{MigrateError, Migrator},
+    postgres::PgPoolOptions,
+};
+use tracing::{info, instrument};
+
+use crate::{
+    core::{Address, ChainId, Sample},
+    fs::{FsError, blob},
+    infra::{
+        AlertRule, AlertStore, HistoryStore, Insertion, OutboxEntry, OutboxStore, PreferenceStore,
+        SavedView, StoreAdmin, StoreError, StoreInfo, StoreStats, TrashStore, TrashedRecord,
+        WalletChange, WalletRecord, WalletStore, WalletUpdate, plan_insertions, search_records,
+    },
+};
+
+static MIGRATOR: Migrator = sqlx::migrate!("migrations/postgres");
+
+#[derive(Debug)]
+pub struct PostgresError(Box<dyn error::Error + Send + Sync + 'static>);
+
+impl fmt::Display for PostgresError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "postgres store error")
+    }
+}
+
+impl error::Error for PostgresError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&*self.0)
+    }
+}
+
+impl From<SqlxError> for PostgresError {
+    fn from(error: SqlxError) -> Self {
+        Self(error.into())
+    }
+}
+
+impl From<MigrateError> for PostgresError {
+    fn from(error: MigrateError) -> Self {
+        Self(error.into())
+    }
+}
+
+impl From<FsError> for PostgresError {
+    fn from(error: FsError) -> Self {
+        Self(error.into())
+    }
+}
+
+impl From<PostgresError> for StoreError {
+    fn from(error: PostgresError) -> Self {
+        Self(error.into())
+    }
+}
+
+#[derive(Clone)]
+pub struct PostgresWalletStore {
+    pool: PgPool,
+}
+
+impl fmt::Debug for PostgresWalletStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The connection URL may carry a password.
+        f.debug_struct("PostgresWalletStore").finish()
+    }
+}
+
+impl PostgresWalletStore {
+    #[instrument(skip(url))]
+    pub async fn open(url: &str, max_connections: u32) -> Result<Self, PostgresError> {
+        let pool = PgPoolOptions::new()
+            .max_connections(max_connections)
+            .connect(url)
+            .await?;
+
+        MIGRATOR.run(&pool).await?;
+        info!("opened postgres wallet store");
+        Ok(Self { pool })
+    }
+
+    async fn upsert_wallet<'e, E>(
+        executor: E,
+        name: &str,
+        record: &WalletRecord,
+    ) -> Result<(), PostgresError>
+    where
+        E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query(
+            "INSERT INTO wallets (name, id, address, record) VALUES ($1, $2, $3, $4)
+             ON CONFLICT (name) DO UPDATE
+             SET id = excluded.id, address = excluded.address, record = excluded.record",
+        )
+        .bind(name)
+        .bind(&record.id)
+        .bind(record.wallet.address().inner().as_slice())
+        .bind(blob::from_record(record)?)
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl WalletStore for PostgresWalletStore {
+    async fn find(&self, name: &str) -> Result<Option<WalletRecord>, StoreError> {
+        let record: Option<Vec<u8>> =
+            sqlx::query_scalar("SELECT record FROM wallets WHERE name = $1")
+                .bind(name)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(PostgresError::from)?;
+        let record = record
+            .map(|bytes| blob::to_record(&bytes))
+            .transpose()
+            .map_err(PostgresError::from)?;
+        Ok(record)
+    }
+
+    async fn all(&self) -> Result<BTreeMap<String, WalletRecord>, StoreError> {
+        let rows: Vec<(String, Vec<u8>)> = sqlx::query_as("SELECT name, record FROM wallets")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(PostgresError::from)?;
+        let wallets = rows
+            .into_iter()
+            .map(|(name, bytes)| Ok((name, blob::to_record(&bytes)?)))
+            .collect::<Result<_, FsError>>()
+            .map_err(PostgresError::from)?;
+        Ok(wallets)
+    }
+
+    async fn exists(&self, name: &str) -> Result<bool, StoreError> {
+        let found: bool =
+            sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM wallets WHERE name = $1)")
+                .bind(name)
+                .fetch_one(&self.pool)
+                .await
+                .map_err(PostgresError::from)?;
+        Ok(found)
+    }
+
+    async fn save(&self, name: &str, record: &WalletRecord) -> Result<(), StoreError> {
+        Self::upsert_wallet(&self.pool, name, record).await?;
+        Ok(())
+    }
+
+    async fn save_many(&self, wallets: &[(String, WalletRecord)]) -> Result<(), StoreError> {
+        let mut tx = self.pool.begin().await.map_err(PostgresError::from)?;
+        for (name, record) in wallets {
+            Self::upsert_wallet(&mut *tx, name, record).await?;
+        }
+        tx.commit().await.map_err(PostgresError::from)?;
+        Ok(())
+    }
+
+    async fn delete(&self, name: &str) -> Result<(), StoreError> {
+        sqlx::query("DELETE FROM wallets WHERE name = $1")
+            .bind(name)
+            .execute(&self.pool)
+            .await
+            .map_err(PostgresError::from)?;
+        Ok(())
+    }
+
+    async fn delete_many(&self, names: &[String]) -> Result<(), StoreError> {
+        sqlx::query("DELETE FROM wallets WHERE name = ANY($1)")
+            .bind(names)
+            .execute(&self.pool)
+            .await
+            .map_err(PostgresError::from)?;
+        Ok(())
+    }
+
+    async fn update(
+        &self,
+        name: &str,
+        mut update: WalletUpdate,
+    ) -> Result<Option<WalletRecord>, StoreError> {
+        let mut tx = self.pool.begin().await.map_err(PostgresError::from)?;
+        // Row locks can't cover a wallet that doesn't exist yet, so the
+        // name itself is locked until the transaction ends.
+        sqlx::query("SELECT pg_advisory_xact_lock(hashtext($1))")
+            .bind(name)
+            .execute(&mut *tx)
+            .await
+            .map_err(PostgresError::from)?;
+        let current: Option<Vec<u8>> =
+            sqlx::query_scalar("SELECT record FROM wallets WHERE name = $1")
+                .bind(name)
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(PostgresError::from)?;
+        let current = current
+            .map(|bytes| blob::to_record(&bytes))
+            .transpose()
+            .map_err(PostgresError::from)?;
+        let Some(record) = update(current) else {
+            return Ok(None);
+        };
+        Self::upsert_wallet(&mut *tx, name, &record).await?;
+        tx.commit().await.map_err(PostgresError::from)?;
+        Ok(Some(record))
+    }
+
+    async fn find_by_address(
+        &self,
+        chain_id: ChainId,
+        address: &Address,
+    ) -> Result<Option<(String, WalletRecord)>, StoreError> {
+        let rows: Vec<(String, Vec<u8>)> =
+            sqlx::query_as("SELECT name, record FROM wallets WHERE address = $1 ORDER BY name")
+                .bind(address.inner().as_slice())
+                .fetch_all(&self.pool)
+                .await
+                .map_err(PostgresError::from)?;
+        for (name, bytes) in rows {
+            let record = blob::to_record(&bytes).map_err(PostgresError::from)?;
+            if record.wallet.chain_id() == chain_id {
+                return Ok(Some((name, record)));
+            }
+        }
+        Ok(None)
+    }
+
+    async fn count(&self) -> Result<usize, StoreError> {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM wallets")
+            .fetch_one(&self.pool)
+            .await
+            .map_err(PostgresError::from)?;
+        Ok(count as usize)
+    }
+
+    async fn insert_many(
+        &self,
+        wallets: &[(String, WalletRecord)],
+    ) -> Result<Vec<Insertion>, StoreError> {
+        let mut tx = self.pool.begin().await.map_err(PostgresError::from)?;
+        // One insert at a time checks for duplicates, and each name is
+        // locked the way `update` locks it.
+        sqlx::query("SELECT pg_advisory_xact_lock(hashtext('wallets:insert'))")
+            .execute(&mut *tx)
+            .await
+            .map_err(PostgresError::from)?;
+        let mut names: Vec<_> = wallets.iter().map(|(name, _)| name.as_str()).collect();
+        names.sort_unstable();
+        names.dedup();
+        for name in names {
+            sqlx::query("SELECT pg_advisory_xact_lock(hashtext($1))")
+                .bind(name)
+                .execute(&mut *tx)
+                .await
+                .map_err(PostgresError::from)?;
+        }
+
+        let mut lookups = Vec::with_capacity(wallets.len());
+        for (name, record) in wallets {
+            let name_taken: bool =
+                sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM wallets WHERE name = $1)")
+                    .bind(name)
+                    .fetch_one(&mut *tx)
+                    .await
+                    .map_err(PostgresError::from)?;
+            let rows: Vec<(String, Vec<u8>)> =
+                sqlx::query_as("SELECT name, record FROM wallets WHERE address = $1 ORDER BY name")
+                    .bind(record.wallet.address().inner().as_slice())
+                    .fetch_all(&mut *tx)
+                    .await
+                    .map_err(PostgresError::from)?;
+            let mut holder = None;
+            for (stored, bytes) in rows {
+                let stored_record = blob::to_record(&bytes).map_err(PostgresError::from)?;
+                if stored_record.wallet.chain_id() == record.wallet.chain_id() {
+                    holder = Some(stored);
+                    break;
+                }
+            }
+            lookups.push((name_taken, holder));
+        }
+        let insertions = plan_insertions(wallets, lookups);
+
+        for ((name, record), insertion) in wallets.iter().zip(&insertions) {
+            if *insertion == Insertion::Saved {
+                Self::upsert_wallet(&mut *tx, name, record).await?;
+            }
+        }
+        tx.commit().await.map_err(PostgresError::from)?;
+        Ok(insertions)
+    }
+
+    async fn search(
+        &self,
+        terms: &[String],
+        limit: usize,
+    ) -> Result<Vec<(String, WalletRecord)>, StoreError> {
+        Ok(search_records(self.all().await?, terms, limit))
+    }
+
+    async fn transaction(&self, changes: &[WalletChange]) -> Result<(), StoreError> {
+        let mut tx = self.pool.begin().await.map_err(PostgresError::from)?;
+        for change in changes {
+            match change {
+                WalletChange::Save { name, record } => {
+                    Self::upsert_wallet(&mut *tx, name, record).await?;
+                }
+                WalletChange::Delete { name } => {
+                    sqlx::query("DELETE FROM wallets WHERE name = $1")
+                        .bind(name)
+                        .execute(&mut *tx)
+                        .await
+                        .map_err(PostgresError::from)?;
+                }
+            }
+        }
+        tx.commit().await.map_err(PostgresError::from)?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TrashStore for PostgresWalletStore {
+    async fn find_trashed(&self, name: &str) -> Result<Option<TrashedRecord>, StoreError> {
+        let row: Option<(Vec<u8>, i64)> =
+            sqlx::query_as("SELECT record, trashed_at FROM trash WHERE name = $1")
+                .bind(name)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(PostgresError::from)?;
+        let trashed = row
+            .map(|(bytes, trashed_at)| blob::to_trashed(&bytes, trashed_at))
+            .transpose()
+            .map_err(PostgresError::from)?;
+        Ok(trashed)
+    }
+
+    async fn all_trashed(&self) -> Result<BTreeMap<String, TrashedRecord>, StoreError> {
+        let rows: Vec<(String, Vec<u8>, i64)> =
+            sqlx::query_as("SELECT name, record, trashed_at FROM trash")
+                .fetch_all(&self.pool)
+                .await
+                .map_err(PostgresError::from)?;
+        let trashed = rows
+            .into_iter()
+            .map(|(name, bytes, trashed_at)| Ok((name, blob::to_trashed(&bytes, trashed_at)?)))
+            .collect::<Result<_, FsError>>()
+            .map_err(PostgresError::from)?;
+        Ok(trashed)
+    }
+
+    async fn trash(&self, name: &str) -> Result<(), StoreError> {
+        sqlx::query(
+            "WITH moved AS (DELETE FROM wallets WHERE name = $1 RETURNING *)
+             INSERT INTO trash (name, id, address, record, trashed_at)
+             SELECT name, id, address, record, $2 FROM moved
+             ON CONFLICT (name) DO UPDATE
+             SET id = excluded.id, address = excluded.address, record = excluded.record,
+                 trashed_at = excluded.trashed_at",
+        )
+        .bind(name)
+        .bind(Utc::now().timestamp())
+        .execute(&self.pool)
+        .await
+        .map_err(PostgresError::from)?;
+        Ok(())
+    }
+
+    async fn restore(&self, name: &str) -> Result<(), StoreError> {
+        sqlx::query(
+            "WITH moved AS (DELETE FROM trash WHERE name = $1 RETURNING *)
+             INSERT INTO wallets (name, id, address, record)
+             SELECT name, id, address, record FROM moved
+             ON CONFLICT (name) DO UPDATE
+             SET id = excluded.id, address = excluded.address, record = excluded.record",
+        )
+        .bind(name)
+        .execute(&self.pool)
+        .await
+        .map_err(PostgresError::from)?;
+        Ok(())
+    }
+
+    async fn purge(&self, name: &str) -> Result<(), StoreError> {
+        sqlx::query(
+            "WITH purged AS (DELETE FROM trash WHERE name = $1 RETURNING id)
+             DELETE FROM history WHERE wallet_id IN (SELECT id FROM purged)",
+        )
+        .bind(name)
+        .execute(&self.pool)
+        .await
+        .map_err(PostgresError::from)?;
+        Ok(())
+    }
+    async fn save_trashed(&self, name: &str, trashed: &TrashedRecord) -> Result<(), StoreError> {
+        let record = &trashed.record;
+        sqlx::query(
+            "INSERT INTO trash (name, id, address, record, trashed_at)
+             VALUES ($1, $2, $3, $4, $5)
+             ON CONFLICT (name) DO UPDATE
+             SET id = excluded.id, address = excluded.address, record = excluded.record,
+                 trashed_at = excluded.trashed_at",
+        )
+        .bind(name)
+        .bind(&record.id)
+        .bind(record.wallet.address().inner().as_slice())
+        .bind(blob::from_record(record)?)
+        .bind(trashed.trashed_at.timestamp())
+        .execute(&self.pool)
+        .await
+        .map_err(PostgresError::from)?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AlertStore for PostgresWalletStore {
+    async fn all_alerts(&self) -> Result<Vec<AlertRule>, StoreError> {
+        let rules: Vec<Vec<u8>> = sqlx::query_scalar("SELECT rule FROM alerts ORDER BY id")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(PostgresError::from)?;
+        let rules = rules
+            .iter()
+            .map(|bytes| blob::to_alert(bytes))
+            .collect::<Result<_, _>>()
+            .map_err(PostgresError::from)?;
+        Ok(rules)
+    }
+
+    async fn save_alert(&self, rule: &AlertRule) -> Result<(), StoreError> {
+        sqlx::query(
+            "INSERT INTO alerts (id, rule) VALUES ($1, $2)
+             ON CONFLICT (id) DO UPDATE SET rule = excluded.rule",
+        )
+        .bind(&rule.id)
+        .bind(blob::from_alert(rule).map_err(PostgresError::from)?)
+        .execute(&self.pool)
+        .await
+        .map_err(PostgresError::from)?;
+        Ok(())
+    }
+
+    async fn delete_alert(&self, id: &str) -> Result<bool, StoreError> {
+        let result = sqlx::query("DELETE FROM alerts WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(PostgresError::from)?;
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[async_trait]
+impl PreferenceStore for PostgresWalletStore {
+    async fn pinned(&self, principal: &str) -> Result<Vec<String>, StoreError> {
+        let wallet_ids: Option<Vec<u8>> =
+            sqlx::query_scalar("SELECT wallet_ids FROM pins WHERE principal = $1")
+                .bind(principal)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(PostgresError::from)?;
+        let pinned = wallet_ids
+            .map(|bytes| blob::decode(&bytes))
+            .transpose()
+            .map_err(PostgresError::from)?
+            .unwrap_or_default();
+        Ok(pinned)
+    }
+
+    async fn set_pinned(&self, principal: &str, wallet_ids: &[String]) -> Result<(), StoreError> {
+        if wallet_ids.is_empty() {
+            sqlx::query("DELETE FROM pins WHERE principal = $1")
+                .bind(principal)
+                .execute(&self.pool)
+                .await
+                .map_err(PostgresError::from)?;
+            return Ok(());
+        }
+
+        sqlx::query(
+            "INSERT INTO pins (principal, wallet_ids) VALUES ($1, $2)
+             ON CONFLICT (principal) DO UPDATE SET wallet_ids = excluded.wallet_ids",
+        )
+        .bind(principal)
+        .bind(blob::encode(wallet_ids).map_err(PostgresError::from)?)
+        .execute(&self.pool)
+        .await
+        .map_err(PostgresError::from)?;
+        Ok(())
+    }
+
+    async fn views(&self, principal: &str) -> Result<Vec<SavedView>, StoreError> {
+        let rows: Vec<(String, Vec<u8>)> =
+            sqlx::query_as("SELECT name, view FROM views WHERE principal = $1 ORDER BY name")
+                .bind(principal)
+                .fetch_all(&self.pool)
+                .await
+                .map_err(PostgresError::from)?;
+        let views = rows
+            .iter()
+            .map(|(name, bytes)| blob::to_view(name, bytes))
+            .collect::<Result<_, _>>()
+            .map_err(PostgresError::from)?;
+        Ok(views)
+    }
+
+    async fn save_view(&self, principal: &str, view: &SavedView) -> Result<(), StoreError> {
+        sqlx::query(
+            "INSERT INTO views (principal, name, view) VALUES ($1, $2, $3)
+             ON CONFLICT (principal, name) DO UPDATE SET view = excluded.view",
+        )
+        .bind(principal)
+        .bind(&view.name)
+        .bind(blob::from_view(view).map_err(PostgresError::from)?)
+        .execute(&self.pool)
+        .await
+        .map_err(PostgresError::from)?;
+        Ok(())
+    }
+
+    async fn delete_view(&self, principal: &str, name: &str) -> Result<bool, StoreError> {
+        let result = sqlx::query("DELETE FROM views WHERE principal = $1 AND name = $2")
+            .bind(principal)
+            .bind(name)
+            .execute(&self.pool)
+            .await
+            .map_err(PostgresError::from)?;
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[async_trait]
+impl OutboxStore for PostgresWalletStore {
+    async fn save_with_outbox(
+        &self,
+        name: &str,
+        record: &WalletRecord,
+        entry: &OutboxEntry,
+    ) -> Result<(), StoreError> {
+        let mut tx = self.pool.begin().await.map_err(PostgresError::from)?;
+        Self::upsert_wallet(&mut *tx, name, record).await?;
+        sqlx::query(
+            "INSERT INTO outbox (id, entry) VALUES ($1, $2)
+             ON CONFLICT (id) DO UPDATE SET entry = excluded.entry",
+        )
+        .bind(&entry.id)
+        .bind(blob::from_outbox(entry).map_err(PostgresError::from)?)
+        .execute(&mut *tx)
+        .await
+        .map_err(PostgresError::from)?;
+        tx.commit().await.map_err(PostgresError::from)?;
+        Ok(())
+    }
+
+    async fn outbox(&self) -> Result<Vec<OutboxEntry>, StoreError> {
+        let rows: Vec<(String, Vec<u8>)> =
+            sqlx::query_as("SELECT id, entry FROM outbox ORDER BY id")
+                .fetch_all(&self.pool)
+                .await
+                .map_err(PostgresError::from)?;
+        let entries = rows
+            .iter()
+            .map(|(id, bytes)| blob::to_outbox(id, bytes))
+            .collect::<Result<_, _>>()
+            .map_err(PostgresError::from)?;
+        Ok(entries)
+    }
+
+    async fn ack(&self, id: &str) -> Result<(), StoreError> {
+        sqlx::query("DELETE FROM outbox WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(PostgresError::from)?;
+        Ok(())
+    }
+
+    async fn reschedule(
+        &self,
+        id: &str,
+        attempts: u32,
+        next_attempt_at: DateTime<Utc>,
+    ) -> Result<(), StoreError> {
+        let mut tx = self.pool.begin().await.map_err(PostgresError::from)?;
+        let bytes: Option<Vec<u8>> =
+            sqlx::query_scalar("SELECT entry FROM outbox WHERE id = $1 FOR UPDATE")
+                .bind(id)
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(PostgresError::from)?;
+        let Some(bytes) = bytes else {
+            return Ok(());
+        };
+
+        let mut entry = blob::to_outbox(id, &bytes).map_err(PostgresError::from)?;
+        entry.attempts = attempts;
+        entry.next_attempt_at = next_attempt_at;
+        sqlx::query("UPDATE outbox SET entry = $1 WHERE id = $2")
+            .bind(blob::from_outbox(&entry).map_err(PostgresError::from)?)
+            .bind(id)
+            .execute(&mut *tx)
+            .await
+            .map_err(PostgresError::from)?;
+        tx.commit().await.map_err(PostgresError::from)?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl HistoryStore for PostgresWalletStore {
+    async fn append_samples(
+        &self,
+        samples: &[(String, Sample)],
+        keep: usize,
+    ) -> Result<(), StoreError> {
+        let keep = i64::try_from(keep).unwrap_or(i64::MAX);
+        let mut tx = self.pool.begin().await.map_err(PostgresError::from)?;
+        for (wallet_id, sample) in samples {
+            sqlx::query("INSERT INTO history (wallet_id, sample) VALUES ($1, $2)")
+                .bind(wallet_id)
+                .bind(blob::from_sample(sample).map_err(PostgresError::from)?)
+                .execute(&mut *tx)
+                .await
+                .map_err(PostgresError::from)?;
+            sqlx::query(
+                "DELETE FROM history WHERE wallet_id = $1 AND seq NOT IN (
+                     SELECT seq FROM history WHERE wallet_id = $1 ORDER BY seq DESC LIMIT $2
+                 )",
+            )
+            .bind(wallet_id)
+            .bind(keep)
+            .execute(&mut *tx)
+            .await
+            .map_err(PostgresError::from)?;
+        }
+        tx.commit().await.map_err(PostgresError::from)?;
+        Ok(())
+    }
+
+    async fn replace_history(&self, wallet_id: &str, samples: &[Sample]) -> Result<(), StoreError> {
+        let mut tx = self.pool.begin().await.map_err(PostgresError::from)?;
+        sqlx::query("DELETE FROM history WHERE wallet_id = $1")
+            .bind(wallet_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(PostgresError::from)?;
+        for sample in samples {
+            sqlx::query("INSERT INTO history (wallet_id, sample) VALUES ($1, $2)")
+                .bind(wallet_id)
+                .bind(blob::from_sample(sample).map_err(PostgresError::from)?)
+                .execute(&mut *tx)
+                .await
+                .map_err(PostgresError::from)?;
+        }
+        tx.commit().await.map_err(PostgresError::from)?;
+        Ok(())
+    }
+
+    async fn history(&self, wallet_id: &str) -> Result<Vec<Sample>, StoreError> {
+        let samples: Vec<Vec<u8>> =
+            sqlx::query_scalar("SELECT sample FROM history WHERE wallet_id = $1 ORDER BY seq")
+                .bind(wallet_id)
+                .fetch_all(&self.pool)
+                .await
+                .map_err(PostgresError::from)?;
+        let samples = samples
+            .iter()
+            .map(|bytes| blob::to_sample(bytes))
+            .collect::<Result<_, _>>()
+            .map_err(PostgresError::from)?;
+        Ok(samples)
+    }
+}
+
+#[async_trait]
+impl StoreAdmin for PostgresWalletStore {
+    fn describe(&self) -> StoreInfo {
+        let options = self.pool.connect_options();
+        StoreInfo {
+            backend: "postgres".to_string(),
+            location: format!(
+                "{}:{}/{}",
+                options.get_host(),
+                options.get_port(),
+                options.get_database().unwrap_or_default()
+            ),
+            recovered_from: None,
+        }
+    }
+
+    async fn stats(&self) -> Result<StoreStats, StoreError> {
+        let (entries, trashed, size_bytes): (i64, i64, i64) = sqlx::query_as(
+            "SELECT
+                 (SELECT COUNT(*) FROM wallets),
+                 (SELECT COUNT(*) FROM trash),
+                 (SELECT SUM(pg_total_relation_size(relid))::BIGINT
+                  FROM pg_catalog.pg_statio_user_tables
+                  WHERE relname IN ('wallets', 'trash', 'alerts', 'pins', 'views', 'outbox',
+                                    'history'))",
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(PostgresError::from)?;
+        Ok(StoreStats {
+            entries: usize::try_from(entries).unwrap_or_default(),
+            trashed: usize::try_from(trashed).unwrap_or_default(),
+            size_bytes: u64::try_from(size_bytes).unwrap_or_default(),
+            ..Default::default()
+        })
+    }
+
+    async fn compact(&self) -> Result<(), StoreError> {
+        sqlx::query("VACUUM wallets, trash, alerts, pins, views, outbox, history")
+            .execute(&self.pool)
+            .await
+            .map_err(PostgresError::from)?;
+        Ok(())
+    }
+
+    async fn backup(&self) -> Result<String, StoreError> {
+        Err(PostgresError("back up the postgres store with pg_dump".into()).into())
+    }
+
+    async fn snapshot(&self, _dest: &str) -> Result<(), StoreError> {
+        self.backup().await.map(|_| ())
+    }
+}
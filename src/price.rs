@@ -0,0 +1,167 @@
+use std::{
+    any::type_name,
+    error, fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use reqwest::{Client, Error as ReqwestError};
+use serde_json::Value;
+use tracing::{debug, instrument};
+
+use crate::infra::{ClientError, PriceOracle};
+
+#[derive(Debug)]
+pub struct PriceError(Box<dyn error::Error + Send + Sync + 'static>);
+
+impl fmt::Display for PriceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "price oracle error")
+    }
+}
+
+impl error::Error for PriceError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&*self.0)
+    }
+}
+
+impl From<ReqwestError> for PriceError {
+    fn from(error: ReqwestError) -> Self {
+        Self(error.into())
+    }
+}
+
+impl From<PriceError> for ClientError {
+    fn from(error: PriceError) -> Self {
+        ClientError(error.into())
+    }
+}
+
+pub struct CoinGeckoOracle {
+    client: Client,
+    url: String,
+}
+
+impl fmt::Debug for CoinGeckoOracle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CoinGeckoOracle")
+            .field("url", &self.url)
+            .finish()
+    }
+}
+
+impl CoinGeckoOracle {
+    pub fn new(url: impl Into<String>) -> Result<Self, PriceError> {
+        Ok(Self {
+            client: Client::builder().timeout(Duration::from_secs(10)).build()?,
+            url: url.into().trim_end_matches('/').to_string(),
+        })
+    }
+}
+
+#[async_trait]
+impl PriceOracle for CoinGeckoOracle {
+    #[instrument(skip(self))]
+    async fn eth_usd_price(&self) -> Result<f64, ClientError> {
+        debug!("fetching eth price");
+        let body: Value = self
+            .client
+            .get(format!("{}/simple/price", self.url))
+            .query(&[("ids", "ethereum"), ("vs_currencies", "usd")])
+            .send()
+            .await
+            .map_err(PriceError::from)?
+            .error_for_status()
+            .map_err(PriceError::from)?
+            .json()
+            .await
+            .map_err(PriceError::from)?;
+        let price = parse_price(&body)?;
+        Ok(price)
+    }
+}
+
+pub struct CachedPriceOracle<O> {
+    inner: Arc<O>,
+    ttl: Duration,
+    cached: Mutex<Option<(Instant, f64)>>,
+}
+
+impl<O> fmt::Debug for CachedPriceOracle<O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>())
+            .field("ttl", &self.ttl)
+            .finish()
+    }
+}
+
+impl<O: PriceOracle> CachedPriceOracle<O> {
+    pub fn new(inner: Arc<O>, ttl: Duration) -> Self {
+        Self {
+            inner,
+            ttl,
+            cached: Mutex::new(None),
+        }
+    }
+
+    fn cached(&self) -> Option<f64> {
+        let cached = *self.cached.lock().unwrap();
+        cached
+            .filter(|(fetched_at, _)| fetched_at.elapsed() < self.ttl)
+            .map(|(_, price)| price)
+    }
+}
+
+#[async_trait]
+impl<O: PriceOracle> PriceOracle for CachedPriceOracle<O> {
+    async fn eth_usd_price(&self) -> Result<f64, ClientError> {
+        if let Some(price) = self.cached() {
+            return Ok(price);
+        }
+
+        let price = self.inner.eth_usd_price().await?;
+        *self.cached.lock().unwrap() = Some((Instant::now(), price));
+        Ok(price)
+    }
+}
+
+fn parse_price(body: &Value) -> Result<f64, PriceError> {
+    body["ethereum"]["usd"]
+        .as_f64()
+        .filter(|price| price.is_finite() && *price > 0.0)
+        .ok_or(PriceError("missing ethereum usd price".into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use serde_json::json;
+
+    use super::{CachedPriceOracle, parse_price};
+    use crate::infra::{MockPriceOracle, PriceOracle};
+
+    #[test]
+    fn price_parse_coingecko() {
+        let body = json!({ "ethereum": { "usd": 3012.45 } });
+        assert_eq!(parse_price(&body).unwrap(), 3012.45);
+
+        assert!(parse_price(&json!({ "ethereum": {} })).is_err());
+        assert!(parse_price(&json!({ "ethereum": { "usd": -1 } })).is_err());
+    }
+
+    #[tokio::test]
+    async fn price_cached_within_ttl() {
+        let mut inner = MockPriceOracle::new();
+        inner
+            .expect_eth_usd_price()
+            .times(1)
+            .returning(|| Ok(3000.0));
+
+        let oracle = CachedPriceOracle::new(Arc::new(inner), Duration::from_secs(60));
+        assert_eq!(oracle.eth_usd_price().await.unwrap(), 3000.0);
+        assert_eq!(oracle.eth_usd_price().await.unwrap(), 3000.0);
+    }
+}
@@ -0,0 +1,814 @@
+use std::{collections::BTreeMap, error, fmt, time::Duration};
+
+use ::redis::{
+    AsyncCommands, Client, RedisError as ClientError, Script, aio::ConnectionManager,
+    cmd as command, pipe,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use tracing::{info, instrument};
+use uuid::Uuid;
+
+use crate::{
+    core::{Address, ChainId, Sample},
+    fs::{FsError, blob},
+    infra::{
+        AlertRule, AlertStore, HistoryStore, Insertion, OutboxEntry, OutboxStore, PreferenceStore,
+        SavedView, StoreAdmin, StoreError, StoreInfo, StoreStats, TrashStore, TrashedRecord,
+        WalletChange, WalletRecord, WalletStore, WalletUpdate, plan_insertions_among,
+        search_records,
+    },
+};
+
+const PREFIX: &str = "mini-wallet:";
+
+const MOVE: &str = "
+    if redis.call('EXISTS', KEYS[1]) == 0 then return 0 end
+    redis.call('RENAME', KEYS[1], KEYS[2])
+    if ARGV[1] == '' then
+        redis.call('HDEL', KEYS[2], 'trashed_at')
+    else
+        redis.call('HSET', KEYS[2], 'trashed_at', ARGV[1])
+    end
+    return 1
+";
+
+const PURGE: &str = "
+    local id = redis.call('HGET', KEYS[1], 'id')
+    redis.call('DEL', KEYS[1])
+    if id then redis.call('DEL', ARGV[1] .. id) end
+    return 1
+";
+
+const REPLACE: &str = "
+    if redis.call('HEXISTS', KEYS[1], ARGV[1]) == 0 then return 0 end
+    redis.call('HSET', KEYS[1], ARGV[1], ARGV[2])
+    return 1
+";
+
+const UNLOCK: &str = "
+    if redis.call('GET', KEYS[1]) ~= ARGV[1] then return 0 end
+    redis.call('DEL', KEYS[1])
+    return 1
+";
+
+const INSERT_LOCK_TTL: Duration = Duration::from_secs(10);
+
+const SWAP: &str = "
+    local current = redis.call('HGET', KEYS[1], 'record') or ''
+    if current ~= ARGV[1] then return 0 end
+    redis.call('HSET', KEYS[1], 'id', ARGV[2], 'address', ARGV[3], 'record', ARGV[4])
+    if ARGV[5] ~= '' then redis.call('EXPIRE', KEYS[1], ARGV[5]) end
+    return 1
+";
+
+#[derive(Debug)]
+pub struct RedisError(Box<dyn error::Error + Send + Sync + 'static>);
+
+impl fmt::Display for RedisError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "redis store error")
+    }
+}
+
+impl error::Error for RedisError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&*self.0)
+    }
+}
+
+impl From<ClientError> for RedisError {
+    fn from(error: ClientError) -> Self {
+        Self(error.into())
+    }
+}
+
+impl From<FsError> for RedisError {
+    fn from(error: FsError) -> Self {
+        Self(error.into())
+    }
+}
+
+impl From<RedisError> for StoreError {
+    fn from(error: RedisError) -> Self {
+        Self(error.into())
+    }
+}
+
+#[derive(Clone)]
+pub struct RedisWalletStore {
+    connection: ConnectionManager,
+    location: String,
+    ttl: Option<Duration>,
+}
+
+impl fmt::Debug for RedisWalletStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RedisWalletStore")
+            .field("location", &self.location)
+            .field("ttl", &self.ttl)
+            .finish()
+    }
+}
+
+impl RedisWalletStore {
+    #[instrument(skip(url))]
+    pub async fn open(url: &str, ttl: Option<Duration>) -> Result<Self, RedisError> {
+        let client = Client::open(url)?;
+        // Not the URL itself, which may carry a password.
+        let location = client.get_connection_info().addr.to_string();
+        let connection = ConnectionManager::new(client).await?;
+        info!("opened redis wallet store");
+        Ok(Self {
+            connection,
+            location,
+            ttl,
+        })
+    }
+
+    fn connection(&self) -> ConnectionManager {
+        self.connection.clone()
+    }
+
+    async fn scan(&self, pattern: &str) -> Result<Vec<String>, RedisError> {
+        let mut connection = self.connection();
+        let mut keys = Vec::new();
+        let mut cursor = 0u64;
+        loop {
+            let (next, batch): (u64, Vec<String>) = command("SCAN")
+                .arg(cursor)
+                .arg("MATCH")
+                .arg(pattern)
+                .arg("COUNT")
+                .arg(100)
+                .query_async(&mut connection)
+                .await?;
+            keys.extend(batch);
+            if next == 0 {
+                return Ok(keys);
+            }
+            cursor = next;
+        }
+    }
+
+    fn queue_wallet(
+        &self,
+        pipeline: &mut ::redis::Pipeline,
+        name: &str,
+        record: &WalletRecord,
+    ) -> Result<(), RedisError> {
+        let key = wallet_key(name);
+        pipeline
+            .hset_multiple(
+                &key,
+                &[
+                    ("id", record.id.as_bytes().to_vec()),
+                    ("address", record.wallet.address().inner().to_vec()),
+                    ("record", blob::from_record(record)?),
+                ],
+            )
+            .ignore();
+        if let Some(ttl) = self.ttl {
+            pipeline.expire(&key, ttl.as_secs() as i64).ignore();
+        }
+        Ok(())
+    }
+
+    async fn field_of_each(
+        &self,
+        keys: &[String],
+        field: &str,
+    ) -> Result<Vec<(String, Vec<u8>)>, RedisError> {
+        let mut pipeline = pipe();
+        for key in keys {
+            pipeline.hget(key, field);
+        }
+        let values: Vec<Option<Vec<u8>>> = pipeline.query_async(&mut self.connection()).await?;
+        Ok(keys
+            .iter()
+            .cloned()
+            .zip(values)
+            .filter_map(|(key, value)| Some((key, value?)))
+            .collect())
+    }
+
+    async fn sorted_hash(&self, key: &str) -> Result<Vec<(String, Vec<u8>)>, RedisError> {
+        let mut entries: Vec<(String, Vec<u8>)> = self.connection().hgetall(key).await?;
+        entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+        Ok(entries)
+    }
+}
+
+fn wallet_key(name: &str) -> String {
+    format!("{PREFIX}wallet:{name}")
+}
+
+fn trash_key(name: &str) -> String {
+    format!("{PREFIX}trash:{name}")
+}
+
+fn views_key(principal: &str) -> String {
+    format!("{PREFIX}views:{principal}")
+}
+
+fn history_key(wallet_id: &str) -> String {
+    format!("{PREFIX}history:{wallet_id}")
+}
+
+fn alerts_key() -> String {
+    format!("{PREFIX}alerts")
+}
+
+fn pins_key() -> String {
+    format!("{PREFIX}pins")
+}
+
+fn outbox_key() -> String {
+    format!("{PREFIX}outbox")
+}
+
+#[async_trait]
+impl WalletStore for RedisWalletStore {
+    async fn find(&self, name: &str) -> Result<Option<WalletRecord>, StoreError> {
+        let record: Option<Vec<u8>> = self
+            .connection()
+            .hget(wallet_key(name), "record")
+            .await
+            .map_err(RedisError::from)?;
+        let record = record
+            .map(|bytes| blob::to_record(&bytes))
+            .transpose()
+            .map_err(RedisError::from)?;
+        Ok(record)
+    }
+
+    async fn all(&self) -> Result<BTreeMap<String, WalletRecord>, StoreError> {
+        let prefix = wallet_key("");
+        let keys = self.scan(&format!("{prefix}*")).await?;
+        let wallets = self
+            .field_of_each(&keys, "record")
+            .await?
+            .into_iter()
+            .map(|(key, bytes)| Ok((key[prefix.len()..].to_owned(), blob::to_record(&bytes)?)))
+            .collect::<Result<_, FsError>>()
+            .map_err(RedisError::from)?;
+        Ok(wallets)
+    }
+
+    async fn exists(&self, name: &str) -> Result<bool, StoreError> {
+        let found: bool = self
+            .connection()
+            .exists(wallet_key(name))
+            .await
+            .map_err(RedisError::from)?;
+        Ok(found)
+    }
+
+    async fn save(&self, name: &str, record: &WalletRecord) -> Result<(), StoreError> {
+        self.save_many(&[(name.to_owned(), record.clone())]).await
+    }
+
+    async fn save_many(&self, wallets: &[(String, WalletRecord)]) -> Result<(), StoreError> {
+        let mut pipeline = pipe();
+        pipeline.atomic();
+        for (name, record) in wallets {
+            self.queue_wallet(&mut pipeline, name, record)?;
+        }
+        let () = pipeline
+            .query_async(&mut self.connection())
+            .await
+            .map_err(RedisError::from)?;
+        Ok(())
+    }
+
+    async fn delete(&self, name: &str) -> Result<(), StoreError> {
+        let () = self
+            .connection()
+            .del(wallet_key(name))
+            .await
+            .map_err(RedisError::from)?;
+        Ok(())
+    }
+
+    async fn delete_many(&self, names: &[String]) -> Result<(), StoreError> {
+        if names.is_empty() {
+            return Ok(());
+        }
+        let keys: Vec<_> = names.iter().map(|name| wallet_key(name)).collect();
+        let () = self
+            .connection()
+            .del(keys)
+            .await
+            .map_err(RedisError::from)?;
+        Ok(())
+    }
+
+    async fn update(
+        &self,
+        name: &str,
+        mut update: WalletUpdate,
+    ) -> Result<Option<WalletRecord>, StoreError> {
+        let mut connection = self.connection();
+        let key = wallet_key(name);
+        let ttl = self
+            .ttl
+            .map(|ttl| ttl.as_secs().to_string())
+            .unwrap_or_default();
+        loop {
+            let current: Option<Vec<u8>> = connection
+                .hget(&key, "record")
+                .await
+                .map_err(RedisError::from)?;
+            let record = current
+                .as_ref()
+                .map(|bytes| blob::to_record(bytes))
+                .transpose()
+                .map_err(RedisError::from)?;
+            let Some(record) = update(record) else {
+                return Ok(None);
+            };
+            let swapped: i64 = Script::new(SWAP)
+                .key(&key)
+                .arg(current.unwrap_or_default())
+                .arg(record.id.as_bytes())
+                .arg(record.wallet.address().inner().as_slice())
+                .arg(blob::from_record(&record).map_err(RedisError::from)?)
+                .arg(&ttl)
+                .invoke_async(&mut connection)
+                .await
+                .map_err(RedisError::from)?;
+            if swapped == 1 {
+                return Ok(Some(record));
+            }
+        }
+    }
+
+    async fn find_by_address(
+        &self,
+        chain_id: ChainId,
+        address: &Address,
+    ) -> Result<Option<(String, WalletRecord)>, StoreError> {
+        let prefix = wallet_key("");
+        let keys = self.scan(&format!("{prefix}*")).await?;
+        let mut names: Vec<_> = self
+            .field_of_each(&keys, "address")
+            .await?
+            .into_iter()
+            .filter(|(_, bytes)| bytes.as_slice() == address.inner())
+            .map(|(key, _)| key[prefix.len()..].to_owned())
+            .collect();
+        names.sort();
+        for name in names {
+            let Some(record) = self.find(&name).await? else {
+                continue;
+            };
+            if record.wallet.chain_id() == chain_id {
+                return Ok(Some((name, record)));
+            }
+        }
+        Ok(None)
+    }
+
+    async fn count(&self) -> Result<usize, StoreError> {
+        let keys = self.scan(&format!("{}*", wallet_key(""))).await?;
+        Ok(keys.len())
+    }
+
+    async fn insert_many(
+        &self,
+        wallets: &[(String, WalletRecord)],
+    ) -> Result<Vec<Insertion>, StoreError> {
+        let mut connection = self.connection();
+        let lock = format!("{PREFIX}insert_lock");
+        let token = Uuid::new_v4().to_string();
+        loop {
+            let locked: Option<String> = command("SET")
+                .arg(&lock)
+                .arg(&token)
+                .arg("NX")
+                .arg("PX")
+                .arg(INSERT_LOCK_TTL.as_millis() as u64)
+                .query_async(&mut connection)
+                .await
+                .map_err(RedisError::from)?;
+            if locked.is_some() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+
+        let result = async {
+            let insertions = plan_insertions_among(wallets, &self.all().await?);
+            let mut pipeline = pipe();
+            pipeline.atomic();
+            for ((name, record), insertion) in wallets.iter().zip(&insertions) {
+                if *insertion == Insertion::Saved {
+                    self.queue_wallet(&mut pipeline, name, record)?;
+                }
+            }
+            let () = pipeline
+                .query_async(&mut connection)
+                .await
+                .map_err(RedisError::from)?;
+            Ok::<_, StoreError>(insertions)
+        }
+        .await;
+
+        let _: i64 = Script::new(UNLOCK)
+            .key(&lock)
+            .arg(&token)
+            .invoke_async(&mut connection)
+            .await
+            .map_err(RedisError::from)?;
+        result
+    }
+
+    async fn search(
+        &self,
+        terms: &[String],
+        limit: usize,
+    ) -> Result<Vec<(String, WalletRecord)>, StoreError> {
+        Ok(search_records(self.all().await?, terms, limit))
+    }
+
+    async fn transaction(&self, changes: &[WalletChange]) -> Result<(), StoreError> {
+        let mut pipeline = pipe();
+        pipeline.atomic();
+        for change in changes {
+            match change {
+                WalletChange::Save { name, record } => {
+                    self.queue_wallet(&mut pipeline, name, record)?;
+                }
+                WalletChange::Delete { name } => {
+                    pipeline.del(wallet_key(name)).ignore();
+                }
+            }
+        }
+        let () = pipeline
+            .query_async(&mut self.connection())
+            .await
+            .map_err(RedisError::from)?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TrashStore for RedisWalletStore {
+    async fn find_trashed(&self, name: &str) -> Result<Option<TrashedRecord>, StoreError> {
+        let (record, trashed_at): (Option<Vec<u8>>, Option<i64>) = self
+            .connection()
+            .hget(trash_key(name), &["record", "trashed_at"])
+            .await
+            .map_err(RedisError::from)?;
+        let trashed = record
+            .map(|bytes| blob::to_trashed(&bytes, trashed_at.unwrap_or_default()))
+            .transpose()
+            .map_err(RedisError::from)?;
+        Ok(trashed)
+    }
+
+    async fn all_trashed(&self) -> Result<BTreeMap<String, TrashedRecord>, StoreError> {
+        let prefix = trash_key("");
+        let keys = self.scan(&format!("{prefix}*")).await?;
+
+        let mut pipeline = pipe();
+        for key in &keys {
+            pipeline.hget(key, &["record", "trashed_at"]);
+        }
+        let values: Vec<(Option<Vec<u8>>, Option<i64>)> = pipeline
+            .query_async(&mut self.connection())
+            .await
+            .map_err(RedisError::from)?;
+
+        let trashed = keys
+            .iter()
+            .zip(values)
+            .filter_map(|(key, (record, trashed_at))| {
+                let name = key[prefix.len()..].to_owned();
+                Some((name, record?, trashed_at.unwrap_or_default()))
+            })
+            .map(|(name, bytes, trashed_at)| Ok((name, blob::to_trashed(&bytes, trashed_at)?)))
+            .collect::<Result<_, FsError>>()
+            .map_err(RedisError::from)?;
+        Ok(trashed)
+    }
+
+    async fn trash(&self, name: &str) -> Result<(), StoreError> {
+        let _: i64 = Script::new(MOVE)
+            .key(wallet_key(name))
+            .key(trash_key(name))
+            .arg(Utc::now().timestamp().to_string())
+            .invoke_async(&mut self.connection())
+            .await
+            .map_err(RedisError::from)?;
+        Ok(())
+    }
+
+    async fn restore(&self, name: &str) -> Result<(), StoreError> {
+        let _: i64 = Script::new(MOVE)
+            .key(trash_key(name))
+            .key(wallet_key(name))
+            .arg("")
+            .invoke_async(&mut self.connection())
+            .await
+            .map_err(RedisError::from)?;
+        Ok(())
+    }
+
+    async fn purge(&self, name: &str) -> Result<(), StoreError> {
+        let _: i64 = Script::new(PURGE)
+            .key(trash_key(name))
+            .arg(history_key(""))
+            .invoke_async(&mut self.connection())
+            .await
+            .map_err(RedisError::from)?;
+        Ok(())
+    }
+    async fn save_trashed(&self, name: &str, trashed: &TrashedRecord) -> Result<(), StoreError> {
+        let record = &trashed.record;
+        let key = trash_key(name);
+        let mut pipeline = pipe();
+        pipeline.atomic().del(&key).ignore();
+        pipeline
+            .hset_multiple(
+                &key,
+                &[
+                    ("id", record.id.as_bytes().to_vec()),
+                    ("address", record.wallet.address().inner().to_vec()),
+                    (
+                        "record",
+                        blob::from_record(record).map_err(RedisError::from)?,
+                    ),
+                    (
+                        "trashed_at",
+                        trashed.trashed_at.timestamp().to_string().into_bytes(),
+                    ),
+                ],
+            )
+            .ignore();
+        let () = pipeline
+            .query_async(&mut self.connection())
+            .await
+            .map_err(RedisError::from)?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AlertStore for RedisWalletStore {
+    async fn all_alerts(&self) -> Result<Vec<AlertRule>, StoreError> {
+        let rules = self
+            .sorted_hash(&alerts_key())
+            .await?
+            .iter()
+            .map(|(_, bytes)| blob::to_alert(bytes))
+            .collect::<Result<_, _>>()
+            .map_err(RedisError::from)?;
+        Ok(rules)
+    }
+
+    async fn save_alert(&self, rule: &AlertRule) -> Result<(), StoreError> {
+        let bytes = blob::from_alert(rule).map_err(RedisError::from)?;
+        let () = self
+            .connection()
+            .hset(alerts_key(), &rule.id, bytes)
+            .await
+            .map_err(RedisError::from)?;
+        Ok(())
+    }
+
+    async fn delete_alert(&self, id: &str) -> Result<bool, StoreError> {
+        let removed: usize = self
+            .connection()
+            .hdel(alerts_key(), id)
+            .await
+            .map_err(RedisError::from)?;
+        Ok(removed > 0)
+    }
+}
+
+#[async_trait]
+impl PreferenceStore for RedisWalletStore {
+    async fn pinned(&self, principal: &str) -> Result<Vec<String>, StoreError> {
+        let wallet_ids: Option<Vec<u8>> = self
+            .connection()
+            .hget(pins_key(), principal)
+            .await
+            .map_err(RedisError::from)?;
+        let pinned = wallet_ids
+            .map(|bytes| blob::decode(&bytes))
+            .transpose()
+            .map_err(RedisError::from)?
+            .unwrap_or_default();
+        Ok(pinned)
+    }
+
+    async fn set_pinned(&self, principal: &str, wallet_ids: &[String]) -> Result<(), StoreError> {
+        let mut connection = self.connection();
+        if wallet_ids.is_empty() {
+            let _: usize = connection
+                .hdel(pins_key(), principal)
+                .await
+                .map_err(RedisError::from)?;
+            return Ok(());
+        }
+
+        let bytes = blob::encode(wallet_ids).map_err(RedisError::from)?;
+        let () = connection
+            .hset(pins_key(), principal, bytes)
+            .await
+            .map_err(RedisError::from)?;
+        Ok(())
+    }
+
+    async fn views(&self, principal: &str) -> Result<Vec<SavedView>, StoreError> {
+        let views = self
+            .sorted_hash(&views_key(principal))
+            .await?
+            .iter()
+            .map(|(name, bytes)| blob::to_view(name, bytes))
+            .collect::<Result<_, _>>()
+            .map_err(RedisError::from)?;
+        Ok(views)
+    }
+
+    async fn save_view(&self, principal: &str, view: &SavedView) -> Result<(), StoreError> {
+        let bytes = blob::from_view(view).map_err(RedisError::from)?;
+        let () = self
+            .connection()
+            .hset(views_key(principal), &view.name, bytes)
+            .await
+            .map_err(RedisError::from)?;
+        Ok(())
+    }
+
+    async fn delete_view(&self, principal: &str, name: &str) -> Result<bool, StoreError> {
+        let removed: usize = self
+            .connection()
+            .hdel(views_key(principal), name)
+            .await
+            .map_err(RedisError::from)?;
+        Ok(removed > 0)
+    }
+}
+
+#[async_trait]
+impl OutboxStore for RedisWalletStore {
+    async fn save_with_outbox(
+        &self,
+        name: &str,
+        record: &WalletRecord,
+        entry: &OutboxEntry,
+    ) -> Result<(), StoreError> {
+        let mut pipeline = pipe();
+        pipeline.atomic();
+        self.queue_wallet(&mut pipeline, name, record)?;
+        let bytes = blob::from_outbox(entry).map_err(RedisError::from)?;
+        pipeline.hset(outbox_key(), &entry.id, bytes).ignore();
+        let () = pipeline
+            .query_async(&mut self.connection())
+            .await
+            .map_err(RedisError::from)?;
+        Ok(())
+    }
+
+    async fn outbox(&self) -> Result<Vec<OutboxEntry>, StoreError> {
+        let entries = self
+            .sorted_hash(&outbox_key())
+            .await?
+            .iter()
+            .map(|(id, bytes)| blob::to_outbox(id, bytes))
+            .collect::<Result<_, _>>()
+            .map_err(RedisError::from)?;
+        Ok(entries)
+    }
+
+    async fn ack(&self, id: &str) -> Result<(), StoreError> {
+        let _: usize = self
+            .connection()
+            .hdel(outbox_key(), id)
+            .await
+            .map_err(RedisError::from)?;
+        Ok(())
+    }
+
+    async fn reschedule(
+        &self,
+        id: &str,
+        attempts: u32,
+        next_attempt_at: DateTime<Utc>,
+    ) -> Result<(), StoreError> {
+        let mut connection = self.connection();
+        let bytes: Option<Vec<u8>> = connection
+            .hget(outbox_key(), id)
+            .await
+            .map_err(RedisError::from)?;
+        let Some(bytes) = bytes else {
+            return Ok(());
+        };
+
+        let mut entry = blob::to_outbox(id, &bytes).map_err(RedisError::from)?;
+        entry.attempts = attempts;
+        entry.next_attempt_at = next_attempt_at;
+        let _: i64 = Script::new(REPLACE)
+            .key(outbox_key())
+            .arg(id)
+            .arg(blob::from_outbox(&entry).map_err(RedisError::from)?)
+            .invoke_async(&mut connection)
+            .await
+            .map_err(RedisError::from)?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl HistoryStore for RedisWalletStore {
+    async fn append_samples(
+        &self,
+        samples: &[(String, Sample)],
+        keep: usize,
+    ) -> Result<(), StoreError> {
+        let keep = isize::try_from(keep).unwrap_or(isize::MAX);
+        let mut pipeline = pipe();
+        pipeline.atomic();
+        for (wallet_id, sample) in samples {
+            let key = history_key(wallet_id);
+            let bytes = blob::from_sample(sample).map_err(RedisError::from)?;
+            pipeline.rpush(&key, bytes).ignore();
+            if keep == 0 {
+                pipeline.del(&key).ignore();
+            } else {
+                pipeline.ltrim(&key, -keep, -1).ignore();
+            }
+        }
+        let () = pipeline
+            .query_async(&mut self.connection())
+            .await
+            .map_err(RedisError::from)?;
+        Ok(())
+    }
+
+    async fn replace_history(&self, wallet_id: &str, samples: &[Sample]) -> Result<(), StoreError> {
+        let key = history_key(wallet_id);
+        let mut pipeline = pipe();
+        pipeline.atomic();
+        pipeline.del(&key).ignore();
+        for sample in samples {
+            let bytes = blob::from_sample(sample).map_err(RedisError::from)?;
+            pipeline.rpush(&key, bytes).ignore();
+        }
+        let () = pipeline
+            .query_async(&mut self.connection())
+            .await
+            .map_err(RedisError::from)?;
+        Ok(())
+    }
+
+    async fn history(&self, wallet_id: &str) -> Result<Vec<Sample>, StoreError> {
+        let samples: Vec<Vec<u8>> = self
+            .connection()
+            .lrange(history_key(wallet_id), 0, -1)
+            .await
+            .map_err(RedisError::from)?;
+        let samples = samples
+            .iter()
+            .map(|bytes| blob::to_sample(bytes))
+            .collect::<Result<_, _>>()
+            .map_err(RedisError::from)?;
+        Ok(samples)
+    }
+}
+
+#[async_trait]
+impl StoreAdmin for RedisWalletStore {
+    fn describe(&self) -> StoreInfo {
+        StoreInfo {
+            backend: "redis".to_string(),
+            location: self.location.clone(),
+            recovered_from: None,
+        }
+    }
+
+    async fn stats(&self) -> Result<StoreStats, StoreError> {
+        let entries = self.scan(&format!("{}*", wallet_key(""))).await?.len();
+        let trashed = self.scan(&format!("{}*", trash_key(""))).await?.len();
+        Ok(StoreStats {
+            entries,
+            trashed,
+            size_bytes: 0,
+            ..Default::default()
+        })
+    }
+
+    async fn compact(&self) -> Result<(), StoreError> {
+        Ok(())
+    }
+
+    async fn backup(&self) -> Result<String, StoreError> {
+        Err(RedisError("back up the redis store with its own persistence".into()).into())
+    }
+
+    async fn snapshot(&self, _dest: &str) -> Result<(), StoreError> {
+        self.backup().await.map(|_| ())
+    }
+}
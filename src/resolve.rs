@@ -0,0 +1,134 @@
+mod resolve_ens;
+mod resolve_lens;
+mod resolve_unstoppable;
+
+use std::{any::type_name, error, fmt, sync::Arc};
+
+use async_trait::async_trait;
+use reqwest::Error as ReqwestError;
+use tracing::debug;
+
+use crate::{
+    core::Address,
+    infra::{ClientError, NameResolver},
+};
+
+pub use resolve_ens::EnsResolver;
+pub use resolve_lens::LensResolver;
+pub use resolve_unstoppable::UnstoppableResolver;
+
+#[derive(Debug)]
+pub struct ResolveError(Box<dyn error::Error + Send + Sync + 'static>);
+
+impl fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "name resolution error")
+    }
+}
+
+impl error::Error for ResolveError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&*self.0)
+    }
+}
+
+impl From<ReqwestError> for ResolveError {
+    fn from(error: ReqwestError) -> Self {
+        Self(error.into())
+    }
+}
+
+impl From<ResolveError> for ClientError {
+    fn from(error: ResolveError) -> Self {
+        ClientError(error.into())
+    }
+}
+
+#[derive(Clone)]
+pub struct ChainResolver {
+    resolvers: Vec<Arc<dyn NameResolver>>,
+}
+
+impl fmt::Debug for ChainResolver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>()).finish()
+    }
+}
+
+impl ChainResolver {
+    pub fn new(resolvers: Vec<Arc<dyn NameResolver>>) -> Self {
+        Self { resolvers }
+    }
+}
+
+#[async_trait]
+impl NameResolver for ChainResolver {
+    async fn resolve(&self, name: &str) -> Result<Option<Address>, ClientError> {
+        let mut first_error = None;
+
+        for resolver in &self.resolvers {
+            match resolver.resolve(name).await {
+                Ok(Some(address)) => return Ok(Some(address)),
+                Ok(None) => {}
+                Err(e) => {
+                    debug!("resolver failed for {name}: {e:?}");
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        match first_error {
+            Some(e) => Err(e),
+            None => Ok(None),
+        }
+    }
+}
+
+fn parse_provider_address(address: &str) -> Option<Address> {
+    let address = address.strip_prefix("0x")?;
+    let mut bytes = [0u8; 20];
+    hex::decode_to_slice(address, &mut bytes).ok()?;
+    (bytes != [0; 20]).then(|| Address::new(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io, sync::Arc};
+
+    use crate::{
+        core::Address,
+        infra::{ClientError, MockNameResolver, NameResolver},
+        resolve::{ChainResolver, parse_provider_address},
+    };
+
+    #[tokio::test]
+    async fn resolve_chain_order() {
+        let mut failing = MockNameResolver::new();
+        failing
+            .expect_resolve()
+            .returning(|_| Err(ClientError(io::Error::other("down").into())));
+
+        let mut unknown = MockNameResolver::new();
+        unknown.expect_resolve().returning(|_| Ok(None));
+
+        let mut known = MockNameResolver::new();
+        known
+            .expect_resolve()
+            .returning(|_| Ok(Some(Address::new([1; 20]))));
+
+        let chain = ChainResolver::new(vec![Arc::new(failing), Arc::new(unknown), Arc::new(known)]);
+        let address = chain.resolve("vitalik.eth").await.unwrap();
+        assert_eq!(address, Some(Address::new([1; 20])));
+    }
+
+    #[test]
+    fn resolve_provider_address() {
+        let address = parse_provider_address("0xab5801a7d398351b8be11c439e05c5b3259aec9b").unwrap();
+        assert_eq!(
+            address.to_string(),
+            "0xAb5801a7D398351b8bE11C439e05C5B3259aeC9B"
+        );
+        assert!(parse_provider_address(&format!("0x{}", "0".repeat(40))).is_none());
+        assert!(parse_provider_address("").is_none());
+    }
+}
@@ -0,0 +1,143 @@
+use std::{fmt, str::FromStr, sync::Arc};
+
+use async_trait::async_trait;
+use tracing::{debug, instrument};
+
+use crate::{
+    client::UpstreamBudget,
+    core::{Address, EnsName, namehash},
+    infra::{ClientError, NameResolver, ReverseResolver},
+    rpc::RpcWalletClient,
+};
+
+const ENS_REGISTRY: &str = "0x00000000000C2E074eC69A0dFb2997BA6C7d2e1e";
+const RESOLVER_SELECTOR: [u8; 4] = [0x01, 0x78, 0xb8, 0xbf];
+const ADDR_SELECTOR: [u8; 4] = [0x3b, 0x3b, 0x57, 0xde];
+const NAME_SELECTOR: [u8; 4] = [0x69, 0x1f, 0x34, 0x31];
+
+#[derive(Clone)]
+pub struct EnsResolver {
+    pub rpc: Arc<RpcWalletClient>,
+    pub budget: Arc<UpstreamBudget>,
+}
+
+impl fmt::Debug for EnsResolver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EnsResolver").finish()
+    }
+}
+
+impl EnsResolver {
+    async fn call(
+        &self,
+        to: &str,
+        selector: [u8; 4],
+        node: &[u8; 32],
+    ) -> Result<Vec<u8>, ClientError> {
+        let mut data = selector.to_vec();
+        data.extend_from_slice(node);
+        self.budget.acquire().await;
+        Ok(self.rpc.eth_call(to, &data).await?)
+    }
+
+    async fn call_address(
+        &self,
+        to: &str,
+        selector: [u8; 4],
+        node: &[u8; 32],
+    ) -> Result<Option<Address>, ClientError> {
+        let result = self.call(to, selector, node).await?;
+        let Some(word) = result.get(..32) else {
+            return Ok(None);
+        };
+
+        let mut bytes = [0u8; 20];
+        bytes.copy_from_slice(&word[12..]);
+        Ok((bytes != [0; 20]).then(|| Address::new(bytes)))
+    }
+}
+
+#[async_trait]
+impl NameResolver for EnsResolver {
+    #[instrument(skip(self))]
+    async fn resolve(&self, name: &str) -> Result<Option<Address>, ClientError> {
+        let Some(name) = EnsName::from_str(name).ok().filter(EnsName::is_eth) else {
+            return Ok(None);
+        };
+
+        let node = name.namehash();
+        let Some(resolver) = self
+            .call_address(ENS_REGISTRY, RESOLVER_SELECTOR, &node)
+            .await?
+        else {
+            debug!("name has no resolver");
+            return Ok(None);
+        };
+
+        self.call_address(&resolver.to_string(), ADDR_SELECTOR, &node)
+            .await
+    }
+}
+
+#[async_trait]
+impl ReverseResolver for EnsResolver {
+    #[instrument(skip(self), fields(address = %address.to_string()))]
+    async fn reverse(&self, address: &Address) -> Result<Option<String>, ClientError> {
+        let node = namehash(&format!("{}.addr.reverse", hex::encode(address.inner())));
+        let Some(resolver) = self
+            .call_address(ENS_REGISTRY, RESOLVER_SELECTOR, &node)
+            .await?
+        else {
+            debug!("address has no reverse resolver");
+            return Ok(None);
+        };
+
+        let result = self
+            .call(&resolver.to_string(), NAME_SELECTOR, &node)
+            .await?;
+        let Some(name) = decode_string(&result).filter(|name| !name.is_empty()) else {
+            return Ok(None);
+        };
+
+        if self.resolve(&name).await? != Some(*address) {
+            debug!(name, "reverse record doesn't resolve back to address");
+            return Ok(None);
+        }
+
+        Ok(Some(name))
+    }
+}
+
+fn decode_string(result: &[u8]) -> Option<String> {
+    let word = |at: usize| -> Option<usize> {
+        let word = result.get(at..at.checked_add(32)?)?;
+        let (high, low) = word.split_at(24);
+        if high.iter().any(|&b| b != 0) {
+            return None;
+        }
+        usize::try_from(u64::from_be_bytes(low.try_into().ok()?)).ok()
+    };
+
+    let offset = word(0)?;
+    let len = word(offset)?;
+    let start = offset.checked_add(32)?;
+    let bytes = result.get(start..start.checked_add(len)?)?;
+    String::from_utf8(bytes.to_vec()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::decode_string;
+
+    #[test]
+    fn resolve_ens_decode_string() {
+        let mut result = vec![0u8; 96];
+        result[31] = 0x20;
+        result[63] = 11;
+        result[64..75].copy_from_slice(b"vitalik.eth");
+        assert_eq!(decode_string(&result).as_deref(), Some("vitalik.eth"));
+
+        assert_eq!(decode_string(&result[..40]), None);
+        assert_eq!(decode_string(&[]), None);
+    }
+}
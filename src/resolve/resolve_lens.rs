@@ -0,0 +1,69 @@
+use std::{fmt, time::Duration};
+
+use async_trait::async_trait;
+use reqwest::Client;
+use serde_json::json;
+use tracing::instrument;
+
+use super::{ResolveError, parse_provider_address};
+use crate::{
+    core::Address,
+    infra::{ClientError, NameResolver},
+};
+
+const PROFILE_QUERY: &str = "query Profile($handle: Handle!) { profile(request: { forHandle: $handle }) { ownedBy { address } } }";
+
+#[derive(Clone)]
+pub struct LensResolver {
+    client: Client,
+    url: String,
+}
+
+impl fmt::Debug for LensResolver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LensResolver")
+            .field("url", &self.url)
+            .finish()
+    }
+}
+
+impl LensResolver {
+    pub fn new(url: impl Into<String>) -> Result<Self, ResolveError> {
+        Ok(Self {
+            client: Client::builder().timeout(Duration::from_secs(30)).build()?,
+            url: url.into(),
+        })
+    }
+}
+
+#[async_trait]
+impl NameResolver for LensResolver {
+    #[instrument(skip(self))]
+    async fn resolve(&self, name: &str) -> Result<Option<Address>, ClientError> {
+        let name = name.to_lowercase();
+        let handle = match (name.strip_prefix("lens/"), name.strip_suffix(".lens")) {
+            (Some(local), _) | (None, Some(local)) => format!("lens/{local}"),
+            (None, None) => return Ok(None),
+        };
+
+        let body: serde_json::Value = self
+            .client
+            .post(&self.url)
+            .json(&json!({
+                "query": PROFILE_QUERY,
+                "variables": { "handle": handle },
+            }))
+            .send()
+            .await
+            .map_err(ResolveError::from)?
+            .error_for_status()
+            .map_err(ResolveError::from)?
+            .json()
+            .await
+            .map_err(ResolveError::from)?;
+
+        Ok(body["data"]["profile"]["ownedBy"]["address"]
+            .as_str()
+            .and_then(parse_provider_address))
+    }
+}
@@ -0,0 +1,85 @@
+use std::{fmt, time::Duration};
+
+use async_trait::async_trait;
+use reqwest::{Client, StatusCode};
+use tracing::instrument;
+
+use super::{ResolveError, parse_provider_address};
+use crate::{
+    core::Address,
+    infra::{ClientError, NameResolver},
+};
+
+const TLDS: &[&str] = &[
+    "crypto",
+    "nft",
+    "wallet",
+    "x",
+    "bitcoin",
+    "dao",
+    "888",
+    "zil",
+    "blockchain",
+];
+
+#[derive(Clone)]
+pub struct UnstoppableResolver {
+    client: Client,
+    url: String,
+    api_key: String,
+}
+
+impl fmt::Debug for UnstoppableResolver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UnstoppableResolver")
+            .field("url", &self.url)
+            .finish()
+    }
+}
+
+impl UnstoppableResolver {
+    pub fn new(url: impl Into<String>, api_key: impl Into<String>) -> Result<Self, ResolveError> {
+        Ok(Self {
+            client: Client::builder().timeout(Duration::from_secs(30)).build()?,
+            url: url.into(),
+            api_key: api_key.into(),
+        })
+    }
+}
+
+#[async_trait]
+impl NameResolver for UnstoppableResolver {
+    #[instrument(skip(self))]
+    async fn resolve(&self, name: &str) -> Result<Option<Address>, ClientError> {
+        let name = name.to_lowercase();
+        let handled = name
+            .rsplit_once('.')
+            .is_some_and(|(_, tld)| TLDS.contains(&tld));
+        if !handled {
+            return Ok(None);
+        }
+
+        let response = self
+            .client
+            .get(format!("{}/resolve/domains/{name}", self.url))
+            .bearer_auth(&self.api_key)
+            .send()
+            .await
+            .map_err(ResolveError::from)?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        let body: serde_json::Value = response
+            .error_for_status()
+            .map_err(ResolveError::from)?
+            .json()
+            .await
+            .map_err(ResolveError::from)?;
+
+        Ok(body["records"]["crypto.ETH.address"]
+            .as_str()
+            .and_then(parse_provider_address))
+    }
+}
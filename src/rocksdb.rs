@@ -0,0 +1,772 @@
+use std::{collections::BTreeMap, error, fmt, sync::Arc};
+
+use ::rocksdb::{
+    ColumnFamily, DB, Direction, Error as DbError, IteratorMode, Options, WriteBatch, WriteOptions,
+    checkpoint::Checkpoint,
+    properties::{self, PropName},
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use tokio::sync::Mutex;
+use tracing::{info, instrument};
+
+use crate::{
+    core::{Address, ChainId, Sample},
+    fs::{FsError, blob},
+    infra::{
+        AlertRule, AlertStore, HistoryStore, Insertion, OutboxEntry, OutboxStore, PreferenceStore,
+        SavedView, StoreAdmin, StoreError, StoreInfo, StoreStats, TrashStore, TrashedRecord,
+        WalletChange, WalletRecord, WalletStore, WalletUpdate, WriteCounters, plan_insertions,
+        search_records,
+    },
+};
+
+const WALLETS: &str = "wallets";
+const BY_ADDRESS: &str = "by_address";
+const TRASH: &str = "trash";
+const ALERTS: &str = "alerts";
+const PINS: &str = "pins";
+const VIEWS: &str = "views";
+const OUTBOX: &str = "outbox";
+const HISTORY: &str = "history";
+
+const COLUMN_FAMILIES: [&str; 8] = [
+    WALLETS, BY_ADDRESS, TRASH, ALERTS, PINS, VIEWS, OUTBOX, HISTORY,
+];
+
+type Entry = (Box<[u8]>, Box<[u8]>);
+
+#[derive(Debug)]
+pub struct RocksError(Box<dyn error::Error + Send + Sync + 'static>);
+
+impl fmt::Display for RocksError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "rocksdb store error")
+    }
+}
+
+impl error::Error for RocksError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&*self.0)
+    }
+}
+
+impl From<DbError> for RocksError {
+    fn from(error: DbError) -> Self {
+        Self(error.into())
+    }
+}
+
+impl From<FsError> for RocksError {
+    fn from(error: FsError) -> Self {
+        Self(error.into())
+    }
+}
+
+impl From<RocksError> for StoreError {
+    fn from(error: RocksError) -> Self {
+        Self(error.into())
+    }
+}
+
+#[derive(Clone)]
+pub struct RocksWalletStore {
+    path: String,
+    db: Arc<DB>,
+    write_lock: Arc<Mutex<()>>,
+    writes: Arc<WriteCounters>,
+}
+
+impl fmt::Debug for RocksWalletStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RocksWalletStore")
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+impl RocksWalletStore {
+    #[instrument(fields(path = %path.as_ref()))]
+    pub fn open(path: impl AsRef<str>) -> Result<Self, RocksError> {
+        let path = path.as_ref().to_owned();
+        let mut options = Options::default();
+        options.create_if_missing(true);
+        options.create_missing_column_families(true);
+        let db = DB::open_cf(&options, &path, COLUMN_FAMILIES)?;
+        info!("opened rocksdb wallet store");
+        Ok(Self {
+            path,
+            db: Arc::new(db),
+            write_lock: Arc::new(Mutex::new(())),
+            writes: Arc::default(),
+        })
+    }
+
+    pub async fn page(
+        &self,
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<(String, WalletRecord)>, RocksError> {
+        let mode = match after {
+            Some(after) => IteratorMode::From(after.as_bytes(), Direction::Forward),
+            None => IteratorMode::Start,
+        };
+
+        let mut wallets = Vec::with_capacity(limit);
+        for entry in self.db.iterator_cf(self.cf(WALLETS), mode) {
+            let (name, bytes) = entry?;
+            if after.is_some_and(|after| *name == *after.as_bytes()) {
+                continue;
+            }
+            if wallets.len() == limit {
+                break;
+            }
+            wallets.push((key_to_string(&name), blob::to_record(&bytes)?));
+        }
+        Ok(wallets)
+    }
+
+    fn cf(&self, name: &str) -> &ColumnFamily {
+        self.db
+            .cf_handle(name)
+            .expect("column families are created on open")
+    }
+
+    fn get(&self, cf: &str, key: impl AsRef<[u8]>) -> Result<Option<Vec<u8>>, RocksError> {
+        Ok(self.db.get_cf(self.cf(cf), key)?)
+    }
+
+    fn scan(&self, cf: &str, prefix: &[u8]) -> Result<Vec<Entry>, RocksError> {
+        let mut entries = Vec::new();
+        for entry in self.db.prefix_iterator_cf(self.cf(cf), prefix) {
+            let (key, value) = entry?;
+            if !key.starts_with(prefix) {
+                break;
+            }
+            entries.push((key, value));
+        }
+        Ok(entries)
+    }
+
+    fn write(&self, batch: WriteBatch) -> Result<(), RocksError> {
+        let mut options = WriteOptions::default();
+        options.set_sync(true);
+        let result = self.db.write_opt(batch, &options);
+        self.writes.record(&result);
+        Ok(result?)
+    }
+
+    fn put_wallet(
+        &self,
+        batch: &mut WriteBatch,
+        name: &str,
+        bytes: &[u8],
+    ) -> Result<(), RocksError> {
+        self.delete_index(batch, name)?;
+        let record = blob::to_record(bytes)?;
+        for address in record.wallet.addresses() {
+            batch.put_cf(self.cf(BY_ADDRESS), index_key(address, name), b"");
+        }
+        batch.put_cf(self.cf(WALLETS), name, bytes);
+        Ok(())
+    }
+
+    fn delete_index(&self, batch: &mut WriteBatch, name: &str) -> Result<(), RocksError> {
+        if let Some(bytes) = self.get(WALLETS, name)? {
+            for address in blob::to_record(&bytes)?.wallet.addresses() {
+                batch.delete_cf(self.cf(BY_ADDRESS), index_key(address, name));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn index_key(address: &Address, name: &str) -> Vec<u8> {
+    [address.inner().as_slice(), name.as_bytes()].concat()
+}
+
+fn to_trashed(bytes: &[u8]) -> Result<TrashedRecord, FsError> {
+    let (trashed_at, record): (i64, Vec<u8>) = blob::decode(bytes)?;
+    blob::to_trashed(&record, trashed_at)
+}
+
+fn view_prefix(principal: &str) -> String {
+    format!("{principal}\0")
+}
+
+fn key_to_string(key: &[u8]) -> String {
+    String::from_utf8_lossy(key).into_owned()
+}
+
+#[async_trait]
+impl WalletStore for RocksWalletStore {
+    async fn find(&self, name: &str) -> Result<Option<WalletRecord>, StoreError> {
+        let record = self
+            .get(WALLETS, name)?
+            .map(|bytes| blob::to_record(&bytes))
+            .transpose()
+            .map_err(RocksError::from)?;
+        Ok(record)
+    }
+
+    async fn all(&self) -> Result<BTreeMap<String, WalletRecord>, StoreError> {
+        let wallets = self
+            .scan(WALLETS, &[])?
+            .iter()
+            .map(|(name, bytes)| Ok((key_to_string(name), blob::to_record(bytes)?)))
+            .collect::<Result<_, RocksError>>()?;
+        Ok(wallets)
+    }
+
+    async fn exists(&self, name: &str) -> Result<bool, StoreError> {
+        Ok(self.get(WALLETS, name)?.is_some())
+    }
+
+    async fn save(&self, name: &str, record: &WalletRecord) -> Result<(), StoreError> {
+        self.save_many(&[(name.to_owned(), record.clone())]).await
+    }
+
+    async fn save_many(&self, wallets: &[(String, WalletRecord)]) -> Result<(), StoreError> {
+        let _guard = self.write_lock.lock().await;
+        let mut batch = WriteBatch::default();
+        for (name, record) in wallets {
+            let bytes = blob::from_record(record).map_err(RocksError::from)?;
+            self.put_wallet(&mut batch, name, &bytes)?;
+        }
+        Ok(self.write(batch)?)
+    }
+
+    async fn delete(&self, name: &str) -> Result<(), StoreError> {
+        let _guard = self.write_lock.lock().await;
+        let mut batch = WriteBatch::default();
+        self.delete_index(&mut batch, name)?;
+        batch.delete_cf(self.cf(WALLETS), name);
+        Ok(self.write(batch)?)
+    }
+
+    async fn delete_many(&self, names: &[String]) -> Result<(), StoreError> {
+        let _guard = self.write_lock.lock().await;
+        let mut batch = WriteBatch::default();
+        for name in names {
+            self.delete_index(&mut batch, name)?;
+            batch.delete_cf(self.cf(WALLETS), name);
+        }
+        Ok(self.write(batch)?)
+    }
+
+    async fn update(
+        &self,
+        name: &str,
+        mut update: WalletUpdate,
+    ) -> Result<Option<WalletRecord>, StoreError> {
+        let _guard = self.write_lock.lock().await;
+        let current = self
+            .get(WALLETS, name)?
+            .map(|bytes| blob::to_record(&bytes))
+            .transpose()
+            .map_err(RocksError::from)?;
+        let Some(record) = update(current) else {
+            return Ok(None);
+        };
+        let bytes = blob::from_record(&record).map_err(RocksError::from)?;
+        let mut batch = WriteBatch::default();
+        self.put_wallet(&mut batch, name, &bytes)?;
+        self.write(batch)?;
+        Ok(Some(record))
+    }
+
+    async fn find_by_address(
+        &self,
+        chain_id: ChainId,
+        address: &Address,
+    ) -> Result<Option<(String, WalletRecord)>, StoreError> {
+        let prefix = address.inner();
+        for (key, _) in self.scan(BY_ADDRESS, prefix)? {
+            let name = key_to_string(&key[prefix.len()..]);
+            let Some(bytes) = self.get(WALLETS, &name)? else {
+                continue;
+            };
+            let record = blob::to_record(&bytes).map_err(RocksError::from)?;
+            if record.wallet.chain_id() == chain_id && record.wallet.address() == address {
+                return Ok(Some((name, record)));
+            }
+        }
+        Ok(None)
+    }
+
+    async fn count(&self) -> Result<usize, StoreError> {
+        let mut count = 0;
+        for entry in self.db.iterator_cf(self.cf(WALLETS), IteratorMode::Start) {
+            entry.map_err(RocksError::from)?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    async fn insert_many(
+        &self,
+        wallets: &[(String, WalletRecord)],
+    ) -> Result<Vec<Insertion>, StoreError> {
+        let _guard = self.write_lock.lock().await;
+        let mut lookups = Vec::with_capacity(wallets.len());
+        for (name, record) in wallets {
+            let holder = self
+                .find_by_address(record.wallet.chain_id(), record.wallet.address())
+                .await?;
+            let name_taken = self.get(WALLETS, name)?.is_some();
+            lookups.push((name_taken, holder.map(|(holder, _)| holder)));
+        }
+        let insertions = plan_insertions(wallets, lookups);
+
+        let mut batch = WriteBatch::default();
+        for ((name, record), insertion) in wallets.iter().zip(&insertions) {
+            if *insertion == Insertion::Saved {
+                let bytes = blob::from_record(record).map_err(RocksError::from)?;
+                self.put_wallet(&mut batch, name, &bytes)?;
+            }
+        }
+        self.write(batch)?;
+        Ok(insertions)
+    }
+
+    async fn search(
+        &self,
+        terms: &[String],
+        limit: usize,
+    ) -> Result<Vec<(String, WalletRecord)>, StoreError> {
+        Ok(search_records(self.all().await?, terms, limit))
+    }
+
+    async fn transaction(&self, changes: &[WalletChange]) -> Result<(), StoreError> {
+        let _guard = self.write_lock.lock().await;
+        let mut batch = WriteBatch::default();
+        for change in changes {
+            match change {
+                WalletChange::Save { name, record } => {
+                    let bytes = blob::from_record(record).map_err(RocksError::from)?;
+                    self.put_wallet(&mut batch, name, &bytes)?;
+                }
+                WalletChange::Delete { name } => {
+                    self.delete_index(&mut batch, name)?;
+                    batch.delete_cf(self.cf(WALLETS), name);
+                }
+            }
+        }
+        Ok(self.write(batch)?)
+    }
+}
+
+#[async_trait]
+impl TrashStore for RocksWalletStore {
+    async fn find_trashed(&self, name: &str) -> Result<Option<TrashedRecord>, StoreError> {
+        let trashed = self
+            .get(TRASH, name)?
+            .map(|bytes| to_trashed(&bytes))
+            .transpose()
+            .map_err(RocksError::from)?;
+        Ok(trashed)
+    }
+
+    async fn all_trashed(&self) -> Result<BTreeMap<String, TrashedRecord>, StoreError> {
+        let trashed = self
+            .scan(TRASH, &[])?
+            .iter()
+            .map(|(name, bytes)| Ok((key_to_string(name), to_trashed(bytes)?)))
+            .collect::<Result<_, RocksError>>()?;
+        Ok(trashed)
+    }
+
+    async fn trash(&self, name: &str) -> Result<(), StoreError> {
+        let _guard = self.write_lock.lock().await;
+        let Some(record) = self.get(WALLETS, name)? else {
+            return Ok(());
+        };
+
+        let mut batch = WriteBatch::default();
+        self.delete_index(&mut batch, name)?;
+        batch.delete_cf(self.cf(WALLETS), name);
+        let trashed = blob::encode((Utc::now().timestamp(), record)).map_err(RocksError::from)?;
+        batch.put_cf(self.cf(TRASH), name, trashed);
+        Ok(self.write(batch)?)
+    }
+
+    async fn restore(&self, name: &str) -> Result<(), StoreError> {
+        let _guard = self.write_lock.lock().await;
+        let Some(bytes) = self.get(TRASH, name)? else {
+            return Ok(());
+        };
+
+        let (_, record): (i64, Vec<u8>) = blob::decode(&bytes).map_err(RocksError::from)?;
+        let mut batch = WriteBatch::default();
+        batch.delete_cf(self.cf(TRASH), name);
+        self.put_wallet(&mut batch, name, &record)?;
+        Ok(self.write(batch)?)
+    }
+
+    async fn purge(&self, name: &str) -> Result<(), StoreError> {
+        let _guard = self.write_lock.lock().await;
+        let Some(bytes) = self.get(TRASH, name)? else {
+            return Ok(());
+        };
+
+        let trashed = to_trashed(&bytes).map_err(RocksError::from)?;
+        let mut batch = WriteBatch::default();
+        batch.delete_cf(self.cf(TRASH), name);
+        batch.delete_cf(self.cf(HISTORY), &trashed.record.id);
+        Ok(self.write(batch)?)
+    }
+    async fn save_trashed(&self, name: &str, trashed: &TrashedRecord) -> Result<(), StoreError> {
+        let record = blob::from_record(&trashed.record).map_err(RocksError::from)?;
+        let bytes =
+            blob::encode((trashed.trashed_at.timestamp(), record)).map_err(RocksError::from)?;
+        let mut batch = WriteBatch::default();
+        batch.put_cf(self.cf(TRASH), name, bytes);
+        Ok(self.write(batch)?)
+    }
+}
+
+#[async_trait]
+impl AlertStore for RocksWalletStore {
+    async fn all_alerts(&self) -> Result<Vec<AlertRule>, StoreError> {
+        let rules = self
+            .scan(ALERTS, &[])?
+            .iter()
+            .map(|(_, bytes)| blob::to_alert(bytes))
+            .collect::<Result<_, _>>()
+            .map_err(RocksError::from)?;
+        Ok(rules)
+    }
+
+    async fn save_alert(&self, rule: &AlertRule) -> Result<(), StoreError> {
+        let mut batch = WriteBatch::default();
+        let bytes = blob::from_alert(rule).map_err(RocksError::from)?;
+        batch.put_cf(self.cf(ALERTS), &rule.id, bytes);
+        Ok(self.write(batch)?)
+    }
+
+    async fn delete_alert(&self, id: &str) -> Result<bool, StoreError> {
+        let _guard = self.write_lock.lock().await;
+        if self.get(ALERTS, id)?.is_none() {
+            return Ok(false);
+        }
+
+        let mut batch = WriteBatch::default();
+        batch.delete_cf(self.cf(ALERTS), id);
+        self.write(batch)?;
+        Ok(true)
+    }
+}
+
+#[async_trait]
+impl PreferenceStore for RocksWalletStore {
+    async fn pinned(&self, principal: &str) -> Result<Vec<String>, StoreError> {
+        let pinned = self
+            .get(PINS, principal)?
+            .map(|bytes| blob::decode(&bytes))
+            .transpose()
+            .map_err(RocksError::from)?
+            .unwrap_or_default();
+        Ok(pinned)
+    }
+
+    async fn set_pinned(&self, principal: &str, wallet_ids: &[String]) -> Result<(), StoreError> {
+        let mut batch = WriteBatch::default();
+        if wallet_ids.is_empty() {
+            batch.delete_cf(self.cf(PINS), principal);
+        } else {
+            let bytes = blob::encode(wallet_ids).map_err(RocksError::from)?;
+            batch.put_cf(self.cf(PINS), principal, bytes);
+        }
+        Ok(self.write(batch)?)
+    }
+
+    async fn views(&self, principal: &str) -> Result<Vec<SavedView>, StoreError> {
+        let prefix = view_prefix(principal);
+        let views = self
+            .scan(VIEWS, prefix.as_bytes())?
+            .iter()
+            .map(|(key, bytes)| blob::to_view(&key_to_string(&key[prefix.len()..]), bytes))
+            .collect::<Result<_, _>>()
+            .map_err(RocksError::from)?;
+        Ok(views)
+    }
+
+    async fn save_view(&self, principal: &str, view: &SavedView) -> Result<(), StoreError> {
+        let key = format!("{}{}", view_prefix(principal), view.name);
+        let bytes = blob::from_view(view).map_err(RocksError::from)?;
+        let mut batch = WriteBatch::default();
+        batch.put_cf(self.cf(VIEWS), key, bytes);
+        Ok(self.write(batch)?)
+    }
+
+    async fn delete_view(&self, principal: &str, name: &str) -> Result<bool, StoreError> {
+        let key = format!("{}{name}", view_prefix(principal));
+        let _guard = self.write_lock.lock().await;
+        if self.get(VIEWS, &key)?.is_none() {
+            return Ok(false);
+        }
+
+        let mut batch = WriteBatch::default();
+        batch.delete_cf(self.cf(VIEWS), key);
+        self.write(batch)?;
+        Ok(true)
+    }
+}
+
+#[async_trait]
+impl OutboxStore for RocksWalletStore {
+    async fn save_with_outbox(
+        &self,
+        name: &str,
+        record: &WalletRecord,
+        entry: &OutboxEntry,
+    ) -> Result<(), StoreError> {
+        let record = blob::from_record(record).map_err(RocksError::from)?;
+        let entry_bytes = blob::from_outbox(entry).map_err(RocksError::from)?;
+        let _guard = self.write_lock.lock().await;
+        let mut batch = WriteBatch::default();
+        self.put_wallet(&mut batch, name, &record)?;
+        batch.put_cf(self.cf(OUTBOX), &entry.id, entry_bytes);
+        Ok(self.write(batch)?)
+    }
+
+    async fn outbox(&self) -> Result<Vec<OutboxEntry>, StoreError> {
+        let entries = self
+            .scan(OUTBOX, &[])?
+            .iter()
+            .map(|(id, bytes)| blob::to_outbox(&key_to_string(id), bytes))
+            .collect::<Result<_, _>>()
+            .map_err(RocksError::from)?;
+        Ok(entries)
+    }
+
+    async fn ack(&self, id: &str) -> Result<(), StoreError> {
+        let mut batch = WriteBatch::default();
+        batch.delete_cf(self.cf(OUTBOX), id);
+        Ok(self.write(batch)?)
+    }
+
+    async fn reschedule(
+        &self,
+        id: &str,
+        attempts: u32,
+        next_attempt_at: DateTime<Utc>,
+    ) -> Result<(), StoreError> {
+        let _guard = self.write_lock.lock().await;
+        let Some(bytes) = self.get(OUTBOX, id)? else {
+            return Ok(());
+        };
+
+        let mut entry = blob::to_outbox(id, &bytes).map_err(RocksError::from)?;
+        entry.attempts = attempts;
+        entry.next_attempt_at = next_attempt_at;
+        let mut batch = WriteBatch::default();
+        let bytes = blob::from_outbox(&entry).map_err(RocksError::from)?;
+        batch.put_cf(self.cf(OUTBOX), id, bytes);
+        Ok(self.write(batch)?)
+    }
+}
+
+#[async_trait]
+impl HistoryStore for RocksWalletStore {
+    async fn append_samples(
+        &self,
+        samples: &[(String, Sample)],
+        keep: usize,
+    ) -> Result<(), StoreError> {
+        let _guard = self.write_lock.lock().await;
+        let mut histories: BTreeMap<&str, Vec<Vec<u8>>> = BTreeMap::new();
+        for (wallet_id, _) in samples {
+            if !histories.contains_key(wallet_id.as_str()) {
+                let kept = match self.get(HISTORY, wallet_id)? {
+                    Some(bytes) => blob::decode(&bytes).map_err(RocksError::from)?,
+                    None => Vec::new(),
+                };
+                histories.insert(wallet_id.as_str(), kept);
+            }
+        }
+        for (wallet_id, sample) in samples {
+            let kept = histories.entry(wallet_id.as_str()).or_default();
+            kept.push(blob::from_sample(sample).map_err(RocksError::from)?);
+            kept.drain(..kept.len().saturating_sub(keep));
+        }
+
+        let mut batch = WriteBatch::default();
+        for (wallet_id, kept) in histories {
+            if kept.is_empty() {
+                batch.delete_cf(self.cf(HISTORY), wallet_id);
+            } else {
+                let bytes = blob::encode(&kept).map_err(RocksError::from)?;
+                batch.put_cf(self.cf(HISTORY), wallet_id, bytes);
+            }
+        }
+        Ok(self.write(batch)?)
+    }
+
+    async fn replace_history(&self, wallet_id: &str, samples: &[Sample]) -> Result<(), StoreError> {
+        let _guard = self.write_lock.lock().await;
+        let mut batch = WriteBatch::default();
+        if samples.is_empty() {
+            batch.delete_cf(self.cf(HISTORY), wallet_id);
+        } else {
+            let samples = samples
+                .iter()
+                .map(blob::from_sample)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(RocksError::from)?;
+            let bytes = blob::encode(&samples).map_err(RocksError::from)?;
+            batch.put_cf(self.cf(HISTORY), wallet_id, bytes);
+        }
+        Ok(self.write(batch)?)
+    }
+
+    async fn history(&self, wallet_id: &str) -> Result<Vec<Sample>, StoreError> {
+        let Some(bytes) = self.get(HISTORY, wallet_id)? else {
+            return Ok(Vec::new());
+        };
+        let samples: Vec<Vec<u8>> = blob::decode(&bytes).map_err(RocksError::from)?;
+        let samples = samples
+            .iter()
+            .map(|bytes| blob::to_sample(bytes))
+            .collect::<Result<_, _>>()
+            .map_err(RocksError::from)?;
+        Ok(samples)
+    }
+}
+
+#[async_trait]
+impl StoreAdmin for RocksWalletStore {
+    fn describe(&self) -> StoreInfo {
+        StoreInfo {
+            backend: "rocksdb".to_string(),
+            location: self.path.clone(),
+            recovered_from: None,
+        }
+    }
+
+    async fn stats(&self) -> Result<StoreStats, StoreError> {
+        let estimate = |cf: &str, property: &PropName| -> Result<u64, RocksError> {
+            Ok(self
+                .db
+                .property_int_value_cf(self.cf(cf), property)?
+                .unwrap_or_default())
+        };
+
+        let mut size_bytes = 0;
+        for cf in COLUMN_FAMILIES {
+            size_bytes += estimate(cf, properties::TOTAL_SST_FILES_SIZE)?;
+        }
+        Ok(self.writes.fill(StoreStats {
+            entries: usize::try_from(estimate(WALLETS, properties::ESTIMATE_NUM_KEYS)?)
+                .unwrap_or_default(),
+            trashed: usize::try_from(estimate(TRASH, properties::ESTIMATE_NUM_KEYS)?)
+                .unwrap_or_default(),
+            size_bytes,
+            ..Default::default()
+        }))
+    }
+
+    async fn compact(&self) -> Result<(), StoreError> {
+        for cf in COLUMN_FAMILIES {
+            self.db
+                .compact_range_cf(self.cf(cf), None::<&[u8]>, None::<&[u8]>);
+        }
+        Ok(())
+    }
+
+    async fn backup(&self) -> Result<String, StoreError> {
+        let backup_path = format!("{}.bak", self.path);
+        match tokio::fs::remove_dir_all(&backup_path).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                return Err(FsError::from(e).into());
+            }
+            _ => {}
+        }
+
+        self.snapshot(&backup_path).await?;
+        Ok(backup_path)
+    }
+
+    async fn snapshot(&self, dest: &str) -> Result<(), StoreError> {
+        Checkpoint::new(&*self.db)
+            .and_then(|checkpoint| checkpoint.create_checkpoint(dest))
+            .map_err(RocksError::from)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, str::FromStr};
+
+    use uuid::Uuid;
+
+    use super::RocksWalletStore;
+    use crate::{
+        core::{Address, ChainId, Wallet},
+        infra::{StoreAdmin, TrashStore, WalletRecord, WalletStore},
+    };
+
+    const ADDR: &str = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
+
+    fn open() -> RocksWalletStore {
+        let path = env::temp_dir().join(format!("mini-wallet-{}.rocksdb", Uuid::new_v4()));
+        RocksWalletStore::open(path.to_string_lossy()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn rocksdb_wallet_round_trip() {
+        let store = open();
+        let address = Address::from_str(ADDR).unwrap();
+        let record = WalletRecord::new(Wallet::new(address));
+        store.save("Vault", &record).await.unwrap();
+
+        let found = store.find("Vault").await.unwrap().unwrap();
+        assert_eq!(found.id, record.id);
+        let (name, _) = store
+            .find_by_address(ChainId::MAINNET, &address)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(name, "Vault");
+        assert!(store.exists("Vault").await.unwrap());
+        assert_eq!(store.all().await.unwrap().len(), 1);
+
+        store.trash("Vault").await.unwrap();
+        assert!(
+            store
+                .find_by_address(ChainId::MAINNET, &address)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(store.find_trashed("Vault").await.unwrap().is_some());
+
+        store.restore("Vault").await.unwrap();
+        assert!(
+            store
+                .find_by_address(ChainId::MAINNET, &address)
+                .await
+                .unwrap()
+                .is_some()
+        );
+        assert!(store.all_trashed().await.unwrap().is_empty());
+        assert!(store.backup().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn rocksdb_page_in_name_order() {
+        let store = open();
+        for (name, byte) in [("c", 3), ("a", 1), ("b", 2), ("d", 4)] {
+            let record = WalletRecord::new(Wallet::new(Address::new([byte; 20])));
+            store.save(name, &record).await.unwrap();
+        }
+
+        let names = |page: Vec<(String, WalletRecord)>| -> Vec<String> {
+            page.into_iter().map(|(name, _)| name).collect()
+        };
+        assert_eq!(names(store.page(None, 2).await.unwrap()), ["a", "b"]);
+        assert_eq!(names(store.page(Some("b"), 2).await.unwrap()), ["c", "d"]);
+        assert!(store.page(Some("d"), 2).await.unwrap().is_empty());
+    }
+}
@@ -1,4 +1,8 @@
-use std::{error, fmt, time::Duration};
+use std::{
+    error, fmt,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
 
 use async_trait::async_trait;
 use hex::FromHexError;
@@ -7,8 +11,8 @@ use serde_json::json;
 use tracing::{debug, instrument};
 
 use crate::{
-    core::{Address, Balance},
-    infra::{ClientError, WalletClient},
+    core::{Address, Balance, U256},
+    infra::{ClientAdmin, ClientError, WalletClient},
 };
 
 #[derive(Debug)]
@@ -41,16 +45,92 @@ impl From<FromHexError> for RpcError {
 #[derive(Debug, Clone)]
 pub struct RpcWalletClient {
     client: Client,
-    url: String,
+    url: Arc<RwLock<String>>,
 }
 
 impl RpcWalletClient {
     pub fn new(url: impl Into<String>) -> Result<Self, RpcError> {
         Ok(Self {
             client: Client::builder().timeout(Duration::from_secs(30)).build()?,
-            url: url.into(),
+            url: Arc::new(RwLock::new(url.into())),
         })
     }
+
+    fn url(&self) -> String {
+        self.url.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    pub async fn chain_id(&self) -> Result<u64, RpcError> {
+        debug!("calling chain id rpc");
+        let response = self
+            .client
+            .post(self.url())
+            .json(&json!({
+                "jsonrpc": "2.0",
+                "method": "eth_chainId",
+                "params": [],
+                "id": 1,
+            }))
+            .send()
+            .await?
+            .error_for_status()?;
+
+        let body: serde_json::Value = response.json().await?;
+        let chain_id = body["result"]
+            .as_str()
+            .and_then(|s| s.strip_prefix("0x"))
+            .ok_or(RpcError("missing result field".into()))?;
+
+        u64::from_str_radix(chain_id, 16).map_err(|e| RpcError(e.into()))
+    }
+
+    pub(crate) async fn eth_call(&self, to: &str, data: &[u8]) -> Result<Vec<u8>, RpcError> {
+        debug!("calling eth_call rpc");
+        let response = self
+            .client
+            .post(self.url())
+            .json(&json!({
+                "jsonrpc": "2.0",
+                "method": "eth_call",
+                "params": [{ "to": to, "data": format!("0x{}", hex::encode(data)) }, "latest"],
+                "id": 1,
+            }))
+            .send()
+            .await?
+            .error_for_status()?;
+
+        let body: serde_json::Value = response.json().await?;
+        let result = body["result"]
+            .as_str()
+            .and_then(|s| s.strip_prefix("0x"))
+            .ok_or(RpcError("missing result field".into()))?;
+
+        Ok(hex::decode(result)?)
+    }
+}
+
+const BALANCE_OF_SELECTOR: [u8; 4] = [0x70, 0xa0, 0x82, 0x31];
+
+const MULTICALL3: &str = "0xcA11bde05977b3631167028862bE2a173976CA11";
+const MULTICALL3_ADDRESS: [u8; 20] = [
+    0xca, 0x11, 0xbd, 0xe0, 0x59, 0x77, 0xb3, 0x63, 0x11, 0x67, 0x02, 0x88, 0x62, 0xbe, 0x2a, 0x17,
+    0x39, 0x76, 0xca, 0x11,
+];
+
+const AGGREGATE_SELECTOR: [u8; 4] = [0x25, 0x2d, 0xba, 0x42];
+
+const GET_ETH_BALANCE_SELECTOR: [u8; 4] = [0x4d, 0x23, 0x01, 0xcc];
+
+pub(crate) const MULTICALL_CHUNK: usize = 500;
+
+impl ClientAdmin for RpcWalletClient {
+    fn endpoint(&self) -> String {
+        self.url()
+    }
+
+    fn set_endpoint(&self, url: &str) {
+        *self.url.write().unwrap_or_else(|e| e.into_inner()) = url.to_owned();
+    }
 }
 
 #[async_trait]
@@ -62,7 +142,7 @@ impl WalletClient for RpcWalletClient {
         debug!("calling wallet balance rpc");
         let response = self
             .client
-            .post(&self.url)
+            .post(self.url())
             .json(&json!({
                 "jsonrpc": "2.0",
                 "method": "eth_getBalance",
@@ -71,6 +151,7 @@ impl WalletClient for RpcWalletClient {
             }))
             .send()
             .await
+            .and_then(|response| response.error_for_status())
             .map_err(RpcError::from)?;
 
         let body: serde_json::Value = response.json().await.map_err(RpcError::from)?;
@@ -82,8 +163,160 @@ impl WalletClient for RpcWalletClient {
         let wei = extract_wei(balance)?;
         debug!(wei = %wei, hex = %balance, "got wallet balance");
 
-        Ok(Balance::new(wei))
+        Ok(Balance::from_wei(wei))
     }
+
+    async fn block_number(&self) -> Result<u64, ClientError> {
+        debug!("calling block number rpc");
+        let response = self
+            .client
+            .post(self.url())
+            .json(&json!({
+                "jsonrpc": "2.0",
+                "method": "eth_blockNumber",
+                "params": [],
+                "id": 1,
+            }))
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(RpcError::from)?;
+
+        let body: serde_json::Value = response.json().await.map_err(RpcError::from)?;
+        let block_number = body["result"]
+            .as_str()
+            .and_then(|s| s.strip_prefix("0x"))
+            .ok_or(RpcError("missing result field".into()))?;
+
+        Ok(u64::from_str_radix(block_number, 16).map_err(|e| RpcError(e.into()))?)
+    }
+
+    #[instrument(skip(self), fields(address = %address.to_string()))]
+    async fn transaction_count(&self, address: &Address) -> Result<u64, ClientError> {
+        debug!("calling transaction count rpc");
+        let response = self
+            .client
+            .post(self.url())
+            .json(&json!({
+                "jsonrpc": "2.0",
+                "method": "eth_getTransactionCount",
+                "params": [address.to_string(), "latest"],
+                "id": 1,
+            }))
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(RpcError::from)?;
+
+        let body: serde_json::Value = response.json().await.map_err(RpcError::from)?;
+        let count = body["result"]
+            .as_str()
+            .and_then(|s| s.strip_prefix("0x"))
+            .ok_or(RpcError("missing result field".into()))?;
+
+        Ok(u64::from_str_radix(count, 16).map_err(|e| RpcError(e.into()))?)
+    }
+
+    #[instrument(skip(self), fields(address = %address.to_string(), token = %token.to_string()))]
+    async fn token_balance(&self, address: &Address, token: &Address) -> Result<U256, ClientError> {
+        let mut data = BALANCE_OF_SELECTOR.to_vec();
+        data.extend_from_slice(&[0; 12]);
+        data.extend_from_slice(address.inner());
+
+        debug!("calling token balance rpc");
+        let result = self.eth_call(&token.to_string(), &data).await?;
+        Ok(extract_uint(&result)?)
+    }
+
+    async fn balances(&self, addresses: &[Address]) -> Result<Vec<Balance>, ClientError> {
+        let mut balances = Vec::with_capacity(addresses.len());
+        for chunk in addresses.chunks(MULTICALL_CHUNK) {
+            debug!(addresses = chunk.len(), "calling multicall balances rpc");
+            let calls: Vec<_> = chunk
+                .iter()
+                .map(|address| {
+                    let mut data = GET_ETH_BALANCE_SELECTOR.to_vec();
+                    data.extend_from_slice(&[0; 12]);
+                    data.extend_from_slice(address.inner());
+                    (MULTICALL3_ADDRESS, data)
+                })
+                .collect();
+            let result = self.eth_call(MULTICALL3, &encode_aggregate(&calls)).await?;
+
+            let answers = decode_aggregate(&result)?;
+            if answers.len() != chunk.len() {
+                return Err(
+                    RpcError("multicall answered for the wrong number of calls".into()).into(),
+                );
+            }
+            for answer in answers {
+                let word = answer
+                    .get(..32)
+                    .ok_or(RpcError("multicall answer is too short".into()))?;
+                balances.push(Balance::from_wei(U256::from_big_endian(word)));
+            }
+        }
+        Ok(balances)
+    }
+}
+
+fn encode_aggregate(calls: &[([u8; 20], Vec<u8>)]) -> Vec<u8> {
+    fn word(n: usize) -> [u8; 32] {
+        let mut word = [0; 32];
+        word[24..].copy_from_slice(&(n as u64).to_be_bytes());
+        word
+    }
+    fn padded_len(len: usize) -> usize {
+        len.div_ceil(32) * 32
+    }
+
+    let mut encoded = AGGREGATE_SELECTOR.to_vec();
+    encoded.extend_from_slice(&word(0x20));
+    encoded.extend_from_slice(&word(calls.len()));
+
+    // Each tuple sits after the offsets, which count from the first of them.
+    let mut offset = calls.len() * 32;
+    for (_, data) in calls {
+        encoded.extend_from_slice(&word(offset));
+        offset += 3 * 32 + padded_len(data.len());
+    }
+    for (target, data) in calls {
+        encoded.extend_from_slice(&[0; 12]);
+        encoded.extend_from_slice(target);
+        encoded.extend_from_slice(&word(0x40));
+        encoded.extend_from_slice(&word(data.len()));
+        encoded.extend_from_slice(data);
+        encoded.resize(encoded.len() + padded_len(data.len()) - data.len(), 0);
+    }
+    encoded
+}
+
+fn decode_aggregate(result: &[u8]) -> Result<Vec<Vec<u8>>, RpcError> {
+    let malformed = || RpcError("malformed multicall result".into());
+    let read_usize = |at: usize| -> Result<usize, RpcError> {
+        let word = result
+            .get(at..at.saturating_add(32))
+            .ok_or_else(malformed)?;
+        if word[..24].iter().any(|&byte| byte != 0) {
+            return Err(malformed());
+        }
+        Ok(u64::from_be_bytes(word[24..].try_into().unwrap()) as usize)
+    };
+
+    let array = read_usize(32)?;
+    let len = read_usize(array)?;
+    let offsets = array.saturating_add(32);
+    (0..len)
+        .map(|i| {
+            let offset = read_usize(offsets.saturating_add(i.saturating_mul(32)))?;
+            let start = offsets.saturating_add(offset).saturating_add(32);
+            let data_len = read_usize(start - 32)?;
+            let data = result
+                .get(start..start.saturating_add(data_len))
+                .ok_or_else(malformed)?;
+            Ok(data.to_vec())
+        })
+        .collect()
 }
 
 impl From<RpcError> for ClientError {
@@ -92,17 +325,61 @@ impl From<RpcError> for ClientError {
     }
 }
 
-fn extract_wei(balance: &str) -> Result<u128, RpcError> {
-    let balance = if balance.len().is_multiple_of(2) {
-        balance.to_string()
-    } else {
-        format!("0{balance}")
-    };
+fn extract_uint(result: &[u8]) -> Result<U256, RpcError> {
+    let word = result
+        .get(..32)
+        .ok_or(RpcError("no contract answered the call".into()))?;
+    Ok(U256::from_big_endian(word))
+}
 
-    let wei = hex::decode(&balance)
-        .map_err(RpcError::from)?
-        .iter()
-        .fold(0, |acc, &byte| acc * 256 + byte as u128);
+fn extract_wei(balance: &str) -> Result<U256, RpcError> {
+    U256::from_str_radix(balance, 16).map_err(|e| RpcError(e.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_aggregate, encode_aggregate, extract_uint};
+    use crate::core::U256;
+
+    fn word(n: u8) -> [u8; 32] {
+        let mut word = [0; 32];
+        word[31] = n;
+        word
+    }
 
-    Ok(wei)
+    #[test]
+    fn rpc_encode_aggregate() {
+        let encoded = encode_aggregate(&[([7; 20], vec![1; 36])]);
+        assert_eq!(encoded.len(), 4 + 3 * 32 + 3 * 32 + 64);
+        assert_eq!(encoded[4..36], word(0x20));
+        assert_eq!(encoded[36..68], word(1));
+        assert_eq!(encoded[68..100], word(0x20));
+        assert_eq!(encoded[112..132], [7; 20]);
+        assert_eq!(encoded[132..164], word(0x40));
+        assert_eq!(encoded[164..196], word(36));
+        assert_eq!(encoded[196..232], [1; 36]);
+    }
+
+    #[test]
+    fn rpc_decode_aggregate() {
+        let mut result = vec![];
+        for part in [word(9), word(0x40), word(2), word(0x40), word(0x80)] {
+            result.extend_from_slice(&part);
+        }
+        for balance in [5, 6] {
+            result.extend_from_slice(&word(32));
+            result.extend_from_slice(&word(balance));
+        }
+
+        let answers = decode_aggregate(&result).unwrap();
+        assert_eq!(answers, [word(5).to_vec(), word(6).to_vec()]);
+        assert!(decode_aggregate(&result[..200]).is_err());
+    }
+
+    #[test]
+    fn rpc_extract_uint() {
+        assert_eq!(extract_uint(&word(7)).unwrap(), U256::from(7));
+        assert_eq!(extract_uint(&[0xff; 32]).unwrap(), U256::MAX);
+        assert!(extract_uint(&[]).is_err());
+    }
 }
@@ -0,0 +1,464 @@
+use std::{fmt, path::PathBuf, sync::Arc, time::Duration};
+
+use chrono::{NaiveTime, Utc};
+use tokio::{
+    sync::Notify,
+    time::{Instant, sleep_until},
+};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, warn};
+
+use crate::{
+    admin::{Collect, Compact, Downsample},
+    alert::Evaluate,
+    infra::{Notification, Notifier},
+    server::compose_error,
+    wallet::{
+        BalanceChange, Deliver, EventBus, Export, ExportFormat, Portfolio, RecvError, Refresh,
+        Subscription, Sweep, WalletEvent,
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompactionWindow {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+    pub every: Duration,
+}
+
+impl CompactionWindow {
+    fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            self.start <= time || time < self.end
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportSchedule {
+    pub every: Duration,
+    pub format: ExportFormat,
+    pub dir: PathBuf,
+}
+
+#[derive(Clone)]
+pub struct Scheduler {
+    refresh: Arc<dyn Refresh>,
+    collect: Arc<dyn Collect>,
+    portfolio: Option<Arc<dyn Portfolio>>,
+    sweep: Option<Arc<dyn Sweep>>,
+    alerts: Option<Arc<dyn Evaluate>>,
+    notifier: Option<(Arc<dyn Notifier>, u32)>,
+    delivery: Option<Arc<dyn Deliver>>,
+    events: Option<Arc<EventBus>>,
+    compaction: Option<(Arc<dyn Compact>, CompactionWindow)>,
+    downsample: Option<Arc<dyn Downsample>>,
+    reports: Option<(Arc<dyn Export>, ReportSchedule)>,
+    interval: Option<Duration>,
+    jitter: Option<Duration>,
+    trigger: Arc<Notify>,
+}
+
+impl fmt::Debug for Scheduler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Scheduler")
+            .field("interval", &self.interval)
+            .field("jitter", &self.jitter)
+            .finish()
+    }
+}
+
+impl Scheduler {
+    pub fn new(refresh: Arc<dyn Refresh>, collect: Arc<dyn Collect>) -> Self {
+        Self {
+            refresh,
+            collect,
+            portfolio: None,
+            sweep: None,
+            alerts: None,
+            notifier: None,
+            delivery: None,
+            events: None,
+            compaction: None,
+            downsample: None,
+            reports: None,
+            interval: None,
+            jitter: None,
+            trigger: Arc::new(Notify::new()),
+        }
+    }
+
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = Some(interval);
+        self
+    }
+
+    pub fn with_jitter(mut self, jitter: Duration) -> Self {
+        self.jitter = Some(jitter);
+        self
+    }
+
+    pub fn with_sweep(mut self, sweep: Arc<dyn Sweep>) -> Self {
+        self.sweep = Some(sweep);
+        self
+    }
+
+    pub fn with_alerts(mut self, alerts: Arc<dyn Evaluate>) -> Self {
+        self.alerts = Some(alerts);
+        self
+    }
+
+    pub fn with_notifier(mut self, notifier: Arc<dyn Notifier>, schema_version: u32) -> Self {
+        self.notifier = Some((notifier, schema_version));
+        self
+    }
+
+    pub fn with_delivery(mut self, delivery: Arc<dyn Deliver>) -> Self {
+        self.delivery = Some(delivery);
+        self
+    }
+
+    pub fn with_events(mut self, events: Arc<EventBus>) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    pub fn with_compaction(mut self, compact: Arc<dyn Compact>, window: CompactionWindow) -> Self {
+        self.compaction = Some((compact, window));
+        self
+    }
+
+    pub fn with_downsampling(mut self, downsample: Arc<dyn Downsample>) -> Self {
+        self.downsample = Some(downsample);
+        self
+    }
+
+    pub fn with_reports(mut self, export: Arc<dyn Export>, schedule: ReportSchedule) -> Self {
+        self.reports = Some((export, schedule));
+        self
+    }
+
+    pub fn with_portfolio(mut self, portfolio: Arc<dyn Portfolio>) -> Self {
+        self.portfolio = Some(portfolio);
+        self
+    }
+
+    pub fn trigger(&self) -> Arc<Notify> {
+        self.trigger.clone()
+    }
+
+    pub async fn run(self, shutdown: CancellationToken) {
+        let interval = self.interval.unwrap_or_else(|| {
+            info!("using default refresh interval");
+            Duration::from_secs(60)
+        });
+        let jitter = self.jitter.unwrap_or_default();
+
+        info!("started refresh scheduler every {interval:?} (+{jitter:?} jitter)");
+
+        let mut events = self.events.as_ref().map(|events| events.subscribe());
+
+        // The first pass runs right away. Passes are kept to a deadline so
+        // events arriving in between don't push the next one back.
+        let mut next_pass = Instant::now();
+        let mut last_compaction = None;
+        let mut last_report = None;
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => {
+                    break;
+                }
+                Some(event) = next_event(&mut events) => {
+                    self.handle_event(event);
+                }
+                _ = sleep_until(next_pass) => {
+                    self.run_refresh().await;
+                    self.run_delivery().await;
+                    self.run_sweep().await;
+                    self.run_collection().await;
+                    self.report_portfolio().await;
+                    self.run_compaction(&mut last_compaction).await;
+                    self.write_report(&mut last_report).await;
+                    next_pass = Instant::now() + interval + jitter.mul_f64(fastrand::f64());
+                }
+                _ = self.trigger.notified() => {
+                    debug!("refreshing on request");
+                    self.run_refresh().await;
+                    self.run_delivery().await;
+                    self.run_sweep().await;
+                }
+            }
+        }
+
+        info!("stopped refresh scheduler");
+    }
+
+    async fn run_refresh(&self) {
+        match self.refresh.execute().await {
+            Ok(summary) => {
+                for (name, e) in &summary.failed {
+                    warn!("couldn't refresh wallet {name}: {}", compose_error(e));
+                }
+                debug!(
+                    "refreshed {} wallets, {} fresh, {} failed",
+                    summary.refreshed,
+                    summary.skipped,
+                    summary.failed.len()
+                );
+                self.run_alerts(&summary.changed).await;
+            }
+            Err(e) => error!("{}", compose_error(&e)),
+        }
+    }
+
+    async fn run_alerts(&self, changes: &[BalanceChange]) {
+        let Some(alerts) = &self.alerts else {
+            return;
+        };
+
+        match alerts.execute(changes).await {
+            Ok(fired) => {
+                for alert in fired {
+                    warn!(
+                        "alert {} fired for wallet {}: {} -> {} wei",
+                        alert.rule.id, alert.change.name, alert.change.before, alert.change.after
+                    );
+                    self.notify(Notification::AlertFired {
+                        rule_id: alert.rule.id,
+                        wallet_id: alert.change.wallet_id,
+                        name: alert.change.name,
+                        before: alert.change.before,
+                        after: alert.change.after,
+                    });
+                }
+            }
+            Err(e) => error!("{}", compose_error(&e)),
+        }
+    }
+
+    fn handle_event(&self, event: WalletEvent) {
+        match event {
+            WalletEvent::Tracked { wallet_id, name } => {
+                debug!("wallet {name} ({wallet_id}) tracked");
+            }
+            WalletEvent::Untracked { wallet_id, name } => {
+                debug!("wallet {name} ({wallet_id}) untracked");
+            }
+            WalletEvent::Renamed {
+                wallet_id,
+                from,
+                to,
+            } => {
+                debug!("wallet {from} ({wallet_id}) renamed to {to}");
+            }
+            WalletEvent::BalanceChanged(change) => {
+                debug!(
+                    "wallet {} balance moved {} -> {} wei",
+                    change.name, change.before, change.after
+                );
+            }
+            WalletEvent::AddressChanged {
+                wallet_id,
+                name,
+                from,
+                to,
+            } => {
+                info!("wallet {name} ({wallet_id}) followed its name from {from} to {to}");
+            }
+        }
+    }
+
+    fn notify(&self, notification: Notification) {
+        let Some((notifier, schema_version)) = self.notifier.clone() else {
+            return;
+        };
+
+        tokio::spawn(async move {
+            let _ = notifier
+                .notify(&notification, schema_version)
+                .await
+                .inspect_err(|e| {
+                    error!("{}", compose_error(e));
+                });
+        });
+    }
+
+    async fn run_delivery(&self) {
+        let Some(delivery) = &self.delivery else {
+            return;
+        };
+
+        match delivery.execute().await {
+            Ok(summary) if summary.failed > 0 => warn!(
+                "delivered {} notifications, {} failed, {} waiting to retry",
+                summary.delivered, summary.failed, summary.waiting
+            ),
+            Ok(summary) => debug!(
+                "delivered {} notifications, {} waiting to retry",
+                summary.delivered, summary.waiting
+            ),
+            Err(e) => error!("{}", compose_error(&e)),
+        }
+    }
+
+    async fn run_sweep(&self) {
+        let Some(sweep) = &self.sweep else {
+            return;
+        };
+
+        match sweep.execute().await {
+            Ok(summary) => debug!(
+                "{} deposit wallets need a sweep, {} new, {} archived",
+                summary.pending,
+                summary.newly_pending,
+                summary.archived.len()
+            ),
+            Err(e) => error!("{}", compose_error(&e)),
+        }
+    }
+
+    async fn run_collection(&self) {
+        match self.collect.execute().await {
+            Ok(collection) => {
+                if let Some(compaction) = collection.compaction {
+                    debug!(
+                        "purged {} expired wallets and reclaimed {} bytes",
+                        collection.purged,
+                        compaction.reclaimed_bytes()
+                    );
+                }
+            }
+            Err(e) => error!("{}", compose_error(&e)),
+        }
+    }
+
+    async fn run_compaction(&self, last_compaction: &mut Option<Instant>) {
+        let Some((compact, window)) = &self.compaction else {
+            return;
+        };
+
+        if !window.contains(Utc::now().time()) {
+            return;
+        }
+        if last_compaction.is_some_and(|last: Instant| last.elapsed() < window.every) {
+            return;
+        }
+
+        *last_compaction = Some(Instant::now());
+        if let Some(downsample) = &self.downsample
+            && let Err(e) = downsample.execute().await
+        {
+            error!("{}", compose_error(&e));
+        }
+        match compact.execute().await {
+            Ok(compaction) => debug!(
+                "scheduled compaction reclaimed {} bytes in {:?}",
+                compaction.reclaimed_bytes(),
+                compaction.duration
+            ),
+            Err(e) => error!("{}", compose_error(&e)),
+        }
+    }
+
+    async fn write_report(&self, last_report: &mut Option<Instant>) {
+        let Some((export, schedule)) = &self.reports else {
+            return;
+        };
+
+        if last_report.is_some_and(|last: Instant| last.elapsed() < schedule.every) {
+            return;
+        }
+
+        *last_report = Some(Instant::now());
+        let report = match export.execute(schedule.format).await {
+            Ok(report) => report,
+            Err(e) => {
+                error!("{}", compose_error(&e));
+                return;
+            }
+        };
+
+        let path = schedule.dir.join(format!(
+            "wallets-{}.{}",
+            Utc::now().format("%Y%m%dT%H%M%SZ"),
+            schedule.format.extension()
+        ));
+        let written = async {
+            tokio::fs::create_dir_all(&schedule.dir).await?;
+            tokio::fs::write(&path, report).await
+        };
+        match written.await {
+            Ok(()) => debug!("wrote wallet report {}", path.display()),
+            Err(e) => error!("couldn't write wallet report {}: {e}", path.display()),
+        }
+    }
+
+    async fn report_portfolio(&self) {
+        let Some(portfolio) = &self.portfolio else {
+            return;
+        };
+
+        match portfolio.execute().await {
+            Ok(summary) => {
+                info!(
+                    "portfolio holds {} ETH across {} wallets",
+                    summary.total.balance, summary.total.wallets
+                );
+                for (tag, total) in &summary.by_tag {
+                    debug!(
+                        "portfolio tag {tag} holds {} ETH across {} wallets",
+                        total.balance, total.wallets
+                    );
+                }
+            }
+            Err(e) => error!("{}", compose_error(&e)),
+        }
+    }
+}
+
+async fn next_event(events: &mut Option<Subscription>) -> Option<WalletEvent> {
+    let receiver = events.as_mut()?;
+    loop {
+        match receiver.recv().await {
+            Ok(event) => return Some(event),
+            Err(RecvError::Lagged(missed)) => warn!("scheduler missed {missed} wallet events"),
+            Err(RecvError::Closed) => {
+                *events = None;
+                return None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use chrono::NaiveTime;
+
+    use super::CompactionWindow;
+
+    #[test]
+    fn scheduler_compaction_window() {
+        let at = |hour| NaiveTime::from_hms_opt(hour, 0, 0).unwrap();
+        let window = |start, end| CompactionWindow {
+            start: at(start),
+            end: at(end),
+            every: Duration::from_secs(24 * 60 * 60),
+        };
+
+        let day = window(2, 5);
+        assert!(day.contains(at(2)));
+        assert!(day.contains(at(4)));
+        assert!(!day.contains(at(5)));
+        assert!(!day.contains(at(23)));
+
+        let overnight = window(23, 2);
+        assert!(overnight.contains(at(23)));
+        assert!(overnight.contains(at(0)));
+        assert!(!overnight.contains(at(2)));
+        assert!(!overnight.contains(at(12)));
+    }
+}
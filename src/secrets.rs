@@ -0,0 +1,202 @@
+use std::{error, fmt, path::PathBuf, time::Duration};
+
+use async_trait::async_trait;
+use reqwest::{Client, StatusCode};
+use serde_json::Value;
+use tokio::{process::Command, sync::OnceCell};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum SecretSource {
+    #[default]
+    Unset,
+    Env(String),
+    File(String),
+    Provider(String),
+}
+
+#[derive(Debug)]
+pub struct SecretsError(Box<dyn error::Error + Send + Sync + 'static>);
+
+impl fmt::Display for SecretsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "secret loading error")
+    }
+}
+
+impl error::Error for SecretsError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&*self.0)
+    }
+}
+
+impl From<reqwest::Error> for SecretsError {
+    fn from(error: reqwest::Error) -> Self {
+        Self(error.into())
+    }
+}
+
+#[async_trait]
+pub trait SecretsProvider: Send + Sync + 'static {
+    async fn secret(&self, reference: &str) -> Result<Option<String>, SecretsError>;
+}
+
+pub async fn resolve(
+    source: &SecretSource,
+    provider: Option<&dyn SecretsProvider>,
+) -> Result<Option<String>, SecretsError> {
+    match source {
+        SecretSource::Unset => Ok(None),
+        SecretSource::Env(name) => Ok(std::env::var(name).ok()),
+        SecretSource::File(path) => {
+            let secret = tokio::fs::read_to_string(path)
+                .await
+                .map_err(|e| SecretsError(format!("couldn't read {path}: {e}").into()))?;
+            Ok(Some(secret.trim_end_matches(['\r', '\n']).to_string()))
+        }
+        SecretSource::Provider(reference) => {
+            let provider = provider.ok_or_else(|| {
+                SecretsError(format!("no secrets provider to look up {reference}").into())
+            })?;
+            provider.secret(reference).await
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct VaultSecrets {
+    client: Client,
+    addr: String,
+    token: String,
+}
+
+impl fmt::Debug for VaultSecrets {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VaultSecrets")
+            .field("addr", &self.addr)
+            .finish()
+    }
+}
+
+impl VaultSecrets {
+    pub fn new(addr: impl Into<String>, token: impl Into<String>) -> Result<Self, SecretsError> {
+        Ok(Self {
+            client: Client::builder().timeout(Duration::from_secs(10)).build()?,
+            addr: addr.into().trim_end_matches('/').to_string(),
+            token: token.into(),
+        })
+    }
+}
+
+#[async_trait]
+impl SecretsProvider for VaultSecrets {
+    async fn secret(&self, reference: &str) -> Result<Option<String>, SecretsError> {
+        let (path, field) = split_reference(reference)?;
+
+        let response = self
+            .client
+            .get(format!("{}/v1/{path}", self.addr))
+            .header("x-vault-token", &self.token)
+            .send()
+            .await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        let body: Value = response.error_for_status()?.json().await?;
+        Ok(lookup(&body["data"]["data"], field))
+    }
+}
+
+#[derive(Debug)]
+pub struct SopsSecrets {
+    path: PathBuf,
+    document: OnceCell<Value>,
+}
+
+impl SopsSecrets {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            document: OnceCell::new(),
+        }
+    }
+
+    async fn decrypt(&self) -> Result<Value, SecretsError> {
+        let output = Command::new("sops")
+            .args(["--decrypt", "--output-type", "json"])
+            .arg(&self.path)
+            .output()
+            .await
+            .map_err(|e| SecretsError(format!("couldn't run sops: {e}").into()))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(SecretsError(
+                format!("sops failed: {}", stderr.trim()).into(),
+            ));
+        }
+
+        serde_json::from_slice(&output.stdout).map_err(|e| SecretsError(e.into()))
+    }
+}
+
+#[async_trait]
+impl SecretsProvider for SopsSecrets {
+    async fn secret(&self, reference: &str) -> Result<Option<String>, SecretsError> {
+        let document = self.document.get_or_try_init(|| self.decrypt()).await?;
+        let mut value = document;
+        for key in reference.split('.') {
+            match value.get(key) {
+                Some(next) => value = next,
+                None => return Ok(None),
+            }
+        }
+        Ok(value.as_str().map(str::to_owned))
+    }
+}
+
+fn split_reference(reference: &str) -> Result<(&str, &str), SecretsError> {
+    reference
+        .split_once('#')
+        .ok_or_else(|| SecretsError(format!("{reference} must name a field after #").into()))
+}
+
+fn lookup(data: &Value, field: &str) -> Option<String> {
+    data.get(field)?.as_str().map(str::to_owned)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use serde_json::json;
+
+    use super::{SecretSource, lookup, resolve, split_reference};
+
+    #[tokio::test]
+    async fn secrets_resolve_file() {
+        let path = env::temp_dir().join(format!("mini-wallet-secret-{}", std::process::id()));
+        tokio::fs::write(&path, "hunter2\n").await.unwrap();
+
+        let source = SecretSource::File(path.to_string_lossy().into_owned());
+        let secret = resolve(&source, None).await.unwrap();
+        tokio::fs::remove_file(&path).await.unwrap();
+        assert_eq!(secret.as_deref(), Some("hunter2"));
+
+        let source = SecretSource::Provider("secret/data/app#key".to_string());
+        assert!(resolve(&source, None).await.is_err());
+        assert_eq!(resolve(&SecretSource::Unset, None).await.unwrap(), None);
+    }
+
+    #[test]
+    fn secrets_vault_reference() {
+        let (path, field) = split_reference("secret/data/mini-wallet#webhook").unwrap();
+        assert_eq!(path, "secret/data/mini-wallet");
+        assert_eq!(field, "webhook");
+        assert!(split_reference("secret/data/mini-wallet").is_err());
+
+        let data = json!({ "webhook": "s3cret", "count": 1 });
+        assert_eq!(lookup(&data, "webhook").as_deref(), Some("s3cret"));
+        assert_eq!(lookup(&data, "count"), None);
+        assert_eq!(lookup(&data, "missing"), None);
+    }
+}
@@ -1,37 +1,82 @@
 use std::{
     any::type_name,
-    error, fmt,
+    error, fmt, io,
     net::{IpAddr, Ipv4Addr, SocketAddr},
+    pin::Pin,
     sync::Arc,
     time::Duration,
 };
 
 use async_trait::async_trait;
-use prost_types::Timestamp;
+use futures::{Stream, stream};
+use prost_types::{Duration as ProtoDuration, FieldMask, Timestamp};
 use tokio::{
+    net::TcpListener,
     signal,
-    sync::oneshot::{self, Sender},
+    sync::{
+        Notify,
+        oneshot::{self, Sender},
+    },
     task::JoinHandle,
-    time::interval,
 };
+use tokio_util::sync::CancellationToken;
 use tonic::{
     Request, Response, Result, Status,
+    body::Body,
+    codegen::{
+        Body as HttpBody, Bytes, Service, StdError,
+        http::{Request as HttpRequest, Response as HttpResponse},
+    },
+    service::Routes,
     transport::{Error as TransportError, Server as InnerServer},
 };
 use tonic_reflection::server::{Builder as ReflectionBuilder, Error as ReflectionError};
+use tower::{
+    Layer,
+    layer::util::{Identity, Stack},
+};
 use tracing::{debug, error, info};
 
-use crate::wallet::{self, WalletError, WalletErrorKind};
+use crate::{
+    admin::{self, AdminError, AdminErrorKind},
+    alert::{self, AlertError, AlertErrorKind},
+    cache::CacheMetrics,
+    core::{AlertCondition, Balance},
+    http::{self, BalanceGauges},
+    infra, payload,
+    wallet::{self, RecvError, WalletError, WalletErrorKind},
+};
+use admin_proto::{
+    BackupResponse, CollectGarbageResponse, CompactResponse, DigestResponse, GetEndpointResponse,
+    SetEndpointRequest, SetEndpointResponse, SnapshotRequest, SnapshotResponse, StatsResponse,
+    admin_service_server::{AdminService, AdminServiceServer},
+};
 use proto::{
-    FILE_DESCRIPTOR_SET, ListResponse, TrackRequest, UntrackRequest, Wallet,
+    AlertRule, BalanceCandle, ChainDonations, DeleteAlertRuleRequest, DeleteViewRequest,
+    DonationPeriod, DonationsReportRequest, DonationsReportResponse, ExportFormat, ExportRequest,
+    ExportResponse, FILE_DESCRIPTOR_SET, FieldError, GetRequest, HistoryRequest, HistoryResponse,
+    ImportFormat, ImportRejection, ImportRequest, ImportResponse, Interface,
+    ListAlertRulesResponse, ListRequest, ListResponse, ListSort, ListViewsResponse, NftHolding,
+    NftHoldingsRequest, NftHoldingsResponse, OwnershipChallengeRequest, OwnershipChallengeResponse,
+    PendingSweep, PendingSweepsResponse, PinWalletRequest, PortfolioTotal, Provenance,
+    PurgeRequest, PurgeResponse, RenameWalletRequest, RestoreRequest, SavedView, SearchRequest,
+    SearchResponse, ServerInfoResponse, ShareWalletRequest, ShareWalletResponse, SummaryResponse,
+    TagTotal, TokenBalance, TrackRequest, TrackResponse, Transaction, TransactionsRequest,
+    TransactionsResponse, TransferDirection, UntrackRequest, UpdateWalletRequest,
+    VerifyOwnershipRequest, Wallet, WatchEvent, WatchRequest,
+    alert_rule::Condition,
     wallet_service_server::{WalletService, WalletServiceServer},
 };
 
-mod proto {
+pub(crate) mod proto {
     pub const FILE_DESCRIPTOR_SET: &[u8] = tonic::include_file_descriptor_set!("descriptor");
     tonic::include_proto!("wallet.v1");
 }
 
+mod admin_proto {
+    tonic::include_proto!("admin.v1");
+}
+
 #[derive(Debug)]
 pub struct ApiError(Box<dyn error::Error + Send + Sync + 'static>);
 
@@ -53,6 +98,12 @@ impl From<TransportError> for ApiError {
     }
 }
 
+impl From<io::Error> for ApiError {
+    fn from(error: io::Error) -> Self {
+        ApiError(error.into())
+    }
+}
+
 impl From<ReflectionError> for ApiError {
     fn from(error: ReflectionError) -> Self {
         ApiError(error.into())
@@ -62,9 +113,35 @@ impl From<ReflectionError> for ApiError {
 #[derive(Clone)]
 pub struct Controller {
     pub wallet_list: Arc<dyn wallet::List>,
+    pub wallet_get: Arc<dyn wallet::Get>,
+    pub wallet_search: Arc<dyn wallet::Search>,
+    pub wallet_portfolio: Arc<dyn wallet::Portfolio>,
+    pub wallet_pending_sweeps: Arc<dyn wallet::PendingSweeps>,
     pub wallet_track: Arc<dyn wallet::Track>,
-    pub wallet_refresh: Arc<dyn wallet::Refresh>,
+    pub wallet_import: Arc<dyn wallet::Import>,
+    pub wallet_export: Arc<dyn wallet::Export>,
+    pub wallet_nft_holdings: Option<Arc<dyn wallet::NftHoldings>>,
+    pub wallet_transactions: Option<Arc<dyn wallet::Transactions>>,
+    pub wallet_donations: Option<Arc<dyn wallet::Donations>>,
+    pub wallet_history: Option<Arc<dyn wallet::History>>,
+    pub wallet_update: Arc<dyn wallet::Update>,
+    pub wallet_rename: Arc<dyn wallet::Rename>,
+    pub wallet_pin: Arc<dyn wallet::Pin>,
+    pub wallet_save_view: Arc<dyn wallet::SaveView>,
+    pub wallet_views: Arc<dyn wallet::Views>,
+    pub wallet_delete_view: Arc<dyn wallet::DeleteView>,
+    pub wallet_share: Arc<dyn wallet::Share>,
+    pub wallet_shared: Arc<dyn wallet::ViewShared>,
+    pub wallet_challenge: Arc<dyn wallet::Challenge>,
+    pub wallet_verify: Arc<dyn wallet::Verify>,
     pub wallet_untrack: Arc<dyn wallet::Untrack>,
+    pub wallet_restore: Arc<dyn wallet::Restore>,
+    pub wallet_purge: Arc<dyn wallet::Purge>,
+    pub wallet_watch: Arc<dyn wallet::Watch>,
+    pub alert_create: Arc<dyn alert::Create>,
+    pub alert_list: Arc<dyn alert::List>,
+    pub alert_delete: Arc<dyn alert::Delete>,
+    pub server_info: Arc<dyn admin::Info>,
 }
 
 impl fmt::Debug for Controller {
@@ -73,11 +150,37 @@ impl fmt::Debug for Controller {
     }
 }
 
+#[derive(Clone)]
+pub struct AdminController {
+    pub admin_stats: Arc<dyn admin::Stats>,
+    pub admin_compact: Arc<dyn admin::Compact>,
+    pub admin_collect: Arc<dyn admin::Collect>,
+    pub admin_backup: Arc<dyn admin::Backup>,
+    pub admin_snapshot: Arc<dyn admin::Snapshot>,
+    pub admin_digest: Arc<dyn admin::Digest>,
+    pub admin_endpoint: Arc<dyn admin::Endpoint>,
+}
+
+impl fmt::Debug for AdminController {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>()).finish()
+    }
+}
+
 #[derive(Debug, Clone)]
-pub struct Server {
+pub struct Server<L = Identity> {
     controller: Controller,
     addr: Option<IpAddr>,
     port: Option<u16>,
+    http_port: Option<u16>,
+    balance_gauges: Option<BalanceGauges>,
+    compactions: Option<Arc<admin::Compactions>>,
+    cache: Option<Arc<CacheMetrics>>,
+    admin: Option<AdminController>,
+    admin_port: Option<u16>,
+    refresh_now: Arc<Notify>,
+    shutdown: CancellationToken,
+    layer: L,
 }
 
 impl Server {
@@ -86,6 +189,34 @@ impl Server {
             controller,
             addr: None,
             port: None,
+            http_port: None,
+            balance_gauges: None,
+            compactions: None,
+            cache: None,
+            admin: None,
+            admin_port: None,
+            refresh_now: Arc::new(Notify::new()),
+            shutdown: CancellationToken::new(),
+            layer: Identity::new(),
+        }
+    }
+}
+
+impl<L> Server<L> {
+    pub fn with_layer<N>(self, layer: N) -> Server<Stack<N, L>> {
+        Server {
+            controller: self.controller,
+            addr: self.addr,
+            port: self.port,
+            http_port: self.http_port,
+            balance_gauges: self.balance_gauges,
+            compactions: self.compactions,
+            cache: self.cache,
+            admin: self.admin,
+            admin_port: self.admin_port,
+            refresh_now: self.refresh_now,
+            shutdown: self.shutdown,
+            layer: Stack::new(layer, self.layer),
         }
     }
 
@@ -99,9 +230,56 @@ impl Server {
         self
     }
 
-    pub async fn run(self) -> Result<(), ApiError> {
-        let (refresh_handle, refresh_shutdown) = spawn_refresh_loop(&self.controller).await;
+    pub fn with_http_port(mut self, port: u16) -> Self {
+        self.http_port = Some(port);
+        self
+    }
+
+    pub fn with_balance_gauges(mut self, balance_gauges: BalanceGauges) -> Self {
+        self.balance_gauges = Some(balance_gauges);
+        self
+    }
+
+    pub fn with_compaction_metrics(mut self, compactions: Arc<admin::Compactions>) -> Self {
+        self.compactions = Some(compactions);
+        self
+    }
 
+    pub fn with_cache_metrics(mut self, cache: Arc<CacheMetrics>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    pub fn with_admin(mut self, admin: AdminController) -> Self {
+        self.admin = Some(admin);
+        self
+    }
+
+    pub fn with_admin_port(mut self, port: u16) -> Self {
+        self.admin_port = Some(port);
+        self
+    }
+
+    pub fn with_refresh_trigger(mut self, refresh_now: Arc<Notify>) -> Self {
+        self.refresh_now = refresh_now;
+        self
+    }
+
+    pub fn with_shutdown(mut self, shutdown: CancellationToken) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+
+    pub async fn run<ResBody>(self) -> Result<(), ApiError>
+    where
+        L: Layer<Routes> + Clone,
+        L::Service:
+            Service<HttpRequest<Body>, Response = HttpResponse<ResBody>> + Clone + Send + 'static,
+        <L::Service as Service<HttpRequest<Body>>>::Future: Send,
+        <L::Service as Service<HttpRequest<Body>>>::Error: Into<StdError> + Send,
+        ResBody: HttpBody<Data = Bytes> + Send + 'static,
+        ResBody::Error: Into<StdError>,
+    {
         let addr = self.addr.unwrap_or_else(|| {
             info!("using default address");
             Ipv4Addr::new(0, 0, 0, 0).into()
@@ -112,55 +290,119 @@ impl Server {
             50051
         });
 
+        let http = match self.http_port {
+            Some(http_port) => Some(
+                spawn_http_listener(
+                    &self.controller,
+                    self.balance_gauges,
+                    self.compactions,
+                    self.cache,
+                    self.admin.as_ref().map(|admin| admin.admin_stats.clone()),
+                    SocketAddr::new(addr, http_port),
+                )
+                .await?,
+            ),
+            None => None,
+        };
+
+        let admin = match self.admin {
+            Some(admin) => {
+                let admin_port = self.admin_port.unwrap_or_else(|| {
+                    info!("using default admin port");
+                    50052
+                });
+                let socket = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), admin_port);
+                Some(spawn_admin_server(admin, socket))
+            }
+            None => None,
+        };
+
         let server_reflection = ReflectionBuilder::configure()
             .register_encoded_file_descriptor_set(FILE_DESCRIPTOR_SET)
             .build_v1()?;
 
         let server_wallet = WalletServiceServer::new(WalletServer {
             controller: self.controller,
+            refresh_now: self.refresh_now,
         });
 
         let socket = SocketAddr::new(addr, port);
         info!("started grpc server on {socket}");
 
         InnerServer::builder()
+            .layer(self.layer)
             .add_service(server_reflection)
             .add_service(server_wallet)
-            .serve_with_shutdown(socket, capture_shutdown_signal())
+            .serve_with_shutdown(socket, capture_shutdown_signal(self.shutdown))
             .await?;
 
-        let _ = refresh_shutdown.send(());
-        let _ = refresh_handle.await;
+        if let Some((admin_handle, admin_shutdown)) = admin {
+            let _ = admin_shutdown.send(());
+            let _ = admin_handle.await;
+        }
+
+        if let Some((http_handle, http_shutdown)) = http {
+            let _ = http_shutdown.send(());
+            let _ = http_handle.await;
+        }
+
         info!("exited with success");
         Ok(())
     }
 }
 
-async fn spawn_refresh_loop(controller: &Controller) -> (JoinHandle<()>, Sender<()>) {
-    let refresh = controller.wallet_refresh.clone();
-    let (tx, mut rx) = oneshot::channel();
+async fn spawn_http_listener(
+    controller: &Controller,
+    balance_gauges: Option<BalanceGauges>,
+    compactions: Option<Arc<admin::Compactions>>,
+    cache: Option<Arc<CacheMetrics>>,
+    store_stats: Option<Arc<dyn admin::Stats>>,
+    socket: SocketAddr,
+) -> Result<(JoinHandle<()>, Sender<()>), ApiError> {
+    let router = http::router(
+        controller.wallet_list.clone(),
+        controller.wallet_get.clone(),
+        controller.wallet_shared.clone(),
+        balance_gauges,
+        compactions,
+        cache,
+        store_stats,
+    );
+    let listener = TcpListener::bind(socket).await?;
+    let (tx, rx) = oneshot::channel::<()>();
 
     let handle = tokio::spawn(async move {
-        let mut interval = interval(Duration::from_secs(60));
+        let _ = axum::serve(listener, router)
+            .with_graceful_shutdown(async move {
+                let _ = rx.await;
+            })
+            .await
+            .inspect_err(|e| error!("{}", compose_error(e)));
+    });
 
-        loop {
-            tokio::select! {
-                _ = &mut rx => {
-                    break;
-                }
-                _ = interval.tick() => {
-                    let _ = refresh.execute().await.inspect_err(|e| {
-                        error!("{}", compose_error(e));
-                    });
-                }
-            }
-        }
+    info!("started http listener on {socket}");
+    Ok((handle, tx))
+}
+
+fn spawn_admin_server(admin: AdminController, socket: SocketAddr) -> (JoinHandle<()>, Sender<()>) {
+    let (tx, rx) = oneshot::channel::<()>();
+    let server_admin = AdminServiceServer::new(AdminServer { admin });
+
+    let handle = tokio::spawn(async move {
+        let _ = InnerServer::builder()
+            .add_service(server_admin)
+            .serve_with_shutdown(socket, async move {
+                let _ = rx.await;
+            })
+            .await
+            .inspect_err(|e| error!("{}", compose_error(e)));
     });
 
+    info!("started admin grpc server on {socket}");
     (handle, tx)
 }
 
-async fn capture_shutdown_signal() {
+async fn capture_shutdown_signal(shutdown: CancellationToken) {
     let interrupt = async {
         signal::ctrl_c()
             .await
@@ -181,48 +423,138 @@ async fn capture_shutdown_signal() {
     tokio::select! {
         _ = interrupt => {},
         _ = terminate => {},
+        _ = shutdown.cancelled() => {
+            info!("shutdown requested");
+            return;
+        }
     }
 
-    info!("received shutdown signal")
+    info!("received shutdown signal");
+    shutdown.cancel();
 }
 
 #[derive(Debug, Clone)]
 struct WalletServer {
     controller: Controller,
+    refresh_now: Arc<Notify>,
 }
 
+type WatchStream = Pin<Box<dyn Stream<Item = Result<WatchEvent>> + Send>>;
+
 #[async_trait]
 impl WalletService for WalletServer {
-    async fn list(&self, _request: Request<()>) -> Result<Response<ListResponse>> {
+    type WatchStream = WatchStream;
+
+    async fn list(&self, request: Request<ListRequest>) -> Result<Response<ListResponse>> {
         debug!("received list request");
 
+        let viewer = provenance(&request).principal;
+        let request = request.into_inner();
+        let created_via = request
+            .created_via
+            .map(|i| Interface::try_from(i).map(interface_from_proto))
+            .transpose()
+            .map_err(|_| Status::invalid_argument("unknown created_via interface"))?;
+        let sort = request
+            .sort
+            .map(|s| ListSort::try_from(s).map(sort_from_proto))
+            .transpose()
+            .map_err(|_| Status::invalid_argument("unknown sort"))?;
+
+        let view = match request.view {
+            Some(name) => self
+                .controller
+                .wallet_views
+                .execute(&viewer)
+                .await
+                .map_err(|e| handle_error_status(&e))?
+                .into_iter()
+                .find(|view| view.name == name.trim())
+                .ok_or(Status::not_found(format!(
+                    "saved view \"{name}\" not found"
+                )))?,
+            None => infra::SavedView::default(),
+        };
+
+        let tags = if request.tags.is_empty() {
+            view.tags
+        } else {
+            request.tags
+        };
+        let filter = wallet::ListFilter {
+            tags,
+            created_by: request.created_by.or(view.created_by),
+            created_via: created_via.or(view.created_via),
+            viewer: Some(viewer),
+            sort: sort.unwrap_or(view.sort),
+        };
+
         let wallets = self
             .controller
             .wallet_list
-            .execute()
+            .execute(&filter)
             .await
             .map_err(|e| handle_error_status(&e))?;
 
+        let wallets = wallets.into_iter().map(wallet_to_proto).collect::<Vec<_>>();
+        let partial = wallets.iter().any(|wallet| !wallet.field_errors.is_empty());
         let wallets = wallets
             .into_iter()
-            .map(|w| Wallet {
-                name: Some(w.name),
-                address: Some(w.address),
-                balance: Some(w.balance),
-                last_update: Some(Timestamp {
-                    seconds: w.last_update.timestamp(),
-                    nanos: 0,
-                }),
-            })
+            .map(|wallet| select_fields(wallet, &view.fields))
             .collect();
 
         debug!("completed list request");
-        Ok(Response::new(ListResponse { wallet: wallets }))
+        Ok(Response::new(ListResponse {
+            wallet: wallets,
+            partial: Some(partial),
+        }))
+    }
+
+    async fn get(&self, request: Request<GetRequest>) -> Result<Response<Wallet>> {
+        debug!("received get request");
+
+        let name = request
+            .into_inner()
+            .name
+            .ok_or(Status::invalid_argument("missing required name"))?;
+
+        let wallet = self
+            .controller
+            .wallet_get
+            .execute(&name)
+            .await
+            .map_err(|e| handle_error_status(&e))?;
+
+        debug!("completed get request");
+        Ok(Response::new(wallet_to_proto(wallet)))
     }
 
-    async fn track(&self, request: Request<TrackRequest>) -> Result<Response<()>> {
+    async fn search(&self, request: Request<SearchRequest>) -> Result<Response<SearchResponse>> {
+        debug!("received search request");
+
+        let request = request.into_inner();
+        let query = request
+            .query
+            .ok_or(Status::invalid_argument("missing required query"))?;
+        let limit = request.limit.unwrap_or(20) as usize;
+
+        let wallets = self
+            .controller
+            .wallet_search
+            .execute(&query, limit)
+            .await
+            .map_err(|e| handle_error_status(&e))?;
+
+        let wallets = wallets.into_iter().map(wallet_to_proto).collect();
+
+        debug!("completed search request");
+        Ok(Response::new(SearchResponse { wallet: wallets }))
+    }
+
+    async fn track(&self, request: Request<TrackRequest>) -> Result<Response<TrackResponse>> {
         debug!("received track request");
 
+        let created_by = provenance(&request);
         let request = request.into_inner();
         let name = request
             .name
@@ -231,16 +563,467 @@ impl WalletService for WalletServer {
             .address
             .ok_or(Status::invalid_argument("missing required address"))?;
 
-        self.controller
+        let skip_balance = !request.fetch_balance.unwrap_or(true);
+        let validate_only = request.validate_only.unwrap_or_default();
+        let wallet = wallet::NewWallet {
+            name,
+            address,
+            linked_addresses: request.linked_addresses,
+            tags: request.tags,
+            note: request.note.unwrap_or_default(),
+            skip_balance,
+            validate_only,
+            created_by,
+        };
+
+        let wallet = self
+            .controller
             .wallet_track
-            .execute(&name, &address)
+            .execute(wallet)
             .await
             .map_err(|e| handle_error_status(&e))?;
 
+        if skip_balance && !validate_only {
+            self.refresh_now.notify_one();
+        }
+
         debug!("completed track request");
+        Ok(Response::new(TrackResponse {
+            wallet: Some(wallet_to_proto(wallet)),
+        }))
+    }
+
+    async fn import(&self, request: Request<ImportRequest>) -> Result<Response<ImportResponse>> {
+        debug!("received import request");
+
+        let created_by = infra::Provenance {
+            interface: infra::Interface::Import,
+            ..provenance(&request)
+        };
+        let request = request.into_inner();
+        let format = match request.format.map(ImportFormat::try_from) {
+            Some(Ok(ImportFormat::Csv)) => wallet::ImportFormat::Csv,
+            Some(Ok(ImportFormat::Json)) => wallet::ImportFormat::Json,
+            _ => return Err(Status::invalid_argument("missing required format")),
+        };
+        let content = request
+            .content
+            .ok_or(Status::invalid_argument("missing required content"))?;
+
+        let skip_balance = !request.fetch_balance.unwrap_or(true);
+        let summary = self
+            .controller
+            .wallet_import
+            .execute(wallet::NewImport {
+                format,
+                content,
+                skip_balance,
+                created_by,
+            })
+            .await
+            .map_err(|e| handle_error_status(&e))?;
+
+        if skip_balance && !summary.imported.is_empty() {
+            self.refresh_now.notify_one();
+        }
+
+        debug!("completed import request");
+        Ok(Response::new(ImportResponse {
+            imported: summary.imported,
+            rejected: summary
+                .rejected
+                .into_iter()
+                .map(|rejection| ImportRejection {
+                    row: Some(rejection.row as u64),
+                    name: Some(rejection.name),
+                    reason: Some(rejection.error.to_string()),
+                })
+                .collect(),
+        }))
+    }
+
+    async fn export(&self, request: Request<ExportRequest>) -> Result<Response<ExportResponse>> {
+        debug!("received export request");
+
+        let format = match request.into_inner().format.map(ExportFormat::try_from) {
+            Some(Ok(ExportFormat::Csv)) => wallet::ExportFormat::Csv,
+            Some(Ok(ExportFormat::Json)) => wallet::ExportFormat::Json,
+            Some(Ok(ExportFormat::Ndjson)) => wallet::ExportFormat::Ndjson,
+            _ => return Err(Status::invalid_argument("missing required format")),
+        };
+
+        let content = self
+            .controller
+            .wallet_export
+            .execute(format)
+            .await
+            .map_err(|e| handle_error_status(&e))?;
+
+        debug!("completed export request");
+        Ok(Response::new(ExportResponse {
+            content: Some(content),
+        }))
+    }
+
+    async fn nft_holdings(
+        &self,
+        request: Request<NftHoldingsRequest>,
+    ) -> Result<Response<NftHoldingsResponse>> {
+        debug!("received nft holdings request");
+
+        let Some(wallet_nft_holdings) = &self.controller.wallet_nft_holdings else {
+            return Err(Status::unimplemented("no nft collections are configured"));
+        };
+        let name = request
+            .into_inner()
+            .name
+            .ok_or(Status::invalid_argument("missing required name"))?;
+
+        let holdings = wallet_nft_holdings
+            .execute(&name)
+            .await
+            .map_err(|e| handle_error_status(&e))?;
+
+        debug!("completed nft holdings request");
+        Ok(Response::new(NftHoldingsResponse {
+            holdings: holdings
+                .into_iter()
+                .map(|holding| NftHolding {
+                    collection: Some(holding.collection),
+                    contract: Some(holding.contract),
+                    count: Some(holding.count),
+                })
+                .collect(),
+        }))
+    }
+
+    async fn transactions(
+        &self,
+        request: Request<TransactionsRequest>,
+    ) -> Result<Response<TransactionsResponse>> {
+        debug!("received transactions request");
+
+        let Some(wallet_transactions) = &self.controller.wallet_transactions else {
+            return Err(Status::unimplemented(
+                "no transaction history provider is configured",
+            ));
+        };
+        let request = request.into_inner();
+        let name = request
+            .name
+            .ok_or(Status::invalid_argument("missing required name"))?;
+        let limit = request.limit.unwrap_or(20) as usize;
+
+        let transfers = wallet_transactions
+            .execute(&name, limit)
+            .await
+            .map_err(|e| handle_error_status(&e))?;
+
+        debug!("completed transactions request");
+        Ok(Response::new(TransactionsResponse {
+            transactions: transfers.into_iter().map(transfer_to_proto).collect(),
+        }))
+    }
+
+    async fn donations_report(
+        &self,
+        request: Request<DonationsReportRequest>,
+    ) -> Result<Response<DonationsReportResponse>> {
+        debug!("received donations report request");
+
+        let Some(wallet_donations) = &self.controller.wallet_donations else {
+            return Err(Status::unimplemented(
+                "no transaction history provider is configured",
+            ));
+        };
+        let request = request.into_inner();
+        let from = request.from.map_or(i64::MIN, |from| from.seconds);
+        let to = request.to.map_or(i64::MAX, |to| to.seconds);
+        let interval = request
+            .interval
+            .map_or(24 * 60 * 60, |interval| interval.seconds);
+        if interval < 1 {
+            return Err(Status::invalid_argument(
+                "interval must be at least a second",
+            ));
+        }
+
+        let report = wallet_donations
+            .execute(from..to, interval)
+            .await
+            .map_err(|e| handle_error_status(&e))?;
+
+        debug!("completed donations report request");
+        Ok(Response::new(DonationsReportResponse {
+            wallets: Some(report.wallets as u32),
+            chains: report
+                .chains
+                .into_iter()
+                .map(|chain| ChainDonations {
+                    chain_id: Some(chain.chain_id.get()),
+                    chain: Some(chain.chain_id.name()),
+                    wallets: Some(chain.wallets as u32),
+                    total: Some(chain.total),
+                    total_wei: Some(chain.total_wei),
+                    periods: chain
+                        .periods
+                        .into_iter()
+                        .map(|period| DonationPeriod {
+                            start: Some(Timestamp {
+                                seconds: period.start.timestamp(),
+                                nanos: 0,
+                            }),
+                            received: Some(period.received),
+                            received_wei: Some(period.received_wei),
+                        })
+                        .collect(),
+                })
+                .collect(),
+        }))
+    }
+
+    async fn history(&self, request: Request<HistoryRequest>) -> Result<Response<HistoryResponse>> {
+        debug!("received history request");
+
+        let Some(wallet_history) = &self.controller.wallet_history else {
+            return Err(Status::unimplemented("balance history is turned off"));
+        };
+        let request = request.into_inner();
+        let name = request
+            .name
+            .ok_or(Status::invalid_argument("missing required name"))?;
+        let from = request.from.map_or(i64::MIN, |from| from.seconds);
+        let to = request.to.map_or(i64::MAX, |to| to.seconds);
+        let interval = request
+            .interval
+            .map_or(60 * 60, |interval| interval.seconds);
+        if interval < 1 {
+            return Err(Status::invalid_argument(
+                "interval must be at least a second",
+            ));
+        }
+
+        let candles = wallet_history
+            .execute(&name, from..to, interval)
+            .await
+            .map_err(|e| handle_error_status(&e))?;
+
+        debug!("completed history request");
+        Ok(Response::new(HistoryResponse {
+            candles: candles.into_iter().map(candle_to_proto).collect(),
+        }))
+    }
+
+    async fn update_wallet(
+        &self,
+        request: Request<UpdateWalletRequest>,
+    ) -> Result<Response<Wallet>> {
+        debug!("received update wallet request");
+
+        let modified_by = provenance(&request);
+        let request = request.into_inner();
+        let wallet = request
+            .wallet
+            .ok_or(Status::invalid_argument("missing required wallet"))?;
+        let update_mask = request
+            .update_mask
+            .ok_or(Status::invalid_argument("missing required update_mask"))?;
+        let name = wallet
+            .name
+            .ok_or(Status::invalid_argument("missing required wallet.name"))?;
+
+        if update_mask.paths.is_empty() {
+            return Err(Status::invalid_argument("update_mask is empty"));
+        }
+
+        let mut update = wallet::WalletUpdate {
+            name,
+            modified_by,
+            ..Default::default()
+        };
+        for path in &update_mask.paths {
+            match path.as_str() {
+                "address" => {
+                    let address = wallet.address.clone().ok_or(Status::invalid_argument(
+                        "missing wallet.address named in update_mask",
+                    ))?;
+                    update.address = Some(address);
+                }
+                "linked_addresses" => {
+                    update.linked_addresses = Some(wallet.linked_addresses.clone());
+                }
+                "tags" => update.tags = Some(wallet.tags.clone()),
+                "note" => update.note = Some(wallet.note.clone().unwrap_or_default()),
+                path => {
+                    return Err(Status::invalid_argument(format!(
+                        "unsupported update_mask path \"{path}\""
+                    )));
+                }
+            }
+        }
+
+        let wallet = self
+            .controller
+            .wallet_update
+            .execute(update)
+            .await
+            .map_err(|e| handle_error_status(&e))?;
+
+        debug!("completed update wallet request");
+        Ok(Response::new(wallet_to_proto(wallet)))
+    }
+
+    async fn rename_wallet(
+        &self,
+        request: Request<RenameWalletRequest>,
+    ) -> Result<Response<Wallet>> {
+        debug!("received rename wallet request");
+
+        let modified_by = provenance(&request);
+        let request = request.into_inner();
+        let name = request
+            .name
+            .ok_or(Status::invalid_argument("missing required name"))?;
+        let new_name = request
+            .new_name
+            .ok_or(Status::invalid_argument("missing required new_name"))?;
+
+        let wallet = self
+            .controller
+            .wallet_rename
+            .execute(&name, &new_name, modified_by)
+            .await
+            .map_err(|e| handle_error_status(&e))?;
+
+        debug!("completed rename wallet request");
+        Ok(Response::new(wallet_to_proto(wallet)))
+    }
+
+    async fn pin_wallet(&self, request: Request<PinWalletRequest>) -> Result<Response<()>> {
+        debug!("received pin wallet request");
+
+        let principal = provenance(&request).principal;
+        let name = request
+            .into_inner()
+            .name
+            .ok_or(Status::invalid_argument("missing required name"))?;
+
+        self.controller
+            .wallet_pin
+            .execute(&name, &principal, true)
+            .await
+            .map_err(|e| handle_error_status(&e))?;
+
+        debug!("completed pin wallet request");
         Ok(Response::new(()))
     }
 
+    async fn unpin_wallet(&self, request: Request<PinWalletRequest>) -> Result<Response<()>> {
+        debug!("received unpin wallet request");
+
+        let principal = provenance(&request).principal;
+        let name = request
+            .into_inner()
+            .name
+            .ok_or(Status::invalid_argument("missing required name"))?;
+
+        self.controller
+            .wallet_pin
+            .execute(&name, &principal, false)
+            .await
+            .map_err(|e| handle_error_status(&e))?;
+
+        debug!("completed unpin wallet request");
+        Ok(Response::new(()))
+    }
+
+    async fn share_wallet(
+        &self,
+        request: Request<ShareWalletRequest>,
+    ) -> Result<Response<ShareWalletResponse>> {
+        debug!("received share wallet request");
+
+        let request = request.into_inner();
+        let name = request
+            .name
+            .ok_or(Status::invalid_argument("missing required name"))?;
+        let ttl = request
+            .ttl
+            .map(Duration::try_from)
+            .transpose()
+            .map_err(|_| Status::invalid_argument("ttl must not be negative"))?;
+
+        let link = self
+            .controller
+            .wallet_share
+            .execute(&name, ttl)
+            .await
+            .map_err(|e| handle_error_status(&e))?;
+
+        debug!("completed share wallet request");
+        Ok(Response::new(ShareWalletResponse {
+            path: Some(format!("/shared/{}", link.token)),
+            token: Some(link.token),
+            expires_at: Some(Timestamp {
+                seconds: link.expires_at.timestamp(),
+                nanos: 0,
+            }),
+        }))
+    }
+
+    async fn ownership_challenge(
+        &self,
+        request: Request<OwnershipChallengeRequest>,
+    ) -> Result<Response<OwnershipChallengeResponse>> {
+        debug!("received ownership challenge request");
+
+        let name = request
+            .into_inner()
+            .name
+            .ok_or(Status::invalid_argument("missing required name"))?;
+
+        let challenge = self
+            .controller
+            .wallet_challenge
+            .execute(&name)
+            .await
+            .map_err(|e| handle_error_status(&e))?;
+
+        debug!("completed ownership challenge request");
+        Ok(Response::new(OwnershipChallengeResponse {
+            message: Some(challenge.message),
+            expires_at: Some(Timestamp {
+                seconds: challenge.expires_at.timestamp(),
+                nanos: 0,
+            }),
+        }))
+    }
+
+    async fn verify_ownership(
+        &self,
+        request: Request<VerifyOwnershipRequest>,
+    ) -> Result<Response<Wallet>> {
+        debug!("received verify ownership request");
+
+        let request = request.into_inner();
+        let name = request
+            .name
+            .ok_or(Status::invalid_argument("missing required name"))?;
+        let signature = request
+            .signature
+            .ok_or(Status::invalid_argument("missing required signature"))?;
+
+        let wallet = self
+            .controller
+            .wallet_verify
+            .execute(&name, &signature)
+            .await
+            .map_err(|e| handle_error_status(&e))?;
+
+        debug!("completed verify ownership request");
+        Ok(Response::new(wallet_to_proto(wallet)))
+    }
+
     async fn untrack(&self, request: Request<UntrackRequest>) -> Result<Response<()>> {
         debug!("received untrack request");
 
@@ -258,6 +1041,761 @@ impl WalletService for WalletServer {
         debug!("completed untrack request");
         Ok(Response::new(()))
     }
+
+    async fn restore(&self, request: Request<RestoreRequest>) -> Result<Response<()>> {
+        debug!("received restore request");
+
+        let name = request
+            .into_inner()
+            .name
+            .ok_or(Status::invalid_argument("missing required name"))?;
+
+        self.controller
+            .wallet_restore
+            .execute(&name)
+            .await
+            .map_err(|e| handle_error_status(&e))?;
+
+        debug!("completed restore request");
+        Ok(Response::new(()))
+    }
+
+    async fn purge(&self, request: Request<PurgeRequest>) -> Result<Response<PurgeResponse>> {
+        debug!("received purge request");
+
+        let name = request.into_inner().name;
+
+        let purged = self
+            .controller
+            .wallet_purge
+            .execute(name)
+            .await
+            .map_err(|e| handle_error_status(&e))?;
+
+        debug!("completed purge request");
+        Ok(Response::new(PurgeResponse {
+            purged: Some(purged as u64),
+        }))
+    }
+
+    async fn watch(&self, request: Request<WatchRequest>) -> Result<Response<WatchStream>> {
+        debug!("received watch request");
+
+        let request = request.into_inner();
+        let schema_version = request.schema_version.unwrap_or(payload::SCHEMA_VERSION);
+        if !payload::SCHEMA_VERSIONS.contains(&schema_version) {
+            return Err(Status::invalid_argument(format!(
+                "unsupported schema_version {schema_version}"
+            )));
+        }
+
+        let subscription = self
+            .controller
+            .wallet_watch
+            .execute(request.resume_token)
+            .await
+            .map_err(|e| handle_error_status(&e))?;
+
+        let events = stream::unfold(Some(subscription), move |subscription| async move {
+            let mut subscription = subscription?;
+            let mut missed = 0;
+            loop {
+                match subscription.recv().await {
+                    Ok(event) => {
+                        let payload = payload::render(&event, schema_version)?;
+                        let event = WatchEvent {
+                            resume_token: Some(subscription.token().to_string()),
+                            schema_version: Some(schema_version),
+                            payload: Some(payload.to_string()),
+                            missed: Some(missed),
+                        };
+                        return Some((Ok(event), Some(subscription)));
+                    }
+                    Err(RecvError::Lagged(lagged)) => missed += lagged,
+                    Err(RecvError::Closed) => {
+                        let status =
+                            Status::unavailable("watch closed, resume from the last resume_token");
+                        return Some((Err(status), None));
+                    }
+                }
+            }
+        });
+
+        debug!("started watch stream");
+        Ok(Response::new(Box::pin(events)))
+    }
+
+    async fn summary(&self, _request: Request<()>) -> Result<Response<SummaryResponse>> {
+        debug!("received summary request");
+
+        let summary = self
+            .controller
+            .wallet_portfolio
+            .execute()
+            .await
+            .map_err(|e| handle_error_status(&e))?;
+
+        debug!("completed summary request");
+        Ok(Response::new(SummaryResponse {
+            total: Some(portfolio_total_to_proto(summary.total)),
+            by_tag: summary
+                .by_tag
+                .into_iter()
+                .map(|(tag, total)| TagTotal {
+                    tag: Some(tag),
+                    total: Some(portfolio_total_to_proto(total)),
+                })
+                .collect(),
+        }))
+    }
+
+    async fn pending_sweeps(
+        &self,
+        _request: Request<()>,
+    ) -> Result<Response<PendingSweepsResponse>> {
+        debug!("received pending sweeps request");
+
+        let pending = self
+            .controller
+            .wallet_pending_sweeps
+            .execute()
+            .await
+            .map_err(|e| handle_error_status(&e))?;
+
+        debug!("completed pending sweeps request");
+        Ok(Response::new(PendingSweepsResponse {
+            sweeps: pending
+                .into_iter()
+                .map(|pending| PendingSweep {
+                    wallet: Some(wallet_to_proto(pending.wallet)),
+                    funded_at: pending.funded_at.map(|funded_at| Timestamp {
+                        seconds: funded_at.timestamp(),
+                        nanos: 0,
+                    }),
+                })
+                .collect(),
+        }))
+    }
+
+    async fn create_alert_rule(&self, request: Request<AlertRule>) -> Result<Response<AlertRule>> {
+        debug!("received create alert rule request");
+
+        let created_by = provenance(&request);
+        let request = request.into_inner();
+        let condition = match request.condition {
+            Some(Condition::BelowWei(wei)) => AlertCondition::Below(parse_wei(&wei)?),
+            Some(Condition::AboveWei(wei)) => AlertCondition::Above(parse_wei(&wei)?),
+            Some(Condition::ChangePercent(percent)) => AlertCondition::Change { percent },
+            None => return Err(Status::invalid_argument("missing required condition")),
+        };
+
+        let rule = self
+            .controller
+            .alert_create
+            .execute(alert::NewAlertRule {
+                wallet: request.wallet,
+                condition,
+                created_by,
+            })
+            .await
+            .map_err(|e| handle_alert_error_status(&e))?;
+
+        debug!("completed create alert rule request");
+        Ok(Response::new(alert_rule_to_proto(rule)))
+    }
+
+    async fn list_alert_rules(
+        &self,
+        _request: Request<()>,
+    ) -> Result<Response<ListAlertRulesResponse>> {
+        debug!("received list alert rules request");
+
+        let rules = self
+            .controller
+            .alert_list
+            .execute()
+            .await
+            .map_err(|e| handle_alert_error_status(&e))?;
+
+        debug!("completed list alert rules request");
+        Ok(Response::new(ListAlertRulesResponse {
+            rules: rules.into_iter().map(alert_rule_to_proto).collect(),
+        }))
+    }
+
+    async fn delete_alert_rule(
+        &self,
+        request: Request<DeleteAlertRuleRequest>,
+    ) -> Result<Response<()>> {
+        debug!("received delete alert rule request");
+
+        let id = request
+            .into_inner()
+            .id
+            .ok_or(Status::invalid_argument("missing required id"))?;
+
+        self.controller
+            .alert_delete
+            .execute(&id)
+            .await
+            .map_err(|e| handle_alert_error_status(&e))?;
+
+        debug!("completed delete alert rule request");
+        Ok(Response::new(()))
+    }
+
+    async fn save_view(&self, request: Request<SavedView>) -> Result<Response<SavedView>> {
+        debug!("received save view request");
+
+        let principal = provenance(&request).principal;
+        let request = request.into_inner();
+        let name = request
+            .name
+            .ok_or(Status::invalid_argument("missing required name"))?;
+        let created_via = request
+            .created_via
+            .map(|i| Interface::try_from(i).map(interface_from_proto))
+            .transpose()
+            .map_err(|_| Status::invalid_argument("unknown created_via interface"))?;
+        let sort = request
+            .sort
+            .map(|s| ListSort::try_from(s).map(sort_from_proto))
+            .transpose()
+            .map_err(|_| Status::invalid_argument("unknown sort"))?
+            .unwrap_or_default();
+        let fields = request.fields.map(|mask| mask.paths).unwrap_or_default();
+        if let Some(field) = fields.iter().find(|f| !WALLET_FIELDS.contains(&f.as_str())) {
+            return Err(Status::invalid_argument(format!(
+                "unknown wallet field \"{field}\""
+            )));
+        }
+
+        let view = infra::SavedView {
+            name,
+            tags: request.tags,
+            created_by: request.created_by,
+            created_via,
+            sort,
+            fields,
+        };
+
+        let view = self
+            .controller
+            .wallet_save_view
+            .execute(&principal, view)
+            .await
+            .map_err(|e| handle_error_status(&e))?;
+
+        debug!("completed save view request");
+        Ok(Response::new(saved_view_to_proto(view)))
+    }
+
+    async fn list_views(&self, request: Request<()>) -> Result<Response<ListViewsResponse>> {
+        debug!("received list views request");
+
+        let principal = provenance(&request).principal;
+        let views = self
+            .controller
+            .wallet_views
+            .execute(&principal)
+            .await
+            .map_err(|e| handle_error_status(&e))?;
+
+        debug!("completed list views request");
+        Ok(Response::new(ListViewsResponse {
+            views: views.into_iter().map(saved_view_to_proto).collect(),
+        }))
+    }
+
+    async fn delete_view(&self, request: Request<DeleteViewRequest>) -> Result<Response<()>> {
+        debug!("received delete view request");
+
+        let principal = provenance(&request).principal;
+        let name = request
+            .into_inner()
+            .name
+            .ok_or(Status::invalid_argument("missing required name"))?;
+
+        self.controller
+            .wallet_delete_view
+            .execute(&principal, &name)
+            .await
+            .map_err(|e| handle_error_status(&e))?;
+
+        debug!("completed delete view request");
+        Ok(Response::new(()))
+    }
+
+    async fn server_info(&self, _request: Request<()>) -> Result<Response<ServerInfoResponse>> {
+        debug!("received server info request");
+
+        let info = self
+            .controller
+            .server_info
+            .execute()
+            .await
+            .map_err(|e| handle_admin_error_status(&e))?;
+
+        debug!("completed server info request");
+        Ok(Response::new(ServerInfoResponse {
+            version: Some(info.version),
+            chain: Some(info.chain),
+            endpoint: Some(info.endpoint),
+            store_backend: Some(info.store_backend),
+            store_location: Some(info.store_location),
+            uptime: Some(ProtoDuration {
+                seconds: info.uptime.as_secs() as i64,
+                nanos: 0,
+            }),
+            wallets: Some(info.wallets as u64),
+            store_recovered_from: info.store_recovered_from,
+        }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct AdminServer {
+    admin: AdminController,
+}
+
+#[async_trait]
+impl AdminService for AdminServer {
+    async fn stats(&self, _request: Request<()>) -> Result<Response<StatsResponse>> {
+        debug!("received stats request");
+
+        let report = self
+            .admin
+            .admin_stats
+            .execute()
+            .await
+            .map_err(|e| handle_admin_error_status(&e))?;
+
+        debug!("completed stats request");
+        Ok(Response::new(StatsResponse {
+            entries: Some(report.stats.entries as u64),
+            trashed: Some(report.stats.trashed as u64),
+            size_bytes: Some(report.stats.size_bytes),
+            writes: Some(report.stats.writes),
+            write_errors: Some(report.stats.write_errors),
+            last_write: report.stats.last_write.map(|last_write| Timestamp {
+                seconds: last_write.timestamp(),
+                nanos: 0,
+            }),
+            compactions: Some(report.compactions.runs),
+            compaction_reclaimed_bytes: Some(report.compactions.reclaimed_bytes),
+            last_compaction: report.compactions.last.map(compaction_to_proto),
+        }))
+    }
+
+    async fn compact(&self, _request: Request<()>) -> Result<Response<CompactResponse>> {
+        debug!("received compact request");
+
+        let compaction = self
+            .admin
+            .admin_compact
+            .execute()
+            .await
+            .map_err(|e| handle_admin_error_status(&e))?;
+
+        debug!("completed compact request");
+        Ok(Response::new(compaction_to_proto(compaction)))
+    }
+
+    async fn collect_garbage(
+        &self,
+        _request: Request<()>,
+    ) -> Result<Response<CollectGarbageResponse>> {
+        debug!("received collect garbage request");
+
+        let collection = self
+            .admin
+            .admin_collect
+            .execute()
+            .await
+            .map_err(|e| handle_admin_error_status(&e))?;
+
+        debug!("completed collect garbage request");
+        Ok(Response::new(CollectGarbageResponse {
+            purged: Some(collection.purged as u64),
+            compaction: collection.compaction.map(compaction_to_proto),
+        }))
+    }
+
+    async fn backup(&self, _request: Request<()>) -> Result<Response<BackupResponse>> {
+        debug!("received backup request");
+
+        let location = self
+            .admin
+            .admin_backup
+            .execute()
+            .await
+            .map_err(|e| handle_admin_error_status(&e))?;
+
+        debug!("completed backup request");
+        Ok(Response::new(BackupResponse {
+            location: Some(location),
+        }))
+    }
+
+    async fn snapshot(
+        &self,
+        request: Request<SnapshotRequest>,
+    ) -> Result<Response<SnapshotResponse>> {
+        debug!("received snapshot request");
+
+        let path = request
+            .into_inner()
+            .path
+            .ok_or(Status::invalid_argument("missing required path"))?;
+
+        self.admin
+            .admin_snapshot
+            .execute(&path)
+            .await
+            .map_err(|e| handle_admin_error_status(&e))?;
+
+        debug!("completed snapshot request");
+        Ok(Response::new(SnapshotResponse {}))
+    }
+
+    async fn digest(&self, _request: Request<()>) -> Result<Response<DigestResponse>> {
+        debug!("received digest request");
+
+        let digest = self
+            .admin
+            .admin_digest
+            .execute()
+            .await
+            .map_err(|e| handle_admin_error_status(&e))?;
+
+        debug!("completed digest request");
+        Ok(Response::new(DigestResponse {
+            root: Some(digest.root),
+            entries: Some(digest.entries as u64),
+        }))
+    }
+
+    async fn get_endpoint(&self, _request: Request<()>) -> Result<Response<GetEndpointResponse>> {
+        debug!("received get endpoint request");
+
+        let url = self
+            .admin
+            .admin_endpoint
+            .execute(None)
+            .await
+            .map_err(|e| handle_admin_error_status(&e))?;
+
+        debug!("completed get endpoint request");
+        Ok(Response::new(GetEndpointResponse { url: Some(url) }))
+    }
+
+    async fn set_endpoint(
+        &self,
+        request: Request<SetEndpointRequest>,
+    ) -> Result<Response<SetEndpointResponse>> {
+        debug!("received set endpoint request");
+
+        let url = request
+            .into_inner()
+            .url
+            .ok_or(Status::invalid_argument("missing required url"))?;
+
+        let previous_url = self
+            .admin
+            .admin_endpoint
+            .execute(Some(url))
+            .await
+            .map_err(|e| handle_admin_error_status(&e))?;
+
+        debug!("completed set endpoint request");
+        Ok(Response::new(SetEndpointResponse {
+            previous_url: Some(previous_url),
+        }))
+    }
+}
+
+fn wallet_to_proto(wallet: wallet::Wallet) -> Wallet {
+    Wallet {
+        id: Some(wallet.id),
+        name: Some(wallet.name),
+        address: Some(wallet.address),
+        linked_addresses: wallet.linked_addresses,
+        balance: Some(wallet.balance),
+        balance_wei: Some(wallet.balance_wei),
+        value_usd: wallet.value_usd,
+        delta_wei: wallet.delta_wei,
+        delta_pct: wallet.delta_pct,
+        tokens: wallet
+            .tokens
+            .into_iter()
+            .map(|token| TokenBalance {
+                symbol: Some(token.symbol),
+                contract: Some(token.contract),
+                decimals: Some(token.decimals),
+                amount: Some(token.amount),
+                amount_raw: Some(token.amount_raw),
+            })
+            .collect(),
+        transaction_count: wallet.transaction_count,
+        tags: wallet.tags,
+        note: wallet.note,
+        created_at: Some(Timestamp {
+            seconds: wallet.created_at.timestamp(),
+            nanos: 0,
+        }),
+        created_by: Some(provenance_to_proto(wallet.created_by)),
+        modified_by: Some(provenance_to_proto(wallet.modified_by)),
+        last_update: Some(Timestamp {
+            seconds: wallet.last_update.timestamp(),
+            nanos: 0,
+        }),
+        field_errors: wallet
+            .field_errors
+            .into_iter()
+            .map(|error| FieldError {
+                field: Some(error.enrichment.field().to_owned()),
+                reason: Some(error.reason),
+            })
+            .collect(),
+        verified_at: wallet.verified_at.map(|verified_at| Timestamp {
+            seconds: verified_at.timestamp(),
+            nanos: 0,
+        }),
+        ens_name: wallet.ens_name,
+        resolved_from: wallet.resolved_from,
+        chain_id: Some(wallet.chain_id),
+        age_seconds: Some(wallet.age_seconds),
+        is_stale: Some(wallet.is_stale),
+        pinned: Some(wallet.pinned),
+    }
+}
+
+fn transfer_to_proto(transfer: wallet::WalletTransfer) -> Transaction {
+    let direction = match transfer.direction {
+        wallet::TransferDirection::Inbound => TransferDirection::Inbound,
+        wallet::TransferDirection::Outbound => TransferDirection::Outbound,
+        wallet::TransferDirection::Internal => TransferDirection::Internal,
+    };
+    Transaction {
+        hash: Some(transfer.hash),
+        direction: Some(direction.into()),
+        from: Some(transfer.from),
+        to: transfer.to,
+        value: Some(transfer.value),
+        value_wei: Some(transfer.value_wei),
+        block_number: Some(transfer.block_number),
+        timestamp: Some(Timestamp {
+            seconds: transfer.timestamp.timestamp(),
+            nanos: 0,
+        }),
+        failed: Some(transfer.failed),
+    }
+}
+
+fn candle_to_proto(candle: wallet::BalanceCandle) -> BalanceCandle {
+    BalanceCandle {
+        start: Some(Timestamp {
+            seconds: candle.start.timestamp(),
+            nanos: 0,
+        }),
+        open: Some(candle.open),
+        open_wei: Some(candle.open_wei),
+        high: Some(candle.high),
+        high_wei: Some(candle.high_wei),
+        low: Some(candle.low),
+        low_wei: Some(candle.low_wei),
+        close: Some(candle.close),
+        close_wei: Some(candle.close_wei),
+    }
+}
+
+fn parse_wei(wei: &str) -> Result<Balance> {
+    wei.parse()
+        .map_err(|_| Status::invalid_argument("threshold must be a whole number of wei"))
+}
+
+const WALLET_FIELDS: &[&str] = &[
+    "id",
+    "name",
+    "chain_id",
+    "address",
+    "linked_addresses",
+    "balance",
+    "balance_wei",
+    "value_usd",
+    "delta_wei",
+    "delta_pct",
+    "tokens",
+    "transaction_count",
+    "tags",
+    "note",
+    "created_at",
+    "created_by",
+    "modified_by",
+    "last_update",
+    "verified_at",
+    "ens_name",
+    "resolved_from",
+    "age_seconds",
+    "is_stale",
+    "pinned",
+    "field_errors",
+];
+
+fn select_fields(wallet: Wallet, fields: &[String]) -> Wallet {
+    if fields.is_empty() {
+        return wallet;
+    }
+    let keep = |field: &str| fields.iter().any(|f| f == field);
+
+    Wallet {
+        id: wallet.id.filter(|_| keep("id")),
+        name: wallet.name.filter(|_| keep("name")),
+        chain_id: wallet.chain_id.filter(|_| keep("chain_id")),
+        address: wallet.address.filter(|_| keep("address")),
+        linked_addresses: if keep("linked_addresses") {
+            wallet.linked_addresses
+        } else {
+            Vec::new()
+        },
+        balance: wallet.balance.filter(|_| keep("balance")),
+        balance_wei: wallet.balance_wei.filter(|_| keep("balance_wei")),
+        value_usd: wallet.value_usd.filter(|_| keep("value_usd")),
+        delta_wei: wallet.delta_wei.filter(|_| keep("delta_wei")),
+        delta_pct: wallet.delta_pct.filter(|_| keep("delta_pct")),
+        tokens: if keep("tokens") {
+            wallet.tokens
+        } else {
+            Vec::new()
+        },
+        transaction_count: wallet
+            .transaction_count
+            .filter(|_| keep("transaction_count")),
+        tags: if keep("tags") {
+            wallet.tags
+        } else {
+            Vec::new()
+        },
+        note: wallet.note.filter(|_| keep("note")),
+        created_at: wallet.created_at.filter(|_| keep("created_at")),
+        created_by: wallet.created_by.filter(|_| keep("created_by")),
+        modified_by: wallet.modified_by.filter(|_| keep("modified_by")),
+        last_update: wallet.last_update.filter(|_| keep("last_update")),
+        verified_at: wallet.verified_at.filter(|_| keep("verified_at")),
+        ens_name: wallet.ens_name.filter(|_| keep("ens_name")),
+        resolved_from: wallet.resolved_from.filter(|_| keep("resolved_from")),
+        age_seconds: wallet.age_seconds.filter(|_| keep("age_seconds")),
+        is_stale: wallet.is_stale.filter(|_| keep("is_stale")),
+        pinned: wallet.pinned.filter(|_| keep("pinned")),
+        field_errors: if keep("field_errors") {
+            wallet.field_errors
+        } else {
+            Vec::new()
+        },
+    }
+}
+
+fn saved_view_to_proto(view: infra::SavedView) -> SavedView {
+    SavedView {
+        name: Some(view.name),
+        tags: view.tags,
+        created_by: view.created_by,
+        created_via: view
+            .created_via
+            .map(|interface| interface_to_proto(interface).into()),
+        sort: Some(sort_to_proto(view.sort).into()),
+        fields: Some(FieldMask { paths: view.fields }),
+    }
+}
+
+fn sort_from_proto(sort: ListSort) -> infra::ListSort {
+    match sort {
+        ListSort::Unspecified | ListSort::Name => infra::ListSort::Name,
+        ListSort::Balance => infra::ListSort::Balance,
+        ListSort::LastUpdate => infra::ListSort::LastUpdate,
+    }
+}
+
+fn sort_to_proto(sort: infra::ListSort) -> ListSort {
+    match sort {
+        infra::ListSort::Name => ListSort::Name,
+        infra::ListSort::Balance => ListSort::Balance,
+        infra::ListSort::LastUpdate => ListSort::LastUpdate,
+    }
+}
+
+fn alert_rule_to_proto(rule: infra::AlertRule) -> AlertRule {
+    let condition = match rule.condition {
+        AlertCondition::Below(threshold) => Condition::BelowWei(threshold.to_string()),
+        AlertCondition::Above(threshold) => Condition::AboveWei(threshold.to_string()),
+        AlertCondition::Change { percent } => Condition::ChangePercent(percent),
+    };
+
+    AlertRule {
+        id: Some(rule.id),
+        wallet: rule.wallet_id,
+        condition: Some(condition),
+        created_by: Some(provenance_to_proto(rule.created_by)),
+    }
+}
+
+fn portfolio_total_to_proto(total: wallet::PortfolioTotal) -> PortfolioTotal {
+    PortfolioTotal {
+        wallets: Some(total.wallets as u64),
+        balance: Some(total.balance),
+        balance_wei: Some(total.balance_wei),
+        value_usd: total.value_usd,
+    }
+}
+
+fn provenance<T>(request: &Request<T>) -> infra::Provenance {
+    infra::Provenance {
+        principal: request
+            .remote_addr()
+            .map(|addr| addr.ip().to_string())
+            .unwrap_or_default(),
+        interface: infra::Interface::Grpc,
+    }
+}
+
+fn provenance_to_proto(provenance: infra::Provenance) -> Provenance {
+    Provenance {
+        principal: Some(provenance.principal),
+        interface: Some(interface_to_proto(provenance.interface).into()),
+    }
+}
+
+fn compaction_to_proto(compaction: admin::Compaction) -> CompactResponse {
+    CompactResponse {
+        size_bytes_before: Some(compaction.size_bytes_before),
+        size_bytes_after: Some(compaction.size_bytes_after),
+        reclaimed_bytes: Some(compaction.reclaimed_bytes()),
+        duration_ms: Some(compaction.duration.as_millis() as u64),
+        finished_at: Some(Timestamp {
+            seconds: compaction.finished_at.timestamp(),
+            nanos: 0,
+        }),
+    }
+}
+
+fn interface_to_proto(interface: infra::Interface) -> Interface {
+    match interface {
+        infra::Interface::Unknown => Interface::Unspecified,
+        infra::Interface::Grpc => Interface::Grpc,
+        infra::Interface::Rest => Interface::Rest,
+        infra::Interface::Cli => Interface::Cli,
+        infra::Interface::Import => Interface::Import,
+    }
+}
+
+fn interface_from_proto(interface: Interface) -> infra::Interface {
+    match interface {
+        Interface::Unspecified => infra::Interface::Unknown,
+        Interface::Grpc => infra::Interface::Grpc,
+        Interface::Rest => infra::Interface::Rest,
+        Interface::Cli => infra::Interface::Cli,
+        Interface::Import => infra::Interface::Import,
+    }
 }
 
 fn handle_error_status(error: &WalletError) -> Status {
@@ -268,15 +1806,62 @@ fn handle_error_status(error: &WalletError) -> Status {
         WalletErrorKind::NameConflict => Status::already_exists(message),
         WalletErrorKind::NameEmpty => Status::invalid_argument(message),
         WalletErrorKind::NameTooLong => Status::invalid_argument(message),
+        WalletErrorKind::TagEmpty => Status::invalid_argument(message),
+        WalletErrorKind::TagTooLong => Status::invalid_argument(message),
+        WalletErrorKind::NoteTooLong => Status::invalid_argument(message),
+        WalletErrorKind::QueryEmpty => Status::invalid_argument(message),
+        WalletErrorKind::ResumeTokenInvalid => Status::invalid_argument(message),
+        WalletErrorKind::ResumeTokenExpired => Status::failed_precondition(message),
         WalletErrorKind::WalletAddrParse => Status::invalid_argument(message),
-        WalletErrorKind::WalletStore | WalletErrorKind::WalletClient => {
+        WalletErrorKind::NameUnresolved => Status::invalid_argument(message),
+        WalletErrorKind::ChainUnsupported => Status::invalid_argument(message),
+        WalletErrorKind::AddressUnspendable => Status::invalid_argument(message),
+        WalletErrorKind::AddressTracked => Status::already_exists(message),
+        WalletErrorKind::ChallengeMissing => Status::failed_precondition(message),
+        WalletErrorKind::SignatureParse => Status::invalid_argument(message),
+        WalletErrorKind::SignerMismatch => Status::permission_denied(message),
+        WalletErrorKind::ViewNotFound => Status::not_found(message),
+        WalletErrorKind::ViewNameEmpty => Status::invalid_argument(message),
+        WalletErrorKind::ViewNameTooLong => Status::invalid_argument(message),
+        WalletErrorKind::ImportMalformed => Status::invalid_argument(message),
+        WalletErrorKind::ImportRowInvalid => Status::invalid_argument(message),
+        WalletErrorKind::BalanceOverflow
+        | WalletErrorKind::WalletStore
+        | WalletErrorKind::WalletClient => {
+            error!("{message}");
+            Status::internal(message)
+        }
+    }
+}
+
+fn handle_alert_error_status(error: &AlertError) -> Status {
+    let message = compose_error(error);
+
+    match error.kind() {
+        AlertErrorKind::NotFound | AlertErrorKind::WalletNotFound => Status::not_found(message),
+        AlertErrorKind::PercentZero => Status::invalid_argument(message),
+        AlertErrorKind::Store => {
+            error!("{message}");
+            Status::internal(message)
+        }
+    }
+}
+
+fn handle_admin_error_status(error: &AdminError) -> Status {
+    let message = compose_error(error);
+
+    match error.kind() {
+        AdminErrorKind::EndpointInvalid | AdminErrorKind::PathInvalid => {
+            Status::invalid_argument(message)
+        }
+        AdminErrorKind::Store | AdminErrorKind::Client => {
             error!("{message}");
             Status::internal(message)
         }
     }
 }
 
-fn compose_error(error: &dyn std::error::Error) -> String {
+pub(crate) fn compose_error(error: &dyn std::error::Error) -> String {
     let mut composed = error.to_string();
 
     let mut next: &dyn std::error::Error = &error;
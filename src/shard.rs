@@ -0,0 +1,688 @@
+use std::{
+    collections::BTreeMap,
+    error, fmt, io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use tokio::{fs, sync::Mutex};
+use tracing::{info, instrument, warn};
+use xxhash_rust::xxh3::{xxh3_64, xxh3_128};
+
+use crate::{
+    core::{Address, ChainId, Sample},
+    fs::{FsError, blob, ensure_absent, write_atomic},
+    infra::{
+        AlertRule, AlertStore, HistoryStore, Insertion, OutboxEntry, OutboxStore, PreferenceStore,
+        SavedView, StoreAdmin, StoreError, StoreInfo, StoreStats, TrashStore, TrashedRecord,
+        WalletChange, WalletRecord, WalletStore, WalletUpdate, WriteCounters,
+        plan_insertions_among, search_records,
+    },
+};
+
+#[derive(Debug)]
+pub struct ShardError(Box<dyn error::Error + Send + Sync + 'static>);
+
+impl fmt::Display for ShardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "sharded store error")
+    }
+}
+
+impl error::Error for ShardError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&*self.0)
+    }
+}
+
+impl From<io::Error> for ShardError {
+    fn from(error: io::Error) -> Self {
+        Self(error.into())
+    }
+}
+
+impl From<FsError> for ShardError {
+    fn from(error: FsError) -> Self {
+        Self(error.into())
+    }
+}
+
+impl From<ShardError> for StoreError {
+    fn from(error: ShardError) -> Self {
+        Self(error.into())
+    }
+}
+
+const SHARDS: [&str; 7] = [
+    "wallets", "trash", "alerts", "pins", "views", "outbox", "history",
+];
+
+#[derive(Debug, Clone)]
+pub struct ShardedWalletStore {
+    path: PathBuf,
+    wallets: Shard,
+    trash: Shard,
+    alerts: Shard,
+    pins: Shard,
+    views: Shard,
+    outbox: Shard,
+    history: Shard,
+    write_lock: Arc<Mutex<()>>,
+}
+
+impl ShardedWalletStore {
+    #[instrument(fields(path = %path.as_ref()))]
+    pub async fn open(path: impl AsRef<str>) -> Result<Self, ShardError> {
+        let path = PathBuf::from(path.as_ref());
+        for shard in SHARDS {
+            fs::create_dir_all(path.join(shard)).await?;
+        }
+        info!("opened sharded wallet store");
+        let writes = Arc::new(WriteCounters::default());
+        Ok(Self {
+            wallets: Shard::new(&path, "wallets", &writes),
+            trash: Shard::new(&path, "trash", &writes),
+            alerts: Shard::new(&path, "alerts", &writes),
+            pins: Shard::new(&path, "pins", &writes),
+            views: Shard::new(&path, "views", &writes),
+            outbox: Shard::new(&path, "outbox", &writes),
+            history: Shard::new(&path, "history", &writes),
+            path,
+            write_lock: Arc::new(Mutex::new(())),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Shard {
+    dir: PathBuf,
+    writes: Arc<WriteCounters>,
+}
+
+impl Shard {
+    fn new(path: &Path, name: &str, writes: &Arc<WriteCounters>) -> Self {
+        Self {
+            dir: path.join(name),
+            writes: writes.clone(),
+        }
+    }
+
+    fn file(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{:032x}", xxh3_128(key.as_bytes())))
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, ShardError> {
+        let path = self.file(key);
+        let bytes = match fs::read(&path).await {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(decode_file(&path, &bytes)
+            .filter(|(stored, _)| stored == key)
+            .map(|(_, value)| value))
+    }
+
+    async fn put(&self, key: &str, value: &[u8]) -> Result<(), ShardError> {
+        let payload = blob::encode((key, value))?;
+        let mut bytes = xxh3_64(&payload).to_le_bytes().to_vec();
+        bytes.extend(payload);
+        let result = write_atomic(&self.file(key), &bytes).await;
+        self.writes.record(&result);
+        Ok(result?)
+    }
+
+    async fn remove(&self, key: &str) -> Result<bool, ShardError> {
+        let result = fs::remove_file(self.file(key)).await;
+        self.writes.record(&result);
+        match result {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn entries(&self) -> Result<(BTreeMap<String, Vec<u8>>, u64), ShardError> {
+        let mut entries = BTreeMap::new();
+        let mut size_bytes = 0;
+        let mut dir = fs::read_dir(&self.dir).await?;
+        while let Some(entry) = dir.next_entry().await? {
+            // Left behind by a crash mid-write; the record it replaced is intact.
+            if entry.path().extension().is_some_and(|ext| ext == "tmp") {
+                continue;
+            }
+            let bytes = fs::read(entry.path()).await?;
+            size_bytes += bytes.len() as u64;
+            if let Some((key, value)) = decode_file(&entry.path(), &bytes) {
+                entries.insert(key, value);
+            }
+        }
+        Ok((entries, size_bytes))
+    }
+}
+
+fn decode_file(path: &Path, bytes: &[u8]) -> Option<(String, Vec<u8>)> {
+    let decoded = bytes
+        .split_first_chunk::<8>()
+        .filter(|(checksum, payload)| u64::from_le_bytes(**checksum) == xxh3_64(payload))
+        .and_then(|(_, payload)| blob::decode(payload).ok());
+    if decoded.is_none() {
+        warn!(path = %path.to_string_lossy(), "skipped corrupt wallet store record");
+    }
+    decoded
+}
+
+fn to_trashed(bytes: &[u8]) -> Result<TrashedRecord, FsError> {
+    let (trashed_at, record): (i64, Vec<u8>) = blob::decode(bytes)?;
+    blob::to_trashed(&record, trashed_at)
+}
+
+#[async_trait]
+impl WalletStore for ShardedWalletStore {
+    async fn find(&self, name: &str) -> Result<Option<WalletRecord>, StoreError> {
+        let Some(bytes) = self.wallets.get(name).await? else {
+            return Ok(None);
+        };
+        Ok(Some(blob::to_record(&bytes)?))
+    }
+
+    async fn all(&self) -> Result<BTreeMap<String, WalletRecord>, StoreError> {
+        let (entries, _) = self.wallets.entries().await?;
+        let wallets = entries
+            .into_iter()
+            .map(|(name, bytes)| Ok((name, blob::to_record(&bytes)?)))
+            .collect::<Result<_, FsError>>()?;
+        Ok(wallets)
+    }
+
+    async fn exists(&self, name: &str) -> Result<bool, StoreError> {
+        Ok(self.wallets.get(name).await?.is_some())
+    }
+
+    async fn save(&self, name: &str, record: &WalletRecord) -> Result<(), StoreError> {
+        let bytes = blob::from_record(record)?;
+        let _guard = self.write_lock.lock().await;
+        self.wallets.put(name, &bytes).await?;
+        Ok(())
+    }
+
+    async fn save_many(&self, wallets: &[(String, WalletRecord)]) -> Result<(), StoreError> {
+        let _guard = self.write_lock.lock().await;
+        for (name, record) in wallets {
+            self.wallets.put(name, &blob::from_record(record)?).await?;
+        }
+        Ok(())
+    }
+
+    async fn delete(&self, name: &str) -> Result<(), StoreError> {
+        let _guard = self.write_lock.lock().await;
+        self.wallets.remove(name).await?;
+        Ok(())
+    }
+
+    async fn delete_many(&self, names: &[String]) -> Result<(), StoreError> {
+        let _guard = self.write_lock.lock().await;
+        for name in names {
+            self.wallets.remove(name).await?;
+        }
+        Ok(())
+    }
+
+    async fn update(
+        &self,
+        name: &str,
+        mut update: WalletUpdate,
+    ) -> Result<Option<WalletRecord>, StoreError> {
+        let _guard = self.write_lock.lock().await;
+        let current = match self.wallets.get(name).await? {
+            Some(bytes) => Some(blob::to_record(&bytes)?),
+            None => None,
+        };
+        let Some(record) = update(current) else {
+            return Ok(None);
+        };
+        self.wallets.put(name, &blob::from_record(&record)?).await?;
+        Ok(Some(record))
+    }
+
+    async fn find_by_address(
+        &self,
+        chain_id: ChainId,
+        address: &Address,
+    ) -> Result<Option<(String, WalletRecord)>, StoreError> {
+        let found = self.all().await?.into_iter().find(|(_, record)| {
+            record.wallet.chain_id() == chain_id && record.wallet.address() == address
+        });
+        Ok(found)
+    }
+
+    async fn count(&self) -> Result<usize, StoreError> {
+        let mut count = 0;
+        let mut dir = fs::read_dir(&self.wallets.dir)
+            .await
+            .map_err(ShardError::from)?;
+        while let Some(entry) = dir.next_entry().await.map_err(ShardError::from)? {
+            if entry.path().extension().is_none_or(|ext| ext != "tmp") {
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
+    async fn insert_many(
+        &self,
+        wallets: &[(String, WalletRecord)],
+    ) -> Result<Vec<Insertion>, StoreError> {
+        let _guard = self.write_lock.lock().await;
+        let insertions = plan_insertions_among(wallets, &self.all().await?);
+        for ((name, record), insertion) in wallets.iter().zip(&insertions) {
+            if *insertion == Insertion::Saved {
+                self.wallets.put(name, &blob::from_record(record)?).await?;
+            }
+        }
+        Ok(insertions)
+    }
+
+    async fn search(
+        &self,
+        terms: &[String],
+        limit: usize,
+    ) -> Result<Vec<(String, WalletRecord)>, StoreError> {
+        Ok(search_records(self.all().await?, terms, limit))
+    }
+
+    async fn transaction(&self, changes: &[WalletChange]) -> Result<(), StoreError> {
+        let _guard = self.write_lock.lock().await;
+        for change in changes {
+            match change {
+                WalletChange::Save { name, record } => {
+                    self.wallets.put(name, &blob::from_record(record)?).await?;
+                }
+                WalletChange::Delete { name } => {
+                    self.wallets.remove(name).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TrashStore for ShardedWalletStore {
+    async fn find_trashed(&self, name: &str) -> Result<Option<TrashedRecord>, StoreError> {
+        let Some(bytes) = self.trash.get(name).await? else {
+            return Ok(None);
+        };
+        Ok(Some(to_trashed(&bytes)?))
+    }
+
+    async fn all_trashed(&self) -> Result<BTreeMap<String, TrashedRecord>, StoreError> {
+        let (entries, _) = self.trash.entries().await?;
+        let trashed = entries
+            .into_iter()
+            .map(|(name, bytes)| Ok((name, to_trashed(&bytes)?)))
+            .collect::<Result<_, FsError>>()?;
+        Ok(trashed)
+    }
+
+    async fn trash(&self, name: &str) -> Result<(), StoreError> {
+        let _guard = self.write_lock.lock().await;
+        let Some(record) = self.wallets.get(name).await? else {
+            return Ok(());
+        };
+        let bytes = blob::encode((Utc::now().timestamp(), record))?;
+        self.trash.put(name, &bytes).await?;
+        self.wallets.remove(name).await?;
+        Ok(())
+    }
+
+    async fn restore(&self, name: &str) -> Result<(), StoreError> {
+        let _guard = self.write_lock.lock().await;
+        let Some(bytes) = self.trash.get(name).await? else {
+            return Ok(());
+        };
+        let (_, record): (i64, Vec<u8>) = blob::decode(&bytes)?;
+        self.wallets.put(name, &record).await?;
+        self.trash.remove(name).await?;
+        Ok(())
+    }
+
+    async fn purge(&self, name: &str) -> Result<(), StoreError> {
+        let _guard = self.write_lock.lock().await;
+        let Some(bytes) = self.trash.get(name).await? else {
+            return Ok(());
+        };
+        let trashed = to_trashed(&bytes)?;
+        self.history.remove(&trashed.record.id).await?;
+        self.trash.remove(name).await?;
+        Ok(())
+    }
+    async fn save_trashed(&self, name: &str, trashed: &TrashedRecord) -> Result<(), StoreError> {
+        let record = blob::from_record(&trashed.record)?;
+        let bytes = blob::encode((trashed.trashed_at.timestamp(), record))?;
+        let _guard = self.write_lock.lock().await;
+        self.trash.put(name, &bytes).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AlertStore for ShardedWalletStore {
+    async fn all_alerts(&self) -> Result<Vec<AlertRule>, StoreError> {
+        let (entries, _) = self.alerts.entries().await?;
+        let rules = entries
+            .values()
+            .map(|bytes| blob::to_alert(bytes))
+            .collect::<Result<_, _>>()?;
+        Ok(rules)
+    }
+
+    async fn save_alert(&self, rule: &AlertRule) -> Result<(), StoreError> {
+        let bytes = blob::from_alert(rule)?;
+        let _guard = self.write_lock.lock().await;
+        self.alerts.put(&rule.id, &bytes).await?;
+        Ok(())
+    }
+
+    async fn delete_alert(&self, id: &str) -> Result<bool, StoreError> {
+        let _guard = self.write_lock.lock().await;
+        Ok(self.alerts.remove(id).await?)
+    }
+}
+
+#[async_trait]
+impl PreferenceStore for ShardedWalletStore {
+    async fn pinned(&self, principal: &str) -> Result<Vec<String>, StoreError> {
+        let Some(bytes) = self.pins.get(principal).await? else {
+            return Ok(Vec::new());
+        };
+        Ok(blob::decode(&bytes)?)
+    }
+
+    async fn set_pinned(&self, principal: &str, wallet_ids: &[String]) -> Result<(), StoreError> {
+        let _guard = self.write_lock.lock().await;
+        if wallet_ids.is_empty() {
+            self.pins.remove(principal).await?;
+        } else {
+            self.pins.put(principal, &blob::encode(wallet_ids)?).await?;
+        }
+        Ok(())
+    }
+
+    async fn views(&self, principal: &str) -> Result<Vec<SavedView>, StoreError> {
+        let Some(bytes) = self.views.get(principal).await? else {
+            return Ok(Vec::new());
+        };
+        let views: BTreeMap<String, Vec<u8>> = blob::decode(&bytes)?;
+        let views = views
+            .iter()
+            .map(|(name, bytes)| blob::to_view(name, bytes))
+            .collect::<Result<_, _>>()?;
+        Ok(views)
+    }
+
+    async fn save_view(&self, principal: &str, view: &SavedView) -> Result<(), StoreError> {
+        let _guard = self.write_lock.lock().await;
+        let mut views: BTreeMap<String, Vec<u8>> = match self.views.get(principal).await? {
+            Some(bytes) => blob::decode(&bytes)?,
+            None => BTreeMap::new(),
+        };
+        views.insert(view.name.clone(), blob::from_view(view)?);
+        self.views.put(principal, &blob::encode(&views)?).await?;
+        Ok(())
+    }
+
+    async fn delete_view(&self, principal: &str, name: &str) -> Result<bool, StoreError> {
+        let _guard = self.write_lock.lock().await;
+        let Some(bytes) = self.views.get(principal).await? else {
+            return Ok(false);
+        };
+        let mut views: BTreeMap<String, Vec<u8>> = blob::decode(&bytes)?;
+        if views.remove(name).is_none() {
+            return Ok(false);
+        }
+        if views.is_empty() {
+            self.views.remove(principal).await?;
+        } else {
+            self.views.put(principal, &blob::encode(&views)?).await?;
+        }
+        Ok(true)
+    }
+}
+
+#[async_trait]
+impl OutboxStore for ShardedWalletStore {
+    async fn save_with_outbox(
+        &self,
+        name: &str,
+        record: &WalletRecord,
+        entry: &OutboxEntry,
+    ) -> Result<(), StoreError> {
+        let record = blob::from_record(record)?;
+        let entry_bytes = blob::from_outbox(entry)?;
+        let _guard = self.write_lock.lock().await;
+        self.wallets.put(name, &record).await?;
+        self.outbox.put(&entry.id, &entry_bytes).await?;
+        Ok(())
+    }
+
+    async fn outbox(&self) -> Result<Vec<OutboxEntry>, StoreError> {
+        let (entries, _) = self.outbox.entries().await?;
+        let entries = entries
+            .iter()
+            .map(|(id, bytes)| blob::to_outbox(id, bytes))
+            .collect::<Result<_, _>>()?;
+        Ok(entries)
+    }
+
+    async fn ack(&self, id: &str) -> Result<(), StoreError> {
+        let _guard = self.write_lock.lock().await;
+        self.outbox.remove(id).await?;
+        Ok(())
+    }
+
+    async fn reschedule(
+        &self,
+        id: &str,
+        attempts: u32,
+        next_attempt_at: DateTime<Utc>,
+    ) -> Result<(), StoreError> {
+        let _guard = self.write_lock.lock().await;
+        let Some(bytes) = self.outbox.get(id).await? else {
+            return Ok(());
+        };
+        let mut entry = blob::to_outbox(id, &bytes)?;
+        entry.attempts = attempts;
+        entry.next_attempt_at = next_attempt_at;
+        self.outbox.put(id, &blob::from_outbox(&entry)?).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl HistoryStore for ShardedWalletStore {
+    async fn append_samples(
+        &self,
+        samples: &[(String, Sample)],
+        keep: usize,
+    ) -> Result<(), StoreError> {
+        let mut by_wallet: BTreeMap<&str, Vec<Vec<u8>>> = BTreeMap::new();
+        for (wallet_id, sample) in samples {
+            by_wallet
+                .entry(wallet_id)
+                .or_default()
+                .push(blob::from_sample(sample)?);
+        }
+
+        let _guard = self.write_lock.lock().await;
+        for (wallet_id, samples) in by_wallet {
+            let mut kept: Vec<Vec<u8>> = match self.history.get(wallet_id).await? {
+                Some(bytes) => blob::decode(&bytes)?,
+                None => Vec::new(),
+            };
+            kept.extend(samples);
+            kept.drain(..kept.len().saturating_sub(keep));
+            if kept.is_empty() {
+                self.history.remove(wallet_id).await?;
+            } else {
+                self.history.put(wallet_id, &blob::encode(&kept)?).await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn replace_history(&self, wallet_id: &str, samples: &[Sample]) -> Result<(), StoreError> {
+        let samples = samples
+            .iter()
+            .map(blob::from_sample)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let _guard = self.write_lock.lock().await;
+        if samples.is_empty() {
+            self.history.remove(wallet_id).await?;
+        } else {
+            self.history
+                .put(wallet_id, &blob::encode(&samples)?)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn history(&self, wallet_id: &str) -> Result<Vec<Sample>, StoreError> {
+        let Some(bytes) = self.history.get(wallet_id).await? else {
+            return Ok(Vec::new());
+        };
+        let samples: Vec<Vec<u8>> = blob::decode(&bytes)?;
+        let samples = samples
+            .iter()
+            .map(|bytes| blob::to_sample(bytes))
+            .collect::<Result<_, _>>()?;
+        Ok(samples)
+    }
+}
+
+#[async_trait]
+impl StoreAdmin for ShardedWalletStore {
+    fn describe(&self) -> StoreInfo {
+        StoreInfo {
+            backend: "sharded".to_string(),
+            location: self.path.to_string_lossy().into_owned(),
+            recovered_from: None,
+        }
+    }
+
+    async fn stats(&self) -> Result<StoreStats, StoreError> {
+        let mut size_bytes = 0;
+        let (wallets, wallet_bytes) = self.wallets.entries().await?;
+        let (trash, trash_bytes) = self.trash.entries().await?;
+        size_bytes += wallet_bytes + trash_bytes;
+        for shard in [
+            &self.alerts,
+            &self.pins,
+            &self.views,
+            &self.outbox,
+            &self.history,
+        ] {
+            size_bytes += shard.entries().await?.1;
+        }
+        Ok(self.wallets.writes.fill(StoreStats {
+            entries: wallets.len(),
+            trashed: trash.len(),
+            size_bytes,
+            ..Default::default()
+        }))
+    }
+
+    async fn compact(&self) -> Result<(), StoreError> {
+        let _guard = self.write_lock.lock().await;
+        for shard in SHARDS {
+            let mut dir = fs::read_dir(self.path.join(shard))
+                .await
+                .map_err(ShardError::from)?;
+            while let Some(entry) = dir.next_entry().await.map_err(ShardError::from)? {
+                if entry.path().extension().is_some_and(|ext| ext == "tmp") {
+                    fs::remove_file(entry.path())
+                        .await
+                        .map_err(ShardError::from)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn backup(&self) -> Result<String, StoreError> {
+        let mut backup_path = self.path.clone().into_os_string();
+        backup_path.push(".bak");
+        let backup_path = PathBuf::from(backup_path);
+        match fs::remove_dir_all(&backup_path).await {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => {
+                return Err(ShardError::from(e).into());
+            }
+            _ => {}
+        }
+
+        self.snapshot(&backup_path.to_string_lossy()).await?;
+        Ok(backup_path.to_string_lossy().into_owned())
+    }
+
+    async fn snapshot(&self, dest: &str) -> Result<(), StoreError> {
+        let dest = Path::new(dest);
+        ensure_absent(dest).await?;
+        let _guard = self.write_lock.lock().await;
+        for shard in SHARDS {
+            let target = dest.join(shard);
+            fs::create_dir_all(&target)
+                .await
+                .map_err(ShardError::from)?;
+            let mut dir = fs::read_dir(self.path.join(shard))
+                .await
+                .map_err(ShardError::from)?;
+            while let Some(entry) = dir.next_entry().await.map_err(ShardError::from)? {
+                fs::copy(entry.path(), target.join(entry.file_name()))
+                    .await
+                    .map_err(ShardError::from)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, str::FromStr};
+
+    use uuid::Uuid;
+
+    use super::ShardedWalletStore;
+    use crate::{
+        core::{Address, Wallet},
+        infra::{StoreAdmin, TrashStore, WalletRecord, WalletStore},
+    };
+
+    #[tokio::test]
+    async fn sharded_corrupt_record_is_skipped() {
+        let path = env::temp_dir().join(format!("mini-wallet-{}", Uuid::new_v4()));
+        let store = ShardedWalletStore::open(path.to_string_lossy())
+            .await
+            .unwrap();
+        let address = Address::from_str("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045").unwrap();
+        let record = WalletRecord::new(Wallet::new(address));
+        for name in ["Vault", "Spare", "Petty"] {
+            store.save(name, &record).await.unwrap();
+        }
+        store.trash("Petty").await.unwrap();
+        assert_eq!(store.stats().await.unwrap().entries, 2);
+        assert!(store.find_trashed("Petty").await.unwrap().is_some());
+
+        let file = store.wallets.file("Spare");
+        let mut bytes = std::fs::read(&file).unwrap();
+        *bytes.last_mut().unwrap() ^= 1;
+        std::fs::write(&file, bytes).unwrap();
+
+        assert!(store.find("Spare").await.unwrap().is_none());
+        let wallets = store.all().await.unwrap();
+        assert_eq!(wallets.keys().collect::<Vec<_>>(), ["Vault"]);
+    }
+}
@@ -0,0 +1,679 @@
+use std::{collections::BTreeMap, error, fmt, path::Path, sync::Arc};
+
+use ::sled::{
+    Batch, Db, Error as DbError, Transactional, Tree,
+    transaction::{ConflictableTransactionError, ConflictableTransactionResult, TransactionError},
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use tokio::sync::Mutex;
+use tracing::{info, instrument};
+
+use crate::{
+    core::{Address, ChainId, Sample},
+    fs::{FsError, blob, ensure_absent},
+    infra::{
+        AlertRule, AlertStore, HistoryStore, Insertion, OutboxEntry, OutboxStore, PreferenceStore,
+        SavedView, StoreAdmin, StoreError, StoreInfo, StoreStats, TrashStore, TrashedRecord,
+        WalletChange, WalletRecord, WalletStore, WalletUpdate, WriteCounters,
+        plan_insertions_among, search_records,
+    },
+};
+
+#[derive(Debug)]
+pub struct SledError(Box<dyn error::Error + Send + Sync + 'static>);
+
+impl fmt::Display for SledError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "sled store error")
+    }
+}
+
+impl error::Error for SledError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&*self.0)
+    }
+}
+
+impl From<DbError> for SledError {
+    fn from(error: DbError) -> Self {
+        Self(error.into())
+    }
+}
+
+impl From<FsError> for SledError {
+    fn from(error: FsError) -> Self {
+        Self(error.into())
+    }
+}
+
+impl From<TransactionError<FsError>> for SledError {
+    fn from(error: TransactionError<FsError>) -> Self {
+        match error {
+            TransactionError::Abort(error) => error.into(),
+            TransactionError::Storage(error) => error.into(),
+        }
+    }
+}
+
+impl From<SledError> for StoreError {
+    fn from(error: SledError) -> Self {
+        Self(error.into())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SledWalletStore {
+    path: String,
+    db: Db,
+    wallets: Tree,
+    trash: Tree,
+    alerts: Tree,
+    pins: Tree,
+    views: Tree,
+    outbox: Tree,
+    history: Tree,
+    insert_lock: Arc<Mutex<()>>,
+    writes: Arc<WriteCounters>,
+}
+
+impl SledWalletStore {
+    #[instrument(fields(path = %path.as_ref()))]
+    pub fn open(path: impl AsRef<str>) -> Result<Self, SledError> {
+        let path = path.as_ref().to_owned();
+        let db = ::sled::open(&path)?;
+        info!("opened sled wallet store");
+        Ok(Self {
+            path,
+            wallets: db.open_tree("wallets")?,
+            trash: db.open_tree("trash")?,
+            alerts: db.open_tree("alerts")?,
+            pins: db.open_tree("pins")?,
+            views: db.open_tree("views")?,
+            outbox: db.open_tree("outbox")?,
+            history: db.open_tree("history")?,
+            insert_lock: Arc::default(),
+            db,
+            writes: Arc::default(),
+        })
+    }
+
+    async fn flush(&self) -> Result<(), SledError> {
+        let result = self.db.flush_async().await;
+        self.writes.record(&result);
+        result?;
+        Ok(())
+    }
+}
+
+fn to_trashed(bytes: &[u8]) -> Result<TrashedRecord, FsError> {
+    let (trashed_at, record): (i64, Vec<u8>) = blob::decode(bytes)?;
+    blob::to_trashed(&record, trashed_at)
+}
+
+fn view_prefix(principal: &str) -> String {
+    format!("{principal}\0")
+}
+
+fn key_to_string(key: &[u8]) -> String {
+    String::from_utf8_lossy(key).into_owned()
+}
+
+#[async_trait]
+impl WalletStore for SledWalletStore {
+    async fn find(&self, name: &str) -> Result<Option<WalletRecord>, StoreError> {
+        let record = self
+            .wallets
+            .get(name)
+            .map_err(SledError::from)?
+            .map(|bytes| blob::to_record(&bytes))
+            .transpose()
+            .map_err(SledError::from)?;
+        Ok(record)
+    }
+
+    async fn all(&self) -> Result<BTreeMap<String, WalletRecord>, StoreError> {
+        let wallets = self
+            .wallets
+            .iter()
+            .map(|entry| {
+                let (name, bytes) = entry?;
+                Ok((key_to_string(&name), blob::to_record(&bytes)?))
+            })
+            .collect::<Result<_, SledError>>()?;
+        Ok(wallets)
+    }
+
+    async fn exists(&self, name: &str) -> Result<bool, StoreError> {
+        Ok(self.wallets.contains_key(name).map_err(SledError::from)?)
+    }
+
+    async fn save(&self, name: &str, record: &WalletRecord) -> Result<(), StoreError> {
+        self.save_many(&[(name.to_owned(), record.clone())]).await
+    }
+
+    async fn save_many(&self, wallets: &[(String, WalletRecord)]) -> Result<(), StoreError> {
+        let mut batch = Batch::default();
+        for (name, record) in wallets {
+            batch.insert(
+                name.as_str(),
+                blob::from_record(record).map_err(SledError::from)?,
+            );
+        }
+        self.wallets.apply_batch(batch).map_err(SledError::from)?;
+        Ok(self.flush().await?)
+    }
+
+    async fn delete(&self, name: &str) -> Result<(), StoreError> {
+        self.wallets.remove(name).map_err(SledError::from)?;
+        Ok(self.flush().await?)
+    }
+
+    async fn delete_many(&self, names: &[String]) -> Result<(), StoreError> {
+        let mut batch = Batch::default();
+        for name in names {
+            batch.remove(name.as_str());
+        }
+        self.wallets.apply_batch(batch).map_err(SledError::from)?;
+        Ok(self.flush().await?)
+    }
+
+    async fn update(
+        &self,
+        name: &str,
+        mut update: WalletUpdate,
+    ) -> Result<Option<WalletRecord>, StoreError> {
+        loop {
+            let current = self.wallets.get(name).map_err(SledError::from)?;
+            let record = current
+                .as_ref()
+                .map(|bytes| blob::to_record(bytes))
+                .transpose()
+                .map_err(SledError::from)?;
+            let Some(record) = update(record) else {
+                return Ok(None);
+            };
+            let bytes = blob::from_record(&record).map_err(SledError::from)?;
+            let swapped = self
+                .wallets
+                .compare_and_swap(name, current, Some(bytes))
+                .map_err(SledError::from)?;
+            if swapped.is_ok() {
+                self.flush().await?;
+                return Ok(Some(record));
+            }
+        }
+    }
+
+    async fn find_by_address(
+        &self,
+        chain_id: ChainId,
+        address: &Address,
+    ) -> Result<Option<(String, WalletRecord)>, StoreError> {
+        for entry in self.wallets.iter() {
+            let (name, bytes) = entry.map_err(SledError::from)?;
+            let record = blob::to_record(&bytes).map_err(SledError::from)?;
+            if record.wallet.chain_id() == chain_id && record.wallet.address() == address {
+                return Ok(Some((key_to_string(&name), record)));
+            }
+        }
+        Ok(None)
+    }
+
+    async fn count(&self) -> Result<usize, StoreError> {
+        Ok(self.wallets.len())
+    }
+
+    async fn insert_many(
+        &self,
+        wallets: &[(String, WalletRecord)],
+    ) -> Result<Vec<Insertion>, StoreError> {
+        let _guard = self.insert_lock.lock().await;
+        let insertions = plan_insertions_among(wallets, &self.all().await?);
+        let mut batch = Batch::default();
+        for ((name, record), insertion) in wallets.iter().zip(&insertions) {
+            if *insertion == Insertion::Saved {
+                batch.insert(
+                    name.as_str(),
+                    blob::from_record(record).map_err(SledError::from)?,
+                );
+            }
+        }
+        self.wallets.apply_batch(batch).map_err(SledError::from)?;
+        self.flush().await?;
+        Ok(insertions)
+    }
+
+    async fn search(
+        &self,
+        terms: &[String],
+        limit: usize,
+    ) -> Result<Vec<(String, WalletRecord)>, StoreError> {
+        Ok(search_records(self.all().await?, terms, limit))
+    }
+
+    async fn transaction(&self, changes: &[WalletChange]) -> Result<(), StoreError> {
+        let mut batch = Batch::default();
+        for change in changes {
+            match change {
+                WalletChange::Save { name, record } => batch.insert(
+                    name.as_str(),
+                    blob::from_record(record).map_err(SledError::from)?,
+                ),
+                WalletChange::Delete { name } => batch.remove(name.as_str()),
+            }
+        }
+        self.wallets.apply_batch(batch).map_err(SledError::from)?;
+        Ok(self.flush().await?)
+    }
+}
+
+#[async_trait]
+impl TrashStore for SledWalletStore {
+    async fn find_trashed(&self, name: &str) -> Result<Option<TrashedRecord>, StoreError> {
+        let trashed = self
+            .trash
+            .get(name)
+            .map_err(SledError::from)?
+            .map(|bytes| to_trashed(&bytes))
+            .transpose()
+            .map_err(SledError::from)?;
+        Ok(trashed)
+    }
+
+    async fn all_trashed(&self) -> Result<BTreeMap<String, TrashedRecord>, StoreError> {
+        let trashed = self
+            .trash
+            .iter()
+            .map(|entry| {
+                let (name, bytes) = entry?;
+                Ok((key_to_string(&name), to_trashed(&bytes)?))
+            })
+            .collect::<Result<_, SledError>>()?;
+        Ok(trashed)
+    }
+
+    async fn trash(&self, name: &str) -> Result<(), StoreError> {
+        let trashed_at = Utc::now().timestamp();
+        (&self.wallets, &self.trash)
+            .transaction(
+                |(wallets, trash)| -> ConflictableTransactionResult<(), FsError> {
+                    let Some(record) = wallets.remove(name)? else {
+                        return Ok(());
+                    };
+                    let bytes = blob::encode((trashed_at, record.to_vec()))
+                        .map_err(ConflictableTransactionError::Abort)?;
+                    trash.insert(name, bytes)?;
+                    Ok(())
+                },
+            )
+            .map_err(SledError::from)?;
+        Ok(self.flush().await?)
+    }
+
+    async fn restore(&self, name: &str) -> Result<(), StoreError> {
+        (&self.wallets, &self.trash)
+            .transaction(
+                |(wallets, trash)| -> ConflictableTransactionResult<(), FsError> {
+                    let Some(bytes) = trash.remove(name)? else {
+                        return Ok(());
+                    };
+                    let (_, record): (i64, Vec<u8>) =
+                        blob::decode(&bytes).map_err(ConflictableTransactionError::Abort)?;
+                    wallets.insert(name, record)?;
+                    Ok(())
+                },
+            )
+            .map_err(SledError::from)?;
+        Ok(self.flush().await?)
+    }
+
+    async fn purge(&self, name: &str) -> Result<(), StoreError> {
+        (&self.trash, &self.history)
+            .transaction(
+                |(trash, history)| -> ConflictableTransactionResult<(), FsError> {
+                    let Some(bytes) = trash.remove(name)? else {
+                        return Ok(());
+                    };
+                    let trashed =
+                        to_trashed(&bytes).map_err(ConflictableTransactionError::Abort)?;
+                    history.remove(trashed.record.id.as_str())?;
+                    Ok(())
+                },
+            )
+            .map_err(SledError::from)?;
+        Ok(self.flush().await?)
+    }
+    async fn save_trashed(&self, name: &str, trashed: &TrashedRecord) -> Result<(), StoreError> {
+        let record = blob::from_record(&trashed.record).map_err(SledError::from)?;
+        let bytes =
+            blob::encode((trashed.trashed_at.timestamp(), record)).map_err(SledError::from)?;
+        self.trash.insert(name, bytes).map_err(SledError::from)?;
+        Ok(self.flush().await?)
+    }
+}
+
+#[async_trait]
+impl AlertStore for SledWalletStore {
+    async fn all_alerts(&self) -> Result<Vec<AlertRule>, StoreError> {
+        let rules = self
+            .alerts
+            .iter()
+            .values()
+            .map(|bytes| Ok(blob::to_alert(&bytes?)?))
+            .collect::<Result<_, SledError>>()?;
+        Ok(rules)
+    }
+
+    async fn save_alert(&self, rule: &AlertRule) -> Result<(), StoreError> {
+        let bytes = blob::from_alert(rule).map_err(SledError::from)?;
+        self.alerts
+            .insert(rule.id.as_str(), bytes)
+            .map_err(SledError::from)?;
+        Ok(self.flush().await?)
+    }
+
+    async fn delete_alert(&self, id: &str) -> Result<bool, StoreError> {
+        let removed = self.alerts.remove(id).map_err(SledError::from)?;
+        self.flush().await?;
+        Ok(removed.is_some())
+    }
+}
+
+#[async_trait]
+impl PreferenceStore for SledWalletStore {
+    async fn pinned(&self, principal: &str) -> Result<Vec<String>, StoreError> {
+        let pinned = self
+            .pins
+            .get(principal)
+            .map_err(SledError::from)?
+            .map(|bytes| blob::decode(&bytes))
+            .transpose()
+            .map_err(SledError::from)?
+            .unwrap_or_default();
+        Ok(pinned)
+    }
+
+    async fn set_pinned(&self, principal: &str, wallet_ids: &[String]) -> Result<(), StoreError> {
+        if wallet_ids.is_empty() {
+            self.pins.remove(principal).map_err(SledError::from)?;
+        } else {
+            let bytes = blob::encode(wallet_ids).map_err(SledError::from)?;
+            self.pins
+                .insert(principal, bytes)
+                .map_err(SledError::from)?;
+        }
+        Ok(self.flush().await?)
+    }
+
+    async fn views(&self, principal: &str) -> Result<Vec<SavedView>, StoreError> {
+        let prefix = view_prefix(principal);
+        let views = self
+            .views
+            .scan_prefix(&prefix)
+            .map(|entry| {
+                let (key, bytes) = entry?;
+                let name = key_to_string(&key[prefix.len()..]);
+                Ok(blob::to_view(&name, &bytes)?)
+            })
+            .collect::<Result<_, SledError>>()?;
+        Ok(views)
+    }
+
+    async fn save_view(&self, principal: &str, view: &SavedView) -> Result<(), StoreError> {
+        let key = format!("{}{}", view_prefix(principal), view.name);
+        let bytes = blob::from_view(view).map_err(SledError::from)?;
+        self.views.insert(key, bytes).map_err(SledError::from)?;
+        Ok(self.flush().await?)
+    }
+
+    async fn delete_view(&self, principal: &str, name: &str) -> Result<bool, StoreError> {
+        let key = format!("{}{name}", view_prefix(principal));
+        let removed = self.views.remove(key).map_err(SledError::from)?;
+        self.flush().await?;
+        Ok(removed.is_some())
+    }
+}
+
+#[async_trait]
+impl OutboxStore for SledWalletStore {
+    async fn save_with_outbox(
+        &self,
+        name: &str,
+        record: &WalletRecord,
+        entry: &OutboxEntry,
+    ) -> Result<(), StoreError> {
+        let record = blob::from_record(record).map_err(SledError::from)?;
+        let entry_bytes = blob::from_outbox(entry).map_err(SledError::from)?;
+        (&self.wallets, &self.outbox)
+            .transaction(
+                |(wallets, outbox)| -> ConflictableTransactionResult<(), FsError> {
+                    wallets.insert(name, record.as_slice())?;
+                    outbox.insert(entry.id.as_str(), entry_bytes.as_slice())?;
+                    Ok(())
+                },
+            )
+            .map_err(SledError::from)?;
+        Ok(self.flush().await?)
+    }
+
+    async fn outbox(&self) -> Result<Vec<OutboxEntry>, StoreError> {
+        let entries = self
+            .outbox
+            .iter()
+            .map(|entry| {
+                let (id, bytes) = entry?;
+                Ok(blob::to_outbox(&key_to_string(&id), &bytes)?)
+            })
+            .collect::<Result<_, SledError>>()?;
+        Ok(entries)
+    }
+
+    async fn ack(&self, id: &str) -> Result<(), StoreError> {
+        self.outbox.remove(id).map_err(SledError::from)?;
+        Ok(self.flush().await?)
+    }
+
+    async fn reschedule(
+        &self,
+        id: &str,
+        attempts: u32,
+        next_attempt_at: DateTime<Utc>,
+    ) -> Result<(), StoreError> {
+        self.outbox
+            .transaction(|outbox| -> ConflictableTransactionResult<(), FsError> {
+                let Some(bytes) = outbox.get(id)? else {
+                    return Ok(());
+                };
+                let mut entry =
+                    blob::to_outbox(id, &bytes).map_err(ConflictableTransactionError::Abort)?;
+                entry.attempts = attempts;
+                entry.next_attempt_at = next_attempt_at;
+                let bytes =
+                    blob::from_outbox(&entry).map_err(ConflictableTransactionError::Abort)?;
+                outbox.insert(id, bytes)?;
+                Ok(())
+            })
+            .map_err(SledError::from)?;
+        Ok(self.flush().await?)
+    }
+}
+
+#[async_trait]
+impl HistoryStore for SledWalletStore {
+    async fn append_samples(
+        &self,
+        samples: &[(String, Sample)],
+        keep: usize,
+    ) -> Result<(), StoreError> {
+        let samples = samples
+            .iter()
+            .map(|(wallet_id, sample)| Ok((wallet_id.as_str(), blob::from_sample(sample)?)))
+            .collect::<Result<Vec<_>, FsError>>()
+            .map_err(SledError::from)?;
+
+        self.history
+            .transaction(|history| -> ConflictableTransactionResult<(), FsError> {
+                for (wallet_id, sample) in &samples {
+                    let mut kept: Vec<Vec<u8>> = match history.get(wallet_id)? {
+                        Some(bytes) => {
+                            blob::decode(&bytes).map_err(ConflictableTransactionError::Abort)?
+                        }
+                        None => Vec::new(),
+                    };
+                    kept.push(sample.clone());
+                    kept.drain(..kept.len().saturating_sub(keep));
+                    if kept.is_empty() {
+                        history.remove(*wallet_id)?;
+                    } else {
+                        let bytes =
+                            blob::encode(&kept).map_err(ConflictableTransactionError::Abort)?;
+                        history.insert(*wallet_id, bytes)?;
+                    }
+                }
+                Ok(())
+            })
+            .map_err(SledError::from)?;
+        Ok(self.flush().await?)
+    }
+
+    async fn replace_history(&self, wallet_id: &str, samples: &[Sample]) -> Result<(), StoreError> {
+        if samples.is_empty() {
+            self.history.remove(wallet_id).map_err(SledError::from)?;
+        } else {
+            let samples = samples
+                .iter()
+                .map(blob::from_sample)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(SledError::from)?;
+            let bytes = blob::encode(&samples).map_err(SledError::from)?;
+            self.history
+                .insert(wallet_id, bytes)
+                .map_err(SledError::from)?;
+        }
+        Ok(self.flush().await?)
+    }
+
+    async fn history(&self, wallet_id: &str) -> Result<Vec<Sample>, StoreError> {
+        let Some(bytes) = self.history.get(wallet_id).map_err(SledError::from)? else {
+            return Ok(Vec::new());
+        };
+        let samples: Vec<Vec<u8>> = blob::decode(&bytes).map_err(SledError::from)?;
+        let samples = samples
+            .iter()
+            .map(|bytes| blob::to_sample(bytes))
+            .collect::<Result<_, _>>()
+            .map_err(SledError::from)?;
+        Ok(samples)
+    }
+}
+
+#[async_trait]
+impl StoreAdmin for SledWalletStore {
+    fn describe(&self) -> StoreInfo {
+        StoreInfo {
+            backend: "sled".to_string(),
+            location: self.path.clone(),
+            recovered_from: None,
+        }
+    }
+
+    async fn stats(&self) -> Result<StoreStats, StoreError> {
+        Ok(self.writes.fill(StoreStats {
+            entries: self.wallets.len(),
+            trashed: self.trash.len(),
+            size_bytes: self.db.size_on_disk().map_err(SledError::from)?,
+            ..Default::default()
+        }))
+    }
+
+    async fn compact(&self) -> Result<(), StoreError> {
+        self.db.flush_async().await.map_err(SledError::from)?;
+        Ok(())
+    }
+
+    async fn backup(&self) -> Result<String, StoreError> {
+        let backup_path = format!("{}.bak", self.path);
+        match tokio::fs::remove_dir_all(&backup_path).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                return Err(FsError::from(e).into());
+            }
+            _ => {}
+        }
+
+        self.snapshot(&backup_path).await?;
+        Ok(backup_path)
+    }
+
+    async fn snapshot(&self, dest: &str) -> Result<(), StoreError> {
+        ensure_absent(Path::new(dest)).await?;
+        let snapshot = ::sled::open(dest).map_err(SledError::from)?;
+        for name in self.db.tree_names() {
+            let source = self.db.open_tree(&name).map_err(SledError::from)?;
+            let target = snapshot.open_tree(&name).map_err(SledError::from)?;
+            for entry in source.iter() {
+                let (key, value) = entry.map_err(SledError::from)?;
+                target.insert(key, value).map_err(SledError::from)?;
+            }
+        }
+        snapshot.flush_async().await.map_err(SledError::from)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, str::FromStr};
+
+    use uuid::Uuid;
+
+    use super::SledWalletStore;
+    use crate::{
+        core::{Address, Balance, Sample, Wallet},
+        infra::{HistoryStore, StoreAdmin, TrashStore, WalletRecord, WalletStore},
+    };
+
+    const ADDR: &str = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
+
+    fn open() -> SledWalletStore {
+        let path = env::temp_dir().join(format!("mini-wallet-{}.sled", Uuid::new_v4()));
+        SledWalletStore::open(path.to_string_lossy()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn sled_wallet_round_trip() {
+        let store = open();
+        let record = WalletRecord::new(Wallet::new(Address::from_str(ADDR).unwrap()));
+        store.save("Vault", &record).await.unwrap();
+
+        let found = store.find("Vault").await.unwrap().unwrap();
+        assert_eq!(found.id, record.id);
+        assert_eq!(found.wallet.address().to_string(), ADDR);
+        assert!(store.exists("Vault").await.unwrap());
+        assert_eq!(store.stats().await.unwrap().entries, 1);
+
+        store.trash("Vault").await.unwrap();
+        assert!(!store.exists("Vault").await.unwrap());
+        assert!(store.find_trashed("Vault").await.unwrap().is_some());
+
+        store.restore("Vault").await.unwrap();
+        assert_eq!(store.all().await.unwrap().len(), 1);
+        assert!(store.all_trashed().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn sled_history_keeps_newest() {
+        let store = open();
+        let sample = |timestamp| Sample {
+            timestamp,
+            block_number: None,
+            balance: Balance::new(1),
+        };
+        let samples: Vec<_> = (1..=4).map(|t| ("w1".to_string(), sample(t))).collect();
+        store.append_samples(&samples, 2).await.unwrap();
+
+        let history = store.history("w1").await.unwrap();
+        let timestamps: Vec<i64> = history.iter().map(|sample| sample.timestamp).collect();
+        assert_eq!(timestamps, [3, 4]);
+    }
+}
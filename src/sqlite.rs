@@ -0,0 +1,980 @@
+use std::{collections::BTreeMap, error, fmt, str::FromStr};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{
+    Error as SqlxError, SqliteConnection, SqlitePool,
+    sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions},
+};
+use tracing::{info, instrument};
+
+use crate::{
+    core::{Address, ChainId, Sample},
+    fs::{FsError, blob},
+    infra::{
+        AlertRule, AlertStore, HistoryStore, Insertion, OutboxEntry, OutboxStore, PreferenceStore,
+        SavedView, StoreAdmin, StoreError, StoreInfo, StoreStats, TrashStore, TrashedRecord,
+        WalletChange, WalletRecord, WalletStore, WalletUpdate, plan_insertions,
+    },
+};
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS wallets (
+        name TEXT PRIMARY KEY NOT NULL,
+        id TEXT NOT NULL,
+        address BLOB NOT NULL,
+        record BLOB NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS wallets_id ON wallets (id);
+    CREATE INDEX IF NOT EXISTS wallets_address ON wallets (address);
+
+    CREATE TABLE IF NOT EXISTS trash (
+        name TEXT PRIMARY KEY NOT NULL,
+        id TEXT NOT NULL,
+        address BLOB NOT NULL,
+        record BLOB NOT NULL,
+        trashed_at INTEGER NOT NULL
+    );
+
+    CREATE TABLE IF NOT EXISTS alerts (
+        id TEXT PRIMARY KEY NOT NULL,
+        rule BLOB NOT NULL
+    );
+
+    CREATE TABLE IF NOT EXISTS pins (
+        principal TEXT PRIMARY KEY NOT NULL,
+        wallet_ids BLOB NOT NULL
+    );
+
+    CREATE TABLE IF NOT EXISTS views (
+        principal TEXT NOT NULL,
+        name TEXT NOT NULL,
+        view BLOB NOT NULL,
+        PRIMARY KEY (principal, name)
+    );
+
+    CREATE TABLE IF NOT EXISTS outbox (
+        id TEXT PRIMARY KEY NOT NULL,
+        entry BLOB NOT NULL
+    );
+
+    CREATE TABLE IF NOT EXISTS history (
+        seq INTEGER PRIMARY KEY AUTOINCREMENT,
+        wallet_id TEXT NOT NULL,
+        sample BLOB NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS history_wallet_id ON history (wallet_id);
+
+    CREATE VIRTUAL TABLE IF NOT EXISTS wallets_search USING fts5 (
+        id UNINDEXED, name, ens_names, tags, note, addresses
+    );
+    CREATE TRIGGER IF NOT EXISTS wallets_search_deleted AFTER DELETE ON wallets
+    WHEN NOT EXISTS (SELECT 1 FROM wallets WHERE id = old.id)
+        AND NOT EXISTS (SELECT 1 FROM trash WHERE id = old.id)
+    BEGIN
+        DELETE FROM wallets_search WHERE id = old.id;
+    END;
+    CREATE TRIGGER IF NOT EXISTS wallets_search_purged AFTER DELETE ON trash
+    WHEN NOT EXISTS (SELECT 1 FROM wallets WHERE id = old.id)
+    BEGIN
+        DELETE FROM wallets_search WHERE id = old.id;
+    END;
+";
+
+// Weights of the wallets_search columns in the order they're declared, so a
+// match on the name outranks one on a note.
+const SEARCH_RANK: &str = "bm25(wallets_search, 0.0, 10.0, 4.0, 2.0, 1.0, 3.0)";
+
+#[derive(Debug)]
+pub struct SqliteError(Box<dyn error::Error + Send + Sync + 'static>);
+
+impl fmt::Display for SqliteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "sqlite store error")
+    }
+}
+
+impl error::Error for SqliteError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&*self.0)
+    }
+}
+
+impl From<SqlxError> for SqliteError {
+    fn from(error: SqlxError) -> Self {
+        Self(error.into())
+    }
+}
+
+impl From<FsError> for SqliteError {
+    fn from(error: FsError) -> Self {
+        Self(error.into())
+    }
+}
+
+impl From<SqliteError> for StoreError {
+    fn from(error: SqliteError) -> Self {
+        Self(error.into())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SqliteWalletStore {
+    path: String,
+    pool: SqlitePool,
+}
+
+impl SqliteWalletStore {
+    #[instrument(fields(path = %path.as_ref()))]
+    pub async fn open(path: impl AsRef<str>) -> Result<Self, SqliteError> {
+        let path = path.as_ref().to_owned();
+        let options = SqliteConnectOptions::from_str(&path)?
+            .create_if_missing(true)
+            .journal_mode(SqliteJournalMode::Wal);
+        let pool = SqlitePoolOptions::new().connect_with(options).await?;
+
+        sqlx::raw_sql(SCHEMA).execute(&pool).await?;
+        info!("opened sqlite wallet store");
+        Ok(Self { path, pool })
+    }
+
+    async fn index_wallet(
+        conn: &mut SqliteConnection,
+        name: &str,
+        record: &WalletRecord,
+    ) -> Result<(), SqliteError> {
+        let ens_names: Vec<_> = [&record.resolved_from, &record.ens_name]
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .collect();
+        let addresses: Vec<_> = record
+            .wallet
+            .addresses()
+            .map(|address| address.to_string())
+            .collect();
+
+        sqlx::query("DELETE FROM wallets_search WHERE id = ?")
+            .bind(&record.id)
+            .execute(&mut *conn)
+            .await?;
+        sqlx::query(
+            "INSERT INTO wallets_search (id, name, ens_names, tags, note, addresses)
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&record.id)
+        .bind(name)
+        .bind(ens_names.join(" "))
+        .bind(record.tags.join(" "))
+        .bind(record.note.as_deref().unwrap_or_default())
+        .bind(addresses.join(" "))
+        .execute(&mut *conn)
+        .await?;
+        Ok(())
+    }
+
+    async fn upsert_wallet(
+        conn: &mut SqliteConnection,
+        name: &str,
+        record: &WalletRecord,
+    ) -> Result<(), SqliteError> {
+        sqlx::query(
+            "INSERT INTO wallets (name, id, address, record) VALUES (?, ?, ?, ?)
+             ON CONFLICT (name) DO UPDATE
+             SET id = excluded.id, address = excluded.address, record = excluded.record",
+        )
+        .bind(name)
+        .bind(&record.id)
+        .bind(record.wallet.address().inner().as_slice())
+        .bind(blob::from_record(record)?)
+        .execute(&mut *conn)
+        .await?;
+        Self::index_wallet(conn, name, record).await
+    }
+}
+
+#[async_trait]
+impl WalletStore for SqliteWalletStore {
+    async fn find(&self, name: &str) -> Result<Option<WalletRecord>, StoreError> {
+        let record: Option<Vec<u8>> =
+            sqlx::query_scalar("SELECT record FROM wallets WHERE name = ?")
+                .bind(name)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(SqliteError::from)?;
+        let record = record
+            .map(|bytes| blob::to_record(&bytes))
+            .transpose()
+            .map_err(SqliteError::from)?;
+        Ok(record)
+    }
+
+    async fn all(&self) -> Result<BTreeMap<String, WalletRecord>, StoreError> {
+        let rows: Vec<(String, Vec<u8>)> = sqlx::query_as("SELECT name, record FROM wallets")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(SqliteError::from)?;
+        let wallets = rows
+            .into_iter()
+            .map(|(name, bytes)| Ok((name, blob::to_record(&bytes)?)))
+            .collect::<Result<_, FsError>>()
+            .map_err(SqliteError::from)?;
+        Ok(wallets)
+    }
+
+    async fn exists(&self, name: &str) -> Result<bool, StoreError> {
+        let found: bool =
+            sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM wallets WHERE name = ?)")
+                .bind(name)
+                .fetch_one(&self.pool)
+                .await
+                .map_err(SqliteError::from)?;
+        Ok(found)
+    }
+
+    async fn save(&self, name: &str, record: &WalletRecord) -> Result<(), StoreError> {
+        let mut tx = self.pool.begin().await.map_err(SqliteError::from)?;
+        Self::upsert_wallet(&mut tx, name, record).await?;
+        tx.commit().await.map_err(SqliteError::from)?;
+        Ok(())
+    }
+
+    async fn save_many(&self, wallets: &[(String, WalletRecord)]) -> Result<(), StoreError> {
+        let mut tx = self.pool.begin().await.map_err(SqliteError::from)?;
+        for (name, record) in wallets {
+            Self::upsert_wallet(&mut tx, name, record).await?;
+        }
+        tx.commit().await.map_err(SqliteError::from)?;
+        Ok(())
+    }
+
+    async fn delete(&self, name: &str) -> Result<(), StoreError> {
+        sqlx::query("DELETE FROM wallets WHERE name = ?")
+            .bind(name)
+            .execute(&self.pool)
+            .await
+            .map_err(SqliteError::from)?;
+        Ok(())
+    }
+
+    async fn delete_many(&self, names: &[String]) -> Result<(), StoreError> {
+        let mut tx = self.pool.begin().await.map_err(SqliteError::from)?;
+        for name in names {
+            sqlx::query("DELETE FROM wallets WHERE name = ?")
+                .bind(name)
+                .execute(&mut *tx)
+                .await
+                .map_err(SqliteError::from)?;
+        }
+        tx.commit().await.map_err(SqliteError::from)?;
+        Ok(())
+    }
+
+    async fn update(
+        &self,
+        name: &str,
+        mut update: WalletUpdate,
+    ) -> Result<Option<WalletRecord>, StoreError> {
+        // Takes the write lock up front, so the read can't go stale.
+        let mut tx = self
+            .pool
+            .begin_with("BEGIN IMMEDIATE")
+            .await
+            .map_err(SqliteError::from)?;
+        let current: Option<Vec<u8>> =
+            sqlx::query_scalar("SELECT record FROM wallets WHERE name = ?")
+                .bind(name)
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(SqliteError::from)?;
+        let current = current
+            .map(|bytes| blob::to_record(&bytes))
+            .transpose()
+            .map_err(SqliteError::from)?;
+        let Some(record) = update(current) else {
+            return Ok(None);
+        };
+        Self::upsert_wallet(&mut tx, name, &record).await?;
+        tx.commit().await.map_err(SqliteError::from)?;
+        Ok(Some(record))
+    }
+
+    async fn find_by_address(
+        &self,
+        chain_id: ChainId,
+        address: &Address,
+    ) -> Result<Option<(String, WalletRecord)>, StoreError> {
+        let rows: Vec<(String, Vec<u8>)> =
+            sqlx::query_as("SELECT name, record FROM wallets WHERE address = ? ORDER BY name")
+                .bind(address.inner().as_slice())
+                .fetch_all(&self.pool)
+                .await
+                .map_err(SqliteError::from)?;
+        for (name, bytes) in rows {
+            let record = blob::to_record(&bytes).map_err(SqliteError::from)?;
+            if record.wallet.chain_id() == chain_id {
+                return Ok(Some((name, record)));
+            }
+        }
+        Ok(None)
+    }
+
+    async fn count(&self) -> Result<usize, StoreError> {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM wallets")
+            .fetch_one(&self.pool)
+            .await
+            .map_err(SqliteError::from)?;
+        Ok(count as usize)
+    }
+
+    async fn insert_many(
+        &self,
+        wallets: &[(String, WalletRecord)],
+    ) -> Result<Vec<Insertion>, StoreError> {
+        // Takes the write lock up front, so the checks can't go stale.
+        let mut tx = self
+            .pool
+            .begin_with("BEGIN IMMEDIATE")
+            .await
+            .map_err(SqliteError::from)?;
+        let mut lookups = Vec::with_capacity(wallets.len());
+        for (name, record) in wallets {
+            let name_taken: bool =
+                sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM wallets WHERE name = ?)")
+                    .bind(name)
+                    .fetch_one(&mut *tx)
+                    .await
+                    .map_err(SqliteError::from)?;
+            let rows: Vec<(String, Vec<u8>)> =
+                sqlx::query_as("SELECT name, record FROM wallets WHERE address = ? ORDER BY name")
+                    .bind(record.wallet.address().inner().as_slice())
+                    .fetch_all(&mut *tx)
+                    .await
+                    .map_err(SqliteError::from)?;
+            let mut holder = None;
+            for (stored, bytes) in rows {
+                let stored_record = blob::to_record(&bytes).map_err(SqliteError::from)?;
+                if stored_record.wallet.chain_id() == record.wallet.chain_id() {
+                    holder = Some(stored);
+                    break;
+                }
+            }
+            lookups.push((name_taken, holder));
+        }
+        let insertions = plan_insertions(wallets, lookups);
+
+        for ((name, record), insertion) in wallets.iter().zip(&insertions) {
+            if *insertion == Insertion::Saved {
+                Self::upsert_wallet(&mut tx, name, record).await?;
+            }
+        }
+        tx.commit().await.map_err(SqliteError::from)?;
+        Ok(insertions)
+    }
+
+    // Each term matches as a prefix of a word, through the FTS5 index.
+    async fn search(
+        &self,
+        terms: &[String],
+        limit: usize,
+    ) -> Result<Vec<(String, WalletRecord)>, StoreError> {
+        let query: Vec<_> = terms
+            .iter()
+            .filter(|term| term.chars().any(char::is_alphanumeric))
+            .map(|term| format!("\"{}\"*", term.replace('"', "\"\"")))
+            .collect();
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let rows: Vec<(String, Vec<u8>)> = sqlx::query_as(&format!(
+            "SELECT wallets.name, wallets.record FROM wallets_search
+             JOIN wallets ON wallets.id = wallets_search.id
+             WHERE wallets_search MATCH ?
+             ORDER BY {SEARCH_RANK}, wallets.name COLLATE NOCASE
+             LIMIT ?"
+        ))
+        .bind(query.join(" "))
+        .bind(i64::try_from(limit).unwrap_or(i64::MAX))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(SqliteError::from)?;
+        let found = rows
+            .into_iter()
+            .map(|(name, bytes)| Ok((name, blob::to_record(&bytes)?)))
+            .collect::<Result<_, FsError>>()
+            .map_err(SqliteError::from)?;
+        Ok(found)
+    }
+
+    async fn transaction(&self, changes: &[WalletChange]) -> Result<(), StoreError> {
+        let mut tx = self.pool.begin().await.map_err(SqliteError::from)?;
+        for change in changes {
+            match change {
+                WalletChange::Save { name, record } => {
+                    Self::upsert_wallet(&mut tx, name, record).await?;
+                }
+                WalletChange::Delete { name } => {
+                    sqlx::query("DELETE FROM wallets WHERE name = ?")
+                        .bind(name)
+                        .execute(&mut *tx)
+                        .await
+                        .map_err(SqliteError::from)?;
+                }
+            }
+        }
+        tx.commit().await.map_err(SqliteError::from)?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TrashStore for SqliteWalletStore {
+    async fn find_trashed(&self, name: &str) -> Result<Option<TrashedRecord>, StoreError> {
+        let row: Option<(Vec<u8>, i64)> =
+            sqlx::query_as("SELECT record, trashed_at FROM trash WHERE name = ?")
+                .bind(name)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(SqliteError::from)?;
+        let trashed = row
+            .map(|(bytes, trashed_at)| blob::to_trashed(&bytes, trashed_at))
+            .transpose()
+            .map_err(SqliteError::from)?;
+        Ok(trashed)
+    }
+
+    async fn all_trashed(&self) -> Result<BTreeMap<String, TrashedRecord>, StoreError> {
+        let rows: Vec<(String, Vec<u8>, i64)> =
+            sqlx::query_as("SELECT name, record, trashed_at FROM trash")
+                .fetch_all(&self.pool)
+                .await
+                .map_err(SqliteError::from)?;
+        let trashed = rows
+            .into_iter()
+            .map(|(name, bytes, trashed_at)| Ok((name, blob::to_trashed(&bytes, trashed_at)?)))
+            .collect::<Result<_, FsError>>()
+            .map_err(SqliteError::from)?;
+        Ok(trashed)
+    }
+
+    async fn trash(&self, name: &str) -> Result<(), StoreError> {
+        let mut tx = self.pool.begin().await.map_err(SqliteError::from)?;
+        sqlx::query(
+            "INSERT OR REPLACE INTO trash (name, id, address, record, trashed_at)
+             SELECT name, id, address, record, ? FROM wallets WHERE name = ?",
+        )
+        .bind(Utc::now().timestamp())
+        .bind(name)
+        .execute(&mut *tx)
+        .await
+        .map_err(SqliteError::from)?;
+        sqlx::query("DELETE FROM wallets WHERE name = ?")
+            .bind(name)
+            .execute(&mut *tx)
+            .await
+            .map_err(SqliteError::from)?;
+        tx.commit().await.map_err(SqliteError::from)?;
+        Ok(())
+    }
+
+    async fn restore(&self, name: &str) -> Result<(), StoreError> {
+        let mut tx = self.pool.begin().await.map_err(SqliteError::from)?;
+        sqlx::query(
+            "INSERT OR REPLACE INTO wallets (name, id, address, record)
+             SELECT name, id, address, record FROM trash WHERE name = ?",
+        )
+        .bind(name)
+        .execute(&mut *tx)
+        .await
+        .map_err(SqliteError::from)?;
+        sqlx::query("DELETE FROM trash WHERE name = ?")
+            .bind(name)
+            .execute(&mut *tx)
+            .await
+            .map_err(SqliteError::from)?;
+        tx.commit().await.map_err(SqliteError::from)?;
+        Ok(())
+    }
+
+    async fn purge(&self, name: &str) -> Result<(), StoreError> {
+        let mut tx = self.pool.begin().await.map_err(SqliteError::from)?;
+        sqlx::query("DELETE FROM history WHERE wallet_id IN (SELECT id FROM trash WHERE name = ?)")
+            .bind(name)
+            .execute(&mut *tx)
+            .await
+            .map_err(SqliteError::from)?;
+        sqlx::query("DELETE FROM trash WHERE name = ?")
+            .bind(name)
+            .execute(&mut *tx)
+            .await
+            .map_err(SqliteError::from)?;
+        tx.commit().await.map_err(SqliteError::from)?;
+        Ok(())
+    }
+    async fn save_trashed(&self, name: &str, trashed: &TrashedRecord) -> Result<(), StoreError> {
+        let record = &trashed.record;
+        sqlx::query(
+            "INSERT OR REPLACE INTO trash (name, id, address, record, trashed_at)
+             VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(name)
+        .bind(&record.id)
+        .bind(record.wallet.address().inner().as_slice())
+        .bind(blob::from_record(record)?)
+        .bind(trashed.trashed_at.timestamp())
+        .execute(&self.pool)
+        .await
+        .map_err(SqliteError::from)?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AlertStore for SqliteWalletStore {
+    async fn all_alerts(&self) -> Result<Vec<AlertRule>, StoreError> {
+        let rules: Vec<Vec<u8>> = sqlx::query_scalar("SELECT rule FROM alerts ORDER BY id")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(SqliteError::from)?;
+        let rules = rules
+            .iter()
+            .map(|bytes| blob::to_alert(bytes))
+            .collect::<Result<_, _>>()
+            .map_err(SqliteError::from)?;
+        Ok(rules)
+    }
+
+    async fn save_alert(&self, rule: &AlertRule) -> Result<(), StoreError> {
+        sqlx::query("INSERT OR REPLACE INTO alerts (id, rule) VALUES (?, ?)")
+            .bind(&rule.id)
+            .bind(blob::from_alert(rule).map_err(SqliteError::from)?)
+            .execute(&self.pool)
+            .await
+            .map_err(SqliteError::from)?;
+        Ok(())
+    }
+
+    async fn delete_alert(&self, id: &str) -> Result<bool, StoreError> {
+        let result = sqlx::query("DELETE FROM alerts WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(SqliteError::from)?;
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[async_trait]
+impl PreferenceStore for SqliteWalletStore {
+    async fn pinned(&self, principal: &str) -> Result<Vec<String>, StoreError> {
+        let wallet_ids: Option<Vec<u8>> =
+            sqlx::query_scalar("SELECT wallet_ids FROM pins WHERE principal = ?")
+                .bind(principal)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(SqliteError::from)?;
+        let pinned = wallet_ids
+            .map(|bytes| blob::decode(&bytes))
+            .transpose()
+            .map_err(SqliteError::from)?
+            .unwrap_or_default();
+        Ok(pinned)
+    }
+
+    async fn set_pinned(&self, principal: &str, wallet_ids: &[String]) -> Result<(), StoreError> {
+        if wallet_ids.is_empty() {
+            sqlx::query("DELETE FROM pins WHERE principal = ?")
+                .bind(principal)
+                .execute(&self.pool)
+                .await
+                .map_err(SqliteError::from)?;
+            return Ok(());
+        }
+
+        sqlx::query("INSERT OR REPLACE INTO pins (principal, wallet_ids) VALUES (?, ?)")
+            .bind(principal)
+            .bind(blob::encode(wallet_ids).map_err(SqliteError::from)?)
+            .execute(&self.pool)
+            .await
+            .map_err(SqliteError::from)?;
+        Ok(())
+    }
+
+    async fn views(&self, principal: &str) -> Result<Vec<SavedView>, StoreError> {
+        let rows: Vec<(String, Vec<u8>)> =
+            sqlx::query_as("SELECT name, view FROM views WHERE principal = ? ORDER BY name")
+                .bind(principal)
+                .fetch_all(&self.pool)
+                .await
+                .map_err(SqliteError::from)?;
+        let views = rows
+            .iter()
+            .map(|(name, bytes)| blob::to_view(name, bytes))
+            .collect::<Result<_, _>>()
+            .map_err(SqliteError::from)?;
+        Ok(views)
+    }
+
+    async fn save_view(&self, principal: &str, view: &SavedView) -> Result<(), StoreError> {
+        sqlx::query("INSERT OR REPLACE INTO views (principal, name, view) VALUES (?, ?, ?)")
+            .bind(principal)
+            .bind(&view.name)
+            .bind(blob::from_view(view).map_err(SqliteError::from)?)
+            .execute(&self.pool)
+            .await
+            .map_err(SqliteError::from)?;
+        Ok(())
+    }
+
+    async fn delete_view(&self, principal: &str, name: &str) -> Result<bool, StoreError> {
+        let result = sqlx::query("DELETE FROM views WHERE principal = ? AND name = ?")
+            .bind(principal)
+            .bind(name)
+            .execute(&self.pool)
+            .await
+            .map_err(SqliteError::from)?;
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[async_trait]
+impl OutboxStore for SqliteWalletStore {
+    async fn save_with_outbox(
+        &self,
+        name: &str,
+        record: &WalletRecord,
+        entry: &OutboxEntry,
+    ) -> Result<(), StoreError> {
+        let mut tx = self.pool.begin().await.map_err(SqliteError::from)?;
+        Self::upsert_wallet(&mut tx, name, record).await?;
+        sqlx::query("INSERT OR REPLACE INTO outbox (id, entry) VALUES (?, ?)")
+            .bind(&entry.id)
+            .bind(blob::from_outbox(entry).map_err(SqliteError::from)?)
+            .execute(&mut *tx)
+            .await
+            .map_err(SqliteError::from)?;
+        tx.commit().await.map_err(SqliteError::from)?;
+        Ok(())
+    }
+
+    async fn outbox(&self) -> Result<Vec<OutboxEntry>, StoreError> {
+        let rows: Vec<(String, Vec<u8>)> =
+            sqlx::query_as("SELECT id, entry FROM outbox ORDER BY id")
+                .fetch_all(&self.pool)
+                .await
+                .map_err(SqliteError::from)?;
+        let entries = rows
+            .iter()
+            .map(|(id, bytes)| blob::to_outbox(id, bytes))
+            .collect::<Result<_, _>>()
+            .map_err(SqliteError::from)?;
+        Ok(entries)
+    }
+
+    async fn ack(&self, id: &str) -> Result<(), StoreError> {
+        sqlx::query("DELETE FROM outbox WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(SqliteError::from)?;
+        Ok(())
+    }
+
+    async fn reschedule(
+        &self,
+        id: &str,
+        attempts: u32,
+        next_attempt_at: DateTime<Utc>,
+    ) -> Result<(), StoreError> {
+        let mut tx = self.pool.begin().await.map_err(SqliteError::from)?;
+        let bytes: Option<Vec<u8>> = sqlx::query_scalar("SELECT entry FROM outbox WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(SqliteError::from)?;
+        let Some(bytes) = bytes else {
+            return Ok(());
+        };
+
+        let mut entry = blob::to_outbox(id, &bytes).map_err(SqliteError::from)?;
+        entry.attempts = attempts;
+        entry.next_attempt_at = next_attempt_at;
+        sqlx::query("UPDATE outbox SET entry = ? WHERE id = ?")
+            .bind(blob::from_outbox(&entry).map_err(SqliteError::from)?)
+            .bind(id)
+            .execute(&mut *tx)
+            .await
+            .map_err(SqliteError::from)?;
+        tx.commit().await.map_err(SqliteError::from)?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl HistoryStore for SqliteWalletStore {
+    async fn append_samples(
+        &self,
+        samples: &[(String, Sample)],
+        keep: usize,
+    ) -> Result<(), StoreError> {
+        let keep = i64::try_from(keep).unwrap_or(i64::MAX);
+        let mut tx = self.pool.begin().await.map_err(SqliteError::from)?;
+        for (wallet_id, sample) in samples {
+            sqlx::query("INSERT INTO history (wallet_id, sample) VALUES (?, ?)")
+                .bind(wallet_id)
+                .bind(blob::from_sample(sample).map_err(SqliteError::from)?)
+                .execute(&mut *tx)
+                .await
+                .map_err(SqliteError::from)?;
+            sqlx::query(
+                "DELETE FROM history WHERE wallet_id = ?1 AND seq NOT IN (
+                     SELECT seq FROM history WHERE wallet_id = ?1 ORDER BY seq DESC LIMIT ?2
+                 )",
+            )
+            .bind(wallet_id)
+            .bind(keep)
+            .execute(&mut *tx)
+            .await
+            .map_err(SqliteError::from)?;
+        }
+        tx.commit().await.map_err(SqliteError::from)?;
+        Ok(())
+    }
+
+    async fn replace_history(&self, wallet_id: &str, samples: &[Sample]) -> Result<(), StoreError> {
+        let mut tx = self.pool.begin().await.map_err(SqliteError::from)?;
+        sqlx::query("DELETE FROM history WHERE wallet_id = ?")
+            .bind(wallet_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(SqliteError::from)?;
+        for sample in samples {
+            sqlx::query("INSERT INTO history (wallet_id, sample) VALUES (?, ?)")
+                .bind(wallet_id)
+                .bind(blob::from_sample(sample).map_err(SqliteError::from)?)
+                .execute(&mut *tx)
+                .await
+                .map_err(SqliteError::from)?;
+        }
+        tx.commit().await.map_err(SqliteError::from)?;
+        Ok(())
+    }
+
+    async fn history(&self, wallet_id: &str) -> Result<Vec<Sample>, StoreError> {
+        let samples: Vec<Vec<u8>> =
+            sqlx::query_scalar("SELECT sample FROM history WHERE wallet_id = ? ORDER BY seq")
+                .bind(wallet_id)
+                .fetch_all(&self.pool)
+                .await
+                .map_err(SqliteError::from)?;
+        let samples = samples
+            .iter()
+            .map(|bytes| blob::to_sample(bytes))
+            .collect::<Result<_, _>>()
+            .map_err(SqliteError::from)?;
+        Ok(samples)
+    }
+}
+
+#[async_trait]
+impl StoreAdmin for SqliteWalletStore {
+    fn describe(&self) -> StoreInfo {
+        StoreInfo {
+            backend: "sqlite".to_string(),
+            location: self.path.clone(),
+            recovered_from: None,
+        }
+    }
+
+    async fn stats(&self) -> Result<StoreStats, StoreError> {
+        let (entries, trashed, size_bytes): (i64, i64, i64) = sqlx::query_as(
+            "SELECT
+                 (SELECT COUNT(*) FROM wallets),
+                 (SELECT COUNT(*) FROM trash),
+                 (SELECT page_count * page_size FROM pragma_page_count(), pragma_page_size())",
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(SqliteError::from)?;
+        Ok(StoreStats {
+            entries: usize::try_from(entries).unwrap_or_default(),
+            trashed: usize::try_from(trashed).unwrap_or_default(),
+            size_bytes: u64::try_from(size_bytes).unwrap_or_default(),
+            ..Default::default()
+        })
+    }
+
+    async fn compact(&self) -> Result<(), StoreError> {
+        sqlx::query("VACUUM")
+            .execute(&self.pool)
+            .await
+            .map_err(SqliteError::from)?;
+        Ok(())
+    }
+
+    async fn backup(&self) -> Result<String, StoreError> {
+        let backup_path = format!("{}.bak", self.path);
+        match tokio::fs::remove_file(&backup_path).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                return Err(FsError::from(e).into());
+            }
+            _ => {}
+        }
+        self.snapshot(&backup_path).await?;
+        Ok(backup_path)
+    }
+
+    async fn snapshot(&self, dest: &str) -> Result<(), StoreError> {
+        sqlx::query("VACUUM INTO ?")
+            .bind(dest)
+            .execute(&self.pool)
+            .await
+            .map_err(SqliteError::from)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, str::FromStr};
+
+    use uuid::Uuid;
+
+    use super::SqliteWalletStore;
+    use crate::{
+        core::{Address, Balance, Sample, Wallet},
+        infra::{
+            HistoryStore, StoreAdmin, TrashStore, WalletChange, WalletRecord, WalletStore,
+            WalletUpdate,
+        },
+    };
+
+    const ADDR: &str = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
+
+    async fn open() -> SqliteWalletStore {
+        let path = env::temp_dir().join(format!("mini-wallet-{}.sqlite", Uuid::new_v4()));
+        SqliteWalletStore::open(path.to_string_lossy())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn sqlite_wallet_round_trip() {
+        let store = open().await;
+        let record = WalletRecord::new(Wallet::new(Address::from_str(ADDR).unwrap()));
+        store.save("Vault", &record).await.unwrap();
+
+        let found = store.find("Vault").await.unwrap().unwrap();
+        assert_eq!(found.id, record.id);
+        assert_eq!(found.wallet.address().to_string(), ADDR);
+        assert!(store.exists("Vault").await.unwrap());
+        assert_eq!(store.stats().await.unwrap().entries, 1);
+
+        store.trash("Vault").await.unwrap();
+        assert!(!store.exists("Vault").await.unwrap());
+        assert!(store.find_trashed("Vault").await.unwrap().is_some());
+
+        store.restore("Vault").await.unwrap();
+        assert_eq!(store.all().await.unwrap().len(), 1);
+        assert!(store.all_trashed().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn sqlite_update_only_if_absent() {
+        let store = open().await;
+        let record = WalletRecord::new(Wallet::new(Address::from_str(ADDR).unwrap()));
+        let insert = |record: WalletRecord| -> WalletUpdate {
+            Box::new(move |current| current.is_none().then(|| record.clone()))
+        };
+
+        let saved = store.update("Vault", insert(record.clone())).await.unwrap();
+        assert_eq!(saved.unwrap().id, record.id);
+
+        let other = WalletRecord::new(Wallet::new(Address::from_str(ADDR).unwrap()));
+        assert!(
+            store
+                .update("Vault", insert(other))
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert_eq!(store.find("Vault").await.unwrap().unwrap().id, record.id);
+    }
+
+    #[tokio::test]
+    async fn sqlite_search_follows_changes() {
+        let store = open().await;
+        let names = |found: Vec<(String, WalletRecord)>| -> Vec<String> {
+            found.into_iter().map(|(name, _)| name).collect()
+        };
+        let search = async |query: &str| {
+            let terms: Vec<_> = query.split_whitespace().map(str::to_lowercase).collect();
+            names(store.search(&terms, 10).await.unwrap())
+        };
+
+        let vitalik = WalletRecord {
+            resolved_from: Some("vitalik.eth".to_string()),
+            ..WalletRecord::new(Wallet::new(Address::from_str(ADDR).unwrap()))
+        };
+        let cold = WalletRecord {
+            tags: vec!["cold".to_string()],
+            note: Some("Hardware key in the safe".to_string()),
+            ..WalletRecord::new(Wallet::new(Address::new([7; 20])))
+        };
+        store.save("Vitalik", &vitalik).await.unwrap();
+        store.save("Cold Wallet", &cold).await.unwrap();
+
+        assert_eq!(search("vital").await, ["Vitalik"]);
+        assert_eq!(search("VITALIK.ETH").await, ["Vitalik"]);
+        assert_eq!(search("0xd8da").await, ["Vitalik"]);
+        assert_eq!(search("hardware saf").await, ["Cold Wallet"]);
+        assert_eq!(search("cold").await, ["Cold Wallet"]);
+        assert!(search("cold vitalik").await.is_empty());
+        assert!(search("\"").await.is_empty());
+
+        store.trash("Cold Wallet").await.unwrap();
+        assert!(search("hardware").await.is_empty());
+        store.restore("Cold Wallet").await.unwrap();
+        assert_eq!(search("hardware").await, ["Cold Wallet"]);
+        store.delete("Cold Wallet").await.unwrap();
+        assert!(search("hardware").await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn sqlite_transaction_renames() {
+        let store = open().await;
+        let record = WalletRecord::new(Wallet::new(Address::from_str(ADDR).unwrap()));
+        store.save("Cold", &record).await.unwrap();
+
+        let changes = [
+            WalletChange::Save {
+                name: "Vault".to_string(),
+                record: Box::new(record.clone()),
+            },
+            WalletChange::Delete {
+                name: "Cold".to_string(),
+            },
+        ];
+        store.transaction(&changes).await.unwrap();
+        assert!(!store.exists("Cold").await.unwrap());
+        assert_eq!(store.find("Vault").await.unwrap().unwrap().id, record.id);
+        assert_eq!(store.count().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn sqlite_history_keeps_newest() {
+        let store = open().await;
+        let sample = |timestamp| Sample {
+            timestamp,
+            block_number: None,
+            balance: Balance::new(1),
+        };
+        let samples: Vec<_> = (1..=4).map(|t| ("w1".to_string(), sample(t))).collect();
+        store.append_samples(&samples, 2).await.unwrap();
+
+        let history = store.history("w1").await.unwrap();
+        let timestamps: Vec<i64> = history.iter().map(|sample| sample.timestamp).collect();
+        assert_eq!(timestamps, [3, 4]);
+    }
+}
@@ -1,25 +1,97 @@
+mod wallet_challenge;
+mod wallet_delete_view;
+mod wallet_deliver;
+mod wallet_donations;
+mod wallet_events;
+mod wallet_export;
+mod wallet_get;
+mod wallet_history;
+mod wallet_import;
 mod wallet_list;
+mod wallet_nft_holdings;
+mod wallet_pending_sweeps;
+mod wallet_pin;
+mod wallet_portfolio;
+mod wallet_purge;
 mod wallet_refresh;
+mod wallet_rename;
+mod wallet_restore;
+mod wallet_save_view;
+mod wallet_search;
+mod wallet_share;
+mod wallet_shared;
+mod wallet_sweep;
 mod wallet_track;
+mod wallet_transactions;
 mod wallet_untrack;
+mod wallet_update;
+mod wallet_verify;
+mod wallet_views;
+mod wallet_watch;
 
-use std::{error, fmt, result};
+use std::{collections::BTreeMap, error, fmt, result, sync::Mutex, time::Duration};
 
 use chrono::{DateTime, Utc};
+use futures::future::try_join_all;
+use tracing::warn;
+use unicode_normalization::UnicodeNormalization;
+use uuid::Uuid;
 
 use crate::{
-    core::AddrParseError,
-    infra::{ClientError, StoreError},
+    core::{
+        AddrParseError, Balance, BalanceDelta, BalanceFormat, SignatureError, Wallet as CoreWallet,
+        uri::UriParseError,
+    },
+    infra::{
+        AuditEntry, AuditOutcome, AuditStore, ClientError, PriceOracle, Provenance, StoreError,
+        TrashStore, TrashedRecord, WalletClient, WalletRecord, WalletStore,
+    },
 };
 
 const NAME_MAX: usize = 30;
+const TAG_MAX: usize = 20;
+const NOTE_MAX: usize = 500;
 
 pub type Result<T> = result::Result<T, WalletError>;
 
-pub use wallet_list::{List, ListExecutor};
-pub use wallet_refresh::{Refresh, RefreshExecutor};
-pub use wallet_track::{Track, TrackExecutor};
+pub use wallet_challenge::{Challenge, ChallengeExecutor, Challenges, OwnershipChallenge};
+pub use wallet_delete_view::{DeleteView, DeleteViewExecutor};
+pub use wallet_deliver::{Deliver, DeliverExecutor, DeliverySummary};
+pub use wallet_donations::{
+    ChainDonations, DonationPeriod, Donations, DonationsExecutor, DonationsReport,
+};
+pub use wallet_events::{
+    EventBus, Overflow, RecvError, ResumeToken, Subscription, TryRecvError, WalletEvent,
+};
+pub use wallet_export::{Export, ExportExecutor, ExportFormat};
+pub use wallet_get::{Get, GetExecutor};
+pub use wallet_history::{BalanceCandle, History, HistoryExecutor};
+pub use wallet_import::{
+    Import, ImportExecutor, ImportFormat, ImportRejection, ImportSummary, NewImport,
+};
+pub use wallet_list::{List, ListExecutor, ListFilter};
+pub use wallet_nft_holdings::{NftHolding, NftHoldings, NftHoldingsExecutor};
+pub use wallet_pending_sweeps::{PendingSweep, PendingSweeps, PendingSweepsExecutor};
+pub use wallet_pin::{Pin, PinExecutor};
+pub use wallet_portfolio::{Portfolio, PortfolioExecutor, PortfolioSummary, PortfolioTotal};
+pub use wallet_purge::{Purge, PurgeExecutor};
+pub use wallet_refresh::{BalanceChange, Refresh, RefreshExecutor, RefreshSummary};
+pub use wallet_rename::{Rename, RenameExecutor};
+pub use wallet_restore::{Restore, RestoreExecutor};
+pub use wallet_save_view::{SaveView, SaveViewExecutor};
+pub use wallet_search::{Search, SearchExecutor};
+pub use wallet_share::{Share, ShareExecutor, ShareLink, Shares};
+pub use wallet_shared::{SharedWallet, ViewShared, ViewSharedExecutor};
+pub use wallet_sweep::{Sweep, SweepExecutor, SweepPolicy, SweepSummary};
+pub use wallet_track::{NewWallet, Track, TrackExecutor};
+pub use wallet_transactions::{
+    Transactions, TransactionsExecutor, TransferDirection, WalletTransfer,
+};
 pub use wallet_untrack::{Untrack, UntrackExecutor};
+pub use wallet_update::{Update, UpdateExecutor, WalletUpdate};
+pub use wallet_verify::{Verify, VerifyExecutor};
+pub use wallet_views::{Views, ViewsExecutor};
+pub use wallet_watch::{Watch, WatchExecutor};
 
 #[derive(Debug)]
 pub struct WalletError {
@@ -48,6 +120,27 @@ impl fmt::Display for WalletError {
             WalletErrorKind::NameTooLong => {
                 write!(f, "wallet name exeeds {NAME_MAX} characters")
             }
+            WalletErrorKind::TagEmpty => {
+                write!(f, "wallet tag is empty")
+            }
+            WalletErrorKind::TagTooLong => {
+                write!(f, "wallet tag exeeds {TAG_MAX} characters")
+            }
+            WalletErrorKind::NoteTooLong => {
+                write!(f, "wallet note exeeds {NOTE_MAX} characters")
+            }
+            WalletErrorKind::QueryEmpty => {
+                write!(f, "search query is empty")
+            }
+            WalletErrorKind::ResumeTokenInvalid => {
+                write!(f, "couldn't parse resume token")
+            }
+            WalletErrorKind::ResumeTokenExpired => {
+                write!(f, "resume token is expired or from another server")
+            }
+            WalletErrorKind::BalanceOverflow => {
+                write!(f, "balance total overflowed")
+            }
             WalletErrorKind::WalletStore => {
                 write!(f, "wallet store error")
             }
@@ -57,6 +150,42 @@ impl fmt::Display for WalletError {
             WalletErrorKind::WalletAddrParse => {
                 write!(f, "couldn't parse wallet address")
             }
+            WalletErrorKind::NameUnresolved => {
+                write!(f, "couldn't resolve name to an address")
+            }
+            WalletErrorKind::ChainUnsupported => {
+                write!(f, "payment uri is for another chain")
+            }
+            WalletErrorKind::AddressUnspendable => {
+                write!(f, "address is the zero or a burn address")
+            }
+            WalletErrorKind::AddressTracked => {
+                write!(f, "address is already tracked on this chain")
+            }
+            WalletErrorKind::ChallengeMissing => {
+                write!(f, "no live ownership challenge for wallet")
+            }
+            WalletErrorKind::SignatureParse => {
+                write!(f, "couldn't parse ownership signature")
+            }
+            WalletErrorKind::SignerMismatch => {
+                write!(f, "signature wasn't made by the wallet address")
+            }
+            WalletErrorKind::ViewNotFound => {
+                write!(f, "saved view not found")
+            }
+            WalletErrorKind::ViewNameEmpty => {
+                write!(f, "view name is empty")
+            }
+            WalletErrorKind::ViewNameTooLong => {
+                write!(f, "view name exeeds {NAME_MAX} characters")
+            }
+            WalletErrorKind::ImportMalformed => {
+                write!(f, "import file is malformed")
+            }
+            WalletErrorKind::ImportRowInvalid => {
+                write!(f, "import row must hold a name and an address")
+            }
         }
     }
 }
@@ -73,9 +202,28 @@ pub enum WalletErrorKind {
     NameConflict,
     NameEmpty,
     NameTooLong,
+    TagEmpty,
+    TagTooLong,
+    NoteTooLong,
+    QueryEmpty,
+    ResumeTokenInvalid,
+    ResumeTokenExpired,
+    BalanceOverflow,
     WalletStore,
     WalletClient,
     WalletAddrParse,
+    NameUnresolved,
+    ChainUnsupported,
+    AddressUnspendable,
+    AddressTracked,
+    ChallengeMissing,
+    SignatureParse,
+    SignerMismatch,
+    ViewNotFound,
+    ViewNameEmpty,
+    ViewNameTooLong,
+    ImportMalformed,
+    ImportRowInvalid,
 }
 
 impl From<StoreError> for WalletError {
@@ -96,6 +244,24 @@ impl From<ClientError> for WalletError {
     }
 }
 
+impl From<UriParseError> for WalletError {
+    fn from(error: UriParseError) -> Self {
+        Self {
+            kind: WalletErrorKind::WalletAddrParse,
+            source: Some(error.into()),
+        }
+    }
+}
+
+impl From<SignatureError> for WalletError {
+    fn from(error: SignatureError) -> Self {
+        Self {
+            kind: WalletErrorKind::SignatureParse,
+            source: Some(error.into()),
+        }
+    }
+}
+
 impl From<AddrParseError> for WalletError {
     fn from(error: AddrParseError) -> Self {
         Self {
@@ -107,8 +273,326 @@ impl From<AddrParseError> for WalletError {
 
 #[derive(Debug, Clone)]
 pub struct Wallet {
+    pub id: String,
     pub name: String,
+    pub chain_id: u64,
     pub address: String,
+    pub linked_addresses: Vec<String>,
     pub balance: String,
+    pub balance_wei: String,
+    pub value_usd: Option<String>,
+    pub delta_wei: Option<String>,
+    pub delta_pct: Option<f64>,
+    pub tokens: Vec<TokenHolding>,
+    pub transaction_count: Option<u64>,
+    pub tags: Vec<String>,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub created_by: Provenance,
+    pub modified_by: Provenance,
     pub last_update: DateTime<Utc>,
+    pub field_errors: Vec<FieldError>,
+    pub verified_at: Option<DateTime<Utc>>,
+    pub ens_name: Option<String>,
+    pub resolved_from: Option<String>,
+    pub age_seconds: u64,
+    pub is_stale: bool,
+    pub pinned: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Enrichment {
+    Price,
+    Ens,
+    Tokens,
+    Nfts,
+}
+
+impl Enrichment {
+    pub fn field(self) -> &'static str {
+        match self {
+            Enrichment::Price => "value_usd",
+            Enrichment::Ens => "ens_name",
+            Enrichment::Tokens => "tokens",
+            Enrichment::Nfts => "nft_holdings",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldError {
+    pub enrichment: Enrichment,
+    pub reason: String,
+}
+
+#[derive(Debug, Default)]
+pub struct EnrichmentErrors {
+    errors: Mutex<BTreeMap<(String, Enrichment), String>>,
+}
+
+impl EnrichmentErrors {
+    pub fn record(&self, wallet_id: &str, enrichment: Enrichment, error: &dyn error::Error) {
+        let reason = crate::server::compose_error(error);
+        let mut errors = self.errors.lock().unwrap();
+        errors.insert((wallet_id.to_owned(), enrichment), reason);
+    }
+
+    pub fn clear(&self, wallet_id: &str, enrichment: Enrichment) {
+        let mut errors = self.errors.lock().unwrap();
+        errors.remove(&(wallet_id.to_owned(), enrichment));
+    }
+
+    pub fn of(&self, wallet_id: &str) -> Vec<FieldError> {
+        let errors = self.errors.lock().unwrap();
+        errors
+            .iter()
+            .filter(|((id, _), _)| id == wallet_id)
+            .map(|((_, enrichment), reason)| FieldError {
+                enrichment: *enrichment,
+                reason: reason.clone(),
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenHolding {
+    pub symbol: String,
+    pub contract: String,
+    pub decimals: u32,
+    pub amount: String,
+    pub amount_raw: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DisplayPolicy {
+    pub format: BalanceFormat,
+    pub stale_after: Duration,
+}
+
+impl Default for DisplayPolicy {
+    fn default() -> Self {
+        Self {
+            format: BalanceFormat::default(),
+            stale_after: Duration::from_secs(5 * 60),
+        }
+    }
+}
+
+impl DisplayPolicy {
+    pub fn format(&self, balance: Balance) -> String {
+        balance.format(&self.format)
+    }
+
+    pub fn staleness(&self, last_update: DateTime<Utc>) -> (u64, bool) {
+        let age = (Utc::now() - last_update).num_seconds().max(0) as u64;
+        (age, age > self.stale_after.as_secs())
+    }
+}
+
+fn to_wallet(display: &DisplayPolicy, name: String, record: WalletRecord) -> Wallet {
+    let (age_seconds, is_stale) = display.staleness(record.last_update);
+    let delta = record.previous_balance.map(|before| BalanceDelta {
+        before,
+        after: record.wallet.balance(),
+    });
+    Wallet {
+        id: record.id,
+        name,
+        chain_id: record.wallet.chain_id().get(),
+        address: record.wallet.address().to_string(),
+        linked_addresses: record
+            .wallet
+            .linked()
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+        balance: display.format(record.wallet.balance()),
+        balance_wei: record.wallet.balance().to_string(),
+        value_usd: None,
+        delta_wei: delta.map(|delta| delta.wei()),
+        delta_pct: delta.and_then(|delta| delta.percent()),
+        tokens: record
+            .tokens
+            .iter()
+            .map(|balance| TokenHolding {
+                symbol: balance.token.symbol.clone(),
+                contract: balance.token.address.to_string(),
+                decimals: balance.token.decimals,
+                amount: balance.units(),
+                amount_raw: balance.amount.to_string(),
+            })
+            .collect(),
+        transaction_count: record.transaction_count,
+        tags: record.tags,
+        note: record.note,
+        created_at: record.created_at,
+        created_by: record.created_by,
+        modified_by: record.modified_by,
+        last_update: record.last_update,
+        field_errors: Vec::new(),
+        verified_at: record.verified_at,
+        ens_name: record.ens_name,
+        resolved_from: record.resolved_from,
+        age_seconds,
+        is_stale,
+        pinned: false,
+    }
+}
+
+async fn eth_usd_price(price_oracle: Option<&dyn PriceOracle>) -> Option<f64> {
+    price_oracle?
+        .eth_usd_price()
+        .await
+        .inspect_err(|e| warn!("couldn't get the eth price: {e:?}"))
+        .ok()
+}
+
+async fn audit<T>(
+    audit_store: &dyn AuditStore,
+    operation: &str,
+    name: &str,
+    address: Option<&str>,
+    result: &Result<T>,
+) {
+    let entry = AuditEntry {
+        operation: operation.to_owned(),
+        name: name.to_owned(),
+        address: address.map(str::to_owned),
+        at: Utc::now(),
+        outcome: match result {
+            Ok(_) => AuditOutcome::Succeeded,
+            Err(e) => AuditOutcome::Failed(e.to_string()),
+        },
+    };
+
+    if let Err(e) = audit_store.append(&entry).await {
+        warn!("couldn't append {operation} of {name} to the audit trail: {e:?}");
+    }
+}
+
+#[cfg(test)]
+fn audit_store() -> std::sync::Arc<dyn AuditStore> {
+    let mut audit_store = crate::infra::MockAuditStore::new();
+    audit_store.expect_append().returning(|_| Ok(()));
+    std::sync::Arc::new(audit_store)
+}
+
+async fn fetch_balance(wallet_client: &dyn WalletClient, wallet: &CoreWallet) -> Result<Balance> {
+    let balances = try_join_all(
+        wallet
+            .addresses()
+            .map(|address| wallet_client.balance(address)),
+    )
+    .await?;
+    Ok(balances
+        .into_iter()
+        .fold(Balance::default(), Balance::saturating_add))
+}
+
+fn normalize_name(name: &str) -> String {
+    name.trim().nfc().collect()
+}
+
+fn validate_name(name: &str) -> Result<String> {
+    let name = normalize_name(name);
+    if name.is_empty() {
+        Err(WalletError {
+            kind: WalletErrorKind::NameEmpty,
+            source: None,
+        })
+    } else if name.chars().count() > NAME_MAX {
+        Err(WalletError {
+            kind: WalletErrorKind::NameTooLong,
+            source: None,
+        })
+    } else {
+        Ok(name)
+    }
+}
+
+pub(crate) async fn resolve(
+    wallet_store: &dyn WalletStore,
+    key: &str,
+) -> Result<(String, WalletRecord)> {
+    let key = normalize_name(key);
+    let key = key.as_str();
+    let found = if Uuid::parse_str(key).is_ok() {
+        wallet_store
+            .all()
+            .await?
+            .into_iter()
+            .find(|(_, record)| record.id == key)
+    } else {
+        wallet_store
+            .find(key)
+            .await?
+            .map(|record| (key.to_owned(), record))
+    };
+
+    found.ok_or(WalletError {
+        kind: WalletErrorKind::NotFound,
+        source: None,
+    })
+}
+
+async fn resolve_trashed(
+    trash_store: &dyn TrashStore,
+    key: &str,
+) -> Result<(String, TrashedRecord)> {
+    let key = normalize_name(key);
+    let key = key.as_str();
+    let found = if Uuid::parse_str(key).is_ok() {
+        trash_store
+            .all_trashed()
+            .await?
+            .into_iter()
+            .find(|(_, trashed)| trashed.record.id == key)
+    } else {
+        trash_store
+            .find_trashed(key)
+            .await?
+            .map(|trashed| (key.to_owned(), trashed))
+    };
+
+    found.ok_or(WalletError {
+        kind: WalletErrorKind::NotFound,
+        source: None,
+    })
+}
+
+fn normalize_note(note: &str) -> Result<Option<String>> {
+    let note = note.trim();
+    if note.chars().count() > NOTE_MAX {
+        Err(WalletError {
+            kind: WalletErrorKind::NoteTooLong,
+            source: None,
+        })
+    } else {
+        Ok((!note.is_empty()).then(|| note.to_owned()))
+    }
+}
+
+fn normalize_tags(tags: &[String]) -> Result<Vec<String>> {
+    let mut normalized = Vec::with_capacity(tags.len());
+
+    for tag in tags {
+        let tag = tag.trim().to_lowercase();
+        if tag.is_empty() {
+            return Err(WalletError {
+                kind: WalletErrorKind::TagEmpty,
+                source: None,
+            });
+        } else if tag.chars().count() > TAG_MAX {
+            return Err(WalletError {
+                kind: WalletErrorKind::TagTooLong,
+                source: None,
+            });
+        }
+        normalized.push(tag);
+    }
+
+    normalized.sort();
+    normalized.dedup();
+    Ok(normalized)
 }
@@ -0,0 +1,108 @@
+use std::{
+    any::type_name,
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::{Result, WalletError, WalletErrorKind, resolve};
+use crate::{core::Address, infra::WalletStore};
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait Challenge: Send + Sync + 'static {
+    async fn execute(&self, name: &str) -> Result<OwnershipChallenge>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnershipChallenge {
+    pub message: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+pub struct Challenges {
+    ttl: Duration,
+    pending: Mutex<HashMap<String, PendingChallenge>>,
+}
+
+#[derive(Debug, Clone)]
+struct PendingChallenge {
+    message: String,
+    address: Address,
+    expires_at: DateTime<Utc>,
+}
+
+impl Challenges {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn issue(&self, id: &str, address: Address) -> OwnershipChallenge {
+        let now = Utc::now();
+        let ttl = chrono::Duration::from_std(self.ttl).unwrap_or(chrono::Duration::MAX);
+        let expires_at = now
+            .checked_add_signed(ttl)
+            .unwrap_or(DateTime::<Utc>::MAX_UTC);
+
+        let message = format!(
+            "mini-wallet ownership challenge\nwallet: {id}\naddress: {address}\nnonce: {}\nissued: {}",
+            Uuid::new_v4(),
+            now.to_rfc3339(),
+        );
+
+        let pending = PendingChallenge {
+            message: message.clone(),
+            address,
+            expires_at,
+        };
+        self.pending.lock().unwrap().insert(id.to_owned(), pending);
+
+        OwnershipChallenge {
+            message,
+            expires_at,
+        }
+    }
+
+    pub(super) fn take(&self, id: &str, address: &Address) -> Result<String> {
+        let pending = self.pending.lock().unwrap().remove(id);
+
+        match pending {
+            Some(pending) if pending.expires_at > Utc::now() && pending.address == *address => {
+                Ok(pending.message)
+            }
+            _ => Err(WalletError {
+                kind: WalletErrorKind::ChallengeMissing,
+                source: None,
+            }),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ChallengeExecutor {
+    pub wallet_store: Arc<dyn WalletStore>,
+    pub challenges: Arc<Challenges>,
+}
+
+impl fmt::Debug for ChallengeExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>()).finish()
+    }
+}
+
+#[async_trait]
+impl Challenge for ChallengeExecutor {
+    async fn execute(&self, name: &str) -> Result<OwnershipChallenge> {
+        let (_, record) = resolve(self.wallet_store.as_ref(), name).await?;
+        Ok(self.challenges.issue(&record.id, *record.wallet.address()))
+    }
+}
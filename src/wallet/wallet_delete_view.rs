@@ -0,0 +1,38 @@
+use std::{any::type_name, fmt, sync::Arc};
+
+use async_trait::async_trait;
+
+use super::{Result, WalletError, WalletErrorKind, normalize_name};
+use crate::infra::PreferenceStore;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait DeleteView: Send + Sync + 'static {
+    async fn execute(&self, principal: &str, name: &str) -> Result<()>;
+}
+
+#[derive(Clone)]
+pub struct DeleteViewExecutor {
+    pub preference_store: Arc<dyn PreferenceStore>,
+}
+
+impl fmt::Debug for DeleteViewExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>()).finish()
+    }
+}
+
+#[async_trait]
+impl DeleteView for DeleteViewExecutor {
+    async fn execute(&self, principal: &str, name: &str) -> Result<()> {
+        let name = normalize_name(name);
+        if self.preference_store.delete_view(principal, &name).await? {
+            Ok(())
+        } else {
+            Err(WalletError {
+                kind: WalletErrorKind::ViewNotFound,
+                source: None,
+            })
+        }
+    }
+}
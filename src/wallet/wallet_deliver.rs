@@ -0,0 +1,165 @@
+use std::{any::type_name, fmt, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use tracing::warn;
+
+use super::Result;
+use crate::infra::{Notifier, OutboxStore};
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait Deliver: Send + Sync + 'static {
+    async fn execute(&self) -> Result<DeliverySummary>;
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DeliverySummary {
+    pub delivered: usize,
+    pub failed: usize,
+    pub waiting: usize,
+}
+
+#[derive(Clone)]
+pub struct DeliverExecutor {
+    pub outbox_store: Arc<dyn OutboxStore>,
+    pub notifier: Arc<dyn Notifier>,
+    pub backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl fmt::Debug for DeliverExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>()).finish()
+    }
+}
+
+#[async_trait]
+impl Deliver for DeliverExecutor {
+    async fn execute(&self) -> Result<DeliverySummary> {
+        let mut entries = self.outbox_store.outbox().await?;
+        entries.sort_by_key(|entry| entry.created_at);
+        let now = Utc::now();
+
+        let mut summary = DeliverySummary::default();
+        for entry in entries {
+            if entry.next_attempt_at > now {
+                summary.waiting += 1;
+                continue;
+            }
+
+            match self
+                .notifier
+                .notify(&entry.notification, entry.schema_version)
+                .await
+            {
+                Ok(()) => {
+                    self.outbox_store.ack(&entry.id).await?;
+                    summary.delivered += 1;
+                }
+                Err(e) => {
+                    warn!("couldn't deliver notification {}: {e:?}", entry.id);
+                    let attempts = entry.attempts.saturating_add(1);
+                    let delay = self
+                        .backoff
+                        .saturating_mul(2u32.saturating_pow(attempts - 1))
+                        .min(self.max_backoff);
+                    let delay = chrono::Duration::from_std(delay).unwrap_or(chrono::Duration::MAX);
+                    let next_attempt_at = now.checked_add_signed(delay).unwrap_or(now);
+                    self.outbox_store
+                        .reschedule(&entry.id, attempts, next_attempt_at)
+                        .await?;
+                    summary.failed += 1;
+                }
+            }
+        }
+
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io, sync::Arc, time::Duration};
+
+    use chrono::{TimeDelta, Utc};
+
+    use crate::{
+        core::Balance,
+        infra::{ClientError, MockNotifier, MockOutboxStore, Notification, OutboxEntry},
+        wallet::{Deliver, DeliverExecutor, DeliverySummary},
+    };
+
+    fn entry(id: &str, name: &str) -> OutboxEntry {
+        OutboxEntry {
+            id: id.to_string(),
+            ..OutboxEntry::new(
+                Notification::BalanceChanged {
+                    wallet_id: "w1".to_string(),
+                    name: name.to_string(),
+                    before: Balance::new(1),
+                    after: Balance::new(2),
+                },
+                2,
+            )
+        }
+    }
+
+    #[tokio::test]
+    async fn wallet_deliver_ack_and_reschedule() {
+        let mut outbox_store = MockOutboxStore::new();
+        outbox_store.expect_outbox().returning(|| {
+            let failing = OutboxEntry {
+                attempts: 2,
+                ..entry("e2", "Down")
+            };
+            let waiting = OutboxEntry {
+                next_attempt_at: Utc::now() + TimeDelta::hours(1),
+                ..entry("e3", "Later")
+            };
+            Ok(vec![entry("e1", "Up"), failing, waiting])
+        });
+        outbox_store
+            .expect_ack()
+            .withf(|id| id == "e1")
+            .times(1)
+            .returning(|_| Ok(()));
+        outbox_store
+            .expect_reschedule()
+            .withf(|id, attempts, next_attempt_at| {
+                let wait = *next_attempt_at - Utc::now();
+                id == "e2" && *attempts == 3 && wait > TimeDelta::seconds(35)
+            })
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        let mut notifier = MockNotifier::new();
+        notifier
+            .expect_notify()
+            .times(2)
+            .withf(|_, schema_version| *schema_version == 2)
+            .returning(|notification, _| match notification {
+                Notification::BalanceChanged { name, .. } if name == "Down" => {
+                    Err(ClientError(io::Error::other("receiver down").into()))
+                }
+                _ => Ok(()),
+            });
+
+        let deliver = DeliverExecutor {
+            outbox_store: Arc::new(outbox_store),
+            notifier: Arc::new(notifier),
+            backoff: Duration::from_secs(10),
+            max_backoff: Duration::from_secs(60 * 60),
+        };
+
+        let summary = deliver.execute().await.unwrap();
+        assert_eq!(
+            summary,
+            DeliverySummary {
+                delivered: 1,
+                failed: 1,
+                waiting: 1,
+            }
+        );
+    }
+}
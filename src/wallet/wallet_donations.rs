@@ -0,0 +1,249 @@
+use std::{
+    any::type_name,
+    collections::{BTreeMap, HashSet},
+    fmt,
+    ops::Range,
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::future::try_join_all;
+
+use super::{DisplayPolicy, Result};
+use crate::{
+    core::{self, Address, Balance, ChainId},
+    infra::{HistoryClient, WalletStore},
+};
+
+// Etherscan pages stop at 10,000 entries.
+const DONATIONS_TRANSFERS_MAX: usize = 10_000;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait Donations: Send + Sync + 'static {
+    async fn execute(&self, range: Range<i64>, interval: i64) -> Result<DonationsReport>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DonationsReport {
+    pub wallets: usize,
+    pub chains: Vec<ChainDonations>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainDonations {
+    pub chain_id: ChainId,
+    pub wallets: usize,
+    pub total: String,
+    pub total_wei: String,
+    pub periods: Vec<DonationPeriod>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DonationPeriod {
+    pub start: DateTime<Utc>,
+    pub received: String,
+    pub received_wei: String,
+}
+
+#[derive(Clone)]
+pub struct DonationsExecutor {
+    pub wallet_store: Arc<dyn WalletStore>,
+    pub history_client: Arc<dyn HistoryClient>,
+    pub tags: Vec<String>,
+    pub display: DisplayPolicy,
+}
+
+impl fmt::Debug for DonationsExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>())
+            .field("tags", &self.tags)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl Donations for DonationsExecutor {
+    async fn execute(&self, range: Range<i64>, interval: i64) -> Result<DonationsReport> {
+        let wallets: Vec<_> = self
+            .wallet_store
+            .all()
+            .await?
+            .into_values()
+            .filter(|record| record.tags.iter().any(|tag| self.tags.contains(tag)))
+            .collect();
+
+        let mut chains: BTreeMap<ChainId, (usize, HashSet<Address>)> = BTreeMap::new();
+        for record in &wallets {
+            let (count, addresses) = chains.entry(record.wallet.chain_id()).or_default();
+            *count += 1;
+            addresses.extend(record.wallet.addresses().copied());
+        }
+
+        let chains = try_join_all(chains.into_iter().map(|(chain_id, (count, addresses))| {
+            self.chain_donations(chain_id, count, addresses, range.clone(), interval)
+        }))
+        .await?;
+
+        Ok(DonationsReport {
+            wallets: wallets.len(),
+            chains,
+        })
+    }
+}
+
+impl DonationsExecutor {
+    async fn chain_donations(
+        &self,
+        chain_id: ChainId,
+        wallets: usize,
+        addresses: HashSet<Address>,
+        range: Range<i64>,
+        interval: i64,
+    ) -> Result<ChainDonations> {
+        let pages = try_join_all(addresses.iter().map(|address| {
+            self.history_client
+                .transfers(address, chain_id, DONATIONS_TRANSFERS_MAX)
+        }))
+        .await?;
+
+        // Moving funds between donation addresses isn't a donation, and a
+        // transfer between two of them shows up on both.
+        let mut seen = HashSet::new();
+        let received = pages
+            .into_iter()
+            .flatten()
+            .filter(|transfer| {
+                !transfer.failed
+                    && range.contains(&transfer.timestamp)
+                    && !addresses.contains(&transfer.from)
+                    && transfer.to.is_some_and(|to| addresses.contains(&to))
+            })
+            .filter(|transfer| seen.insert(transfer.hash.clone()))
+            .map(|transfer| (transfer.timestamp, transfer.value));
+        let inflows = core::inflows(received, interval);
+
+        let total = inflows.iter().fold(Balance::default(), |total, inflow| {
+            total.saturating_add(inflow.amount)
+        });
+        Ok(ChainDonations {
+            chain_id,
+            wallets,
+            total: self.display.format(total),
+            total_wei: total.to_string(),
+            periods: inflows
+                .into_iter()
+                .map(|inflow| DonationPeriod {
+                    start: DateTime::from_timestamp(inflow.start, 0).unwrap_or_default(),
+                    received: self.display.format(inflow.amount),
+                    received_wei: inflow.amount.to_string(),
+                })
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, sync::Arc};
+
+    use crate::{
+        core::{Address, Balance, ChainId, Transfer, Wallet},
+        infra::{MockHistoryClient, MockWalletStore, WalletRecord},
+        wallet::{ChainDonations, DisplayPolicy, Donations, DonationsExecutor},
+    };
+
+    const DAY: i64 = 24 * 60 * 60;
+
+    fn record(byte: u8, chain_id: u64, tags: &[&str]) -> WalletRecord {
+        let address = Address::new([byte; 20]);
+        WalletRecord {
+            id: format!("w{byte}"),
+            tags: tags.iter().map(ToString::to_string).collect(),
+            ..WalletRecord::new(Wallet::on_chain(ChainId::new(chain_id), address))
+        }
+    }
+
+    fn transfer(hash: &str, timestamp: i64, from: u8, to: u8, wei: u128) -> Transfer {
+        Transfer {
+            hash: hash.to_string(),
+            block_number: 0,
+            timestamp,
+            from: Address::new([from; 20]),
+            to: Some(Address::new([to; 20])),
+            value: Balance::new(wei),
+            failed: false,
+        }
+    }
+
+    fn periods(chain: &ChainDonations) -> Vec<(i64, &str)> {
+        chain
+            .periods
+            .iter()
+            .map(|period| (period.start.timestamp(), period.received_wei.as_str()))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn wallet_donations_sum_tagged_inflows() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_all().returning(|| {
+            Ok(BTreeMap::from([
+                ("Fund".to_string(), record(1, 1, &["donation"])),
+                ("Grants".to_string(), record(2, 1, &["grants"])),
+                ("Base".to_string(), record(4, 8453, &["donation"])),
+                ("Hot".to_string(), record(3, 1, &[])),
+            ]))
+        });
+
+        let mut history_client = MockHistoryClient::new();
+        history_client
+            .expect_transfers()
+            .returning(|address, chain_id, _| {
+                Ok(match (address.inner()[0], chain_id.get()) {
+                    (1, 1) => vec![
+                        transfer("0x1", 60, 9, 1, 10),
+                        // Spending doesn't count against what came in.
+                        transfer("0x2", 120, 1, 9, 7),
+                        transfer("0x3", DAY + 60, 9, 1, 3),
+                        // Moved between donation addresses.
+                        transfer("0x4", DAY + 120, 1, 2, 5),
+                        Transfer {
+                            failed: true,
+                            ..transfer("0x5", DAY + 180, 9, 1, 100)
+                        },
+                        transfer("0x6", 2 * DAY, 9, 1, 100),
+                    ],
+                    (2, 1) => vec![
+                        transfer("0x4", DAY + 120, 1, 2, 5),
+                        transfer("0x7", 180, 3, 2, 4),
+                    ],
+                    (4, 8453) => vec![transfer("0x8", DAY, 9, 4, 6)],
+                    (byte, chain) => panic!("read transfers of wallet {byte} on chain {chain}"),
+                })
+            });
+
+        let donations = DonationsExecutor {
+            wallet_store: Arc::new(wallet_store),
+            history_client: Arc::new(history_client),
+            tags: vec!["donation".to_string(), "grants".to_string()],
+            display: DisplayPolicy::default(),
+        };
+
+        let report = donations.execute(0..2 * DAY, DAY).await.unwrap();
+        assert_eq!(report.wallets, 3);
+        let [mainnet, base] = report.chains.as_slice() else {
+            panic!("expected two chains, got {:?}", report.chains);
+        };
+
+        assert_eq!(mainnet.chain_id, ChainId::MAINNET);
+        assert_eq!(mainnet.wallets, 2);
+        assert_eq!(mainnet.total_wei, "17");
+        assert_eq!(periods(mainnet), [(0, "14"), (DAY, "3")]);
+
+        assert_eq!(base.chain_id, ChainId::new(8453));
+        assert_eq!(base.total_wei, "6");
+        assert_eq!(periods(base), [(DAY, "6")]);
+    }
+}
@@ -0,0 +1,328 @@
+use std::{
+    collections::VecDeque,
+    fmt,
+    num::ParseIntError,
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+
+use tokio::sync::Notify;
+
+use super::BalanceChange;
+use crate::core::Address;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WalletEvent {
+    Tracked {
+        wallet_id: String,
+        name: String,
+    },
+    Untracked {
+        wallet_id: String,
+        name: String,
+    },
+    Renamed {
+        wallet_id: String,
+        from: String,
+        to: String,
+    },
+    BalanceChanged(BalanceChange),
+    AddressChanged {
+        wallet_id: String,
+        name: String,
+        from: Address,
+        to: Address,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ResumeToken {
+    epoch: u64,
+    seq: u64,
+}
+
+impl fmt::Display for ResumeToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:016x}{:016x}", self.epoch, self.seq)
+    }
+}
+
+impl FromStr for ResumeToken {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (epoch, seq) = s.split_at_checked(16).unwrap_or((s, ""));
+        Ok(Self {
+            epoch: u64::from_str_radix(epoch, 16)?,
+            seq: u64::from_str_radix(seq, 16)?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Overflow {
+    #[default]
+    DropOldest,
+    Disconnect,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecvError {
+    Lagged(u64),
+    Closed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TryRecvError {
+    Empty,
+    Lagged(u64),
+    Closed,
+}
+
+#[derive(Debug)]
+pub struct EventBus {
+    capacity: usize,
+    overflow: Overflow,
+    epoch: u64,
+    state: Mutex<BusState>,
+}
+
+#[derive(Debug, Default)]
+struct BusState {
+    seq: u64,
+    recent: VecDeque<(u64, WalletEvent)>,
+    subscribers: Vec<Arc<Queue>>,
+}
+
+#[derive(Debug, Default)]
+struct Queue {
+    state: Mutex<QueueState>,
+    ready: Notify,
+}
+
+#[derive(Debug, Default)]
+struct QueueState {
+    events: VecDeque<(u64, WalletEvent)>,
+    missed: u64,
+    closed: bool,
+}
+
+impl Queue {
+    fn close(&self) {
+        self.state.lock().unwrap().closed = true;
+        self.ready.notify_one();
+    }
+}
+
+impl EventBus {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            overflow: Overflow::default(),
+            epoch: fastrand::u64(..),
+            state: Mutex::default(),
+        }
+    }
+
+    pub fn with_overflow(mut self, overflow: Overflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    pub fn publish(&self, event: WalletEvent) {
+        let mut state = self.state.lock().unwrap();
+        state.seq += 1;
+        let seq = state.seq;
+        if state.recent.len() == self.capacity {
+            state.recent.pop_front();
+        }
+        state.recent.push_back((seq, event.clone()));
+
+        // Having no subscribers isn't an error. Dropped and disconnected
+        // ones are forgotten here.
+        state.subscribers.retain(|queue| {
+            if Arc::strong_count(queue) == 1 {
+                return false;
+            }
+            let mut queued = queue.state.lock().unwrap();
+            if queued.closed {
+                return false;
+            }
+            if queued.events.len() == self.capacity {
+                match self.overflow {
+                    Overflow::DropOldest => {
+                        queued.events.pop_front();
+                        queued.missed += 1;
+                    }
+                    Overflow::Disconnect => {
+                        queued.closed = true;
+                        drop(queued);
+                        queue.ready.notify_one();
+                        return false;
+                    }
+                }
+            }
+            queued.events.push_back((seq, event.clone()));
+            drop(queued);
+            queue.ready.notify_one();
+            true
+        });
+    }
+
+    pub fn subscribe(&self) -> Subscription {
+        let mut state = self.state.lock().unwrap();
+        let token = ResumeToken {
+            epoch: self.epoch,
+            seq: state.seq,
+        };
+        self.attach(&mut state, token, VecDeque::new())
+    }
+
+    pub fn subscribe_from(&self, token: ResumeToken) -> Option<Subscription> {
+        let mut state = self.state.lock().unwrap();
+        let oldest = state.recent.front().map_or(state.seq + 1, |(seq, _)| *seq);
+        if token.epoch != self.epoch || token.seq > state.seq || token.seq + 1 < oldest {
+            return None;
+        }
+
+        let missed = state
+            .recent
+            .iter()
+            .filter(|(seq, _)| *seq > token.seq)
+            .cloned()
+            .collect();
+        Some(self.attach(&mut state, token, missed))
+    }
+
+    fn attach(
+        &self,
+        state: &mut BusState,
+        token: ResumeToken,
+        events: VecDeque<(u64, WalletEvent)>,
+    ) -> Subscription {
+        let queue = Arc::new(Queue {
+            state: Mutex::new(QueueState {
+                events,
+                ..QueueState::default()
+            }),
+            ready: Notify::new(),
+        });
+        state.subscribers.push(queue.clone());
+        Subscription { queue, token }
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new(256)
+    }
+}
+
+impl Drop for EventBus {
+    fn drop(&mut self) {
+        let state = self.state.get_mut().unwrap();
+        for queue in state.subscribers.drain(..) {
+            queue.close();
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Subscription {
+    queue: Arc<Queue>,
+    token: ResumeToken,
+}
+
+impl Subscription {
+    pub async fn recv(&mut self) -> Result<WalletEvent, RecvError> {
+        loop {
+            match self.try_recv() {
+                Ok(event) => return Ok(event),
+                Err(TryRecvError::Lagged(missed)) => return Err(RecvError::Lagged(missed)),
+                Err(TryRecvError::Closed) => return Err(RecvError::Closed),
+                Err(TryRecvError::Empty) => self.queue.ready.notified().await,
+            }
+        }
+    }
+
+    pub fn try_recv(&mut self) -> Result<WalletEvent, TryRecvError> {
+        let mut queued = self.queue.state.lock().unwrap();
+        if queued.missed > 0 {
+            return Err(TryRecvError::Lagged(std::mem::take(&mut queued.missed)));
+        }
+        match queued.events.pop_front() {
+            Some((seq, event)) => {
+                self.token.seq = seq;
+                Ok(event)
+            }
+            None if queued.closed => Err(TryRecvError::Closed),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+
+    pub fn token(&self) -> ResumeToken {
+        self.token
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EventBus, Overflow, ResumeToken, TryRecvError, WalletEvent};
+    use crate::{core::Balance, wallet::BalanceChange};
+
+    fn changed(wei: u128) -> WalletEvent {
+        WalletEvent::BalanceChanged(BalanceChange {
+            wallet_id: "w1".to_string(),
+            name: "Hot".to_string(),
+            before: Balance::new(0),
+            after: Balance::new(wei),
+        })
+    }
+
+    #[test]
+    fn event_bus_drop_oldest() {
+        let events = EventBus::new(2);
+        let mut received = events.subscribe();
+        for wei in [1, 2, 3] {
+            events.publish(changed(wei));
+        }
+
+        assert_eq!(received.try_recv(), Err(TryRecvError::Lagged(1)));
+        assert_eq!(received.try_recv(), Ok(changed(2)));
+        assert_eq!(received.try_recv(), Ok(changed(3)));
+        assert_eq!(received.try_recv(), Err(TryRecvError::Empty));
+    }
+
+    #[test]
+    fn event_bus_disconnect_then_resume() {
+        let events = EventBus::new(2).with_overflow(Overflow::Disconnect);
+        let mut received = events.subscribe();
+        events.publish(changed(1));
+        assert_eq!(received.try_recv(), Ok(changed(1)));
+        for wei in [2, 3, 4] {
+            events.publish(changed(wei));
+        }
+
+        assert_eq!(received.try_recv(), Ok(changed(2)));
+        assert_eq!(received.try_recv(), Ok(changed(3)));
+        assert_eq!(received.try_recv(), Err(TryRecvError::Closed));
+
+        let token = received.token().to_string().parse().unwrap();
+        let mut resumed = events.subscribe_from(token).unwrap();
+        assert_eq!(resumed.try_recv(), Ok(changed(4)));
+        assert_eq!(resumed.try_recv(), Err(TryRecvError::Empty));
+    }
+
+    #[test]
+    fn event_bus_resume_expired() {
+        let events = EventBus::new(2);
+        let token = events.subscribe().token();
+        for wei in [1, 2, 3] {
+            events.publish(changed(wei));
+        }
+        assert!(events.subscribe_from(token).is_none());
+
+        let foreign: ResumeToken = "0".repeat(32).parse().unwrap();
+        assert!(EventBus::new(2).subscribe_from(foreign).is_none());
+    }
+}
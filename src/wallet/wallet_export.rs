@@ -0,0 +1,222 @@
+use std::{any::type_name, fmt, sync::Arc};
+
+use async_trait::async_trait;
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_json::{Value, json};
+
+use super::{DisplayPolicy, Result};
+use crate::infra::{WalletRecord, WalletStore};
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait Export: Send + Sync + 'static {
+    async fn execute(&self, format: ExportFormat) -> Result<String>;
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    Json,
+    Ndjson,
+}
+
+impl ExportFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "csv" => Some(Self::Csv),
+            "json" => Some(Self::Json),
+            "ndjson" => Some(Self::Ndjson),
+            _ => None,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Json => "json",
+            Self::Ndjson => "ndjson",
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ExportExecutor {
+    pub wallet_store: Arc<dyn WalletStore>,
+    pub display: DisplayPolicy,
+}
+
+impl fmt::Debug for ExportExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>()).finish()
+    }
+}
+
+#[async_trait]
+impl Export for ExportExecutor {
+    async fn execute(&self, format: ExportFormat) -> Result<String> {
+        let wallets = self.wallet_store.all().await?;
+
+        let rows = wallets
+            .iter()
+            .map(|(name, record)| self.to_json(name, record));
+        let rendered = match format {
+            ExportFormat::Csv => {
+                let mut csv = CSV_COLUMNS.join(",");
+                csv.push('\n');
+                for row in rows {
+                    let fields: Vec<String> = CSV_COLUMNS
+                        .iter()
+                        .map(|column| csv_field(&row[column]))
+                        .collect();
+                    csv.push_str(&fields.join(","));
+                    csv.push('\n');
+                }
+                csv
+            }
+            ExportFormat::Json => format!("{:#}\n", Value::Array(rows.collect())),
+            ExportFormat::Ndjson => rows.map(|row| format!("{row}\n")).collect(),
+        };
+        Ok(rendered)
+    }
+}
+
+const CSV_COLUMNS: [&str; 10] = [
+    "id",
+    "name",
+    "address",
+    "linked_addresses",
+    "tags",
+    "note",
+    "balance",
+    "balance_wei",
+    "last_update",
+    "verified_at",
+];
+
+impl ExportExecutor {
+    fn to_json(&self, name: &str, record: &WalletRecord) -> Value {
+        let linked: Vec<String> = record
+            .wallet
+            .addresses()
+            .skip(1)
+            .map(ToString::to_string)
+            .collect();
+        let balance = record.wallet.balance();
+
+        json!({
+            "id": record.id,
+            "name": name,
+            "address": record.wallet.address().to_string(),
+            "linked_addresses": linked,
+            "tags": record.tags,
+            "note": record.note,
+            "balance": self.display.format(balance),
+            "balance_wei": balance.to_string(),
+            "last_update": timestamp(record.last_update),
+            "verified_at": record.verified_at.map(timestamp),
+        })
+    }
+}
+
+fn timestamp(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+fn csv_field(value: &Value) -> String {
+    let field = match value {
+        Value::Null => return String::new(),
+        Value::String(s) => s.clone(),
+        Value::Array(values) => values
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join(";"),
+        value => value.to_string(),
+    };
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, str::FromStr, sync::Arc};
+
+    use chrono::DateTime;
+    use serde_json::Value;
+
+    use crate::{
+        core::{Address, Balance, BalanceFormat, Wallet},
+        infra::{MockWalletStore, WalletRecord},
+        wallet::{DisplayPolicy, Export, ExportExecutor, ExportFormat},
+    };
+
+    const ADDR: &str = "0xB644Babc370f46f202DB5eaf2071A9Ee66fA1D5E";
+
+    fn executor() -> ExportExecutor {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_all().returning(|| {
+            let mut wallet = Wallet::new(Address::from_str(ADDR).unwrap());
+            *wallet.balance_mut() = Balance::new(1_500_000_000_000_000_000);
+            let hot = WalletRecord {
+                id: "w1".to_string(),
+                tags: vec!["ops".to_string(), "hot".to_string()],
+                note: Some("payroll, \"main\"".to_string()),
+                last_update: DateTime::UNIX_EPOCH,
+                ..WalletRecord::new(wallet.clone())
+            };
+            let cold = WalletRecord {
+                id: "w2".to_string(),
+                last_update: DateTime::UNIX_EPOCH,
+                ..WalletRecord::new(wallet)
+            };
+            Ok(BTreeMap::from([
+                ("Hot".to_string(), hot),
+                ("Cold".to_string(), cold),
+            ]))
+        });
+
+        ExportExecutor {
+            wallet_store: Arc::new(wallet_store),
+            display: DisplayPolicy {
+                format: BalanceFormat {
+                    decimals: 2,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn wallet_export_formats() {
+        let export = executor();
+
+        let csv = export.execute(ExportFormat::Csv).await.unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "id,name,address,linked_addresses,tags,note,balance,balance_wei,last_update,verified_at"
+        );
+        assert!(lines[1].starts_with("w2,Cold,"));
+        assert!(lines[2].ends_with(
+            ",ops;hot,\"payroll, \"\"main\"\"\",1.50,1500000000000000000,1970-01-01T00:00:00Z,"
+        ));
+
+        let json = export.execute(ExportFormat::Json).await.unwrap();
+        let wallets: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(wallets[1]["name"], "Hot");
+        assert_eq!(wallets[1]["tags"][1], "hot");
+        assert_eq!(wallets[1]["verified_at"], Value::Null);
+
+        let ndjson = export.execute(ExportFormat::Ndjson).await.unwrap();
+        let names: Vec<Value> = ndjson
+            .lines()
+            .map(|line| serde_json::from_str::<Value>(line).unwrap()["name"].clone())
+            .collect();
+        assert_eq!(names, ["Cold", "Hot"]);
+    }
+}
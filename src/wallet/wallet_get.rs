@@ -0,0 +1,196 @@
+use std::{any::type_name, fmt, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use tracing::warn;
+
+use super::{DisplayPolicy, Result, Wallet, eth_usd_price, fetch_balance, resolve, to_wallet};
+use crate::infra::{PriceOracle, WalletClient, WalletRecord, WalletStore};
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait Get: Send + Sync + 'static {
+    async fn execute(&self, name: &str) -> Result<Wallet>;
+}
+
+#[derive(Clone)]
+pub struct GetExecutor {
+    pub wallet_store: Arc<dyn WalletStore>,
+    pub wallet_client: Arc<dyn WalletClient>,
+    pub display: DisplayPolicy,
+    pub refresh_after: Option<Duration>,
+    pub price_oracle: Option<Arc<dyn PriceOracle>>,
+}
+
+impl fmt::Debug for GetExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>()).finish()
+    }
+}
+
+#[async_trait]
+impl Get for GetExecutor {
+    async fn execute(&self, name: &str) -> Result<Wallet> {
+        let (name, record) = resolve(self.wallet_store.as_ref(), name).await?;
+
+        let record = match self.refresh_after {
+            Some(refresh_after) if is_older(&record, refresh_after) => {
+                self.read_through(&name, record).await?
+            }
+            _ => record,
+        };
+
+        let price = eth_usd_price(self.price_oracle.as_deref()).await;
+        let value_usd = price.map(|price| record.wallet.balance().usd(price));
+        Ok(Wallet {
+            value_usd,
+            ..to_wallet(&self.display, name, record)
+        })
+    }
+}
+
+impl GetExecutor {
+    async fn read_through(&self, name: &str, mut record: WalletRecord) -> Result<WalletRecord> {
+        match fetch_balance(self.wallet_client.as_ref(), &record.wallet).await {
+            Ok(balance) => {
+                record.previous_balance = Some(record.wallet.balance());
+                *record.wallet.balance_mut() = balance;
+                record.last_update = Utc::now();
+                self.wallet_store.save(name, &record).await?;
+            }
+            Err(e) => warn!("serving stored balance for {name}: {e:?}"),
+        }
+        Ok(record)
+    }
+}
+
+fn is_older(record: &WalletRecord, age: Duration) -> bool {
+    let age = chrono::Duration::from_std(age).unwrap_or(chrono::Duration::MAX);
+    Utc::now().signed_duration_since(record.last_update) > age
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, str::FromStr, sync::Arc, time::Duration};
+
+    use chrono::{DateTime, TimeDelta, Utc};
+
+    use crate::{
+        core::{Address, Balance, Wallet},
+        infra::{Interface, MockWalletClient, MockWalletStore, Provenance, WalletRecord},
+        wallet::{DisplayPolicy, Get, GetExecutor, WalletErrorKind},
+    };
+
+    #[tokio::test]
+    async fn wallet_get_success() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_find().returning(|_| {
+            let address = "0xB644Babc370f46f202DB5eaf2071A9Ee66fA1D5E";
+            let address = Address::from_str(address).unwrap();
+            Ok(Some(WalletRecord {
+                created_at: DateTime::UNIX_EPOCH,
+                created_by: Provenance {
+                    principal: "10.0.0.1".to_string(),
+                    interface: Interface::Grpc,
+                },
+                ..WalletRecord::new(Wallet::new(address))
+            }))
+        });
+
+        let get = GetExecutor {
+            wallet_store: Arc::new(wallet_store),
+            wallet_client: Arc::new(MockWalletClient::new()),
+            display: DisplayPolicy::default(),
+            refresh_after: None,
+            price_oracle: None,
+        };
+
+        let wallet = get.execute("David's Wallet").await.unwrap();
+        assert_eq!(wallet.name, "David's Wallet");
+        assert_eq!(wallet.created_by.principal, "10.0.0.1");
+        assert_eq!(wallet.created_by.interface, Interface::Grpc);
+        assert_eq!(wallet.created_at, DateTime::UNIX_EPOCH);
+    }
+
+    #[tokio::test]
+    async fn wallet_get_by_id() {
+        let id = "0b5cf4c5-7d9a-4c4b-9a57-0e3c9f3c2a11";
+
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_find().never();
+        wallet_store.expect_all().returning(move || {
+            let address = "0xB644Babc370f46f202DB5eaf2071A9Ee66fA1D5E";
+            let address = Address::from_str(address).unwrap();
+            let record = WalletRecord {
+                id: id.to_string(),
+                ..WalletRecord::new(Wallet::new(address))
+            };
+            Ok(BTreeMap::from([("David's Wallet".to_string(), record)]))
+        });
+
+        let get = GetExecutor {
+            wallet_store: Arc::new(wallet_store),
+            wallet_client: Arc::new(MockWalletClient::new()),
+            display: DisplayPolicy::default(),
+            refresh_after: None,
+            price_oracle: None,
+        };
+
+        let wallet = get.execute(id).await.unwrap();
+        assert_eq!(wallet.name, "David's Wallet");
+        assert_eq!(wallet.id, id);
+    }
+
+    #[tokio::test]
+    async fn wallet_get_not_found() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_find().returning(|_| Ok(None));
+
+        let get = GetExecutor {
+            wallet_store: Arc::new(wallet_store),
+            wallet_client: Arc::new(MockWalletClient::new()),
+            display: DisplayPolicy::default(),
+            refresh_after: None,
+            price_oracle: None,
+        };
+
+        let error = get.execute("David's Wallet").await.unwrap_err();
+        assert_eq!(error.kind(), WalletErrorKind::NotFound);
+    }
+
+    #[tokio::test]
+    async fn wallet_get_read_through() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_find().returning(|_| {
+            let address = "0xB644Babc370f46f202DB5eaf2071A9Ee66fA1D5E";
+            let address = Address::from_str(address).unwrap();
+            Ok(Some(WalletRecord {
+                last_update: Utc::now() - TimeDelta::seconds(600),
+                ..WalletRecord::new(Wallet::new(address))
+            }))
+        });
+        wallet_store
+            .expect_save()
+            .withf(|_, record| record.wallet.balance() == Balance::new(42))
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let mut wallet_client = MockWalletClient::new();
+        wallet_client
+            .expect_balance()
+            .times(1)
+            .returning(|_| Ok(Balance::new(42)));
+
+        let get = GetExecutor {
+            wallet_store: Arc::new(wallet_store),
+            wallet_client: Arc::new(wallet_client),
+            display: DisplayPolicy::default(),
+            refresh_after: Some(Duration::from_secs(60)),
+            price_oracle: None,
+        };
+
+        let wallet = get.execute("David's Wallet").await.unwrap();
+        assert_eq!(wallet.balance_wei, "42");
+        assert!(!wallet.is_stale);
+    }
+}
@@ -0,0 +1,140 @@
+use std::{any::type_name, fmt, ops::Range, sync::Arc};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use super::{DisplayPolicy, Result, resolve};
+use crate::{
+    core::{self, Balance},
+    infra::{HistoryStore, WalletStore},
+};
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait History: Send + Sync + 'static {
+    async fn execute(
+        &self,
+        name: &str,
+        range: Range<i64>,
+        interval: i64,
+    ) -> Result<Vec<BalanceCandle>>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BalanceCandle {
+    pub start: DateTime<Utc>,
+    pub open: String,
+    pub open_wei: String,
+    pub high: String,
+    pub high_wei: String,
+    pub low: String,
+    pub low_wei: String,
+    pub close: String,
+    pub close_wei: String,
+}
+
+#[derive(Clone)]
+pub struct HistoryExecutor {
+    pub wallet_store: Arc<dyn WalletStore>,
+    pub history_store: Arc<dyn HistoryStore>,
+    pub display: DisplayPolicy,
+}
+
+impl fmt::Debug for HistoryExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>()).finish()
+    }
+}
+
+#[async_trait]
+impl History for HistoryExecutor {
+    async fn execute(
+        &self,
+        name: &str,
+        range: Range<i64>,
+        interval: i64,
+    ) -> Result<Vec<BalanceCandle>> {
+        let (_, record) = resolve(self.wallet_store.as_ref(), name).await?;
+        let samples = self.history_store.history_in(&record.id, range).await?;
+
+        Ok(core::candles(&samples, interval)
+            .into_iter()
+            .map(|candle| {
+                let format = |balance: Balance| (self.display.format(balance), balance.to_string());
+                let (open, open_wei) = format(candle.open);
+                let (high, high_wei) = format(candle.high);
+                let (low, low_wei) = format(candle.low);
+                let (close, close_wei) = format(candle.close);
+                BalanceCandle {
+                    start: DateTime::from_timestamp(candle.start, 0).unwrap_or_default(),
+                    open,
+                    open_wei,
+                    high,
+                    high_wei,
+                    low,
+                    low_wei,
+                    close,
+                    close_wei,
+                }
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{
+        core::{Address, Balance, Sample, Wallet},
+        infra::{MockHistoryStore, MockWalletStore, WalletRecord},
+        wallet::{DisplayPolicy, History, HistoryExecutor},
+    };
+
+    #[tokio::test]
+    async fn wallet_history_candles_in_range() {
+        let record = WalletRecord::new(Wallet::new(Address::new([1; 20])));
+        let wallet_id = record.id.clone();
+
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store
+            .expect_find()
+            .returning(move |_| Ok(Some(record.clone())));
+
+        let mut history_store = MockHistoryStore::new();
+        history_store
+            .expect_history_in()
+            .withf(move |id, range| id == wallet_id && *range == (0..7200))
+            .returning(|_, _| {
+                Ok([(0, 5), (60, 9), (120, 2), (3600, 4)]
+                    .into_iter()
+                    .map(|(timestamp, wei)| Sample {
+                        timestamp,
+                        block_number: None,
+                        balance: Balance::new(wei),
+                    })
+                    .collect())
+            });
+
+        let history = HistoryExecutor {
+            wallet_store: Arc::new(wallet_store),
+            history_store: Arc::new(history_store),
+            display: DisplayPolicy::default(),
+        };
+
+        let candles = history.execute("Vault", 0..7200, 3600).await.unwrap();
+        let wei: Vec<[&str; 4]> = candles
+            .iter()
+            .map(|candle| {
+                [
+                    candle.open_wei.as_str(),
+                    candle.high_wei.as_str(),
+                    candle.low_wei.as_str(),
+                    candle.close_wei.as_str(),
+                ]
+            })
+            .collect();
+        assert_eq!(wei, [["5", "9", "2", "2"], ["4", "4", "4", "4"]]);
+        assert_eq!(candles[1].start.timestamp(), 3600);
+    }
+}
@@ -0,0 +1,607 @@
+use std::{any::type_name, collections::HashSet, fmt, mem, str::FromStr, sync::Arc};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use tracing::warn;
+
+use super::{
+    EventBus, Result, WalletError, WalletErrorKind, WalletEvent, audit, fetch_balance,
+    validate_name,
+};
+use crate::{
+    core::{Address, ChainId, Wallet},
+    infra::{AuditStore, Insertion, Provenance, WalletClient, WalletRecord, WalletStore},
+};
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait Import: Send + Sync + 'static {
+    async fn execute(&self, import: NewImport) -> Result<ImportSummary>;
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    #[default]
+    Csv,
+    Json,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct NewImport {
+    pub format: ImportFormat,
+    pub content: String,
+    pub skip_balance: bool,
+    pub created_by: Provenance,
+}
+
+#[derive(Debug, Default)]
+pub struct ImportSummary {
+    pub imported: Vec<String>,
+    pub rejected: Vec<ImportRejection>,
+}
+
+#[derive(Debug)]
+pub struct ImportRejection {
+    pub row: usize,
+    pub name: String,
+    pub error: WalletError,
+}
+
+#[derive(Clone)]
+pub struct ImportExecutor {
+    pub wallet_store: Arc<dyn WalletStore>,
+    pub wallet_client: Arc<dyn WalletClient>,
+    pub audit_store: Arc<dyn AuditStore>,
+    pub events: Arc<EventBus>,
+    pub chain_id: ChainId,
+    pub batch_balances: bool,
+}
+
+impl fmt::Debug for ImportExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>()).finish()
+    }
+}
+
+#[async_trait]
+impl Import for ImportExecutor {
+    async fn execute(&self, import: NewImport) -> Result<ImportSummary> {
+        let NewImport {
+            format,
+            content,
+            skip_balance,
+            created_by,
+        } = import;
+
+        let rows = match format {
+            ImportFormat::Csv => parse_csv(&content),
+            ImportFormat::Json => parse_json(&content)?,
+        };
+
+        let mut seen = Seen::default();
+        let mut accepted = vec![];
+        let mut accepted_rows = vec![];
+        let mut rejected = vec![];
+        for row in rows {
+            match self.validate(&row, &seen, &created_by).await {
+                Ok((name, record)) => {
+                    seen.names.insert(name.clone());
+                    seen.addresses.insert(*record.wallet.address());
+                    accepted.push((name, record));
+                    accepted_rows.push(row.row);
+                }
+                Err(error) => rejected.push(ImportRejection {
+                    row: row.row,
+                    name: row.name,
+                    error,
+                }),
+            }
+        }
+
+        if !skip_balance {
+            (accepted, accepted_rows) = self
+                .fetch_balances(accepted, accepted_rows, &mut rejected)
+                .await;
+        }
+
+        if accepted.is_empty() {
+            return Ok(ImportSummary {
+                imported: vec![],
+                rejected,
+            });
+        }
+
+        // One write for the whole file, so an import never lands halfway.
+        // Rows whose name or address was taken since they were checked are
+        // left out of it.
+        let inserted = self.wallet_store.insert_many(&accepted).await;
+        let insertions = match inserted {
+            Ok(insertions) => insertions,
+            Err(error) => {
+                for (name, record) in &accepted {
+                    let address = record.wallet.address().to_string();
+                    let result: Result<()> = Err(WalletError {
+                        kind: WalletErrorKind::WalletStore,
+                        source: None,
+                    });
+                    audit(
+                        self.audit_store.as_ref(),
+                        "import",
+                        name,
+                        Some(&address),
+                        &result,
+                    )
+                    .await;
+                }
+                return Err(error.into());
+            }
+        };
+
+        let mut imported = Vec::with_capacity(accepted.len());
+        for ((row, (name, record)), insertion) in
+            accepted_rows.into_iter().zip(accepted).zip(insertions)
+        {
+            let kind = match insertion {
+                Insertion::Saved => {
+                    let address = record.wallet.address().to_string();
+                    audit(
+                        self.audit_store.as_ref(),
+                        "import",
+                        &name,
+                        Some(&address),
+                        &Ok(()),
+                    )
+                    .await;
+                    self.events.publish(WalletEvent::Tracked {
+                        wallet_id: record.id,
+                        name: name.clone(),
+                    });
+                    imported.push(name);
+                    continue;
+                }
+                Insertion::NameTaken => WalletErrorKind::NameConflict,
+                Insertion::AddressTaken(_) => WalletErrorKind::AddressTracked,
+            };
+            rejected.push(ImportRejection {
+                row,
+                name,
+                error: WalletError { kind, source: None },
+            });
+        }
+        rejected.sort_by_key(|rejection| rejection.row);
+
+        Ok(ImportSummary { imported, rejected })
+    }
+}
+
+impl ImportExecutor {
+    async fn validate(
+        &self,
+        row: &ImportRow,
+        seen: &Seen,
+        created_by: &Provenance,
+    ) -> Result<(String, WalletRecord)> {
+        let (Some(name), Some(address)) = (&row.fields.0, &row.fields.1) else {
+            return Err(WalletError {
+                kind: WalletErrorKind::ImportRowInvalid,
+                source: None,
+            });
+        };
+
+        let name = validate_name(name)?;
+        if seen.names.contains(&name) || self.wallet_store.exists(&name).await? {
+            return Err(WalletError {
+                kind: WalletErrorKind::NameConflict,
+                source: None,
+            });
+        }
+
+        let address = Address::from_str(address.trim())?;
+        if address.is_burn() {
+            return Err(WalletError {
+                kind: WalletErrorKind::AddressUnspendable,
+                source: None,
+            });
+        }
+        if seen.addresses.contains(&address)
+            || self
+                .wallet_store
+                .find_by_address(self.chain_id, &address)
+                .await?
+                .is_some()
+        {
+            return Err(WalletError {
+                kind: WalletErrorKind::AddressTracked,
+                source: None,
+            });
+        }
+        // The balance is left for the next refresh to fill in unless
+        // `fetch_balances` reads it.
+        let record = WalletRecord {
+            created_by: created_by.clone(),
+            modified_by: created_by.clone(),
+            last_update: DateTime::UNIX_EPOCH,
+            ..WalletRecord::new(Wallet::on_chain(self.chain_id, address))
+        };
+        Ok((name, record))
+    }
+
+    async fn fetch_balances(
+        &self,
+        accepted: Vec<(String, WalletRecord)>,
+        accepted_rows: Vec<usize>,
+        rejected: &mut Vec<ImportRejection>,
+    ) -> (Vec<(String, WalletRecord)>, Vec<usize>) {
+        if self.batch_balances {
+            let addresses: Vec<_> = accepted
+                .iter()
+                .map(|(_, record)| *record.wallet.address())
+                .collect();
+            match self.wallet_client.balances(&addresses).await {
+                Ok(balances) if balances.len() == accepted.len() => {
+                    let now = Utc::now();
+                    let accepted = accepted
+                        .into_iter()
+                        .zip(balances)
+                        .map(|((name, mut record), balance)| {
+                            *record.wallet.balance_mut() = balance;
+                            record.last_update = now;
+                            (name, record)
+                        })
+                        .collect();
+                    return (accepted, accepted_rows);
+                }
+                Ok(_) => warn!("batched balances didn't match the addresses asked for"),
+                Err(e) => warn!("couldn't read the import's balances in one batch: {e:?}"),
+            }
+        }
+
+        let mut fetched = (vec![], vec![]);
+        for ((name, mut record), row) in accepted.into_iter().zip(accepted_rows) {
+            match fetch_balance(self.wallet_client.as_ref(), &record.wallet).await {
+                Ok(balance) => {
+                    *record.wallet.balance_mut() = balance;
+                    record.last_update = Utc::now();
+                    fetched.0.push((name, record));
+                    fetched.1.push(row);
+                }
+                Err(error) => rejected.push(ImportRejection { row, name, error }),
+            }
+        }
+        fetched
+    }
+}
+
+#[derive(Default)]
+struct Seen {
+    names: HashSet<String>,
+    addresses: HashSet<Address>,
+}
+
+struct ImportRow {
+    row: usize,
+    name: String,
+    fields: (Option<String>, Option<String>),
+}
+
+fn parse_csv(content: &str) -> Vec<ImportRow> {
+    let mut rows = vec![];
+    let mut first = true;
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let fields = split_csv_line(line);
+        let is_header = mem::take(&mut first)
+            && fields.as_ref().is_some_and(|fields| {
+                fields.len() == 2
+                    && fields[0].trim().eq_ignore_ascii_case("name")
+                    && fields[1].trim().eq_ignore_ascii_case("address")
+            });
+        if is_header {
+            continue;
+        }
+
+        let name = fields
+            .as_ref()
+            .and_then(|fields| fields.first().cloned())
+            .unwrap_or_default();
+        let fields = match fields {
+            Some(mut fields) if fields.len() == 2 => {
+                let address = fields.pop();
+                (fields.pop(), address)
+            }
+            _ => (None, None),
+        };
+        rows.push(ImportRow {
+            row: i + 1,
+            name,
+            fields,
+        });
+    }
+    rows
+}
+
+fn split_csv_line(line: &str) -> Option<Vec<String>> {
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+
+    if quoted {
+        return None;
+    }
+    fields.push(field);
+    Some(fields)
+}
+
+fn parse_json(content: &str) -> Result<Vec<ImportRow>> {
+    let malformed = |source: Option<serde_json::Error>| WalletError {
+        kind: WalletErrorKind::ImportMalformed,
+        source: source.map(Into::into),
+    };
+
+    let value: Value = serde_json::from_str(content).map_err(|e| malformed(Some(e)))?;
+    let Value::Array(entries) = value else {
+        return Err(malformed(None));
+    };
+
+    let rows = entries
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let field = |key| entry.get(key).and_then(Value::as_str).map(str::to_owned);
+            ImportRow {
+                row: i + 1,
+                name: field("name").unwrap_or_default(),
+                fields: (field("name"), field("address")),
+            }
+        })
+        .collect();
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use chrono::DateTime;
+
+    use crate::{
+        core::{Balance, ChainId, Wallet},
+        infra::{Insertion, MockWalletClient, MockWalletStore, WalletRecord},
+        wallet::{
+            EventBus, Import, ImportExecutor, ImportFormat, NewImport, WalletErrorKind, audit_store,
+        },
+    };
+
+    const ADDR: &str = "0xB644Babc370f46f202DB5eaf2071A9Ee66fA1D5E";
+    const COLD: &str = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
+    const KNOWN: &str = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2";
+
+    fn executor(wallet_store: MockWalletStore) -> ImportExecutor {
+        let mut wallet_client = MockWalletClient::new();
+        wallet_client
+            .expect_balance()
+            .returning(|_| Ok(Balance::new(42)));
+
+        ImportExecutor {
+            wallet_store: Arc::new(wallet_store),
+            wallet_client: Arc::new(wallet_client),
+            audit_store: audit_store(),
+            events: Arc::new(EventBus::default()),
+            chain_id: ChainId::MAINNET,
+            batch_balances: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn wallet_import_csv() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store
+            .expect_exists()
+            .returning(|name| Ok(name == "Taken"));
+        wallet_store
+            .expect_find_by_address()
+            .returning(|chain_id, address| {
+                let known = address.to_string() == KNOWN;
+                let record = WalletRecord::new(Wallet::on_chain(chain_id, *address));
+                Ok(known.then(|| ("Known".to_string(), record)))
+            });
+        wallet_store
+            .expect_insert_many()
+            .withf(|wallets| {
+                let names: Vec<&str> = wallets.iter().map(|(name, _)| name.as_str()).collect();
+                names == ["Hot, Main", "Cold \"Vault\""]
+                    && wallets
+                        .iter()
+                        .all(|(_, record)| record.wallet.balance() == Balance::new(42))
+            })
+            .times(1)
+            .returning(|wallets| Ok(vec![Insertion::Saved; wallets.len()]));
+
+        let content = format!(
+            "name,address\n\
+             \"Hot, Main\",{ADDR}\n\
+             \n\
+             \"Cold \"\"Vault\"\"\",{COLD}\n\
+             Taken,{ADDR}\n\
+             Hot, Main,{ADDR}\n\
+             Broken,0x1234\n\
+             \"Hot, Main\",{ADDR}\n\
+             Zero,0x0000000000000000000000000000000000000000\n\
+             Dead,0x000000000000000000000000000000000000dEaD\n\
+             Twin,{ADDR}\n\
+             Again,{KNOWN}\n"
+        );
+        let import = NewImport {
+            format: ImportFormat::Csv,
+            content,
+            ..Default::default()
+        };
+
+        let summary = executor(wallet_store).execute(import).await.unwrap();
+        assert_eq!(summary.imported, ["Hot, Main", "Cold \"Vault\""]);
+
+        let rejected: Vec<(usize, WalletErrorKind)> = summary
+            .rejected
+            .iter()
+            .map(|rejection| (rejection.row, rejection.error.kind()))
+            .collect();
+        assert_eq!(
+            rejected,
+            [
+                (5, WalletErrorKind::NameConflict),
+                (6, WalletErrorKind::ImportRowInvalid),
+                (7, WalletErrorKind::WalletAddrParse),
+                (8, WalletErrorKind::NameConflict),
+                (9, WalletErrorKind::AddressUnspendable),
+                (10, WalletErrorKind::AddressUnspendable),
+                (11, WalletErrorKind::AddressTracked),
+                (12, WalletErrorKind::AddressTracked),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn wallet_import_json() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_exists().returning(|_| Ok(false));
+        wallet_store
+            .expect_find_by_address()
+            .returning(|_, _| Ok(None));
+        wallet_store
+            .expect_insert_many()
+            .withf(|wallets| {
+                wallets.len() == 1
+                    && wallets[0].0 == "Hot"
+                    && wallets[0].1.last_update == DateTime::UNIX_EPOCH
+            })
+            .times(1)
+            .returning(|_| Ok(vec![Insertion::Saved]));
+        let import = executor(wallet_store);
+
+        let content = format!(r#"[{{"name": "Hot", "address": "{ADDR}"}}, {{"name": "Cold"}}]"#);
+        let summary = import
+            .execute(NewImport {
+                format: ImportFormat::Json,
+                content,
+                skip_balance: true,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(summary.imported, ["Hot"]);
+        assert_eq!(summary.rejected[0].row, 2);
+        assert_eq!(summary.rejected[0].name, "Cold");
+
+        let error = import
+            .execute(NewImport {
+                format: ImportFormat::Json,
+                content: r#"{"name": "Hot"}"#.to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), WalletErrorKind::ImportMalformed);
+    }
+
+    #[tokio::test]
+    async fn wallet_import_taken_meanwhile() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_exists().returning(|_| Ok(false));
+        wallet_store
+            .expect_find_by_address()
+            .returning(|_, _| Ok(None));
+        wallet_store.expect_insert_many().times(1).returning(|_| {
+            Ok(vec![
+                Insertion::NameTaken,
+                Insertion::Saved,
+                Insertion::AddressTaken("Other".to_string()),
+            ])
+        });
+
+        let content = format!("Hot,{ADDR}\nCold,{COLD}\nAgain,{KNOWN}\nBroken\n");
+        let summary = executor(wallet_store)
+            .execute(NewImport {
+                format: ImportFormat::Csv,
+                content,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(summary.imported, ["Cold"]);
+
+        let rejected: Vec<(usize, WalletErrorKind)> = summary
+            .rejected
+            .iter()
+            .map(|rejection| (rejection.row, rejection.error.kind()))
+            .collect();
+        assert_eq!(
+            rejected,
+            [
+                (1, WalletErrorKind::NameConflict),
+                (3, WalletErrorKind::AddressTracked),
+                (4, WalletErrorKind::ImportRowInvalid),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn wallet_import_batch_balances() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_exists().returning(|_| Ok(false));
+        wallet_store
+            .expect_find_by_address()
+            .returning(|_, _| Ok(None));
+        wallet_store
+            .expect_insert_many()
+            .withf(|wallets| {
+                let balances: Vec<_> = wallets
+                    .iter()
+                    .map(|(_, record)| record.wallet.balance())
+                    .collect();
+                balances == [Balance::new(1), Balance::new(2)]
+            })
+            .times(1)
+            .returning(|wallets| Ok(vec![Insertion::Saved; wallets.len()]));
+
+        let mut wallet_client = MockWalletClient::new();
+        wallet_client
+            .expect_balances()
+            .withf(|addresses| addresses.len() == 2)
+            .times(1)
+            .returning(|_| Ok(vec![Balance::new(1), Balance::new(2)]));
+        wallet_client.expect_balance().never();
+
+        let import = ImportExecutor {
+            batch_balances: true,
+            wallet_client: Arc::new(wallet_client),
+            ..executor(wallet_store)
+        };
+        let summary = import
+            .execute(NewImport {
+                format: ImportFormat::Csv,
+                content: format!("Hot,{ADDR}\nCold,{COLD}\n"),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(summary.imported, ["Hot", "Cold"]);
+    }
+}
@@ -1,19 +1,39 @@
 use async_trait::async_trait;
-use std::{any::type_name, fmt, sync::Arc};
+use std::{any::type_name, cmp::Ordering, fmt, sync::Arc};
+use tracing::warn;
 
-use crate::infra::WalletStore;
+use crate::{
+    core::Balance,
+    infra::{Interface, ListSort, PreferenceStore, PriceOracle, WalletStore},
+};
 
-use super::{Result, Wallet};
+use super::{
+    DisplayPolicy, Enrichment, EnrichmentErrors, FieldError, Result, Wallet, normalize_tags,
+    to_wallet,
+};
 
 #[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait List: Send + Sync + 'static {
-    async fn execute(&self) -> Result<Vec<Wallet>>;
+    async fn execute(&self, filter: &ListFilter) -> Result<Vec<Wallet>>;
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ListFilter {
+    pub tags: Vec<String>,
+    pub created_by: Option<String>,
+    pub created_via: Option<Interface>,
+    pub viewer: Option<String>,
+    pub sort: ListSort,
 }
 
 #[derive(Clone)]
 pub struct ListExecutor {
     pub wallet_store: Arc<dyn WalletStore>,
+    pub preference_store: Arc<dyn PreferenceStore>,
+    pub display: DisplayPolicy,
+    pub enrichment_errors: Arc<EnrichmentErrors>,
+    pub price_oracle: Option<Arc<dyn PriceOracle>>,
 }
 
 impl fmt::Debug for ListExecutor {
@@ -24,24 +44,77 @@ impl fmt::Debug for ListExecutor {
 
 #[async_trait]
 impl List for ListExecutor {
-    async fn execute(&self) -> Result<Vec<Wallet>> {
+    async fn execute(&self, filter: &ListFilter) -> Result<Vec<Wallet>> {
+        let tags = normalize_tags(&filter.tags)?;
+        let (price, price_error) = match &self.price_oracle {
+            Some(price_oracle) => match price_oracle.eth_usd_price().await {
+                Ok(price) => (Some(price), None),
+                Err(e) => {
+                    warn!("couldn't get the eth price: {e:?}");
+                    let error = FieldError {
+                        enrichment: Enrichment::Price,
+                        reason: crate::server::compose_error(&e),
+                    };
+                    (None, Some(error))
+                }
+            },
+            None => (None, None),
+        };
+
         let mut wallets: Vec<Wallet> = self
             .wallet_store
             .all()
             .await?
             .into_iter()
-            .map(|(name, record)| Wallet {
-                name,
-                address: record.wallet.address().to_string(),
-                balance: record.wallet.balance().eth(),
-                last_update: record.last_update,
+            .filter(|(_, record)| tags.iter().all(|tag| record.tags.contains(tag)))
+            .filter(|(_, record)| {
+                filter
+                    .created_by
+                    .as_ref()
+                    .is_none_or(|principal| record.created_by.principal == *principal)
+            })
+            .filter(|(_, record)| {
+                filter
+                    .created_via
+                    .is_none_or(|interface| record.created_by.interface == interface)
+            })
+            .map(|(name, record)| {
+                let value_usd = price.map(|price| record.wallet.balance().usd(price));
+                let mut field_errors = self.enrichment_errors.of(&record.id);
+                field_errors.extend(price_error.clone());
+                field_errors.sort_by_key(|error| error.enrichment);
+                Wallet {
+                    value_usd,
+                    field_errors,
+                    ..to_wallet(&self.display, name, record)
+                }
             })
             .collect();
 
-        wallets.sort_by(|a, b| {
-            let a = a.name.to_lowercase();
-            let b = b.name.to_lowercase();
-            a.cmp(&b)
+        let pinned = match &filter.viewer {
+            Some(viewer) => self.preference_store.pinned(viewer).await?,
+            None => Vec::new(),
+        };
+        for wallet in &mut wallets {
+            wallet.pinned = pinned.contains(&wallet.id);
+        }
+
+        // Pinned wallets first in the order they were pinned, then the rest
+        // by the requested sort with ties broken by name.
+        let pin_rank = |wallet: &Wallet| pinned.iter().position(|id| *id == wallet.id);
+        let wei = |wallet: &Wallet| wallet.balance_wei.parse::<Balance>().unwrap_or_default();
+        wallets.sort_by(|a, b| match (pin_rank(a), pin_rank(b)) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => {
+                let by_sort = match filter.sort {
+                    ListSort::Name => Ordering::Equal,
+                    ListSort::Balance => wei(b).cmp(&wei(a)),
+                    ListSort::LastUpdate => b.last_update.cmp(&a.last_update),
+                };
+                by_sort.then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+            }
         });
 
         Ok(wallets)
@@ -50,54 +123,234 @@ impl List for ListExecutor {
 
 #[cfg(test)]
 mod tests {
-    use std::{collections::HashMap, str::FromStr, sync::Arc};
+    use std::{collections::BTreeMap, str::FromStr, sync::Arc, time::Duration};
 
-    use chrono::Utc;
+    use chrono::{TimeDelta, Utc};
 
     use crate::{
-        core::{Address, Balance, Wallet},
-        infra::{MockWalletStore, WalletRecord},
-        wallet::{List, ListExecutor},
+        core::{Address, Balance, BalanceFormat, Wallet},
+        infra::{
+            ClientError, Interface, ListSort, MockPreferenceStore, MockPriceOracle,
+            MockWalletStore, Provenance, WalletRecord,
+        },
+        wallet::{DisplayPolicy, Enrichment, EnrichmentErrors, List, ListExecutor, ListFilter},
     };
 
     #[tokio::test]
     async fn wallet_list_success() {
         let mut wallet_store = MockWalletStore::new();
         wallet_store.expect_all().returning(|| {
-            let mut records = HashMap::new();
+            let mut records = BTreeMap::new();
 
             let address = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
             let address = Address::from_str(address).unwrap();
             let mut wallet = Wallet::new(address);
             *wallet.balance_mut() = Balance::new(3_756_447_340_569_860_785);
+            records.insert("Vitalik's Wallet".to_string(), WalletRecord::new(wallet));
+
+            let address = "0xB644Babc370f46f202DB5eaf2071A9Ee66fA1D5E";
+            let address = Address::from_str(address).unwrap();
+            let wallet = Wallet::new(address);
+            records.insert("David's Wallet".to_string(), WalletRecord::new(wallet));
+
+            let address = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2";
+            let address = Address::from_str(address).unwrap();
+            let mut wallet = Wallet::new(address);
+            *wallet.balance_mut() = Balance::new(2_203_446_400_537_254_477_610_554);
+            records.insert("Wrapped Ether".to_string(), WalletRecord::new(wallet));
+
+            Ok(records)
+        });
+
+        let list = ListExecutor {
+            wallet_store: Arc::new(wallet_store),
+            preference_store: Arc::new(MockPreferenceStore::new()),
+            display: DisplayPolicy::default(),
+            enrichment_errors: Arc::default(),
+            price_oracle: None,
+        };
+
+        let wallets = list.execute(&ListFilter::default()).await.unwrap();
+        assert_eq!(wallets[0].name, "David's Wallet");
+        assert_eq!(wallets[1].name, "Vitalik's Wallet");
+        assert_eq!(wallets[2].name, "Wrapped Ether");
+
+        let filter = ListFilter {
+            sort: ListSort::Balance,
+            ..Default::default()
+        };
+        let by_balance = list.execute(&filter).await.unwrap();
+        assert_eq!(by_balance[0].name, "Wrapped Ether");
+        assert_eq!(by_balance[1].name, "Vitalik's Wallet");
+        assert_eq!(by_balance[2].name, "David's Wallet");
+
+        assert_eq!(wallets[0].balance, "0.000000000000000000");
+        assert_eq!(wallets[1].balance, "3.756447340569860785");
+        assert_eq!(wallets[2].balance, "2203446.400537254477610554");
+
+        assert_eq!(wallets[1].balance_wei, "3756447340569860785");
+    }
+
+    #[tokio::test]
+    async fn wallet_list_pinned_first() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_all().returning(|| {
+            let address = "0xB644Babc370f46f202DB5eaf2071A9Ee66fA1D5E";
+            let address = Address::from_str(address).unwrap();
+            let record = |id: &str| WalletRecord {
+                id: id.to_string(),
+                ..WalletRecord::new(Wallet::new(address))
+            };
+
+            Ok(BTreeMap::from([
+                ("Alpha".to_string(), record("a")),
+                ("Bravo".to_string(), record("b")),
+                ("Charlie".to_string(), record("c")),
+                ("Delta".to_string(), record("d")),
+            ]))
+        });
+
+        let mut preference_store = MockPreferenceStore::new();
+        preference_store
+            .expect_pinned()
+            .withf(|principal| principal == "alice")
+            .returning(|_| Ok(vec!["d".to_string(), "b".to_string()]));
+
+        let list = ListExecutor {
+            wallet_store: Arc::new(wallet_store),
+            preference_store: Arc::new(preference_store),
+            display: DisplayPolicy::default(),
+            enrichment_errors: Arc::default(),
+            price_oracle: None,
+        };
+
+        let filter = ListFilter {
+            viewer: Some("alice".to_string()),
+            ..Default::default()
+        };
+        let wallets = list.execute(&filter).await.unwrap();
+        let names: Vec<_> = wallets.iter().map(|w| w.name.as_str()).collect();
+        assert_eq!(names, ["Delta", "Bravo", "Alpha", "Charlie"]);
+        let pinned: Vec<_> = wallets.iter().map(|w| w.pinned).collect();
+        assert_eq!(pinned, [true, true, false, false]);
+    }
+
+    #[tokio::test]
+    async fn wallet_list_display_policy() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_all().returning(|| {
+            let address = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
+            let address = Address::from_str(address).unwrap();
+            let mut wallet = Wallet::new(address);
+            *wallet.balance_mut() = Balance::new(3_756_447_340_569_860_785);
+
+            let mut records = BTreeMap::new();
+            records.insert("Vitalik's Wallet".to_string(), WalletRecord::new(wallet));
+            Ok(records)
+        });
+
+        let list = ListExecutor {
+            wallet_store: Arc::new(wallet_store),
+            preference_store: Arc::new(MockPreferenceStore::new()),
+            display: DisplayPolicy {
+                format: BalanceFormat {
+                    decimals: 4,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            enrichment_errors: Arc::default(),
+            price_oracle: None,
+        };
+
+        let wallets = list.execute(&ListFilter::default()).await.unwrap();
+        assert_eq!(wallets[0].balance, "3.7564");
+        assert_eq!(wallets[0].balance_wei, "3756447340569860785");
+    }
+
+    #[tokio::test]
+    async fn wallet_list_filter_tags() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_all().returning(|| {
+            let mut records = BTreeMap::new();
+
+            let address = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
+            let address = Address::from_str(address).unwrap();
             records.insert(
                 "Vitalik's Wallet".to_string(),
                 WalletRecord {
-                    wallet,
-                    last_update: Utc::now(),
+                    tags: vec!["cold".to_string(), "public".to_string()],
+                    ..WalletRecord::new(Wallet::new(address))
                 },
             );
 
             let address = "0xB644Babc370f46f202DB5eaf2071A9Ee66fA1D5E";
             let address = Address::from_str(address).unwrap();
-            let wallet = Wallet::new(address);
             records.insert(
                 "David's Wallet".to_string(),
                 WalletRecord {
-                    wallet,
-                    last_update: Utc::now(),
+                    tags: vec!["cold".to_string()],
+                    ..WalletRecord::new(Wallet::new(address))
                 },
             );
 
-            let address = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2";
+            Ok(records)
+        });
+
+        let list = ListExecutor {
+            wallet_store: Arc::new(wallet_store),
+            preference_store: Arc::new(MockPreferenceStore::new()),
+            display: DisplayPolicy::default(),
+            enrichment_errors: Arc::default(),
+            price_oracle: None,
+        };
+
+        let filter = ListFilter {
+            tags: vec![" Cold ".to_string()],
+            ..Default::default()
+        };
+        let wallets = list.execute(&filter).await.unwrap();
+        assert_eq!(wallets.len(), 2);
+
+        let filter = ListFilter {
+            tags: vec!["cold".to_string(), "public".to_string()],
+            ..Default::default()
+        };
+        let wallets = list.execute(&filter).await.unwrap();
+        assert_eq!(wallets.len(), 1);
+        assert_eq!(wallets[0].name, "Vitalik's Wallet");
+        assert_eq!(wallets[0].tags, ["cold", "public"]);
+    }
+
+    #[tokio::test]
+    async fn wallet_list_filter_created_by() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_all().returning(|| {
+            let mut records = BTreeMap::new();
+
+            let address = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
+            let address = Address::from_str(address).unwrap();
+            records.insert(
+                "Vitalik's Wallet".to_string(),
+                WalletRecord {
+                    created_by: Provenance {
+                        principal: "10.0.0.1".to_string(),
+                        interface: Interface::Grpc,
+                    },
+                    ..WalletRecord::new(Wallet::new(address))
+                },
+            );
+
+            let address = "0xB644Babc370f46f202DB5eaf2071A9Ee66fA1D5E";
             let address = Address::from_str(address).unwrap();
-            let mut wallet = Wallet::new(address);
-            *wallet.balance_mut() = Balance::new(2_203_446_400_537_254_477_610_554);
             records.insert(
-                "Wrapped Ether".to_string(),
+                "David's Wallet".to_string(),
                 WalletRecord {
-                    wallet,
-                    last_update: Utc::now(),
+                    created_by: Provenance {
+                        principal: "10.0.0.2".to_string(),
+                        interface: Interface::Import,
+                    },
+                    ..WalletRecord::new(Wallet::new(address))
                 },
             );
 
@@ -106,15 +359,124 @@ mod tests {
 
         let list = ListExecutor {
             wallet_store: Arc::new(wallet_store),
+            preference_store: Arc::new(MockPreferenceStore::new()),
+            display: DisplayPolicy::default(),
+            enrichment_errors: Arc::default(),
+            price_oracle: None,
+        };
+
+        let filter = ListFilter {
+            created_by: Some("10.0.0.1".to_string()),
+            ..Default::default()
         };
+        let wallets = list.execute(&filter).await.unwrap();
+        assert_eq!(wallets.len(), 1);
+        assert_eq!(wallets[0].name, "Vitalik's Wallet");
 
-        let wallets = list.execute().await.unwrap();
+        let filter = ListFilter {
+            created_via: Some(Interface::Import),
+            ..Default::default()
+        };
+        let wallets = list.execute(&filter).await.unwrap();
+        assert_eq!(wallets.len(), 1);
         assert_eq!(wallets[0].name, "David's Wallet");
-        assert_eq!(wallets[1].name, "Vitalik's Wallet");
-        assert_eq!(wallets[2].name, "Wrapped Ether");
+    }
 
-        assert_eq!(wallets[0].balance, "0.000000000000000000");
-        assert_eq!(wallets[1].balance, "3.756447340569860785");
-        assert_eq!(wallets[2].balance, "2203446.400537254477610554");
+    #[tokio::test]
+    async fn wallet_list_partial_enrichments() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_all().returning(|| {
+            let address = "0xB644Babc370f46f202DB5eaf2071A9Ee66fA1D5E";
+            let address = Address::from_str(address).unwrap();
+            let record = |id: &str| WalletRecord {
+                id: id.to_string(),
+                ..WalletRecord::new(Wallet::new(address))
+            };
+
+            Ok(BTreeMap::from([
+                ("Alpha".to_string(), record("a")),
+                ("Bravo".to_string(), record("b")),
+            ]))
+        });
+
+        let enrichment_errors = Arc::new(EnrichmentErrors::default());
+        let error = std::io::Error::other("timed out");
+        enrichment_errors.record("b", Enrichment::Ens, &error);
+        enrichment_errors.record("b", Enrichment::Tokens, &error);
+        enrichment_errors.clear("b", Enrichment::Tokens);
+
+        let mut price_oracle = MockPriceOracle::new();
+        price_oracle
+            .expect_eth_usd_price()
+            .returning(|| Err(ClientError("rate limited".into())));
+
+        let list = ListExecutor {
+            wallet_store: Arc::new(wallet_store),
+            display: DisplayPolicy::default(),
+            enrichment_errors,
+            preference_store: Arc::new(MockPreferenceStore::new()),
+            price_oracle: Some(Arc::new(price_oracle)),
+        };
+
+        let wallets = list.execute(&ListFilter::default()).await.unwrap();
+        assert_eq!(wallets[0].value_usd, None);
+        let failed = |index: usize| -> Vec<(Enrichment, String)> {
+            wallets[index]
+                .field_errors
+                .iter()
+                .map(|error| (error.enrichment, error.reason.clone()))
+                .collect()
+        };
+        let rate_limited = "internal client error: rate limited".to_string();
+        assert_eq!(failed(0), [(Enrichment::Price, rate_limited.clone())]);
+        assert_eq!(
+            failed(1),
+            [
+                (Enrichment::Price, rate_limited),
+                (Enrichment::Ens, "timed out".to_string())
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn wallet_list_staleness() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_all().returning(|| {
+            let address = "0xB644Babc370f46f202DB5eaf2071A9Ee66fA1D5E";
+            let address = Address::from_str(address).unwrap();
+
+            let fresh = WalletRecord {
+                last_update: Utc::now() - TimeDelta::seconds(30),
+                ..WalletRecord::new(Wallet::new(address))
+            };
+            let stale = WalletRecord {
+                last_update: Utc::now() - TimeDelta::seconds(600),
+                ..WalletRecord::new(Wallet::new(address))
+            };
+
+            Ok(BTreeMap::from([
+                ("Fresh".to_string(), fresh),
+                ("Stale".to_string(), stale),
+            ]))
+        });
+
+        let list = ListExecutor {
+            wallet_store: Arc::new(wallet_store),
+            preference_store: Arc::new(MockPreferenceStore::new()),
+            display: DisplayPolicy {
+                stale_after: Duration::from_secs(300),
+                ..Default::default()
+            },
+            enrichment_errors: Arc::default(),
+            price_oracle: None,
+        };
+
+        let wallets = list.execute(&ListFilter::default()).await.unwrap();
+        assert_eq!(wallets[0].name, "Fresh");
+        assert!(!wallets[0].is_stale);
+        assert!((30..40).contains(&wallets[0].age_seconds));
+        assert_eq!(wallets[1].name, "Stale");
+        assert!(wallets[1].is_stale);
+        assert!(wallets[1].age_seconds >= 600);
     }
 }
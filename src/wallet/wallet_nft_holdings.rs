@@ -0,0 +1,137 @@
+use std::{any::type_name, fmt, sync::Arc};
+
+use async_trait::async_trait;
+use futures::future::try_join_all;
+
+use super::{Enrichment, EnrichmentErrors, Result, resolve};
+use crate::{
+    core::{Collection, U256, Wallet},
+    infra::{WalletClient, WalletStore},
+};
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait NftHoldings: Send + Sync + 'static {
+    async fn execute(&self, name: &str) -> Result<Vec<NftHolding>>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NftHolding {
+    pub collection: String,
+    pub contract: String,
+    pub count: u64,
+}
+
+#[derive(Clone)]
+pub struct NftHoldingsExecutor {
+    pub wallet_store: Arc<dyn WalletStore>,
+    pub wallet_client: Arc<dyn WalletClient>,
+    pub collections: Vec<Collection>,
+    pub enrichment_errors: Arc<EnrichmentErrors>,
+}
+
+impl fmt::Debug for NftHoldingsExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>()).finish()
+    }
+}
+
+#[async_trait]
+impl NftHoldings for NftHoldingsExecutor {
+    async fn execute(&self, name: &str) -> Result<Vec<NftHolding>> {
+        let (_, record) = resolve(self.wallet_store.as_ref(), name).await?;
+
+        let holdings = try_join_all(
+            self.collections
+                .iter()
+                .map(|collection| self.count(&record.wallet, collection)),
+        )
+        .await;
+        match &holdings {
+            Ok(_) => self.enrichment_errors.clear(&record.id, Enrichment::Nfts),
+            Err(e) => self
+                .enrichment_errors
+                .record(&record.id, Enrichment::Nfts, e),
+        }
+        holdings
+    }
+}
+
+impl NftHoldingsExecutor {
+    async fn count(&self, wallet: &Wallet, collection: &Collection) -> Result<NftHolding> {
+        let counts = try_join_all(wallet.addresses().map(|address| {
+            self.wallet_client
+                .token_balance(address, &collection.address)
+        }))
+        .await?;
+        let count = counts
+            .iter()
+            .fold(U256::zero(), |sum, count| sum.saturating_add(*count));
+
+        Ok(NftHolding {
+            collection: collection.name.clone(),
+            contract: collection.address.to_string(),
+            count: u64::try_from(count).unwrap_or(u64::MAX),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr, sync::Arc};
+
+    use crate::{
+        core::{Address, Collection, U256, Wallet},
+        infra::{MockWalletClient, MockWalletStore, WalletRecord},
+        wallet::{NftHolding, NftHoldings, NftHoldingsExecutor},
+    };
+
+    const HOT: &str = "0xB644Babc370f46f202DB5eaf2071A9Ee66fA1D5E";
+    const COLD: &str = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
+
+    #[tokio::test]
+    async fn wallet_nft_holdings_sum_addresses() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_find().returning(|_| {
+            let mut wallet = Wallet::new(Address::from_str(HOT).unwrap());
+            wallet.set_linked([Address::from_str(COLD).unwrap()]);
+            Ok(Some(WalletRecord::new(wallet)))
+        });
+
+        let mut wallet_client = MockWalletClient::new();
+        wallet_client
+            .expect_token_balance()
+            .returning(|address, collection| {
+                let hot = address.to_string() == HOT;
+                Ok(U256::from(match collection.inner() {
+                    [1, ..] if hot => 2,
+                    [1, ..] => 3,
+                    _ => 0,
+                }))
+            });
+
+        let collection = |name: &str, byte| Collection {
+            name: name.to_string(),
+            address: Address::new([byte; 20]),
+        };
+        let holdings = NftHoldingsExecutor {
+            wallet_store: Arc::new(wallet_store),
+            wallet_client: Arc::new(wallet_client),
+            collections: vec![collection("Punks", 1), collection("Apes", 2)],
+            enrichment_errors: Arc::default(),
+        };
+
+        let counts: Vec<(String, u64)> = holdings
+            .execute("Vault")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(
+                |NftHolding {
+                     collection, count, ..
+                 }| (collection, count),
+            )
+            .collect();
+        assert_eq!(counts, [("Punks".to_string(), 5), ("Apes".to_string(), 0)]);
+    }
+}
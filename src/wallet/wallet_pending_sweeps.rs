@@ -0,0 +1,64 @@
+use std::{any::type_name, fmt, sync::Arc};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use super::{DisplayPolicy, Result, SweepPolicy, Wallet, to_wallet};
+use crate::infra::WalletStore;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait PendingSweeps: Send + Sync + 'static {
+    async fn execute(&self) -> Result<Vec<PendingSweep>>;
+}
+
+#[derive(Debug, Clone)]
+pub struct PendingSweep {
+    pub wallet: Wallet,
+    pub funded_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone)]
+pub struct PendingSweepsExecutor {
+    pub wallet_store: Arc<dyn WalletStore>,
+    pub policy: SweepPolicy,
+    pub display: DisplayPolicy,
+}
+
+impl fmt::Debug for PendingSweepsExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>()).finish()
+    }
+}
+
+#[async_trait]
+impl PendingSweeps for PendingSweepsExecutor {
+    async fn execute(&self) -> Result<Vec<PendingSweep>> {
+        let mut pending: Vec<PendingSweep> = self
+            .wallet_store
+            .all()
+            .await?
+            .into_iter()
+            .filter(|(_, record)| record.tags.contains(&self.policy.tag))
+            .filter(|(_, record)| !record.wallet.balance().is_zero())
+            .map(|(name, record)| PendingSweep {
+                funded_at: record.funded_at,
+                wallet: to_wallet(&self.display, name, record),
+            })
+            .collect();
+
+        pending.sort_by(|a, b| {
+            let a = (
+                a.funded_at.unwrap_or(DateTime::<Utc>::MAX_UTC),
+                &a.wallet.name,
+            );
+            let b = (
+                b.funded_at.unwrap_or(DateTime::<Utc>::MAX_UTC),
+                &b.wallet.name,
+            );
+            a.cmp(&b)
+        });
+
+        Ok(pending)
+    }
+}
@@ -0,0 +1,146 @@
+use std::{any::type_name, fmt, sync::Arc};
+
+use async_trait::async_trait;
+
+use super::{Result, resolve};
+use crate::infra::{PreferenceStore, WalletStore};
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait Pin: Send + Sync + 'static {
+    async fn execute(&self, name: &str, principal: &str, pinned: bool) -> Result<()>;
+}
+
+#[derive(Clone)]
+pub struct PinExecutor {
+    pub wallet_store: Arc<dyn WalletStore>,
+    pub preference_store: Arc<dyn PreferenceStore>,
+}
+
+impl fmt::Debug for PinExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>()).finish()
+    }
+}
+
+#[async_trait]
+impl Pin for PinExecutor {
+    async fn execute(&self, name: &str, principal: &str, pinned: bool) -> Result<()> {
+        let (_, record) = resolve(self.wallet_store.as_ref(), name).await?;
+
+        let mut wallet_ids = self.preference_store.pinned(principal).await?;
+        let position = wallet_ids.iter().position(|id| *id == record.id);
+        match (pinned, position) {
+            (true, None) => wallet_ids.push(record.id),
+            (false, Some(position)) => {
+                wallet_ids.remove(position);
+            }
+            _ => return Ok(()),
+        }
+
+        self.preference_store
+            .set_pinned(principal, &wallet_ids)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr, sync::Arc};
+
+    use crate::{
+        core::{Address, Wallet},
+        infra::{MockPreferenceStore, MockWalletStore, WalletRecord},
+        wallet::{Pin, PinExecutor, WalletErrorKind},
+    };
+
+    const ADDR: &str = "0xB644Babc370f46f202DB5eaf2071A9Ee66fA1D5E";
+
+    fn wallet_store() -> MockWalletStore {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store
+            .expect_find()
+            .withf(|name| name == "Cold")
+            .returning(|_| {
+                let address = Address::from_str(ADDR).unwrap();
+                Ok(Some(WalletRecord {
+                    id: "w2".to_string(),
+                    ..WalletRecord::new(Wallet::new(address))
+                }))
+            });
+        wallet_store
+    }
+
+    #[tokio::test]
+    async fn wallet_pin_appends() {
+        let mut preference_store = MockPreferenceStore::new();
+        preference_store
+            .expect_pinned()
+            .withf(|principal| principal == "alice")
+            .returning(|_| Ok(vec!["w1".to_string()]));
+        preference_store
+            .expect_set_pinned()
+            .withf(|principal, ids| principal == "alice" && ids == ["w1", "w2"])
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let pin = PinExecutor {
+            wallet_store: Arc::new(wallet_store()),
+            preference_store: Arc::new(preference_store),
+        };
+
+        pin.execute("Cold", "alice", true).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn wallet_pin_unpin() {
+        let mut preference_store = MockPreferenceStore::new();
+        preference_store
+            .expect_pinned()
+            .returning(|_| Ok(vec!["w2".to_string(), "w1".to_string()]));
+        preference_store
+            .expect_set_pinned()
+            .withf(|_, ids| ids == ["w1"])
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let pin = PinExecutor {
+            wallet_store: Arc::new(wallet_store()),
+            preference_store: Arc::new(preference_store),
+        };
+
+        pin.execute("Cold", "alice", false).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn wallet_pin_already_pinned() {
+        let mut preference_store = MockPreferenceStore::new();
+        preference_store
+            .expect_pinned()
+            .returning(|_| Ok(vec!["w2".to_string()]));
+        preference_store.expect_set_pinned().never();
+
+        let pin = PinExecutor {
+            wallet_store: Arc::new(wallet_store()),
+            preference_store: Arc::new(preference_store),
+        };
+
+        pin.execute("Cold", "alice", true).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn wallet_pin_not_found() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_find().returning(|_| Ok(None));
+
+        let pin = PinExecutor {
+            wallet_store: Arc::new(wallet_store),
+            preference_store: Arc::new(MockPreferenceStore::new()),
+        };
+
+        let err = pin.execute("Warm", "alice", true).await.unwrap_err();
+        assert_eq!(err.kind, WalletErrorKind::NotFound);
+    }
+}
@@ -0,0 +1,196 @@
+use std::{any::type_name, collections::BTreeMap, fmt, sync::Arc};
+
+use async_trait::async_trait;
+
+use super::{DisplayPolicy, Result, WalletError, WalletErrorKind, eth_usd_price};
+use crate::{
+    core::Balance,
+    infra::{PriceOracle, WalletStore},
+};
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait Portfolio: Send + Sync + 'static {
+    async fn execute(&self) -> Result<PortfolioSummary>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PortfolioSummary {
+    pub total: PortfolioTotal,
+    pub by_tag: Vec<(String, PortfolioTotal)>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PortfolioTotal {
+    pub wallets: usize,
+    pub balance: String,
+    pub balance_wei: String,
+    pub value_usd: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct PortfolioExecutor {
+    pub wallet_store: Arc<dyn WalletStore>,
+    pub display: DisplayPolicy,
+    pub price_oracle: Option<Arc<dyn PriceOracle>>,
+}
+
+impl fmt::Debug for PortfolioExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>()).finish()
+    }
+}
+
+#[async_trait]
+impl Portfolio for PortfolioExecutor {
+    async fn execute(&self) -> Result<PortfolioSummary> {
+        let mut total = (0, Balance::default());
+        let mut by_tag: BTreeMap<String, (usize, Balance)> = BTreeMap::new();
+
+        for record in self.wallet_store.all().await?.values() {
+            let balance = record.wallet.balance();
+            add(&mut total, balance)?;
+            for tag in &record.tags {
+                add(by_tag.entry(tag.clone()).or_default(), balance)?;
+            }
+        }
+
+        let price = eth_usd_price(self.price_oracle.as_deref()).await;
+        Ok(PortfolioSummary {
+            total: self.to_total(total, price),
+            by_tag: by_tag
+                .into_iter()
+                .map(|(tag, total)| (tag, self.to_total(total, price)))
+                .collect(),
+        })
+    }
+}
+
+impl PortfolioExecutor {
+    fn to_total(&self, (wallets, balance): (usize, Balance), price: Option<f64>) -> PortfolioTotal {
+        PortfolioTotal {
+            wallets,
+            balance: self.display.format(balance),
+            balance_wei: balance.to_string(),
+            value_usd: price.map(|price| balance.usd(price)),
+        }
+    }
+}
+
+fn add((wallets, sum): &mut (usize, Balance), balance: Balance) -> Result<()> {
+    *sum = sum.checked_add(balance).ok_or(WalletError {
+        kind: WalletErrorKind::BalanceOverflow,
+        source: None,
+    })?;
+    *wallets += 1;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, io, sync::Arc};
+
+    use crate::{
+        core::{Address, Balance, U256, Wallet},
+        infra::{ClientError, MockPriceOracle, MockWalletStore, WalletRecord},
+        wallet::{DisplayPolicy, Portfolio, PortfolioExecutor, WalletErrorKind},
+    };
+
+    fn record(byte: u8, wei: u128, tags: &[&str]) -> WalletRecord {
+        let mut wallet = Wallet::new(Address::new([byte; 20]));
+        *wallet.balance_mut() = Balance::new(wei);
+        WalletRecord {
+            tags: tags.iter().map(ToString::to_string).collect(),
+            ..WalletRecord::new(wallet)
+        }
+    }
+
+    #[tokio::test]
+    async fn wallet_portfolio_by_tag() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_all().returning(|| {
+            Ok(BTreeMap::from([
+                ("Hot".to_string(), record(1, 10, &["personal"])),
+                ("Cold".to_string(), record(2, 20, &["personal", "savings"])),
+                ("Work".to_string(), record(3, 5, &[])),
+            ]))
+        });
+
+        let portfolio = PortfolioExecutor {
+            wallet_store: Arc::new(wallet_store),
+            display: DisplayPolicy::default(),
+            price_oracle: None,
+        };
+
+        let summary = portfolio.execute().await.unwrap();
+        assert_eq!(summary.total.wallets, 3);
+        assert_eq!(summary.total.balance_wei, "35");
+
+        let tags: Vec<_> = summary
+            .by_tag
+            .iter()
+            .map(|(tag, total)| (tag.as_str(), total.wallets, total.balance_wei.as_str()))
+            .collect();
+        assert_eq!(tags, [("personal", 2, "30"), ("savings", 1, "20")]);
+    }
+
+    #[tokio::test]
+    async fn wallet_portfolio_overflow() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_all().returning(|| {
+            let mut whale = record(1, 0, &[]);
+            *whale.wallet.balance_mut() = Balance::from_wei(U256::MAX);
+            Ok(BTreeMap::from([
+                ("A".to_string(), whale),
+                ("B".to_string(), record(2, 1, &[])),
+            ]))
+        });
+
+        let portfolio = PortfolioExecutor {
+            wallet_store: Arc::new(wallet_store),
+            display: DisplayPolicy::default(),
+            price_oracle: None,
+        };
+
+        let error = portfolio.execute().await.unwrap_err();
+        assert_eq!(error.kind(), WalletErrorKind::BalanceOverflow);
+    }
+
+    #[tokio::test]
+    async fn wallet_portfolio_value_usd() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_all().returning(|| {
+            Ok(BTreeMap::from([(
+                "Hot".to_string(),
+                record(1, 1_500_000_000_000_000_000, &["personal"]),
+            )]))
+        });
+        let wallet_store = Arc::new(wallet_store);
+
+        let mut price_oracle = MockPriceOracle::new();
+        price_oracle.expect_eth_usd_price().returning(|| Ok(2000.0));
+        let portfolio = PortfolioExecutor {
+            wallet_store: wallet_store.clone(),
+            display: DisplayPolicy::default(),
+            price_oracle: Some(Arc::new(price_oracle)),
+        };
+
+        let summary = portfolio.execute().await.unwrap();
+        assert_eq!(summary.total.value_usd.as_deref(), Some("3000.00"));
+        assert_eq!(summary.by_tag[0].1.value_usd.as_deref(), Some("3000.00"));
+
+        // A failing oracle leaves values out instead of failing the summary.
+        let mut price_oracle = MockPriceOracle::new();
+        price_oracle
+            .expect_eth_usd_price()
+            .returning(|| Err(ClientError(io::Error::other("rate limited").into())));
+        let portfolio = PortfolioExecutor {
+            wallet_store,
+            display: DisplayPolicy::default(),
+            price_oracle: Some(Arc::new(price_oracle)),
+        };
+
+        let summary = portfolio.execute().await.unwrap();
+        assert_eq!(summary.total.value_usd, None);
+    }
+}
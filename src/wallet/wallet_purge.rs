@@ -0,0 +1,148 @@
+use std::{any::type_name, fmt, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use tracing::info;
+
+use super::{Result, audit, resolve_trashed};
+use crate::infra::{AuditStore, TrashStore, TrashedRecord};
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait Purge: Send + Sync + 'static {
+    async fn execute(&self, name: Option<String>) -> Result<usize>;
+}
+
+#[derive(Clone)]
+pub struct PurgeExecutor {
+    pub trash_store: Arc<dyn TrashStore>,
+    pub audit_store: Arc<dyn AuditStore>,
+    pub retention: Duration,
+}
+
+impl fmt::Debug for PurgeExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>()).finish()
+    }
+}
+
+#[async_trait]
+impl Purge for PurgeExecutor {
+    async fn execute(&self, name: Option<String>) -> Result<usize> {
+        match name {
+            Some(name) => {
+                let result = self.purge_one(&name).await;
+                if result.is_err() {
+                    audit(self.audit_store.as_ref(), "purge", &name, None, &result).await;
+                }
+                result
+            }
+            None => self.purge_expired().await,
+        }
+    }
+}
+
+impl PurgeExecutor {
+    async fn purge_one(&self, name: &str) -> Result<usize> {
+        let (name, trashed) = resolve_trashed(self.trash_store.as_ref(), name).await?;
+        self.trash_store.purge(&name).await?;
+        self.audit_purged(&name, &trashed).await;
+        Ok(1)
+    }
+
+    async fn purge_expired(&self) -> Result<usize> {
+        let retention = chrono::Duration::from_std(self.retention).unwrap_or(chrono::Duration::MAX);
+        let cutoff = Utc::now().checked_sub_signed(retention).unwrap_or_default();
+
+        let mut purged = 0;
+        for (name, trashed) in self.trash_store.all_trashed().await? {
+            if trashed.trashed_at < cutoff {
+                self.trash_store.purge(&name).await?;
+                self.audit_purged(&name, &trashed).await;
+                purged += 1;
+            }
+        }
+
+        if purged > 0 {
+            info!("purged {purged} expired wallets from trash");
+        }
+        Ok(purged)
+    }
+
+    async fn audit_purged(&self, name: &str, trashed: &TrashedRecord) {
+        let address = trashed.record.wallet.address().to_string();
+        let result: Result<()> = Ok(());
+        audit(
+            self.audit_store.as_ref(),
+            "purge",
+            name,
+            Some(&address),
+            &result,
+        )
+        .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, str::FromStr, sync::Arc, time::Duration};
+
+    use chrono::{TimeDelta, Utc};
+
+    use crate::{
+        core::{Address, Wallet},
+        infra::{MockTrashStore, TrashedRecord, WalletRecord},
+        wallet::{Purge, PurgeExecutor, WalletErrorKind, audit_store},
+    };
+
+    fn trashed(age: TimeDelta) -> TrashedRecord {
+        let address = "0xB644Babc370f46f202DB5eaf2071A9Ee66fA1D5E";
+        let address = Address::from_str(address).unwrap();
+        TrashedRecord {
+            record: WalletRecord::new(Wallet::new(address)),
+            trashed_at: Utc::now() - age,
+        }
+    }
+
+    #[tokio::test]
+    async fn wallet_purge_expired() {
+        let mut trash_store = MockTrashStore::new();
+        trash_store.expect_all_trashed().returning(|| {
+            let mut trashed_records = BTreeMap::new();
+            trashed_records.insert("Old".to_string(), trashed(TimeDelta::days(31)));
+            trashed_records.insert("New".to_string(), trashed(TimeDelta::days(1)));
+            Ok(trashed_records)
+        });
+        trash_store
+            .expect_purge()
+            .withf(|name| name == "Old")
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let purge = PurgeExecutor {
+            trash_store: Arc::new(trash_store),
+            audit_store: audit_store(),
+            retention: Duration::from_secs(30 * 24 * 60 * 60),
+        };
+
+        assert_eq!(purge.execute(None).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn wallet_purge_not_found() {
+        let mut trash_store = MockTrashStore::new();
+        trash_store.expect_find_trashed().returning(|_| Ok(None));
+
+        let purge = PurgeExecutor {
+            trash_store: Arc::new(trash_store),
+            audit_store: audit_store(),
+            retention: Duration::ZERO,
+        };
+
+        let error = purge
+            .execute(Some("David's Wallet".to_string()))
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), WalletErrorKind::NotFound);
+    }
+}
@@ -1,23 +1,61 @@
-use std::{any::type_name, fmt, sync::Arc};
+use std::{any::type_name, collections::HashMap, error, fmt, sync::Arc, time::Duration};
 
 use async_trait::async_trait;
 use chrono::Utc;
-use futures::future::try_join_all;
+use futures::future::{join_all, try_join_all};
+use tracing::warn;
 
-use crate::infra::{WalletClient, WalletRecord, WalletStore};
+use crate::{
+    core::{Address, Balance, Sample, Token, TokenBalance, U256, Wallet},
+    infra::{
+        HistoryStore, NameResolver, Notification, OutboxEntry, OutboxStore, ReverseResolver,
+        StoreError, WalletClient, WalletRecord, WalletStore,
+    },
+};
 
-use super::Result;
+use super::{
+    Enrichment, EnrichmentErrors, EventBus, Result, WalletError, WalletEvent, fetch_balance,
+};
 
 #[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait Refresh: Send + Sync + 'static {
-    async fn execute(&self) -> Result<()>;
+    async fn execute(&self) -> Result<RefreshSummary>;
+}
+
+#[derive(Debug, Default)]
+pub struct RefreshSummary {
+    pub refreshed: usize,
+    pub skipped: usize,
+    pub failed: Vec<(String, WalletError)>,
+    pub changed: Vec<BalanceChange>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BalanceChange {
+    pub name: String,
+    pub wallet_id: String,
+    pub before: Balance,
+    pub after: Balance,
 }
 
 #[derive(Clone)]
 pub struct RefreshExecutor {
     pub wallet_store: Arc<dyn WalletStore>,
     pub wallet_client: Arc<dyn WalletClient>,
+    pub events: Arc<EventBus>,
+    pub freshness: Duration,
+    pub reverse_resolver: Option<Arc<dyn ReverseResolver>>,
+    pub reverse_ttl: Duration,
+    pub enrichment_errors: Arc<EnrichmentErrors>,
+    pub name_resolver: Option<Arc<dyn NameResolver>>,
+    pub resolve_ttl: Duration,
+    pub outbox_store: Option<Arc<dyn OutboxStore>>,
+    pub outbox_schema_version: u32,
+    pub history_store: Option<Arc<dyn HistoryStore>>,
+    pub history_keep: usize,
+    pub tokens: Vec<Token>,
+    pub batch_balances: bool,
 }
 
 impl fmt::Debug for RefreshExecutor {
@@ -28,31 +66,1018 @@ impl fmt::Debug for RefreshExecutor {
 
 #[async_trait]
 impl Refresh for RefreshExecutor {
-    async fn execute(&self) -> Result<()> {
+    async fn execute(&self) -> Result<RefreshSummary> {
         let wallets = self.wallet_store.all().await?;
 
-        let futures: Vec<_> = wallets
+        let freshness = chrono::Duration::from_std(self.freshness).unwrap_or(chrono::Duration::MAX);
+        let fresh_after = Utc::now().checked_sub_signed(freshness).unwrap_or_default();
+        let (fresh, stale): (Vec<_>, Vec<_>) = wallets
             .iter()
-            .map(|(name, record)| self.refresh_wallet(name, record))
+            .partition(|(_, record)| record.last_update > fresh_after);
+
+        // One head block for the whole pass, rather than a call per wallet.
+        let block_number = match &self.history_store {
+            Some(_) if !stale.is_empty() => self
+                .wallet_client
+                .block_number()
+                .await
+                .inspect_err(|e| warn!("couldn't read the head block: {e:?}"))
+                .ok(),
+            _ => None,
+        };
+
+        let prefetched = if self.batch_balances {
+            self.prefetch_balances(&stale).await
+        } else {
+            HashMap::new()
+        };
+        let prefetched = &prefetched;
+
+        let futures: Vec<_> = stale
+            .into_iter()
+            .map(|(name, record)| async move {
+                (name, self.refresh_wallet(name, record, prefetched).await)
+            })
             .collect();
 
-        try_join_all(futures).await?;
-        Ok(())
+        let mut summary = RefreshSummary {
+            skipped: fresh.len(),
+            ..Default::default()
+        };
+        let mut refreshed = vec![];
+        for (name, outcome) in join_all(futures).await {
+            match outcome {
+                Ok(wallet) => refreshed.push(wallet),
+                Err(e) => summary.failed.push((name.to_owned(), e)),
+            }
+        }
+
+        let mut samples = vec![];
+        for Refreshed {
+            change,
+            record,
+            moved_from,
+        } in self.save(refreshed, &mut summary.failed).await
+        {
+            summary.refreshed += 1;
+            samples.push((
+                change.wallet_id.clone(),
+                Sample {
+                    timestamp: record.last_update.timestamp(),
+                    block_number,
+                    balance: change.after,
+                },
+            ));
+            if let Some(from) = moved_from {
+                self.events.publish(WalletEvent::AddressChanged {
+                    wallet_id: record.id,
+                    name: change.name.clone(),
+                    from,
+                    to: *record.wallet.address(),
+                });
+            }
+            if change.before != change.after {
+                self.events
+                    .publish(WalletEvent::BalanceChanged(change.clone()));
+                summary.changed.push(change);
+            }
+        }
+
+        self.append_history(&samples).await;
+
+        summary.failed.sort_by(|(a, _), (b, _)| a.cmp(b));
+        summary.changed.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(summary)
     }
 }
 
+struct Refreshed {
+    change: BalanceChange,
+    record: WalletRecord,
+    moved_from: Option<Address>,
+}
+
 impl RefreshExecutor {
-    async fn refresh_wallet(&self, name: &str, record: &WalletRecord) -> Result<()> {
-        let balance = self.wallet_client.balance(record.wallet.address()).await?;
+    async fn save(
+        &self,
+        refreshed: Vec<Refreshed>,
+        failed: &mut Vec<(String, WalletError)>,
+    ) -> Vec<Refreshed> {
+        let mut saved = Vec::with_capacity(refreshed.len());
+        let mut batch = vec![];
+        for wallet in refreshed {
+            let change = &wallet.change;
+            match &self.outbox_store {
+                Some(outbox_store) if change.before != change.after => {
+                    let entry = OutboxEntry::new(
+                        Notification::BalanceChanged {
+                            wallet_id: change.wallet_id.clone(),
+                            name: change.name.clone(),
+                            before: change.before,
+                            after: change.after,
+                        },
+                        self.outbox_schema_version,
+                    );
+                    match outbox_store
+                        .save_with_outbox(&change.name, &wallet.record, &entry)
+                        .await
+                    {
+                        Ok(()) => saved.push(wallet),
+                        Err(e) => failed.push((change.name.clone(), e.into())),
+                    }
+                }
+                _ => batch.push(wallet),
+            }
+        }
+        if batch.is_empty() {
+            return saved;
+        }
+
+        let records: Vec<_> = batch
+            .iter()
+            .map(|wallet| (wallet.change.name.clone(), wallet.record.clone()))
+            .collect();
+        match self.wallet_store.save_many(&records).await {
+            Ok(()) => saved.extend(batch),
+            Err(e) => {
+                warn!("couldn't save {} refreshed wallets: {e:?}", batch.len());
+                // Every wallet in the batch failed for the same reason.
+                let source: Arc<dyn error::Error + Send + Sync> = Arc::from(e.0);
+                failed.extend(batch.into_iter().map(|wallet| {
+                    let error = StoreError(Box::new(source.clone()));
+                    (wallet.change.name, error.into())
+                }));
+            }
+        }
+        saved
+    }
+
+    async fn append_history(&self, samples: &[(String, Sample)]) {
+        let Some(history_store) = &self.history_store else {
+            return;
+        };
+        if samples.is_empty() {
+            return;
+        }
+
+        if let Err(e) = history_store
+            .append_samples(samples, self.history_keep)
+            .await
+        {
+            warn!("couldn't append {} history samples: {e:?}", samples.len());
+        }
+    }
+
+    async fn prefetch_balances(
+        &self,
+        stale: &[(&String, &WalletRecord)],
+    ) -> HashMap<Address, Balance> {
+        let mut addresses: Vec<_> = stale
+            .iter()
+            .flat_map(|(_, record)| record.wallet.addresses().copied())
+            .collect();
+        addresses.sort();
+        addresses.dedup();
+        if addresses.is_empty() {
+            return HashMap::new();
+        }
+
+        match self.wallet_client.balances(&addresses).await {
+            Ok(balances) => addresses.into_iter().zip(balances).collect(),
+            Err(e) => {
+                warn!("couldn't read {} balances at once: {e:?}", addresses.len());
+                HashMap::new()
+            }
+        }
+    }
+
+    async fn refresh_wallet(
+        &self,
+        name: &str,
+        record: &WalletRecord,
+        prefetched: &HashMap<Address, Balance>,
+    ) -> Result<Refreshed> {
+        let mut record = record.clone();
+        let moved_from = match &self.name_resolver {
+            Some(name_resolver) => self.follow_name(name_resolver.as_ref(), &mut record).await,
+            None => None,
+        };
+        let cached: Option<Vec<_>> = record
+            .wallet
+            .addresses()
+            .map(|address| prefetched.get(address).copied())
+            .collect();
+        let balance = match cached {
+            Some(balances) => balances
+                .into_iter()
+                .fold(Balance::default(), Balance::saturating_add),
+            None => fetch_balance(self.wallet_client.as_ref(), &record.wallet).await?,
+        };
+
+        // A move between different addresses isn't a move at all.
+        let balance_before = match moved_from {
+            Some(_) => balance,
+            None => record.wallet.balance(),
+        };
+        record.previous_balance = moved_from.is_none().then_some(balance_before);
+        *record.wallet.balance_mut() = balance;
+        record.tokens = self.fetch_tokens(&record).await;
+        record.transaction_count = self
+            .fetch_transaction_count(&record)
+            .await
+            .or(record.transaction_count);
+        record.last_update = Utc::now();
+
+        if let Some(reverse_resolver) = &self.reverse_resolver {
+            self.refresh_ens_name(reverse_resolver.as_ref(), &mut record)
+                .await;
+        }
+
+        let change = BalanceChange {
+            name: name.to_owned(),
+            wallet_id: record.id.clone(),
+            before: balance_before,
+            after: balance,
+        };
+        Ok(Refreshed {
+            change,
+            record,
+            moved_from,
+        })
+    }
+
+    async fn follow_name(
+        &self,
+        name_resolver: &dyn NameResolver,
+        record: &mut WalletRecord,
+    ) -> Option<Address> {
+        let resolved_from = record.resolved_from.clone()?;
+        let ttl = chrono::Duration::from_std(self.resolve_ttl).unwrap_or(chrono::Duration::MAX);
+        let now = Utc::now();
+        if record
+            .resolved_at
+            .is_some_and(|resolved_at| now.signed_duration_since(resolved_at) < ttl)
+        {
+            return None;
+        }
+
+        let address = match name_resolver.resolve(&resolved_from).await {
+            Ok(Some(address)) => address,
+            Ok(None) => {
+                warn!("{resolved_from} no longer resolves; keeping its last address");
+                return None;
+            }
+            Err(e) => {
+                warn!("couldn't resolve {resolved_from}: {e:?}");
+                return None;
+            }
+        };
+        record.resolved_at = Some(now);
+
+        let from = *record.wallet.address();
+        if address == from {
+            return None;
+        }
+
+        let mut wallet = Wallet::on_chain(record.wallet.chain_id(), address);
+        wallet.set_linked(record.wallet.linked().to_vec());
+        record.wallet = wallet;
+        record.verified_at = None;
+        record.ens_name = None;
+        record.ens_checked_at = None;
+        record.tokens.clear();
+        record.transaction_count = None;
+        Some(from)
+    }
+
+    async fn fetch_tokens(&self, record: &WalletRecord) -> Vec<TokenBalance> {
+        let futures = self.tokens.iter().map(|token| async move {
+            let amounts = try_join_all(
+                record
+                    .wallet
+                    .addresses()
+                    .map(|address| self.wallet_client.token_balance(address, &token.address)),
+            )
+            .await;
+
+            let amount = match amounts {
+                Ok(amounts) => amounts
+                    .iter()
+                    .fold(U256::zero(), |sum, amount| sum.saturating_add(*amount)),
+                Err(e) => {
+                    warn!(token = %token.symbol, "couldn't read token balance: {e:?}");
+                    self.enrichment_errors
+                        .record(&record.id, Enrichment::Tokens, &e);
+                    let held = record
+                        .tokens
+                        .iter()
+                        .find(|held| held.token.address == token.address)
+                        .cloned();
+                    return (held, false);
+                }
+            };
+            let held = TokenBalance {
+                token: token.clone(),
+                amount,
+            };
+            (Some(held), true)
+        });
+
+        let (tokens, read): (Vec<_>, Vec<_>) = join_all(futures).await.into_iter().unzip();
+        if read.into_iter().all(|read| read) {
+            self.enrichment_errors.clear(&record.id, Enrichment::Tokens);
+        }
+        tokens.into_iter().flatten().collect()
+    }
+
+    async fn fetch_transaction_count(&self, record: &WalletRecord) -> Option<u64> {
+        let counts = try_join_all(
+            record
+                .wallet
+                .addresses()
+                .map(|address| self.wallet_client.transaction_count(address)),
+        )
+        .await
+        .inspect_err(|e| warn!("couldn't read transaction count: {e:?}"))
+        .ok()?;
+        Some(
+            counts
+                .iter()
+                .fold(0u64, |sum, count| sum.saturating_add(*count)),
+        )
+    }
+
+    async fn refresh_ens_name(
+        &self,
+        reverse_resolver: &dyn ReverseResolver,
+        record: &mut WalletRecord,
+    ) {
+        let ttl = chrono::Duration::from_std(self.reverse_ttl).unwrap_or(chrono::Duration::MAX);
+        let now = Utc::now();
+        if record
+            .ens_checked_at
+            .is_some_and(|checked_at| now.signed_duration_since(checked_at) < ttl)
+        {
+            return;
+        }
+
+        match reverse_resolver.reverse(record.wallet.address()).await {
+            Ok(ens_name) => {
+                record.ens_name = ens_name;
+                record.ens_checked_at = Some(now);
+                self.enrichment_errors.clear(&record.id, Enrichment::Ens);
+            }
+            Err(e) => {
+                warn!(address = %record.wallet.address(), "reverse lookup failed: {e:?}");
+                self.enrichment_errors
+                    .record(&record.id, Enrichment::Ens, &e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, io, str::FromStr, sync::Arc, time::Duration};
+
+    use chrono::{DateTime, Utc};
+
+    use crate::{
+        core::{Address, Balance, Token, TokenBalance, U256, Wallet},
+        infra::{
+            ClientError, MockHistoryStore, MockNameResolver, MockOutboxStore, MockReverseResolver,
+            MockWalletClient, MockWalletStore, Notification, StoreError, WalletRecord,
+        },
+        server::compose_error,
+        wallet::{
+            Enrichment, EnrichmentErrors, EventBus, Refresh, RefreshExecutor, WalletErrorKind,
+            WalletEvent,
+        },
+    };
+
+    const GOOD: &str = "0xB644Babc370f46f202DB5eaf2071A9Ee66fA1D5E";
+    const BAD: &str = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
+
+    #[tokio::test]
+    async fn wallet_refresh_partial_failure() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_all().returning(|| {
+            let record = |addr| WalletRecord {
+                last_update: DateTime::UNIX_EPOCH,
+                ..WalletRecord::new(Wallet::new(Address::from_str(addr).unwrap()))
+            };
+            Ok(BTreeMap::from([
+                ("Good".to_string(), record(GOOD)),
+                ("Bad".to_string(), record(BAD)),
+            ]))
+        });
+        wallet_store
+            .expect_save_many()
+            .withf(|wallets| {
+                let [(name, record)] = wallets else {
+                    return false;
+                };
+                name == "Good"
+                    && record.wallet.balance() == Balance::new(42)
+                    && record.previous_balance == Some(Balance::new(0))
+                    && record.transaction_count == Some(3)
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let mut wallet_client = MockWalletClient::new();
+        wallet_client
+            .expect_transaction_count()
+            .returning(|_| Ok(3));
+        wallet_client.expect_balance().returning(|address| {
+            if address.to_string() == GOOD {
+                Ok(Balance::new(42))
+            } else {
+                Err(ClientError(io::Error::other("upstream down").into()))
+            }
+        });
+        wallet_client
+            .expect_block_number()
+            .times(1)
+            .returning(|| Ok(7));
+
+        // Only the wallet that refreshed gets a sample.
+        let mut history_store = MockHistoryStore::new();
+        history_store
+            .expect_append_samples()
+            .withf(|samples, keep| {
+                *keep == 100
+                    && samples.len() == 1
+                    && samples[0].1.block_number == Some(7)
+                    && samples[0].1.balance == Balance::new(42)
+            })
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let refresh = RefreshExecutor {
+            wallet_store: Arc::new(wallet_store),
+            wallet_client: Arc::new(wallet_client),
+            events: Arc::new(EventBus::default()),
+            freshness: Duration::ZERO,
+            reverse_resolver: None,
+            reverse_ttl: Duration::ZERO,
+            enrichment_errors: Arc::default(),
+            outbox_store: None,
+            outbox_schema_version: 2,
+            history_store: Some(Arc::new(history_store)),
+            history_keep: 100,
+            tokens: vec![],
+            batch_balances: false,
+            name_resolver: None,
+            resolve_ttl: Duration::ZERO,
+        };
+
+        let summary = refresh.execute().await.unwrap();
+        assert_eq!(summary.refreshed, 1);
+        assert_eq!(summary.failed.len(), 1);
+        assert_eq!(summary.failed[0].0, "Bad");
+        assert_eq!(summary.failed[0].1.kind(), WalletErrorKind::WalletClient);
+        assert_eq!(summary.changed.len(), 1);
+        assert_eq!(summary.changed[0].after, Balance::new(42));
+    }
+
+    #[tokio::test]
+    async fn wallet_refresh_batch_balances() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_all().returning(|| {
+            let record = |addr| WalletRecord {
+                last_update: DateTime::UNIX_EPOCH,
+                ..WalletRecord::new(Wallet::new(Address::from_str(addr).unwrap()))
+            };
+            Ok(BTreeMap::from([
+                ("Good".to_string(), record(GOOD)),
+                ("Bad".to_string(), record(BAD)),
+            ]))
+        });
+        wallet_store
+            .expect_save_many()
+            .withf(|wallets| {
+                wallets.len() == 2
+                    && wallets.iter().all(|(name, record)| {
+                        let expected = if name == "Good" { 1 } else { 2 };
+                        record.wallet.balance() == Balance::new(expected)
+                    })
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+
+        // Asked for in address order, which puts GOOD first.
+        let mut wallet_client = MockWalletClient::new();
+        wallet_client
+            .expect_transaction_count()
+            .returning(|_| Ok(3));
+        wallet_client
+            .expect_balances()
+            .withf(|addresses| addresses.len() == 2 && addresses[0].to_string() == GOOD)
+            .times(1)
+            .returning(|_| Ok(vec![Balance::new(1), Balance::new(2)]));
+        wallet_client.expect_balance().never();
+
+        let refresh = RefreshExecutor {
+            wallet_store: Arc::new(wallet_store),
+            wallet_client: Arc::new(wallet_client),
+            freshness: Duration::ZERO,
+            reverse_resolver: None,
+            reverse_ttl: Duration::ZERO,
+            events: Arc::new(EventBus::default()),
+            outbox_store: None,
+            history_store: None,
+            history_keep: 0,
+            tokens: vec![],
+            batch_balances: true,
+            enrichment_errors: Arc::default(),
+            outbox_schema_version: 2,
+            name_resolver: None,
+            resolve_ttl: Duration::ZERO,
+        };
+
+        let summary = refresh.execute().await.unwrap();
+        assert_eq!(summary.refreshed, 2);
+        assert!(summary.failed.is_empty());
+    }
+
+    #[tokio::test]
+    async fn wallet_refresh_save_fails() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_all().returning(|| {
+            let record = WalletRecord {
+                last_update: DateTime::UNIX_EPOCH,
+                ..WalletRecord::new(Wallet::new(Address::from_str(GOOD).unwrap()))
+            };
+            Ok(BTreeMap::from([
+                ("One".to_string(), record.clone()),
+                ("Two".to_string(), record),
+            ]))
+        });
+        wallet_store
+            .expect_save_many()
+            .times(1)
+            .returning(|_| Err(StoreError(io::Error::other("disk full").into())));
+
+        let mut wallet_client = MockWalletClient::new();
+        wallet_client
+            .expect_transaction_count()
+            .returning(|_| Ok(3));
+        wallet_client
+            .expect_balance()
+            .returning(|_| Ok(Balance::new(42)));
+
+        let refresh = RefreshExecutor {
+            wallet_store: Arc::new(wallet_store),
+            wallet_client: Arc::new(wallet_client),
+            freshness: Duration::ZERO,
+            reverse_resolver: None,
+            reverse_ttl: Duration::ZERO,
+            enrichment_errors: Arc::default(),
+            events: Arc::new(EventBus::default()),
+            outbox_store: None,
+            outbox_schema_version: 2,
+            history_store: None,
+            history_keep: 0,
+            tokens: vec![],
+            batch_balances: false,
+            name_resolver: None,
+            resolve_ttl: Duration::ZERO,
+        };
+
+        // The wallets were read but not saved, so none count as refreshed.
+        let summary = refresh.execute().await.unwrap();
+        assert_eq!(summary.refreshed, 0);
+        assert!(summary.changed.is_empty());
+        assert_eq!(summary.failed.len(), 2);
+        for (_, error) in &summary.failed {
+            assert_eq!(error.kind(), WalletErrorKind::WalletStore);
+            assert!(compose_error(error).contains("disk full"));
+        }
+    }
+
+    #[tokio::test]
+    async fn wallet_refresh_skip_fresh() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_all().returning(|| {
+            let address = Address::from_str(GOOD).unwrap();
+            let stale = WalletRecord {
+                last_update: Utc::now() - Duration::from_secs(120),
+                ..WalletRecord::new(Wallet::new(address))
+            };
+            let fresh = WalletRecord {
+                last_update: Utc::now() - Duration::from_secs(5),
+                ..WalletRecord::new(Wallet::new(address))
+            };
+            Ok(BTreeMap::from([
+                ("Stale".to_string(), stale),
+                ("Fresh".to_string(), fresh),
+            ]))
+        });
+        wallet_store
+            .expect_save_many()
+            .withf(|wallets| wallets.len() == 1 && wallets[0].0 == "Stale")
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let mut wallet_client = MockWalletClient::new();
+        wallet_client
+            .expect_transaction_count()
+            .returning(|_| Ok(3));
+        wallet_client
+            .expect_balance()
+            .times(1)
+            .returning(|_| Ok(Balance::new(42)));
+
+        let refresh = RefreshExecutor {
+            wallet_store: Arc::new(wallet_store),
+            wallet_client: Arc::new(wallet_client),
+            freshness: Duration::from_secs(30),
+            events: Arc::new(EventBus::default()),
+            reverse_resolver: None,
+            reverse_ttl: Duration::ZERO,
+            enrichment_errors: Arc::default(),
+            outbox_store: None,
+            outbox_schema_version: 2,
+            history_store: None,
+            history_keep: 0,
+            tokens: vec![],
+            batch_balances: false,
+            name_resolver: None,
+            resolve_ttl: Duration::ZERO,
+        };
+
+        let summary = refresh.execute().await.unwrap();
+        assert_eq!(summary.refreshed, 1);
+        assert_eq!(summary.skipped, 1);
+        assert!(summary.failed.is_empty());
+    }
+
+    #[tokio::test]
+    async fn wallet_refresh_reverse_name() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_all().returning(|| {
+            let record = |checked_at| WalletRecord {
+                last_update: DateTime::UNIX_EPOCH,
+                ens_name: Some("old.eth".to_string()),
+                ens_checked_at: checked_at,
+                ..WalletRecord::new(Wallet::new(Address::from_str(GOOD).unwrap()))
+            };
+            Ok(BTreeMap::from([
+                ("Expired".to_string(), record(None)),
+                ("Cached".to_string(), record(Some(Utc::now()))),
+            ]))
+        });
+        wallet_store
+            .expect_save_many()
+            .withf(|wallets| {
+                wallets.len() == 2
+                    && wallets.iter().all(|(name, record)| {
+                        let expected = if name == "Expired" {
+                            "new.eth"
+                        } else {
+                            "old.eth"
+                        };
+                        record.ens_name.as_deref() == Some(expected)
+                    })
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let mut wallet_client = MockWalletClient::new();
+        wallet_client
+            .expect_transaction_count()
+            .returning(|_| Ok(3));
+        wallet_client
+            .expect_balance()
+            .returning(|_| Ok(Balance::new(42)));
+
+        let mut reverse_resolver = MockReverseResolver::new();
+        reverse_resolver
+            .expect_reverse()
+            .times(1)
+            .returning(|_| Ok(Some("new.eth".to_string())));
+
+        let refresh = RefreshExecutor {
+            wallet_store: Arc::new(wallet_store),
+            wallet_client: Arc::new(wallet_client),
+            freshness: Duration::ZERO,
+            reverse_resolver: Some(Arc::new(reverse_resolver)),
+            reverse_ttl: Duration::from_secs(60 * 60),
+            enrichment_errors: Arc::default(),
+            events: Arc::new(EventBus::default()),
+            outbox_store: None,
+            outbox_schema_version: 2,
+            history_store: None,
+            history_keep: 0,
+            tokens: vec![],
+            batch_balances: false,
+            name_resolver: None,
+            resolve_ttl: Duration::ZERO,
+        };
+
+        let summary = refresh.execute().await.unwrap();
+        assert_eq!(summary.refreshed, 2);
+    }
+
+    #[tokio::test]
+    async fn wallet_refresh_reverse_name_failure() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_all().returning(|| {
+            let record = WalletRecord {
+                id: "a".to_string(),
+                last_update: DateTime::UNIX_EPOCH,
+                ens_name: Some("old.eth".to_string()),
+                ..WalletRecord::new(Wallet::new(Address::from_str(GOOD).unwrap()))
+            };
+            Ok(BTreeMap::from([("Alpha".to_string(), record)]))
+        });
+        wallet_store
+            .expect_save_many()
+            .withf(|wallets| {
+                let [(_, record)] = wallets else {
+                    return false;
+                };
+                record.ens_name.as_deref() == Some("old.eth")
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let mut wallet_client = MockWalletClient::new();
+        wallet_client
+            .expect_balance()
+            .returning(|_| Ok(Balance::new(42)));
+        wallet_client
+            .expect_transaction_count()
+            .returning(|_| Ok(0));
+
+        let mut reverse_resolver = MockReverseResolver::new();
+        reverse_resolver
+            .expect_reverse()
+            .returning(|_| Err(ClientError(io::Error::other("timed out").into())));
+
+        let enrichment_errors = Arc::new(EnrichmentErrors::default());
+        let refresh = RefreshExecutor {
+            wallet_store: Arc::new(wallet_store),
+            wallet_client: Arc::new(wallet_client),
+            events: Arc::new(EventBus::default()),
+            freshness: Duration::ZERO,
+            reverse_resolver: Some(Arc::new(reverse_resolver)),
+            reverse_ttl: Duration::ZERO,
+            enrichment_errors: enrichment_errors.clone(),
+            outbox_schema_version: 2,
+            outbox_store: None,
+            history_store: None,
+            history_keep: 0,
+            tokens: vec![],
+            batch_balances: false,
+            name_resolver: None,
+            resolve_ttl: Duration::ZERO,
+        };
+
+        let summary = refresh.execute().await.unwrap();
+        assert_eq!(summary.refreshed, 1);
+        let failed = enrichment_errors.of("a");
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].enrichment, Enrichment::Ens);
+    }
+
+    #[tokio::test]
+    async fn wallet_refresh_outbox() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_all().returning(|| {
+            let record = |balance| {
+                let mut wallet = Wallet::new(Address::from_str(GOOD).unwrap());
+                *wallet.balance_mut() = Balance::new(balance);
+                WalletRecord {
+                    last_update: DateTime::UNIX_EPOCH,
+                    ..WalletRecord::new(wallet)
+                }
+            };
+            Ok(BTreeMap::from([
+                ("Moved".to_string(), record(7)),
+                ("Still".to_string(), record(42)),
+            ]))
+        });
+        wallet_store
+            .expect_save_many()
+            .withf(|wallets| wallets.len() == 1 && wallets[0].0 == "Still")
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let mut outbox_store = MockOutboxStore::new();
+        outbox_store
+            .expect_save_with_outbox()
+            .withf(|name, record, entry| {
+                name == "Moved"
+                    && record.wallet.balance() == Balance::new(42)
+                    && entry.schema_version == 2
+                    && entry.notification
+                        == Notification::BalanceChanged {
+                            wallet_id: record.id.clone(),
+                            name: "Moved".to_string(),
+                            before: Balance::new(7),
+                            after: Balance::new(42),
+                        }
+            })
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        let mut wallet_client = MockWalletClient::new();
+        wallet_client
+            .expect_transaction_count()
+            .returning(|_| Ok(3));
+        wallet_client
+            .expect_balance()
+            .returning(|_| Ok(Balance::new(42)));
+
+        let refresh = RefreshExecutor {
+            wallet_store: Arc::new(wallet_store),
+            wallet_client: Arc::new(wallet_client),
+            freshness: Duration::ZERO,
+            reverse_resolver: None,
+            reverse_ttl: Duration::ZERO,
+            events: Arc::new(EventBus::default()),
+            outbox_store: Some(Arc::new(outbox_store)),
+            outbox_schema_version: 2,
+            enrichment_errors: Arc::default(),
+            history_store: None,
+            history_keep: 0,
+            tokens: vec![],
+            batch_balances: false,
+            name_resolver: None,
+            resolve_ttl: Duration::ZERO,
+        };
+
+        let summary = refresh.execute().await.unwrap();
+        assert_eq!(summary.refreshed, 2);
+        assert_eq!(summary.changed.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn wallet_refresh_tokens() {
+        let token = |symbol: &str, byte| Token {
+            symbol: symbol.to_string(),
+            address: Address::new([byte; 20]),
+            decimals: 6,
+        };
+        let (usdc, dai) = (token("USDC", 1), token("DAI", 2));
+
+        let mut wallet_store = MockWalletStore::new();
+        let held = dai.clone();
+        wallet_store.expect_all().returning(move || {
+            let mut wallet = Wallet::new(Address::from_str(GOOD).unwrap());
+            wallet.set_linked([Address::from_str(BAD).unwrap()]);
+            let record = WalletRecord {
+                last_update: DateTime::UNIX_EPOCH,
+                tokens: vec![TokenBalance {
+                    token: held.clone(),
+                    amount: U256::from(9),
+                }],
+                ..WalletRecord::new(wallet)
+            };
+            Ok(BTreeMap::from([("Hot".to_string(), record)]))
+        });
+        let expected = vec![
+            TokenBalance {
+                token: usdc.clone(),
+                amount: U256::from(10),
+            },
+            TokenBalance {
+                token: dai.clone(),
+                amount: U256::from(9),
+            },
+        ];
+        wallet_store
+            .expect_save_many()
+            .withf(move |wallets| {
+                let [(_, record)] = wallets else {
+                    return false;
+                };
+                record.tokens == expected && record.transaction_count == Some(6)
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+
+        // USDC is summed over both addresses; DAI fails and keeps its amount.
+        let mut wallet_client = MockWalletClient::new();
+        wallet_client
+            .expect_transaction_count()
+            .returning(|_| Ok(3));
+        wallet_client
+            .expect_balance()
+            .returning(|_| Ok(Balance::new(42)));
+        wallet_client
+            .expect_token_balance()
+            .returning(|_, token| match token.inner() {
+                [1, ..] => Ok(U256::from(5)),
+                _ => Err(ClientError(io::Error::other("reverted").into())),
+            });
+
+        let refresh = RefreshExecutor {
+            wallet_store: Arc::new(wallet_store),
+            wallet_client: Arc::new(wallet_client),
+            freshness: Duration::ZERO,
+            reverse_resolver: None,
+            reverse_ttl: Duration::ZERO,
+            events: Arc::new(EventBus::default()),
+            outbox_store: None,
+            history_store: None,
+            history_keep: 0,
+            tokens: vec![usdc, dai],
+            enrichment_errors: Arc::default(),
+            outbox_schema_version: 2,
+            batch_balances: false,
+            name_resolver: None,
+            resolve_ttl: Duration::ZERO,
+        };
+
+        let summary = refresh.execute().await.unwrap();
+        assert_eq!(summary.refreshed, 1);
+        assert!(summary.failed.is_empty());
+
+        let id = summary.changed[0].wallet_id.clone();
+        let failed = refresh.enrichment_errors.of(&id);
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].enrichment, Enrichment::Tokens);
+    }
+
+    #[tokio::test]
+    async fn wallet_refresh_follows_name() {
+        let (good, bad) = (
+            Address::from_str(GOOD).unwrap(),
+            Address::from_str(BAD).unwrap(),
+        );
+
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_all().returning(move || {
+            let mut wallet = Wallet::new(good);
+            *wallet.balance_mut() = Balance::new(7);
+            let record = WalletRecord {
+                last_update: DateTime::UNIX_EPOCH,
+                ens_name: Some("vault.eth".to_string()),
+                ens_checked_at: Some(Utc::now()),
+                resolved_from: Some("vault.eth".to_string()),
+                ..WalletRecord::new(wallet)
+            };
+            Ok(BTreeMap::from([("Vault".to_string(), record)]))
+        });
+        wallet_store
+            .expect_save_many()
+            .withf(move |wallets| {
+                let [(_, record)] = wallets else {
+                    return false;
+                };
+                *record.wallet.address() == bad
+                    && record.previous_balance.is_none()
+                    && record.resolved_at.is_some()
+                    && record.ens_checked_at.is_none()
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let mut wallet_client = MockWalletClient::new();
+        wallet_client
+            .expect_transaction_count()
+            .returning(|_| Ok(3));
+        wallet_client
+            .expect_balance()
+            .withf(move |address| *address == bad)
+            .returning(|_| Ok(Balance::new(42)));
+
+        let mut name_resolver = MockNameResolver::new();
+        name_resolver
+            .expect_resolve()
+            .withf(|name| name == "vault.eth")
+            .times(1)
+            .returning(move |_| Ok(Some(bad)));
 
-        let mut wallet = record.wallet.clone();
-        *wallet.balance_mut() = balance;
-        let record = WalletRecord {
-            wallet,
-            last_update: Utc::now(),
+        let events = Arc::new(EventBus::default());
+        let mut received = events.subscribe();
+        let refresh = RefreshExecutor {
+            wallet_store: Arc::new(wallet_store),
+            wallet_client: Arc::new(wallet_client),
+            freshness: Duration::ZERO,
+            reverse_resolver: None,
+            reverse_ttl: Duration::ZERO,
+            events,
+            outbox_store: None,
+            history_store: None,
+            history_keep: 0,
+            tokens: vec![],
+            enrichment_errors: Arc::default(),
+            outbox_schema_version: 2,
+            batch_balances: false,
+            name_resolver: Some(Arc::new(name_resolver)),
+            resolve_ttl: Duration::from_secs(60 * 60),
         };
 
-        self.wallet_store.save(name, &record).await?;
-        Ok(())
+        // The jump in balance came with the move, so it isn't a change.
+        let summary = refresh.execute().await.unwrap();
+        assert!(summary.changed.is_empty());
+        assert!(matches!(
+            received.try_recv().unwrap(),
+            WalletEvent::AddressChanged { from, to, .. } if from == good && to == bad
+        ));
     }
 }
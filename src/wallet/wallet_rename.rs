@@ -0,0 +1,163 @@
+use std::{any::type_name, fmt, sync::Arc};
+
+use async_trait::async_trait;
+
+use super::{
+    DisplayPolicy, EventBus, Result, Wallet, WalletError, WalletErrorKind, WalletEvent, audit,
+    resolve, to_wallet, validate_name,
+};
+use crate::infra::{AuditStore, Provenance, WalletChange, WalletStore};
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait Rename: Send + Sync + 'static {
+    async fn execute(&self, name: &str, new_name: &str, modified_by: Provenance) -> Result<Wallet>;
+}
+
+#[derive(Clone)]
+pub struct RenameExecutor {
+    pub wallet_store: Arc<dyn WalletStore>,
+    pub audit_store: Arc<dyn AuditStore>,
+    pub events: Arc<EventBus>,
+    pub display: DisplayPolicy,
+}
+
+impl fmt::Debug for RenameExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>()).finish()
+    }
+}
+
+#[async_trait]
+impl Rename for RenameExecutor {
+    async fn execute(&self, name: &str, new_name: &str, modified_by: Provenance) -> Result<Wallet> {
+        let result = self.rename(name, new_name, modified_by).await;
+        let address = result.as_ref().ok().map(|wallet| wallet.address.as_str());
+        audit(self.audit_store.as_ref(), "rename", name, address, &result).await;
+        result
+    }
+}
+
+impl RenameExecutor {
+    async fn rename(&self, name: &str, new_name: &str, modified_by: Provenance) -> Result<Wallet> {
+        let new_name = validate_name(new_name)?;
+        let (name, mut record) = resolve(self.wallet_store.as_ref(), name).await?;
+
+        if new_name != name && self.wallet_store.exists(&new_name).await? {
+            return Err(WalletError {
+                kind: WalletErrorKind::NameConflict,
+                source: None,
+            });
+        }
+
+        record.modified_by = modified_by;
+        if new_name == name {
+            self.wallet_store.save(&name, &record).await?;
+        } else {
+            let changes = [
+                WalletChange::Save {
+                    name: new_name.clone(),
+                    record: Box::new(record.clone()),
+                },
+                WalletChange::Delete { name: name.clone() },
+            ];
+            self.wallet_store.transaction(&changes).await?;
+            self.events.publish(WalletEvent::Renamed {
+                wallet_id: record.id.clone(),
+                from: name,
+                to: new_name.clone(),
+            });
+        }
+
+        Ok(to_wallet(&self.display, new_name, record))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr, sync::Arc};
+
+    use crate::{
+        core::{Address, Wallet},
+        infra::{MockWalletStore, Provenance, WalletChange, WalletRecord},
+        wallet::{
+            DisplayPolicy, EventBus, Rename, RenameExecutor, WalletErrorKind, WalletEvent,
+            audit_store,
+        },
+    };
+
+    const ADDR: &str = "0xB644Babc370f46f202DB5eaf2071A9Ee66fA1D5E";
+
+    fn wallet_store() -> MockWalletStore {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store
+            .expect_find()
+            .withf(|name| name == "Cold")
+            .returning(|_| {
+                let address = Address::from_str(ADDR).unwrap();
+                Ok(Some(WalletRecord::new(Wallet::new(address))))
+            });
+        wallet_store
+    }
+
+    #[tokio::test]
+    async fn wallet_rename_success() {
+        let mut wallet_store = wallet_store();
+        wallet_store.expect_exists().returning(|_| Ok(false));
+        wallet_store
+            .expect_transaction()
+            .withf(|changes| {
+                matches!(
+                    changes,
+                    [
+                        WalletChange::Save { name: saved, .. },
+                        WalletChange::Delete { name: deleted },
+                    ] if saved == "Vault" && deleted == "Cold"
+                )
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let events = Arc::new(EventBus::default());
+        let mut received = events.subscribe();
+        let rename = RenameExecutor {
+            wallet_store: Arc::new(wallet_store),
+            audit_store: audit_store(),
+            events,
+            display: DisplayPolicy::default(),
+        };
+
+        let wallet = rename
+            .execute(" Cold ", "Vault ", Provenance::default())
+            .await
+            .unwrap();
+        assert_eq!(wallet.name, "Vault");
+        assert_eq!(
+            received.try_recv().unwrap(),
+            WalletEvent::Renamed {
+                wallet_id: wallet.id,
+                from: "Cold".to_string(),
+                to: "Vault".to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn wallet_rename_name_conflict() {
+        let mut wallet_store = wallet_store();
+        wallet_store.expect_exists().returning(|_| Ok(true));
+
+        let rename = RenameExecutor {
+            wallet_store: Arc::new(wallet_store),
+            audit_store: audit_store(),
+            events: Arc::new(EventBus::default()),
+            display: DisplayPolicy::default(),
+        };
+
+        let error = rename
+            .execute("Cold", "Hot", Provenance::default())
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), WalletErrorKind::NameConflict);
+    }
+}
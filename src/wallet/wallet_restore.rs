@@ -0,0 +1,143 @@
+use std::{any::type_name, fmt, sync::Arc};
+
+use async_trait::async_trait;
+
+use super::{Result, WalletError, WalletErrorKind, audit, resolve_trashed};
+use crate::infra::{AuditStore, TrashStore, WalletStore};
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait Restore: Send + Sync + 'static {
+    async fn execute(&self, name: &str) -> Result<()>;
+}
+
+#[derive(Clone)]
+pub struct RestoreExecutor {
+    pub wallet_store: Arc<dyn WalletStore>,
+    pub trash_store: Arc<dyn TrashStore>,
+    pub audit_store: Arc<dyn AuditStore>,
+}
+
+impl fmt::Debug for RestoreExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>()).finish()
+    }
+}
+
+#[async_trait]
+impl Restore for RestoreExecutor {
+    async fn execute(&self, name: &str) -> Result<()> {
+        let result = self.restore(name).await;
+        let address = result.as_deref().ok();
+        audit(self.audit_store.as_ref(), "restore", name, address, &result).await;
+        result.map(|_| ())
+    }
+}
+
+impl RestoreExecutor {
+    async fn restore(&self, name: &str) -> Result<String> {
+        let (name, trashed) = resolve_trashed(self.trash_store.as_ref(), name).await?;
+
+        if self.wallet_store.exists(&name).await? {
+            return Err(WalletError {
+                kind: WalletErrorKind::NameConflict,
+                source: None,
+            });
+        }
+
+        self.trash_store.restore(&name).await?;
+        Ok(trashed.record.wallet.address().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr, sync::Arc};
+
+    use chrono::Utc;
+
+    use crate::{
+        core::{Address, Wallet},
+        infra::{
+            AuditOutcome, MockAuditStore, MockTrashStore, MockWalletStore, TrashedRecord,
+            WalletRecord,
+        },
+        wallet::{Restore, RestoreExecutor, WalletErrorKind, audit_store},
+    };
+
+    fn trashed() -> TrashedRecord {
+        let address = "0xB644Babc370f46f202DB5eaf2071A9Ee66fA1D5E";
+        let address = Address::from_str(address).unwrap();
+        TrashedRecord {
+            record: WalletRecord::new(Wallet::new(address)),
+            trashed_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn wallet_restore_success() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_exists().returning(|_| Ok(false));
+
+        let mut trash_store = MockTrashStore::new();
+        trash_store
+            .expect_find_trashed()
+            .returning(|_| Ok(Some(trashed())));
+        trash_store.expect_restore().times(1).returning(|_| Ok(()));
+
+        let restore = RestoreExecutor {
+            wallet_store: Arc::new(wallet_store),
+            trash_store: Arc::new(trash_store),
+            audit_store: audit_store(),
+        };
+
+        assert!(restore.execute("David's Wallet").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn wallet_restore_not_found() {
+        let mut trash_store = MockTrashStore::new();
+        trash_store.expect_find_trashed().returning(|_| Ok(None));
+
+        let restore = RestoreExecutor {
+            wallet_store: Arc::new(MockWalletStore::new()),
+            trash_store: Arc::new(trash_store),
+            audit_store: audit_store(),
+        };
+
+        let error = restore.execute("David's Wallet").await.unwrap_err();
+        assert_eq!(error.kind(), WalletErrorKind::NotFound);
+    }
+
+    #[tokio::test]
+    async fn wallet_restore_name_conflict() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_exists().returning(|_| Ok(true));
+
+        let mut trash_store = MockTrashStore::new();
+        trash_store
+            .expect_find_trashed()
+            .returning(|_| Ok(Some(trashed())));
+
+        let mut audit_store = MockAuditStore::new();
+        audit_store
+            .expect_append()
+            .withf(|entry| {
+                entry.operation == "restore"
+                    && entry.name == "David's Wallet"
+                    && entry.address.is_none()
+                    && entry.outcome == AuditOutcome::Failed("name conflict".to_string())
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let restore = RestoreExecutor {
+            wallet_store: Arc::new(wallet_store),
+            trash_store: Arc::new(trash_store),
+            audit_store: Arc::new(audit_store),
+        };
+
+        let error = restore.execute("David's Wallet").await.unwrap_err();
+        assert_eq!(error.kind(), WalletErrorKind::NameConflict);
+    }
+}
@@ -0,0 +1,103 @@
+use std::{any::type_name, fmt, sync::Arc};
+
+use async_trait::async_trait;
+
+use super::{NAME_MAX, Result, WalletError, WalletErrorKind, normalize_name, normalize_tags};
+use crate::infra::{PreferenceStore, SavedView};
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait SaveView: Send + Sync + 'static {
+    async fn execute(&self, principal: &str, view: SavedView) -> Result<SavedView>;
+}
+
+#[derive(Clone)]
+pub struct SaveViewExecutor {
+    pub preference_store: Arc<dyn PreferenceStore>,
+}
+
+impl fmt::Debug for SaveViewExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>()).finish()
+    }
+}
+
+#[async_trait]
+impl SaveView for SaveViewExecutor {
+    async fn execute(&self, principal: &str, view: SavedView) -> Result<SavedView> {
+        let name = normalize_name(&view.name);
+        if name.is_empty() {
+            return Err(WalletError {
+                kind: WalletErrorKind::ViewNameEmpty,
+                source: None,
+            });
+        } else if name.chars().count() > NAME_MAX {
+            return Err(WalletError {
+                kind: WalletErrorKind::ViewNameTooLong,
+                source: None,
+            });
+        }
+
+        let view = SavedView {
+            name,
+            tags: normalize_tags(&view.tags)?,
+            ..view
+        };
+
+        self.preference_store.save_view(principal, &view).await?;
+
+        Ok(view)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{
+        infra::{ListSort, MockPreferenceStore, SavedView},
+        wallet::{SaveView, SaveViewExecutor, WalletErrorKind},
+    };
+
+    #[tokio::test]
+    async fn wallet_save_view_success() {
+        let mut preference_store = MockPreferenceStore::new();
+        preference_store
+            .expect_save_view()
+            .withf(|principal, view| {
+                principal == "alice" && view.name == "Treasury" && view.tags == ["cold", "ops"]
+            })
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let save = SaveViewExecutor {
+            preference_store: Arc::new(preference_store),
+        };
+
+        let view = SavedView {
+            name: "  Treasury ".to_string(),
+            tags: vec!["Ops".to_string(), "cold".to_string()],
+            sort: ListSort::Balance,
+            fields: vec!["name".to_string(), "balance".to_string()],
+            ..Default::default()
+        };
+        let view = save.execute("alice", view).await.unwrap();
+        assert_eq!(view.name, "Treasury");
+        assert_eq!(view.sort, ListSort::Balance);
+        assert_eq!(view.fields, ["name", "balance"]);
+    }
+
+    #[tokio::test]
+    async fn wallet_save_view_name_empty() {
+        let save = SaveViewExecutor {
+            preference_store: Arc::new(MockPreferenceStore::new()),
+        };
+
+        let view = SavedView {
+            name: "   ".to_string(),
+            ..Default::default()
+        };
+        let err = save.execute("alice", view).await.unwrap_err();
+        assert_eq!(err.kind, WalletErrorKind::ViewNameEmpty);
+    }
+}
@@ -0,0 +1,149 @@
+use std::{any::type_name, fmt, sync::Arc};
+
+use async_trait::async_trait;
+
+use super::{DisplayPolicy, Result, Wallet, WalletError, WalletErrorKind, to_wallet};
+use crate::infra::WalletStore;
+
+const SEARCH_LIMIT_MAX: usize = 100;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait Search: Send + Sync + 'static {
+    async fn execute(&self, query: &str, limit: usize) -> Result<Vec<Wallet>>;
+}
+
+#[derive(Clone)]
+pub struct SearchExecutor {
+    pub wallet_store: Arc<dyn WalletStore>,
+    pub display: DisplayPolicy,
+}
+
+impl fmt::Debug for SearchExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>()).finish()
+    }
+}
+
+#[async_trait]
+impl Search for SearchExecutor {
+    async fn execute(&self, query: &str, limit: usize) -> Result<Vec<Wallet>> {
+        let terms: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+        if terms.is_empty() {
+            return Err(WalletError {
+                kind: WalletErrorKind::QueryEmpty,
+                source: None,
+            });
+        }
+
+        let wallets = self
+            .wallet_store
+            .search(&terms, limit.min(SEARCH_LIMIT_MAX))
+            .await?
+            .into_iter()
+            .map(|(name, record)| to_wallet(&self.display, name, record))
+            .collect();
+
+        Ok(wallets)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, str::FromStr, sync::Arc};
+
+    use crate::{
+        core::{Address, Wallet},
+        infra::{MockWalletStore, WalletRecord, search_records},
+        wallet::{DisplayPolicy, Search, SearchExecutor, WalletErrorKind},
+    };
+
+    fn search_executor() -> SearchExecutor {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_search().returning(|terms, limit| {
+            let mut records = BTreeMap::new();
+
+            let address = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
+            let address = Address::from_str(address).unwrap();
+            records.insert(
+                "Vitalik's Wallet".to_string(),
+                WalletRecord {
+                    tags: vec!["public".to_string()],
+                    ens_name: Some("vitalik.eth".to_string()),
+                    ..WalletRecord::new(Wallet::new(address))
+                },
+            );
+
+            let address = "0xB644Babc370f46f202DB5eaf2071A9Ee66fA1D5E";
+            let address = Address::from_str(address).unwrap();
+            records.insert(
+                "David's Wallet".to_string(),
+                WalletRecord {
+                    tags: vec!["cold".to_string()],
+                    note: Some("Hardware wallet, kept offline".to_string()),
+                    ..WalletRecord::new(Wallet::new(address))
+                },
+            );
+
+            let address = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2";
+            let address = Address::from_str(address).unwrap();
+            records.insert(
+                "Wrapped Ether".to_string(),
+                WalletRecord {
+                    tags: vec!["public".to_string(), "defi".to_string()],
+                    ..WalletRecord::new(Wallet::new(address))
+                },
+            );
+
+            Ok(search_records(records, terms, limit))
+        });
+
+        SearchExecutor {
+            wallet_store: Arc::new(wallet_store),
+            display: DisplayPolicy::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn wallet_search_ranking() {
+        let search = search_executor();
+
+        let wallets = search.execute("wallet", 10).await.unwrap();
+        assert_eq!(wallets.len(), 2);
+        assert_eq!(wallets[0].name, "David's Wallet");
+        assert_eq!(wallets[1].name, "Vitalik's Wallet");
+
+        let wallets = search.execute("public", 10).await.unwrap();
+        assert_eq!(wallets.len(), 2);
+
+        let wallets = search.execute("Wrapped public", 10).await.unwrap();
+        assert_eq!(wallets.len(), 1);
+        assert_eq!(wallets[0].name, "Wrapped Ether");
+
+        let wallets = search.execute("0xb644", 10).await.unwrap();
+        assert_eq!(wallets.len(), 1);
+        assert_eq!(wallets[0].name, "David's Wallet");
+
+        let wallets = search.execute("vitalik.eth", 10).await.unwrap();
+        assert_eq!(wallets.len(), 1);
+        assert_eq!(wallets[0].name, "Vitalik's Wallet");
+
+        let wallets = search.execute("offline", 10).await.unwrap();
+        assert_eq!(wallets.len(), 1);
+        assert_eq!(wallets[0].name, "David's Wallet");
+
+        let wallets = search.execute("wallet", 1).await.unwrap();
+        assert_eq!(wallets.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn wallet_search_query_empty() {
+        let search = SearchExecutor {
+            wallet_store: Arc::new(MockWalletStore::new()),
+            display: DisplayPolicy::default(),
+        };
+
+        let error = search.execute("  ", 10).await.unwrap_err();
+        assert_eq!(error.kind(), WalletErrorKind::QueryEmpty);
+    }
+}
@@ -0,0 +1,98 @@
+use std::{
+    any::type_name,
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::{Result, resolve};
+use crate::infra::WalletStore;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait Share: Send + Sync + 'static {
+    async fn execute(&self, name: &str, ttl: Option<Duration>) -> Result<ShareLink>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShareLink {
+    pub token: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+pub struct Shares {
+    default_ttl: Duration,
+    max_ttl: Duration,
+    grants: Mutex<HashMap<String, ShareGrant>>,
+}
+
+#[derive(Debug, Clone)]
+struct ShareGrant {
+    wallet_id: String,
+    expires_at: DateTime<Utc>,
+}
+
+impl Shares {
+    pub fn new(default_ttl: Duration, max_ttl: Duration) -> Self {
+        Self {
+            default_ttl,
+            max_ttl,
+            grants: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn issue(&self, wallet_id: &str, ttl: Option<Duration>) -> ShareLink {
+        let now = Utc::now();
+        let ttl = ttl.unwrap_or(self.default_ttl).min(self.max_ttl);
+        let ttl = chrono::Duration::from_std(ttl).unwrap_or(chrono::Duration::MAX);
+        let expires_at = now
+            .checked_add_signed(ttl)
+            .unwrap_or(DateTime::<Utc>::MAX_UTC);
+
+        let token = Uuid::new_v4().simple().to_string();
+        let grant = ShareGrant {
+            wallet_id: wallet_id.to_owned(),
+            expires_at,
+        };
+
+        let mut grants = self.grants.lock().unwrap();
+        grants.retain(|_, grant| grant.expires_at > now);
+        grants.insert(token.clone(), grant);
+
+        ShareLink { token, expires_at }
+    }
+
+    pub(super) fn wallet_id(&self, token: &str) -> Option<String> {
+        let grants = self.grants.lock().unwrap();
+        grants
+            .get(token)
+            .filter(|grant| grant.expires_at > Utc::now())
+            .map(|grant| grant.wallet_id.clone())
+    }
+}
+
+#[derive(Clone)]
+pub struct ShareExecutor {
+    pub wallet_store: Arc<dyn WalletStore>,
+    pub shares: Arc<Shares>,
+}
+
+impl fmt::Debug for ShareExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>()).finish()
+    }
+}
+
+#[async_trait]
+impl Share for ShareExecutor {
+    async fn execute(&self, name: &str, ttl: Option<Duration>) -> Result<ShareLink> {
+        let (_, record) = resolve(self.wallet_store.as_ref(), name).await?;
+        Ok(self.shares.issue(&record.id, ttl))
+    }
+}
@@ -0,0 +1,147 @@
+use std::{any::type_name, fmt, sync::Arc};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use super::{DisplayPolicy, Result, Shares, WalletError, WalletErrorKind, resolve};
+use crate::infra::WalletStore;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait ViewShared: Send + Sync + 'static {
+    async fn execute(&self, token: &str) -> Result<SharedWallet>;
+}
+
+#[derive(Debug, Clone)]
+pub struct SharedWallet {
+    pub name: String,
+    pub address: String,
+    pub linked_addresses: Vec<String>,
+    pub ens_name: Option<String>,
+    pub balance: String,
+    pub balance_wei: String,
+    pub last_update: DateTime<Utc>,
+    pub is_stale: bool,
+}
+
+#[derive(Clone)]
+pub struct ViewSharedExecutor {
+    pub wallet_store: Arc<dyn WalletStore>,
+    pub shares: Arc<Shares>,
+    pub display: DisplayPolicy,
+}
+
+impl fmt::Debug for ViewSharedExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>()).finish()
+    }
+}
+
+#[async_trait]
+impl ViewShared for ViewSharedExecutor {
+    async fn execute(&self, token: &str) -> Result<SharedWallet> {
+        let wallet_id = self.shares.wallet_id(token).ok_or(WalletError {
+            kind: WalletErrorKind::NotFound,
+            source: None,
+        })?;
+
+        let (name, record) = resolve(self.wallet_store.as_ref(), &wallet_id).await?;
+        let (_, is_stale) = self.display.staleness(record.last_update);
+
+        Ok(SharedWallet {
+            name,
+            address: record.wallet.address().to_string(),
+            linked_addresses: record
+                .wallet
+                .linked()
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            ens_name: record.ens_name,
+            balance: self.display.format(record.wallet.balance()),
+            balance_wei: record.wallet.balance().to_string(),
+            last_update: record.last_update,
+            is_stale,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, str::FromStr, sync::Arc, time::Duration};
+
+    use crate::{
+        core::{Address, Wallet},
+        infra::{MockWalletStore, WalletRecord},
+        wallet::{
+            DisplayPolicy, Share, ShareExecutor, Shares, ViewShared, ViewSharedExecutor,
+            WalletErrorKind,
+        },
+    };
+
+    const ADDR: &str = "0xB644Babc370f46f202DB5eaf2071A9Ee66fA1D5E";
+
+    fn wallet_store() -> Arc<MockWalletStore> {
+        let record = WalletRecord::new(Wallet::new(Address::from_str(ADDR).unwrap()));
+        let by_id = record.clone();
+
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store
+            .expect_find()
+            .returning(move |_| Ok(Some(record.clone())));
+        wallet_store
+            .expect_all()
+            .returning(move || Ok(BTreeMap::from([("Donations".to_string(), by_id.clone())])));
+        Arc::new(wallet_store)
+    }
+
+    #[tokio::test]
+    async fn wallet_shared_success() {
+        let wallet_store = wallet_store();
+        let shares = Arc::new(Shares::new(
+            Duration::from_secs(60),
+            Duration::from_secs(60 * 60),
+        ));
+
+        let share = ShareExecutor {
+            wallet_store: wallet_store.clone(),
+            shares: shares.clone(),
+        };
+        let view = ViewSharedExecutor {
+            wallet_store,
+            shares,
+            display: DisplayPolicy::default(),
+        };
+
+        let link = share.execute("Donations", None).await.unwrap();
+        let wallet = view.execute(&link.token).await.unwrap();
+        assert_eq!(wallet.name, "Donations");
+        assert_eq!(wallet.address, ADDR);
+
+        let error = view.execute("not-a-token").await.unwrap_err();
+        assert_eq!(error.kind(), WalletErrorKind::NotFound);
+    }
+
+    #[tokio::test]
+    async fn wallet_shared_expired() {
+        let wallet_store = wallet_store();
+        let shares = Arc::new(Shares::new(Duration::from_secs(60), Duration::ZERO));
+
+        let share = ShareExecutor {
+            wallet_store: wallet_store.clone(),
+            shares: shares.clone(),
+        };
+        let view = ViewSharedExecutor {
+            wallet_store,
+            shares,
+            display: DisplayPolicy::default(),
+        };
+
+        let link = share
+            .execute("Donations", Some(Duration::from_secs(60)))
+            .await
+            .unwrap();
+        let error = view.execute(&link.token).await.unwrap_err();
+        assert_eq!(error.kind(), WalletErrorKind::NotFound);
+    }
+}
@@ -0,0 +1,178 @@
+use std::{any::type_name, fmt, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use tracing::{info, warn};
+
+use super::Result;
+use crate::infra::{TrashStore, WalletStore};
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait Sweep: Send + Sync + 'static {
+    async fn execute(&self) -> Result<SweepSummary>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SweepPolicy {
+    pub tag: String,
+    pub grace: Duration,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SweepSummary {
+    pub pending: usize,
+    pub newly_pending: usize,
+    pub archived: Vec<String>,
+}
+
+#[derive(Clone)]
+pub struct SweepExecutor {
+    pub wallet_store: Arc<dyn WalletStore>,
+    pub trash_store: Arc<dyn TrashStore>,
+    pub policy: SweepPolicy,
+}
+
+impl fmt::Debug for SweepExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>()).finish()
+    }
+}
+
+#[async_trait]
+impl Sweep for SweepExecutor {
+    async fn execute(&self) -> Result<SweepSummary> {
+        let grace = chrono::Duration::from_std(self.policy.grace).unwrap_or(chrono::Duration::MAX);
+        let now = Utc::now();
+        let mut summary = SweepSummary::default();
+
+        let wallets = self.wallet_store.all().await?;
+        let deposits = wallets
+            .into_iter()
+            .filter(|(_, record)| record.tags.contains(&self.policy.tag));
+
+        for (name, mut record) in deposits {
+            let funded = !record.wallet.balance().is_zero();
+            let mut changed = false;
+
+            if funded {
+                summary.pending += 1;
+                if record.funded_at.is_none() {
+                    warn!("deposit wallet {name} received funds and needs a sweep");
+                    record.funded_at = Some(now);
+                    summary.newly_pending += 1;
+                    changed = true;
+                }
+                if record.swept_at.take().is_some() {
+                    changed = true;
+                }
+            } else if record.funded_at.is_some() {
+                match record.swept_at {
+                    None => {
+                        record.swept_at = Some(now);
+                        changed = true;
+                    }
+                    Some(swept_at) if now.signed_duration_since(swept_at) >= grace => {
+                        if self.trash_store.find_trashed(&name).await?.is_some() {
+                            warn!(
+                                "can't archive swept wallet {name}, its name is taken in the trash"
+                            );
+                            continue;
+                        }
+                        self.trash_store.trash(&name).await?;
+                        summary.archived.push(name);
+                        continue;
+                    }
+                    Some(_) => {}
+                }
+            }
+
+            if changed {
+                self.wallet_store.save(&name, &record).await?;
+            }
+        }
+
+        if !summary.archived.is_empty() {
+            info!("archived {} swept deposit wallets", summary.archived.len());
+        }
+        summary.archived.sort();
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, sync::Arc, time::Duration};
+
+    use chrono::Utc;
+
+    use crate::{
+        core::{Address, Balance, Wallet},
+        infra::{MockTrashStore, MockWalletStore, TrashedRecord, WalletRecord},
+        wallet::{Sweep, SweepExecutor, SweepPolicy},
+    };
+
+    #[tokio::test]
+    async fn wallet_sweep_pending_and_archive() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_all().returning(|| {
+            let record = |byte, wei, funded: bool, swept_secs_ago: Option<u64>| {
+                let mut wallet = Wallet::new(Address::new([byte; 20]));
+                *wallet.balance_mut() = Balance::new(wei);
+                WalletRecord {
+                    tags: vec!["deposit".to_string()],
+                    funded_at: funded.then(Utc::now),
+                    swept_at: swept_secs_ago.map(|secs| Utc::now() - Duration::from_secs(secs)),
+                    ..WalletRecord::new(wallet)
+                }
+            };
+            Ok(BTreeMap::from([
+                ("Unused".to_string(), record(1, 0, false, None)),
+                ("Funded".to_string(), record(2, 5, false, None)),
+                ("Swept".to_string(), record(3, 0, true, None)),
+                ("Expired".to_string(), record(4, 0, true, Some(2 * 60 * 60))),
+                ("Taken".to_string(), record(6, 0, true, Some(2 * 60 * 60))),
+                (
+                    "Untagged".to_string(),
+                    WalletRecord::new(Wallet::new(Address::new([5; 20]))),
+                ),
+            ]))
+        });
+        wallet_store
+            .expect_save()
+            .withf(|name, record| match name {
+                "Funded" => record.funded_at.is_some(),
+                "Swept" => record.swept_at.is_some(),
+                _ => false,
+            })
+            .times(2)
+            .returning(|_, _| Ok(()));
+
+        let mut trash_store = MockTrashStore::new();
+        trash_store.expect_find_trashed().returning(|name| {
+            Ok((name == "Taken").then(|| TrashedRecord {
+                record: WalletRecord::new(Wallet::new(Address::new([7; 20]))),
+                trashed_at: Utc::now(),
+            }))
+        });
+        trash_store
+            .expect_trash()
+            .withf(|name| name == "Expired")
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let sweep = SweepExecutor {
+            wallet_store: Arc::new(wallet_store),
+            trash_store: Arc::new(trash_store),
+            policy: SweepPolicy {
+                tag: "deposit".to_string(),
+                grace: Duration::from_secs(60 * 60),
+            },
+        };
+
+        let summary = sweep.execute().await.unwrap();
+        assert_eq!(summary.pending, 1);
+        assert_eq!(summary.newly_pending, 1);
+        assert_eq!(summary.archived, ["Expired"]);
+    }
+}
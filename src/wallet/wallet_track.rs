@@ -1,24 +1,46 @@
 use std::{any::type_name, fmt, str::FromStr, sync::Arc};
 
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 
-use super::{NAME_MAX, Result, WalletError, WalletErrorKind};
+use super::{
+    DisplayPolicy, EventBus, Result, Wallet as WalletDto, WalletError, WalletErrorKind,
+    WalletEvent, audit, fetch_balance, normalize_note, normalize_tags, to_wallet, validate_name,
+};
 use crate::{
-    core::{Address, Wallet},
-    infra::{WalletClient, WalletRecord, WalletStore},
+    core::{Address, ChainId, EnsName, Wallet, uri::PaymentUri},
+    infra::{
+        AuditStore, Insertion, NameResolver, Provenance, WalletClient, WalletRecord, WalletStore,
+    },
 };
 
 #[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait Track: Send + Sync + 'static {
-    async fn execute(&self, name: &str, address: &str) -> Result<()>;
+    async fn execute(&self, wallet: NewWallet) -> Result<WalletDto>;
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct NewWallet {
+    pub name: String,
+    pub address: String,
+    pub linked_addresses: Vec<String>,
+    pub tags: Vec<String>,
+    pub note: String,
+    pub skip_balance: bool,
+    pub validate_only: bool,
+    pub created_by: Provenance,
 }
 
 #[derive(Clone)]
 pub struct TrackExecutor {
     pub wallet_store: Arc<dyn WalletStore>,
     pub wallet_client: Arc<dyn WalletClient>,
+    pub name_resolver: Arc<dyn NameResolver>,
+    pub audit_store: Arc<dyn AuditStore>,
+    pub events: Arc<EventBus>,
+    pub display: DisplayPolicy,
+    pub chain_id: ChainId,
 }
 
 impl fmt::Debug for TrackExecutor {
@@ -29,44 +51,173 @@ impl fmt::Debug for TrackExecutor {
 
 #[async_trait]
 impl Track for TrackExecutor {
-    async fn execute(&self, name: &str, address: &str) -> Result<()> {
-        validate_name(name)?;
+    async fn execute(&self, wallet: NewWallet) -> Result<WalletDto> {
+        // Nothing changes, so there is nothing to audit.
+        if wallet.validate_only {
+            return self.track(wallet).await;
+        }
 
-        if self.wallet_store.exists(name).await? {
-            return Err(WalletError {
-                kind: WalletErrorKind::NameConflict,
-                source: None,
-            });
+        let name = wallet.name.clone();
+        let address = wallet.address.clone();
+
+        let result = self.track(wallet).await;
+        let address = match &result {
+            Ok(wallet) => &wallet.address,
+            Err(_) => &address,
+        };
+        audit(
+            self.audit_store.as_ref(),
+            "track",
+            &name,
+            Some(address),
+            &result,
+        )
+        .await;
+        result
+    }
+}
+
+fn name_conflict() -> WalletError {
+    WalletError {
+        kind: WalletErrorKind::NameConflict,
+        source: None,
+    }
+}
+
+fn address_tracked() -> WalletError {
+    WalletError {
+        kind: WalletErrorKind::AddressTracked,
+        source: None,
+    }
+}
+
+impl TrackExecutor {
+    async fn track(&self, wallet: NewWallet) -> Result<WalletDto> {
+        let NewWallet {
+            name,
+            address,
+            linked_addresses,
+            tags,
+            note,
+            skip_balance,
+            validate_only,
+            created_by,
+        } = wallet;
+
+        let name = validate_name(&name)?;
+        let tags = normalize_tags(&tags)?;
+        let note = normalize_note(&note)?;
+
+        // Checked again when saving; this only spares the upstream calls.
+        if self.wallet_store.exists(&name).await? {
+            return Err(name_conflict());
         }
 
-        let address = Address::from_str(address)?;
-        let balance = self.wallet_client.balance(&address).await?;
+        let (address, resolved_from) = self.parse_address(&address).await?;
+        if self
+            .wallet_store
+            .find_by_address(self.chain_id, &address)
+            .await?
+            .is_some()
+        {
+            return Err(address_tracked());
+        }
+        let mut wallet = Wallet::on_chain(self.chain_id, address);
+
+        let mut linked = Vec::with_capacity(linked_addresses.len());
+        for address in &linked_addresses {
+            linked.push(self.parse_address(address).await?.0);
+        }
+        wallet.set_linked(linked);
+
+        let last_update = if skip_balance {
+            DateTime::UNIX_EPOCH
+        } else {
+            *wallet.balance_mut() = fetch_balance(self.wallet_client.as_ref(), &wallet).await?;
+            Utc::now()
+        };
 
-        let mut wallet = Wallet::new(address);
-        *wallet.balance_mut() = balance;
         let record = WalletRecord {
-            wallet,
-            last_update: Utc::now(),
+            resolved_at: resolved_from.as_ref().map(|_| Utc::now()),
+            resolved_from,
+            tags,
+            note,
+            modified_by: created_by.clone(),
+            created_by,
+            last_update,
+            ..WalletRecord::new(wallet)
         };
 
-        self.wallet_store.save(name, &record).await?;
-        Ok(())
+        if validate_only {
+            return Ok(to_wallet(&self.display, name, record));
+        }
+
+        // Another track of the same name or address may have landed since
+        // the checks.
+        let wallets = [(name.clone(), record)];
+        match self.wallet_store.insert_many(&wallets).await?.as_slice() {
+            [Insertion::Saved] => {}
+            [Insertion::AddressTaken(_)] => return Err(address_tracked()),
+            _ => return Err(name_conflict()),
+        }
+        let [(_, record)] = wallets;
+        self.events.publish(WalletEvent::Tracked {
+            wallet_id: record.id.clone(),
+            name: name.clone(),
+        });
+        Ok(to_wallet(&self.display, name, record))
     }
-}
 
-fn validate_name(name: &str) -> Result<()> {
-    if name.trim().is_empty() {
-        Err(WalletError {
-            kind: WalletErrorKind::NameEmpty,
-            source: None,
-        })
-    } else if name.chars().count() > NAME_MAX {
-        Err(WalletError {
-            kind: WalletErrorKind::NameTooLong,
-            source: None,
-        })
-    } else {
-        Ok(())
+    async fn parse_address(&self, input: &str) -> Result<(Address, Option<String>)> {
+        let (address, resolved_from) = self.read_address(input).await?;
+        if address.is_burn() {
+            return Err(WalletError {
+                kind: WalletErrorKind::AddressUnspendable,
+                source: None,
+            });
+        }
+        Ok((address, resolved_from))
+    }
+
+    async fn read_address(&self, input: &str) -> Result<(Address, Option<String>)> {
+        if input.contains(':') {
+            let uri = PaymentUri::from_str(input)?;
+            if uri
+                .chain_id
+                .is_some_and(|id| ChainId::new(id) != self.chain_id)
+            {
+                return Err(WalletError {
+                    kind: WalletErrorKind::ChainUnsupported,
+                    source: None,
+                });
+            }
+            return Ok((uri.recipient()?, None));
+        }
+
+        // Names are dotted (`vitalik.eth`) or namespaced (`lens/stani`);
+        // anything else is treated as a hex address so typos surface as
+        // parse errors rather than failed lookups.
+        let looks_like_name = !input.starts_with("0x")
+            && !input.contains(char::is_whitespace)
+            && (input.contains('.') || input.contains('/'));
+        if !looks_like_name {
+            return Ok((Address::from_str(input)?, None));
+        }
+
+        // Keep the normalized form so `Vitalik.ETH` and `vitalik.eth` are
+        // stored, and later followed, as the same name. Lens handles and
+        // anything else UTS-46 rejects go to the resolvers as typed.
+        let name =
+            EnsName::from_str(input).map_or_else(|_| input.to_owned(), |name| name.to_string());
+        let address = self
+            .name_resolver
+            .resolve(&name)
+            .await?
+            .ok_or(WalletError {
+                kind: WalletErrorKind::NameUnresolved,
+                source: None,
+            })?;
+        Ok((address, Some(name)))
     }
 }
 
@@ -74,19 +225,90 @@ fn validate_name(name: &str) -> Result<()> {
 mod tests {
     use std::sync::Arc;
 
+    use std::str::FromStr;
+
+    use chrono::DateTime;
+    use uuid::Uuid;
+
     use crate::{
-        core::Balance,
-        infra::{MockWalletClient, MockWalletStore},
-        wallet::{NAME_MAX, Track, TrackExecutor, WalletErrorKind},
+        core::{Address, Balance, ChainId, Wallet},
+        infra::{
+            Insertion, MockAuditStore, MockNameResolver, MockWalletClient, MockWalletStore,
+            WalletRecord,
+        },
+        wallet::{
+            DisplayPolicy, EventBus, NAME_MAX, NewWallet, Track, TrackExecutor, WalletErrorKind,
+            WalletEvent, audit_store,
+        },
     };
 
     const ADDR: &str = "0xB644Babc370f46f202DB5eaf2071A9Ee66fA1D5E";
 
+    fn new_wallet(name: &str, address: &str) -> NewWallet {
+        NewWallet {
+            name: name.to_string(),
+            address: address.to_string(),
+            ..Default::default()
+        }
+    }
+
     #[tokio::test]
     async fn wallet_track_success() {
         let mut wallet_store = MockWalletStore::new();
         wallet_store.expect_exists().returning(|_| Ok(false));
-        wallet_store.expect_save().returning(|_, _| Ok(()));
+        wallet_store
+            .expect_find_by_address()
+            .returning(|_, _| Ok(None));
+        wallet_store
+            .expect_insert_many()
+            .returning(|wallets| Ok(vec![Insertion::Saved; wallets.len()]));
+
+        let mut wallet_client = MockWalletClient::new();
+        wallet_client
+            .expect_balance()
+            .returning(|_| Ok(Balance::default()));
+
+        let events = Arc::new(EventBus::default());
+        let mut received = events.subscribe();
+        let track = TrackExecutor {
+            wallet_store: Arc::new(wallet_store),
+            wallet_client: Arc::new(wallet_client),
+            name_resolver: Arc::new(MockNameResolver::new()),
+            audit_store: audit_store(),
+            events,
+            display: DisplayPolicy::default(),
+            chain_id: ChainId::MAINNET,
+        };
+
+        let wallet = track
+            .execute(new_wallet("David's Wallet", ADDR))
+            .await
+            .unwrap();
+        assert_eq!(wallet.name, "David's Wallet");
+        assert!(Uuid::parse_str(&wallet.id).is_ok());
+        assert_eq!(
+            received.try_recv().unwrap(),
+            WalletEvent::Tracked {
+                wallet_id: wallet.id,
+                name: wallet.name,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn wallet_track_normalize_name() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store
+            .expect_exists()
+            .withf(|name| name == "Caf\u{e9}")
+            .returning(|_| Ok(false));
+        wallet_store
+            .expect_find_by_address()
+            .returning(|_, _| Ok(None));
+        wallet_store
+            .expect_insert_many()
+            .withf(|wallets| wallets[0].0 == "Caf\u{e9}")
+            .returning(|wallets| Ok(vec![Insertion::Saved; wallets.len()]));
 
         let mut wallet_client = MockWalletClient::new();
         wallet_client
@@ -96,9 +318,65 @@ mod tests {
         let track = TrackExecutor {
             wallet_store: Arc::new(wallet_store),
             wallet_client: Arc::new(wallet_client),
+            name_resolver: Arc::new(MockNameResolver::new()),
+            audit_store: audit_store(),
+            events: Arc::new(EventBus::default()),
+            display: DisplayPolicy::default(),
+            chain_id: ChainId::MAINNET,
         };
 
-        assert!(track.execute("David's Wallet", ADDR).await.is_ok())
+        // Decomposed: "e" followed by a combining acute accent.
+        let wallet = track
+            .execute(new_wallet(" Cafe\u{301}  ", ADDR))
+            .await
+            .unwrap();
+        assert_eq!(wallet.name, "Caf\u{e9}");
+    }
+
+    #[tokio::test]
+    async fn wallet_track_address_tracked() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_exists().returning(|_| Ok(false));
+        wallet_store
+            .expect_find_by_address()
+            .returning(|chain_id, address| {
+                let wallet = Wallet::on_chain(chain_id, *address);
+                let found = chain_id == ChainId::MAINNET;
+                Ok(found.then(|| ("Taken".to_string(), WalletRecord::new(wallet))))
+            });
+        wallet_store
+            .expect_insert_many()
+            .returning(|wallets| Ok(vec![Insertion::Saved; wallets.len()]));
+
+        let mut wallet_client = MockWalletClient::new();
+        wallet_client
+            .expect_balance()
+            .returning(|_| Ok(Balance::default()));
+        let wallet_store = Arc::new(wallet_store);
+        let wallet_client = Arc::new(wallet_client);
+
+        let track = |chain_id| TrackExecutor {
+            wallet_store: wallet_store.clone(),
+            wallet_client: wallet_client.clone(),
+            name_resolver: Arc::new(MockNameResolver::new()),
+            audit_store: audit_store(),
+            events: Arc::new(EventBus::default()),
+            display: DisplayPolicy::default(),
+            chain_id,
+        };
+
+        let error = track(ChainId::MAINNET)
+            .execute(new_wallet("Hot", ADDR))
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), WalletErrorKind::AddressTracked);
+
+        // The same address on another chain is another account.
+        let wallet = track(ChainId::new(10))
+            .execute(new_wallet("Hot", ADDR))
+            .await
+            .unwrap();
+        assert_eq!(wallet.chain_id, 10);
     }
 
     #[tokio::test]
@@ -106,12 +384,17 @@ mod tests {
         let track = TrackExecutor {
             wallet_store: Arc::new(MockWalletStore::new()),
             wallet_client: Arc::new(MockWalletClient::new()),
+            name_resolver: Arc::new(MockNameResolver::new()),
+            audit_store: audit_store(),
+            events: Arc::new(EventBus::default()),
+            display: DisplayPolicy::default(),
+            chain_id: ChainId::MAINNET,
         };
 
-        let error = track.execute("", ADDR).await.unwrap_err();
+        let error = track.execute(new_wallet("", ADDR)).await.unwrap_err();
         assert_eq!(error.kind(), WalletErrorKind::NameEmpty);
 
-        let error = track.execute("   ", ADDR).await.unwrap_err();
+        let error = track.execute(new_wallet("   ", ADDR)).await.unwrap_err();
         assert_eq!(error.kind(), WalletErrorKind::NameEmpty);
     }
 
@@ -120,10 +403,15 @@ mod tests {
         let track = TrackExecutor {
             wallet_store: Arc::new(MockWalletStore::new()),
             wallet_client: Arc::new(MockWalletClient::new()),
+            name_resolver: Arc::new(MockNameResolver::new()),
+            audit_store: audit_store(),
+            events: Arc::new(EventBus::default()),
+            display: DisplayPolicy::default(),
+            chain_id: ChainId::MAINNET,
         };
 
         let error = track
-            .execute(&"s".repeat(NAME_MAX + 1), ADDR)
+            .execute(new_wallet(&"s".repeat(NAME_MAX + 1), ADDR))
             .await
             .unwrap_err();
         assert_eq!(error.kind(), WalletErrorKind::NameTooLong);
@@ -137,26 +425,344 @@ mod tests {
         let track = TrackExecutor {
             wallet_store: Arc::new(wallet_store),
             wallet_client: Arc::new(MockWalletClient::new()),
+            name_resolver: Arc::new(MockNameResolver::new()),
+            audit_store: audit_store(),
+            events: Arc::new(EventBus::default()),
+            display: DisplayPolicy::default(),
+            chain_id: ChainId::MAINNET,
         };
 
-        let error = track.execute("David's Wallet", ADDR).await.unwrap_err();
+        let error = track
+            .execute(new_wallet("David's Wallet", ADDR))
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), WalletErrorKind::NameConflict);
+    }
+
+    #[tokio::test]
+    async fn wallet_track_name_taken_meanwhile() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_exists().returning(|_| Ok(false));
+        wallet_store
+            .expect_find_by_address()
+            .returning(|_, _| Ok(None));
+        wallet_store
+            .expect_insert_many()
+            .returning(|_| Ok(vec![Insertion::NameTaken]));
+
+        let mut wallet_client = MockWalletClient::new();
+        wallet_client
+            .expect_balance()
+            .returning(|_| Ok(Balance::default()));
+
+        let events = Arc::new(EventBus::default());
+        let mut received = events.subscribe();
+        let track = TrackExecutor {
+            wallet_store: Arc::new(wallet_store),
+            wallet_client: Arc::new(wallet_client),
+            name_resolver: Arc::new(MockNameResolver::new()),
+            audit_store: audit_store(),
+            events,
+            display: DisplayPolicy::default(),
+            chain_id: ChainId::MAINNET,
+        };
+
+        let error = track
+            .execute(new_wallet("David's Wallet", ADDR))
+            .await
+            .unwrap_err();
         assert_eq!(error.kind(), WalletErrorKind::NameConflict);
+        assert!(received.try_recv().is_err());
     }
 
     #[tokio::test]
     async fn wallet_track_parse_address() {
         let mut wallet_store = MockWalletStore::new();
         wallet_store.expect_exists().returning(|_| Ok(false));
+        wallet_store
+            .expect_find_by_address()
+            .returning(|_, _| Ok(None));
+
+        let track = TrackExecutor {
+            wallet_store: Arc::new(wallet_store),
+            wallet_client: Arc::new(MockWalletClient::new()),
+            name_resolver: Arc::new(MockNameResolver::new()),
+            audit_store: audit_store(),
+            events: Arc::new(EventBus::default()),
+            display: DisplayPolicy::default(),
+            chain_id: ChainId::MAINNET,
+        };
+
+        let error = track
+            .execute(new_wallet("David's Wallet", "not an address"))
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), WalletErrorKind::WalletAddrParse);
+    }
+
+    #[tokio::test]
+    async fn wallet_track_burn_address() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_exists().returning(|_| Ok(false));
+        wallet_store
+            .expect_find_by_address()
+            .returning(|_, _| Ok(None));
+
+        let mut name_resolver = MockNameResolver::new();
+        name_resolver
+            .expect_resolve()
+            .returning(|_| Ok(Some(Address::DEAD)));
+
+        let track = TrackExecutor {
+            wallet_store: Arc::new(wallet_store),
+            wallet_client: Arc::new(MockWalletClient::new()),
+            name_resolver: Arc::new(name_resolver),
+            audit_store: audit_store(),
+            events: Arc::new(EventBus::default()),
+            display: DisplayPolicy::default(),
+            chain_id: ChainId::MAINNET,
+        };
+
+        let zero = Address::ZERO.to_string();
+        for address in [zero.as_str(), "burned.eth"] {
+            let error = track
+                .execute(new_wallet("Burn", address))
+                .await
+                .unwrap_err();
+            assert_eq!(error.kind(), WalletErrorKind::AddressUnspendable);
+        }
+    }
+
+    #[tokio::test]
+    async fn wallet_track_tags() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_exists().returning(|_| Ok(false));
+        wallet_store
+            .expect_find_by_address()
+            .returning(|_, _| Ok(None));
+        wallet_store.expect_insert_many().returning(|wallets| {
+            let record = &wallets[0].1;
+            assert_eq!(record.tags, ["cold", "defi"]);
+            Ok(vec![Insertion::Saved])
+        });
+
+        let mut wallet_client = MockWalletClient::new();
+        wallet_client
+            .expect_balance()
+            .returning(|_| Ok(Balance::default()));
+
+        let track = TrackExecutor {
+            wallet_store: Arc::new(wallet_store),
+            wallet_client: Arc::new(wallet_client),
+            name_resolver: Arc::new(MockNameResolver::new()),
+            audit_store: audit_store(),
+            events: Arc::new(EventBus::default()),
+            display: DisplayPolicy::default(),
+            chain_id: ChainId::MAINNET,
+        };
+
+        let mut wallet = new_wallet("David's Wallet", ADDR);
+        wallet.tags = vec!["DeFi".to_string(), "cold ".to_string(), "defi".to_string()];
+        assert!(track.execute(wallet).await.is_ok());
+
+        let mut wallet = new_wallet("David's Wallet", ADDR);
+        wallet.tags = vec![" ".to_string()];
+        let error = track.execute(wallet).await.unwrap_err();
+        assert_eq!(error.kind(), WalletErrorKind::TagEmpty);
+    }
+
+    #[tokio::test]
+    async fn wallet_track_skip_balance() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_exists().returning(|_| Ok(false));
+        wallet_store
+            .expect_find_by_address()
+            .returning(|_, _| Ok(None));
+        wallet_store.expect_insert_many().returning(|wallets| {
+            let record = &wallets[0].1;
+            assert_eq!(record.last_update, DateTime::UNIX_EPOCH);
+            Ok(vec![Insertion::Saved])
+        });
+
+        let track = TrackExecutor {
+            wallet_store: Arc::new(wallet_store),
+            wallet_client: Arc::new(MockWalletClient::new()),
+            name_resolver: Arc::new(MockNameResolver::new()),
+            audit_store: audit_store(),
+            events: Arc::new(EventBus::default()),
+            display: DisplayPolicy::default(),
+            chain_id: ChainId::MAINNET,
+        };
+
+        let mut wallet = new_wallet("David's Wallet", ADDR);
+        wallet.skip_balance = true;
+        assert!(track.execute(wallet).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn wallet_track_linked_addresses() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_exists().returning(|_| Ok(false));
+        wallet_store
+            .expect_find_by_address()
+            .returning(|_, _| Ok(None));
+        wallet_store.expect_insert_many().returning(|wallets| {
+            let record = &wallets[0].1;
+            assert_eq!(record.wallet.linked().len(), 1);
+            Ok(vec![Insertion::Saved])
+        });
+
+        let mut wallet_client = MockWalletClient::new();
+        wallet_client
+            .expect_balance()
+            .times(2)
+            .returning(|_| Ok(Balance::new(21)));
+
+        let track = TrackExecutor {
+            wallet_store: Arc::new(wallet_store),
+            wallet_client: Arc::new(wallet_client),
+            name_resolver: Arc::new(MockNameResolver::new()),
+            audit_store: audit_store(),
+            events: Arc::new(EventBus::default()),
+            display: DisplayPolicy::default(),
+            chain_id: ChainId::MAINNET,
+        };
+
+        let cold = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
+        let mut wallet = new_wallet("David's Wallet", ADDR);
+        wallet.linked_addresses = vec![cold.to_string(), ADDR.to_string()];
+
+        let wallet = track.execute(wallet).await.unwrap();
+        assert_eq!(wallet.linked_addresses, [cold]);
+        assert_eq!(wallet.balance_wei, "42");
+    }
+
+    #[tokio::test]
+    async fn wallet_track_payment_uri() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_exists().returning(|_| Ok(false));
+        wallet_store
+            .expect_find_by_address()
+            .returning(|_, _| Ok(None));
+        wallet_store.expect_insert_many().returning(|wallets| {
+            let record = &wallets[0].1;
+            assert!(
+                record.wallet.address().to_string() == ADDR
+                    && record.wallet.chain_id() == ChainId::new(137)
+            );
+            Ok(vec![Insertion::Saved])
+        });
 
         let track = TrackExecutor {
             wallet_store: Arc::new(wallet_store),
             wallet_client: Arc::new(MockWalletClient::new()),
+            name_resolver: Arc::new(MockNameResolver::new()),
+            audit_store: audit_store(),
+            events: Arc::new(EventBus::default()),
+            display: DisplayPolicy::default(),
+            chain_id: ChainId::new(137),
+        };
+
+        let uri = format!("ethereum:{ADDR}@137?value=1e18");
+        let wallet = NewWallet {
+            skip_balance: true,
+            ..new_wallet("David's Wallet", &uri)
         };
+        assert_eq!(track.execute(wallet).await.unwrap().chain_id, 137);
 
+        let uri = format!("ethereum:{ADDR}@1");
         let error = track
-            .execute("David's Wallet", "not an address")
+            .execute(new_wallet("David's Wallet", &uri))
             .await
             .unwrap_err();
+        assert_eq!(error.kind(), WalletErrorKind::ChainUnsupported);
+    }
+
+    #[tokio::test]
+    async fn wallet_track_resolve_name() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_exists().returning(|_| Ok(false));
+        wallet_store
+            .expect_find_by_address()
+            .returning(|_, _| Ok(None));
+        wallet_store.expect_insert_many().returning(|wallets| {
+            let record = &wallets[0].1;
+            assert_eq!(record.wallet.address().to_string(), ADDR);
+            Ok(vec![Insertion::Saved])
+        });
+
+        let mut name_resolver = MockNameResolver::new();
+        name_resolver.expect_resolve().returning(|name| match name {
+            "david.eth" => Ok(Some(Address::from_str(ADDR).unwrap())),
+            _ => Ok(None),
+        });
+
+        let track = TrackExecutor {
+            wallet_store: Arc::new(wallet_store),
+            wallet_client: Arc::new(MockWalletClient::new()),
+            name_resolver: Arc::new(name_resolver),
+            audit_store: audit_store(),
+            events: Arc::new(EventBus::default()),
+            display: DisplayPolicy::default(),
+            chain_id: ChainId::MAINNET,
+        };
+
+        let wallet = NewWallet {
+            skip_balance: true,
+            ..new_wallet("David's Wallet", "David.ETH")
+        };
+        assert!(track.execute(wallet).await.is_ok());
+
+        let error = track
+            .execute(new_wallet("David's Wallet", "nobody.eth"))
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), WalletErrorKind::NameUnresolved);
+    }
+
+    #[tokio::test]
+    async fn wallet_track_validate_only() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_exists().returning(|_| Ok(false));
+        wallet_store
+            .expect_find_by_address()
+            .returning(|_, _| Ok(None));
+        wallet_store.expect_insert_many().never();
+
+        let mut wallet_client = MockWalletClient::new();
+        wallet_client
+            .expect_balance()
+            .times(1)
+            .returning(|_| Ok(Balance::new(7)));
+
+        let events = Arc::new(EventBus::default());
+        let mut received = events.subscribe();
+        let track = TrackExecutor {
+            wallet_store: Arc::new(wallet_store),
+            wallet_client: Arc::new(wallet_client),
+            name_resolver: Arc::new(MockNameResolver::new()),
+            audit_store: Arc::new(MockAuditStore::new()),
+            events,
+            display: DisplayPolicy::default(),
+            chain_id: ChainId::MAINNET,
+        };
+
+        let wallet = NewWallet {
+            validate_only: true,
+            ..new_wallet("David's Wallet", ADDR)
+        };
+        let wallet = track.execute(wallet).await.unwrap();
+        assert_eq!(wallet.balance_wei, "7");
+        assert!(received.try_recv().is_err());
+
+        let wallet = NewWallet {
+            validate_only: true,
+            ..new_wallet(
+                "David's Wallet",
+                "0xB644Babc370f46f202DB5eaf2071A9Ee66fA1D5e",
+            )
+        };
+        let error = track.execute(wallet).await.unwrap_err();
         assert_eq!(error.kind(), WalletErrorKind::WalletAddrParse);
     }
 }
@@ -0,0 +1,189 @@
+use std::{any::type_name, cmp::Reverse, collections::HashSet, fmt, sync::Arc};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::future::try_join_all;
+
+use super::{DisplayPolicy, Result, resolve};
+use crate::{
+    core::{Address, Transfer},
+    infra::{HistoryClient, WalletStore},
+};
+
+const TRANSACTIONS_LIMIT_MAX: usize = 100;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait Transactions: Send + Sync + 'static {
+    async fn execute(&self, name: &str, limit: usize) -> Result<Vec<WalletTransfer>>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferDirection {
+    Inbound,
+    Outbound,
+    Internal,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WalletTransfer {
+    pub hash: String,
+    pub direction: TransferDirection,
+    pub from: String,
+    pub to: Option<String>,
+    pub value: String,
+    pub value_wei: String,
+    pub block_number: u64,
+    pub timestamp: DateTime<Utc>,
+    pub failed: bool,
+}
+
+#[derive(Clone)]
+pub struct TransactionsExecutor {
+    pub wallet_store: Arc<dyn WalletStore>,
+    pub history_client: Arc<dyn HistoryClient>,
+    pub display: DisplayPolicy,
+}
+
+impl fmt::Debug for TransactionsExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>()).finish()
+    }
+}
+
+#[async_trait]
+impl Transactions for TransactionsExecutor {
+    async fn execute(&self, name: &str, limit: usize) -> Result<Vec<WalletTransfer>> {
+        let (_, record) = resolve(self.wallet_store.as_ref(), name).await?;
+        let limit = limit.min(TRANSACTIONS_LIMIT_MAX);
+        let addresses: Vec<Address> = record.wallet.addresses().copied().collect();
+        let chain_id = record.wallet.chain_id();
+
+        // Each address's newest `limit` is enough to find the newest `limit`
+        // overall.
+        let pages = try_join_all(
+            addresses
+                .iter()
+                .map(|address| self.history_client.transfers(address, chain_id, limit)),
+        )
+        .await?;
+
+        // A transfer between two of the wallet's addresses shows up on both.
+        let mut seen = HashSet::new();
+        let mut transfers: Vec<Transfer> = pages
+            .into_iter()
+            .flatten()
+            .filter(|transfer| seen.insert(transfer.hash.clone()))
+            .collect();
+        transfers.sort_by_key(|transfer| Reverse(transfer.block_number));
+        transfers.truncate(limit);
+
+        Ok(transfers
+            .into_iter()
+            .map(|transfer| self.to_wallet_transfer(&addresses, transfer))
+            .collect())
+    }
+}
+
+impl TransactionsExecutor {
+    fn to_wallet_transfer(&self, addresses: &[Address], transfer: Transfer) -> WalletTransfer {
+        let sent = addresses.contains(&transfer.from);
+        let received = transfer.to.is_some_and(|to| addresses.contains(&to));
+        let direction = match (sent, received) {
+            (true, true) => TransferDirection::Internal,
+            (true, false) => TransferDirection::Outbound,
+            _ => TransferDirection::Inbound,
+        };
+
+        WalletTransfer {
+            hash: transfer.hash,
+            direction,
+            from: transfer.from.to_string(),
+            to: transfer.to.map(|to| to.to_string()),
+            value: self.display.format(transfer.value),
+            value_wei: transfer.value.to_string(),
+            block_number: transfer.block_number,
+            timestamp: DateTime::from_timestamp(transfer.timestamp, 0).unwrap_or_default(),
+            failed: transfer.failed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr, sync::Arc};
+
+    use crate::{
+        core::{Address, Balance, ChainId, Transfer, Wallet},
+        infra::{MockHistoryClient, MockWalletStore, WalletRecord},
+        wallet::{DisplayPolicy, Transactions, TransactionsExecutor, TransferDirection},
+    };
+
+    const HOT: &str = "0xB644Babc370f46f202DB5eaf2071A9Ee66fA1D5E";
+    const COLD: &str = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
+
+    fn transfer(hash: &str, block_number: u64, from: Address, to: Address) -> Transfer {
+        Transfer {
+            hash: hash.to_string(),
+            block_number,
+            timestamp: 0,
+            from,
+            to: Some(to),
+            value: Balance::new(1),
+            failed: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn wallet_transactions_merge_addresses() {
+        let hot = Address::from_str(HOT).unwrap();
+        let cold = Address::from_str(COLD).unwrap();
+        let outside = Address::new([7; 20]);
+
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_find().returning(move |_| {
+            let mut wallet = Wallet::new(hot);
+            wallet.set_linked([cold]);
+            Ok(Some(WalletRecord::new(wallet)))
+        });
+
+        let mut history_client = MockHistoryClient::new();
+        history_client
+            .expect_transfers()
+            .withf(|_, chain_id, limit| *chain_id == ChainId::MAINNET && *limit == 3)
+            .returning(move |address, _, _| {
+                Ok(if *address == hot {
+                    vec![
+                        transfer("0x3", 30, hot, cold),
+                        transfer("0x2", 20, outside, hot),
+                    ]
+                } else {
+                    vec![
+                        transfer("0x4", 40, cold, outside),
+                        transfer("0x3", 30, hot, cold),
+                        transfer("0x1", 10, outside, cold),
+                    ]
+                })
+            });
+
+        let transactions = TransactionsExecutor {
+            wallet_store: Arc::new(wallet_store),
+            history_client: Arc::new(history_client),
+            display: DisplayPolicy::default(),
+        };
+
+        let transfers = transactions.execute("Vault", 3).await.unwrap();
+        let listed: Vec<(&str, TransferDirection)> = transfers
+            .iter()
+            .map(|transfer| (transfer.hash.as_str(), transfer.direction))
+            .collect();
+        assert_eq!(
+            listed,
+            [
+                ("0x4", TransferDirection::Outbound),
+                ("0x3", TransferDirection::Internal),
+                ("0x2", TransferDirection::Inbound),
+            ]
+        );
+    }
+}
@@ -2,8 +2,8 @@ use std::{any::type_name, fmt, sync::Arc};
 
 use async_trait::async_trait;
 
-use super::{Result, WalletError, WalletErrorKind};
-use crate::infra::WalletStore;
+use super::{EventBus, Result, WalletError, WalletErrorKind, WalletEvent, audit, resolve};
+use crate::infra::{AuditStore, TrashStore, WalletStore};
 
 #[cfg_attr(test, mockall::automock)]
 #[async_trait]
@@ -14,6 +14,9 @@ pub trait Untrack: Send + Sync + 'static {
 #[derive(Clone)]
 pub struct UntrackExecutor {
     pub wallet_store: Arc<dyn WalletStore>,
+    pub trash_store: Arc<dyn TrashStore>,
+    pub audit_store: Arc<dyn AuditStore>,
+    pub events: Arc<EventBus>,
 }
 
 impl fmt::Debug for UntrackExecutor {
@@ -25,14 +28,100 @@ impl fmt::Debug for UntrackExecutor {
 #[async_trait]
 impl Untrack for UntrackExecutor {
     async fn execute(&self, name: &str) -> Result<()> {
-        if self.wallet_store.exists(name).await? {
-            self.wallet_store.delete(name).await?;
-            Ok(())
-        } else {
-            Err(WalletError {
-                kind: WalletErrorKind::NotFound,
+        let result = self.untrack(name).await;
+        let address = result.as_deref().ok();
+        audit(self.audit_store.as_ref(), "untrack", name, address, &result).await;
+        result.map(|_| ())
+    }
+}
+
+impl UntrackExecutor {
+    async fn untrack(&self, name: &str) -> Result<String> {
+        let (name, record) = resolve(self.wallet_store.as_ref(), name).await?;
+        if self.trash_store.find_trashed(&name).await?.is_some() {
+            return Err(WalletError {
+                kind: WalletErrorKind::NameConflict,
                 source: None,
-            })
+            });
+        }
+
+        self.trash_store.trash(&name).await?;
+        let address = record.wallet.address().to_string();
+        self.events.publish(WalletEvent::Untracked {
+            wallet_id: record.id,
+            name,
+        });
+        Ok(address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr, sync::Arc};
+
+    use chrono::Utc;
+
+    use crate::{
+        core::{Address, Wallet},
+        infra::{MockTrashStore, MockWalletStore, TrashedRecord, WalletRecord},
+        wallet::{EventBus, Untrack, UntrackExecutor, WalletErrorKind, audit_store},
+    };
+
+    fn trashed() -> TrashedRecord {
+        let address = "0xB644Babc370f46f202DB5eaf2071A9Ee66fA1D5E";
+        let address = Address::from_str(address).unwrap();
+        TrashedRecord {
+            record: WalletRecord::new(Wallet::new(address)),
+            trashed_at: Utc::now(),
         }
     }
+
+    #[tokio::test]
+    async fn wallet_untrack_success() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store
+            .expect_find()
+            .returning(|_| Ok(Some(trashed().record)));
+
+        let mut trash_store = MockTrashStore::new();
+        trash_store.expect_find_trashed().returning(|_| Ok(None));
+        trash_store
+            .expect_trash()
+            .withf(|name| name == "David's Wallet")
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let untrack = UntrackExecutor {
+            wallet_store: Arc::new(wallet_store),
+            trash_store: Arc::new(trash_store),
+            events: Arc::new(EventBus::default()),
+            audit_store: audit_store(),
+        };
+
+        assert!(untrack.execute("David's Wallet").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn wallet_untrack_trash_name_conflict() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store
+            .expect_find()
+            .returning(|_| Ok(Some(trashed().record)));
+
+        let mut trash_store = MockTrashStore::new();
+        trash_store
+            .expect_find_trashed()
+            .returning(|_| Ok(Some(trashed())));
+        trash_store.expect_trash().never();
+
+        let untrack = UntrackExecutor {
+            wallet_store: Arc::new(wallet_store),
+            trash_store: Arc::new(trash_store),
+            events: Arc::new(EventBus::default()),
+            audit_store: audit_store(),
+        };
+
+        let error = untrack.execute("David's Wallet").await.unwrap_err();
+        assert_eq!(error.kind(), WalletErrorKind::NameConflict);
+    }
 }
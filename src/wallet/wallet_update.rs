@@ -0,0 +1,284 @@
+use std::{any::type_name, fmt, result, str::FromStr, sync::Arc};
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+use super::{
+    DisplayPolicy, Result, Wallet, audit, fetch_balance, normalize_note, normalize_tags, resolve,
+    to_wallet,
+};
+use crate::{
+    core::{Address, Wallet as CoreWallet},
+    infra::{AuditStore, Provenance, WalletClient, WalletStore},
+};
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait Update: Send + Sync + 'static {
+    async fn execute(&self, update: WalletUpdate) -> Result<Wallet>;
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct WalletUpdate {
+    pub name: String,
+    pub address: Option<String>,
+    pub linked_addresses: Option<Vec<String>>,
+    pub tags: Option<Vec<String>>,
+    pub note: Option<String>,
+    pub modified_by: Provenance,
+}
+
+#[derive(Clone)]
+pub struct UpdateExecutor {
+    pub wallet_store: Arc<dyn WalletStore>,
+    pub wallet_client: Arc<dyn WalletClient>,
+    pub audit_store: Arc<dyn AuditStore>,
+    pub display: DisplayPolicy,
+}
+
+impl fmt::Debug for UpdateExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>()).finish()
+    }
+}
+
+#[async_trait]
+impl Update for UpdateExecutor {
+    async fn execute(&self, update: WalletUpdate) -> Result<Wallet> {
+        let name = update.name.clone();
+        let result = self.update(update).await;
+        let address = result.as_ref().ok().map(|wallet| wallet.address.as_str());
+        audit(self.audit_store.as_ref(), "update", &name, address, &result).await;
+        result
+    }
+}
+
+impl UpdateExecutor {
+    async fn update(&self, update: WalletUpdate) -> Result<Wallet> {
+        let WalletUpdate {
+            name,
+            address,
+            linked_addresses,
+            tags,
+            note,
+            modified_by,
+        } = update;
+
+        let (name, mut record) = resolve(self.wallet_store.as_ref(), &name).await?;
+
+        if let Some(tags) = tags {
+            record.tags = normalize_tags(&tags)?;
+        }
+
+        if let Some(note) = note {
+            record.note = normalize_note(&note)?;
+        }
+
+        let mut addresses_changed = false;
+        if let Some(address) = address {
+            let address = Address::from_str(&address)?;
+            if address != *record.wallet.address() {
+                let mut wallet = CoreWallet::on_chain(record.wallet.chain_id(), address);
+                wallet.set_linked(record.wallet.linked().to_vec());
+                record.wallet = wallet;
+                record.verified_at = None;
+                record.ens_name = None;
+                record.ens_checked_at = None;
+                record.resolved_from = None;
+                record.resolved_at = None;
+                addresses_changed = true;
+            }
+        }
+
+        if let Some(linked_addresses) = linked_addresses {
+            let linked = linked_addresses
+                .iter()
+                .map(|address| Address::from_str(address))
+                .collect::<result::Result<Vec<_>, _>>()?;
+            record.wallet.set_linked(linked);
+            addresses_changed = true;
+        }
+
+        if addresses_changed {
+            // A move between different addresses isn't a move at all.
+            record.previous_balance = None;
+            // Left for the next refresh to read for the new addresses.
+            record.tokens.clear();
+            record.transaction_count = None;
+            *record.wallet.balance_mut() =
+                fetch_balance(self.wallet_client.as_ref(), &record.wallet).await?;
+            record.last_update = Utc::now();
+        }
+
+        record.modified_by = modified_by;
+        self.wallet_store.save(&name, &record).await?;
+        Ok(to_wallet(&self.display, name, record))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr, sync::Arc};
+
+    use crate::{
+        core::{Address, Balance, Wallet},
+        infra::{MockWalletClient, MockWalletStore, WalletRecord},
+        wallet::{
+            DisplayPolicy, NOTE_MAX, Update, UpdateExecutor, WalletErrorKind, WalletUpdate,
+            audit_store,
+        },
+    };
+
+    const ADDR: &str = "0xB644Babc370f46f202DB5eaf2071A9Ee66fA1D5E";
+
+    fn wallet_store() -> MockWalletStore {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_find().returning(|_| {
+            let address = Address::from_str(ADDR).unwrap();
+            Ok(Some(WalletRecord {
+                tags: vec!["cold".to_string()],
+                ..WalletRecord::new(Wallet::new(address))
+            }))
+        });
+        wallet_store
+    }
+
+    #[tokio::test]
+    async fn wallet_update_tags_only() {
+        let mut wallet_store = wallet_store();
+        wallet_store
+            .expect_save()
+            .withf(|_, record| {
+                record.tags == ["hot"] && record.wallet.address().to_string() == ADDR
+            })
+            .returning(|_, _| Ok(()));
+
+        let update = UpdateExecutor {
+            wallet_store: Arc::new(wallet_store),
+            wallet_client: Arc::new(MockWalletClient::new()),
+            audit_store: audit_store(),
+            display: DisplayPolicy::default(),
+        };
+
+        let wallet = update
+            .execute(WalletUpdate {
+                name: "David's Wallet".to_string(),
+                tags: Some(vec!["Hot".to_string()]),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(wallet.tags, ["hot"]);
+    }
+
+    #[tokio::test]
+    async fn wallet_update_note() {
+        let mut wallet_store = wallet_store();
+        wallet_store
+            .expect_save()
+            .withf(|_, record| record.note.as_deref() == Some("grant payouts"))
+            .returning(|_, _| Ok(()));
+
+        let update = UpdateExecutor {
+            wallet_store: Arc::new(wallet_store),
+            wallet_client: Arc::new(MockWalletClient::new()),
+            audit_store: audit_store(),
+            display: DisplayPolicy::default(),
+        };
+
+        let wallet = update
+            .execute(WalletUpdate {
+                name: "David's Wallet".to_string(),
+                note: Some("  grant payouts\n".to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(wallet.note.as_deref(), Some("grant payouts"));
+
+        let error = update
+            .execute(WalletUpdate {
+                name: "David's Wallet".to_string(),
+                note: Some("n".repeat(NOTE_MAX + 1)),
+                ..Default::default()
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), WalletErrorKind::NoteTooLong);
+    }
+
+    #[tokio::test]
+    async fn wallet_update_address() {
+        let mut wallet_store = wallet_store();
+        wallet_store
+            .expect_save()
+            .withf(|_, record| record.tags == ["cold"])
+            .returning(|_, _| Ok(()));
+
+        let mut wallet_client = MockWalletClient::new();
+        wallet_client
+            .expect_balance()
+            .returning(|_| Ok(Balance::new(42)));
+
+        let update = UpdateExecutor {
+            wallet_store: Arc::new(wallet_store),
+            wallet_client: Arc::new(wallet_client),
+            audit_store: audit_store(),
+            display: DisplayPolicy::default(),
+        };
+
+        let address = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
+        let wallet = update
+            .execute(WalletUpdate {
+                name: "David's Wallet".to_string(),
+                address: Some(address.to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(wallet.address, address);
+        assert_eq!(wallet.balance_wei, "42");
+    }
+
+    #[tokio::test]
+    async fn wallet_update_not_found() {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_find().returning(|_| Ok(None));
+
+        let update = UpdateExecutor {
+            wallet_store: Arc::new(wallet_store),
+            wallet_client: Arc::new(MockWalletClient::new()),
+            audit_store: audit_store(),
+            display: DisplayPolicy::default(),
+        };
+
+        let error = update
+            .execute(WalletUpdate {
+                name: "David's Wallet".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), WalletErrorKind::NotFound);
+    }
+
+    #[tokio::test]
+    async fn wallet_update_parse_address() {
+        let update = UpdateExecutor {
+            wallet_store: Arc::new(wallet_store()),
+            wallet_client: Arc::new(MockWalletClient::new()),
+            audit_store: audit_store(),
+            display: DisplayPolicy::default(),
+        };
+
+        let error = update
+            .execute(WalletUpdate {
+                name: "David's Wallet".to_string(),
+                address: Some("not an address".to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), WalletErrorKind::WalletAddrParse);
+    }
+}
@@ -0,0 +1,188 @@
+use std::{any::type_name, fmt, sync::Arc};
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+use super::{
+    Challenges, DisplayPolicy, Result, Wallet, WalletError, WalletErrorKind, audit, resolve,
+    to_wallet,
+};
+use crate::{
+    core::recover_personal_sign,
+    infra::{AuditStore, WalletStore},
+};
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait Verify: Send + Sync + 'static {
+    async fn execute(&self, name: &str, signature: &str) -> Result<Wallet>;
+}
+
+#[derive(Clone)]
+pub struct VerifyExecutor {
+    pub wallet_store: Arc<dyn WalletStore>,
+    pub challenges: Arc<Challenges>,
+    pub audit_store: Arc<dyn AuditStore>,
+    pub display: DisplayPolicy,
+}
+
+impl fmt::Debug for VerifyExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>()).finish()
+    }
+}
+
+#[async_trait]
+impl Verify for VerifyExecutor {
+    async fn execute(&self, name: &str, signature: &str) -> Result<Wallet> {
+        let result = self.verify(name, signature).await;
+        let address = result.as_ref().ok().map(|wallet| wallet.address.as_str());
+        audit(self.audit_store.as_ref(), "verify", name, address, &result).await;
+        result
+    }
+}
+
+impl VerifyExecutor {
+    async fn verify(&self, name: &str, signature: &str) -> Result<Wallet> {
+        let (name, mut record) = resolve(self.wallet_store.as_ref(), name).await?;
+        let address = *record.wallet.address();
+
+        let message = self.challenges.take(&record.id, &address)?;
+        let signer = recover_personal_sign(&message, signature)?;
+        if signer != address {
+            return Err(WalletError {
+                kind: WalletErrorKind::SignerMismatch,
+                source: None,
+            });
+        }
+
+        record.verified_at = Some(Utc::now());
+        self.wallet_store.save(&name, &record).await?;
+        Ok(to_wallet(&self.display, name, record))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr, sync::Arc, time::Duration};
+
+    use k256::ecdsa::SigningKey;
+    use tiny_keccak::{Hasher, Keccak};
+
+    use crate::{
+        core::{Address, Wallet},
+        infra::{MockWalletStore, WalletRecord},
+        wallet::{
+            Challenge, ChallengeExecutor, Challenges, DisplayPolicy, Verify, VerifyExecutor,
+            WalletErrorKind, audit_store,
+        },
+    };
+
+    const ADDR: &str = "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23";
+    const ID: &str = "0b5cf4c5-7d9a-4c4b-9a57-0e3c9f3c2a11";
+
+    fn wallet_store() -> MockWalletStore {
+        let mut wallet_store = MockWalletStore::new();
+        wallet_store.expect_find().returning(|_| {
+            let address = Address::from_str(ADDR).unwrap();
+            Ok(Some(WalletRecord {
+                id: ID.to_string(),
+                ..WalletRecord::new(Wallet::new(address))
+            }))
+        });
+        wallet_store
+    }
+
+    fn personal_sign(message: &str) -> String {
+        // Private key behind ADDR, from the web3.js `accounts.sign` docs.
+        let key = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+        let key = SigningKey::from_slice(&hex::decode(key).unwrap()).unwrap();
+
+        let mut hash = [0u8; 32];
+        let mut keccak = Keccak::v256();
+        keccak.update(format!("\x19Ethereum Signed Message:\n{}", message.len()).as_bytes());
+        keccak.update(message.as_bytes());
+        keccak.finalize(&mut hash);
+
+        let (signature, recovery_id) = key.sign_prehash_recoverable(&hash).unwrap();
+        let v = recovery_id.to_byte() + 27;
+        format!("0x{}{v:02x}", hex::encode(signature.to_bytes()))
+    }
+
+    #[tokio::test]
+    async fn wallet_verify_success() {
+        let challenges = Arc::new(Challenges::new(Duration::from_secs(600)));
+
+        let challenge = ChallengeExecutor {
+            wallet_store: Arc::new(wallet_store()),
+            challenges: challenges.clone(),
+        };
+        let issued = challenge.execute("David's Wallet").await.unwrap();
+
+        let mut wallet_store = wallet_store();
+        wallet_store
+            .expect_save()
+            .withf(|_, record| record.verified_at.is_some())
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let verify = VerifyExecutor {
+            wallet_store: Arc::new(wallet_store),
+            challenges,
+            audit_store: audit_store(),
+            display: DisplayPolicy::default(),
+        };
+
+        let signature = personal_sign(&issued.message);
+        let wallet = verify.execute("David's Wallet", &signature).await.unwrap();
+        assert!(wallet.verified_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn wallet_verify_without_challenge() {
+        let verify = VerifyExecutor {
+            wallet_store: Arc::new(wallet_store()),
+            challenges: Arc::new(Challenges::new(Duration::from_secs(600))),
+            audit_store: audit_store(),
+            display: DisplayPolicy::default(),
+        };
+
+        let error = verify.execute("David's Wallet", "0x00").await.unwrap_err();
+        assert_eq!(error.kind(), WalletErrorKind::ChallengeMissing);
+    }
+
+    #[tokio::test]
+    async fn wallet_verify_bad_signature_consumes_challenge() {
+        let challenges = Arc::new(Challenges::new(Duration::from_secs(600)));
+
+        let challenge = ChallengeExecutor {
+            wallet_store: Arc::new(wallet_store()),
+            challenges: challenges.clone(),
+        };
+        let issued = challenge.execute("David's Wallet").await.unwrap();
+        assert!(issued.message.contains(ID));
+        assert!(issued.message.contains(ADDR));
+
+        let verify = VerifyExecutor {
+            wallet_store: Arc::new(wallet_store()),
+            challenges,
+            audit_store: audit_store(),
+            display: DisplayPolicy::default(),
+        };
+
+        // A valid signature, just not over this challenge.
+        let signature = "0xb91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd\
+                         6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a0291c";
+        let error = verify
+            .execute("David's Wallet", signature)
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), WalletErrorKind::SignerMismatch);
+
+        let error = verify
+            .execute("David's Wallet", signature)
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), WalletErrorKind::ChallengeMissing);
+    }
+}
@@ -0,0 +1,32 @@
+use std::{any::type_name, fmt, sync::Arc};
+
+use async_trait::async_trait;
+
+use super::Result;
+use crate::infra::{PreferenceStore, SavedView};
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait Views: Send + Sync + 'static {
+    async fn execute(&self, principal: &str) -> Result<Vec<SavedView>>;
+}
+
+#[derive(Clone)]
+pub struct ViewsExecutor {
+    pub preference_store: Arc<dyn PreferenceStore>,
+}
+
+impl fmt::Debug for ViewsExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>()).finish()
+    }
+}
+
+#[async_trait]
+impl Views for ViewsExecutor {
+    async fn execute(&self, principal: &str) -> Result<Vec<SavedView>> {
+        let mut views = self.preference_store.views(principal).await?;
+        views.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(views)
+    }
+}
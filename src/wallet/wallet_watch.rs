@@ -0,0 +1,104 @@
+use std::{any::type_name, fmt, sync::Arc};
+
+use async_trait::async_trait;
+
+use super::{EventBus, Result, ResumeToken, Subscription, WalletError, WalletErrorKind};
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait Watch: Send + Sync + 'static {
+    async fn execute(&self, resume_token: Option<String>) -> Result<Subscription>;
+}
+
+#[derive(Clone)]
+pub struct WatchExecutor {
+    pub events: Arc<EventBus>,
+}
+
+impl fmt::Debug for WatchExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>()).finish()
+    }
+}
+
+#[async_trait]
+impl Watch for WatchExecutor {
+    async fn execute(&self, resume_token: Option<String>) -> Result<Subscription> {
+        let Some(resume_token) = resume_token else {
+            return Ok(self.events.subscribe());
+        };
+
+        let token: ResumeToken = resume_token.parse().map_err(|e| WalletError {
+            kind: WalletErrorKind::ResumeTokenInvalid,
+            source: Some(Box::new(e)),
+        })?;
+
+        self.events.subscribe_from(token).ok_or(WalletError {
+            kind: WalletErrorKind::ResumeTokenExpired,
+            source: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{
+        core::Balance,
+        wallet::{BalanceChange, EventBus, WalletErrorKind, WalletEvent, Watch, WatchExecutor},
+    };
+
+    fn changed(wei: u128) -> WalletEvent {
+        WalletEvent::BalanceChanged(BalanceChange {
+            wallet_id: "w1".to_string(),
+            name: "Hot".to_string(),
+            before: Balance::new(0),
+            after: Balance::new(wei),
+        })
+    }
+
+    #[tokio::test]
+    async fn wallet_watch_resume() {
+        let events = Arc::new(EventBus::new(4));
+        let watch = WatchExecutor {
+            events: events.clone(),
+        };
+
+        let mut subscription = watch.execute(None).await.unwrap();
+        events.publish(changed(1));
+        events.publish(changed(2));
+        assert_eq!(subscription.recv().await, Ok(changed(1)));
+
+        let token = subscription.token().to_string();
+        drop(subscription);
+
+        let mut resumed = watch.execute(Some(token)).await.unwrap();
+        assert_eq!(resumed.recv().await, Ok(changed(2)));
+    }
+
+    #[tokio::test]
+    async fn wallet_watch_resume_token_expired() {
+        let events = Arc::new(EventBus::new(1));
+        let watch = WatchExecutor {
+            events: events.clone(),
+        };
+
+        let token = watch.execute(None).await.unwrap().token().to_string();
+        events.publish(changed(1));
+        events.publish(changed(2));
+
+        let error = watch.execute(Some(token)).await.unwrap_err();
+        assert_eq!(error.kind(), WalletErrorKind::ResumeTokenExpired);
+    }
+
+    #[tokio::test]
+    async fn wallet_watch_resume_token_invalid() {
+        let watch = WatchExecutor {
+            events: Arc::new(EventBus::default()),
+        };
+
+        let error = watch.execute(Some("nope".to_string())).await.unwrap_err();
+        assert_eq!(error.kind(), WalletErrorKind::ResumeTokenInvalid);
+    }
+}
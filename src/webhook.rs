@@ -0,0 +1,213 @@
+use std::{error, fmt, time::Duration};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use futures::future::join_all;
+use hmac::{Hmac, Mac};
+use reqwest::{
+    Client, Error as ReqwestError, StatusCode,
+    header::{CONTENT_TYPE, HeaderValue},
+};
+use sha2::Sha256;
+use tokio::time::sleep;
+use tracing::{debug, instrument, warn};
+
+use crate::{
+    infra::{ClientError, Notification, Notifier},
+    payload,
+    server::compose_error,
+};
+
+pub const TIMESTAMP_HEADER: &str = "x-wallet-timestamp";
+pub const SIGNATURE_HEADER: &str = "x-wallet-signature";
+
+#[derive(Debug)]
+pub struct WebhookError(Box<dyn error::Error + Send + Sync + 'static>);
+
+impl fmt::Display for WebhookError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "webhook delivery error")
+    }
+}
+
+impl error::Error for WebhookError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&*self.0)
+    }
+}
+
+impl From<ReqwestError> for WebhookError {
+    fn from(error: ReqwestError) -> Self {
+        Self(error.into())
+    }
+}
+
+impl From<WebhookError> for ClientError {
+    fn from(error: WebhookError) -> Self {
+        ClientError(error.into())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub attempts: u32,
+    pub backoff: Duration,
+}
+
+impl RetryPolicy {
+    fn delay(&self, failed: u32) -> Duration {
+        self.backoff
+            .saturating_mul(2u32.saturating_pow(failed.saturating_sub(1)))
+    }
+}
+
+#[derive(Clone)]
+pub struct WebhookNotifier {
+    client: Client,
+    urls: Vec<String>,
+    secret: Vec<u8>,
+    retry: RetryPolicy,
+}
+
+impl fmt::Debug for WebhookNotifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WebhookNotifier")
+            .field("urls", &self.urls)
+            .field("retry", &self.retry)
+            .finish()
+    }
+}
+
+impl WebhookNotifier {
+    pub fn new(
+        urls: Vec<String>,
+        secret: impl Into<Vec<u8>>,
+        retry: RetryPolicy,
+    ) -> Result<Self, WebhookError> {
+        Ok(Self {
+            client: Client::builder().timeout(Duration::from_secs(10)).build()?,
+            urls,
+            secret: secret.into(),
+            retry,
+        })
+    }
+
+    async fn deliver(&self, url: &str, body: &str, timestamp: i64) -> Result<(), WebhookError> {
+        let signature = format!("sha256={}", sign(&self.secret, timestamp, body));
+
+        let mut failed = 0;
+        loop {
+            let result = self
+                .client
+                .post(url)
+                .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+                .header(TIMESTAMP_HEADER, timestamp)
+                .header(SIGNATURE_HEADER, &signature)
+                .body(body.to_owned())
+                .send()
+                .await;
+
+            let (error, retryable) = match result {
+                Ok(response) if response.status().is_success() => return Ok(()),
+                Ok(response) => {
+                    let status = response.status();
+                    let retryable = status.is_server_error()
+                        || status == StatusCode::TOO_MANY_REQUESTS
+                        || status == StatusCode::REQUEST_TIMEOUT;
+                    (WebhookError(format!("answered {status}").into()), retryable)
+                }
+                Err(e) => (WebhookError::from(e), true),
+            };
+
+            failed += 1;
+            if !retryable || failed >= self.retry.attempts {
+                return Err(error);
+            }
+
+            let delay = self.retry.delay(failed);
+            debug!("webhook attempt {failed} failed, retrying in {delay:?}");
+            sleep(delay).await;
+        }
+    }
+}
+
+#[async_trait]
+impl Notifier for WebhookNotifier {
+    #[instrument(skip_all)]
+    async fn notify(
+        &self,
+        notification: &Notification,
+        schema_version: u32,
+    ) -> Result<(), ClientError> {
+        let body = payload::render_notification(notification, schema_version)
+            .ok_or_else(|| {
+                WebhookError(format!("unsupported schema_version {schema_version}").into())
+            })?
+            .to_string();
+        let timestamp = Utc::now().timestamp();
+
+        let results = join_all(
+            self.urls
+                .iter()
+                .map(|url| self.deliver(url, &body, timestamp)),
+        )
+        .await;
+
+        let mut failed = 0;
+        for (url, result) in self.urls.iter().zip(results) {
+            if let Err(e) = result {
+                warn!("couldn't deliver webhook to {url}: {}", compose_error(&e));
+                failed += 1;
+            }
+        }
+
+        if failed > 0 {
+            let message = format!("{failed} of {} webhooks failed", self.urls.len());
+            return Err(WebhookError(message.into()).into());
+        }
+
+        Ok(())
+    }
+}
+
+fn sign(secret: &[u8], timestamp: i64, body: &str) -> String {
+    // HMAC accepts keys of any length, so this can't fail.
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).unwrap();
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256;
+
+    use super::{RetryPolicy, sign};
+
+    #[test]
+    fn webhook_sign() {
+        let body = r#"{"event":"balance_changed"}"#;
+        let signature = sign(b"secret", 1_700_000_000, body);
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"secret").unwrap();
+        mac.update(br#"1700000000.{"event":"balance_changed"}"#);
+        assert_eq!(signature, hex::encode(mac.finalize().into_bytes()));
+        assert_ne!(signature, sign(b"other", 1_700_000_000, body));
+        assert_ne!(signature, sign(b"secret", 1_700_000_001, body));
+    }
+
+    #[test]
+    fn webhook_retry_delay() {
+        let retry = RetryPolicy {
+            attempts: 5,
+            backoff: Duration::from_secs(1),
+        };
+        assert_eq!(retry.delay(1), Duration::from_secs(1));
+        assert_eq!(retry.delay(2), Duration::from_secs(2));
+        assert_eq!(retry.delay(4), Duration::from_secs(8));
+    }
+}